@@ -8,6 +8,11 @@ pub fn map_primitive_type(ident: &Ident) -> Ident {
         "u32" => Ident::new("U32", ident.span()),
         "u64" => Ident::new("U64", ident.span()),
         "u128" => Ident::new("U128", ident.span()),
+        "i8" => Ident::new("I8", ident.span()),
+        "i16" => Ident::new("I16", ident.span()),
+        "i32" => Ident::new("I32", ident.span()),
+        "i64" => Ident::new("I64", ident.span()),
+        "i128" => Ident::new("I128", ident.span()),
         _ => ident.clone(),
     }
 }
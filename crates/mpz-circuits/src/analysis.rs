@@ -0,0 +1,246 @@
+//! Analysis of the structure of circuits.
+
+use crate::{components::Gate, Circuit};
+
+/// A layer of gates in a circuit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Layer {
+    gates: Vec<usize>,
+    and_count: usize,
+    xor_count: usize,
+    inv_count: usize,
+    lut_count: usize,
+}
+
+impl Layer {
+    /// Returns the indices of the gates in the layer, see [`Circuit::gates`].
+    pub fn gates(&self) -> &[usize] {
+        &self.gates
+    }
+
+    /// Returns the number of gates in the layer.
+    pub fn len(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Returns whether the layer is empty.
+    pub fn is_empty(&self) -> bool {
+        self.gates.is_empty()
+    }
+
+    /// Returns the number of AND gates in the layer.
+    pub fn and_count(&self) -> usize {
+        self.and_count
+    }
+
+    /// Returns the number of XOR gates in the layer.
+    pub fn xor_count(&self) -> usize {
+        self.xor_count
+    }
+
+    /// Returns the number of INV gates in the layer.
+    pub fn inv_count(&self) -> usize {
+        self.inv_count
+    }
+
+    /// Returns the number of lookup gates in the layer.
+    pub fn lut_count(&self) -> usize {
+        self.lut_count
+    }
+
+    fn push(&mut self, idx: usize, gate: &Gate) {
+        match gate {
+            Gate::Xor { .. } => self.xor_count += 1,
+            Gate::And { .. } => self.and_count += 1,
+            Gate::Inv { .. } => self.inv_count += 1,
+            Gate::Lut(_) => self.lut_count += 1,
+        }
+        self.gates.push(idx);
+    }
+}
+
+impl Circuit {
+    /// Returns the AND depth of the circuit, ie the maximum number of AND gates on
+    /// any path through the circuit.
+    ///
+    /// Lookup gates are counted as AND gates.
+    pub fn and_depth(&self) -> usize {
+        self.gate_depths(true).into_iter().max().unwrap_or(0)
+    }
+
+    /// Returns the depth of the circuit, ie the maximum number of gates on any path
+    /// through the circuit.
+    pub fn depth(&self) -> usize {
+        self.gate_depths(false).into_iter().max().unwrap_or(0)
+    }
+
+    /// Partitions the gates of the circuit into layers by AND depth.
+    ///
+    /// Layer `i` contains the AND and lookup gates at AND depth `i`, in the order of the
+    /// circuit, followed by the XOR and INV gates which depend on them. The AND and lookup gates
+    /// in a layer only depend on gates in previous layers, so they can be evaluated in a single
+    /// batch, eg one round of communication. The remaining gates of a layer are in topological
+    /// order.
+    ///
+    /// The first layer never contains AND or lookup gates, and the number of layers is always
+    /// [`Circuit::and_depth`] + 1.
+    pub fn and_layers(&self) -> Vec<Layer> {
+        let depths = self.gate_depths(true);
+        let mut layers = vec![Layer::default(); depths.iter().max().unwrap_or(&0) + 1];
+
+        for (idx, gate) in self.gates.iter().enumerate() {
+            if matches!(gate, Gate::And { .. } | Gate::Lut(_)) {
+                layers[depths[idx]].push(idx, gate);
+            }
+        }
+
+        for (idx, gate) in self.gates.iter().enumerate() {
+            if !matches!(gate, Gate::And { .. } | Gate::Lut(_)) {
+                layers[depths[idx]].push(idx, gate);
+            }
+        }
+
+        layers
+    }
+
+    /// Partitions the gates of the circuit into topological layers.
+    ///
+    /// Every gate only depends on the inputs of the circuit and gates in previous layers, so
+    /// the gates within a layer are independent of each other. The number of layers is
+    /// [`Circuit::depth`].
+    pub fn layers(&self) -> Vec<Layer> {
+        let depths = self.gate_depths(false);
+        let mut layers = vec![Layer::default(); depths.iter().max().copied().unwrap_or(0)];
+
+        for (idx, gate) in self.gates.iter().enumerate() {
+            layers[depths[idx] - 1].push(idx, gate);
+        }
+
+        layers
+    }
+
+    /// Returns the depth of each gate.
+    ///
+    /// If `and_only` is set, only AND and lookup gates contribute to the depth.
+    fn gate_depths(&self, and_only: bool) -> Vec<usize> {
+        let mut feed_depths = vec![0; self.feed_count];
+
+        self.gates
+            .iter()
+            .map(|gate| {
+                let depth = gate.inputs().map(|x| feed_depths[x.id]).max().unwrap_or(0);
+
+                let depth = if !and_only || matches!(gate, Gate::And { .. } | Gate::Lut(_)) {
+                    depth + 1
+                } else {
+                    depth
+                };
+
+                for z in gate.outputs() {
+                    feed_depths[z.id] = depth;
+                }
+
+                depth
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ops::WrappingAdd,
+        optimize::{optimize_with, Pass},
+        CircuitBuilder,
+    };
+
+    use super::*;
+
+    fn build_adder() -> Circuit {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_add(b));
+        builder.build().unwrap()
+    }
+
+    /// Asserts that the gates are in topological order when visited layer by layer.
+    fn assert_topological(circ: &Circuit, layers: &[Layer]) {
+        let mut set = vec![false; circ.feed_count()];
+        for node in circ.inputs().iter().flat_map(|input| input.iter()) {
+            set[node.id()] = true;
+        }
+
+        let mut visited = 0;
+        for layer in layers {
+            for idx in layer.gates() {
+                let gate = &circ.gates()[*idx];
+                assert!(gate.inputs().all(|x| set[x.id()]));
+                for z in gate.outputs() {
+                    set[z.id()] = true;
+                }
+                visited += 1;
+            }
+        }
+
+        assert_eq!(visited, circ.gates().len());
+    }
+
+    #[test]
+    fn test_and_depth() {
+        let circ = build_adder();
+
+        // The carry out of the last bit is computed but not used.
+        assert_eq!(circ.and_depth(), 8);
+
+        let (circ, _) = optimize_with(&circ, &[Pass::DeadGateElimination]).unwrap();
+
+        assert_eq!(circ.and_depth(), 7);
+    }
+
+    #[test]
+    fn test_and_layers() {
+        let circ = build_adder();
+
+        let layers = circ.and_layers();
+
+        assert_eq!(layers.len(), circ.and_depth() + 1);
+        assert_eq!(layers[0].and_count(), 0);
+        assert!(layers[1..].iter().all(|layer| layer.and_count() == 1));
+        assert_eq!(
+            layers.iter().map(Layer::len).sum::<usize>(),
+            circ.gates().len()
+        );
+        assert_topological(&circ, &layers);
+    }
+
+    #[test]
+    fn test_layers() {
+        let circ = build_adder();
+
+        let layers = circ.layers();
+
+        assert_eq!(layers.len(), circ.depth());
+        assert!(layers.iter().all(|layer| !layer.is_empty()));
+        assert_topological(&circ, &layers);
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_layers_aes() {
+        use crate::circuits::AES128;
+
+        let and_layers = AES128.and_layers();
+
+        assert_eq!(
+            and_layers.iter().map(Layer::and_count).sum::<usize>(),
+            AES128.and_count()
+        );
+        assert_eq!(
+            and_layers.iter().map(Layer::xor_count).sum::<usize>(),
+            AES128.xor_count()
+        );
+        assert_topological(&AES128, &and_layers);
+        assert_topological(&AES128, &AES128.layers());
+    }
+}
@@ -0,0 +1,284 @@
+use std::io::Write;
+
+use crate::{components::Gate, Circuit};
+
+impl Circuit {
+    /// Writes the circuit in Bristol-fashion format.
+    ///
+    /// See `https://homes.esat.kuleuven.be/~nsmart/MPC/` for more information.
+    ///
+    /// The input and output wires are grouped according to the binary representation of
+    /// each input and output of the circuit, using the same bit order expected by
+    /// [`Circuit::parse`].
+    ///
+    /// Bristol-fashion requires the output wires to be the last wires of the circuit. Any
+    /// output which is not the unique output of a gate, eg an input which is passed
+    /// through or a wire which appears in multiple outputs, is copied into a new wire
+    /// using a pair of INV gates.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer to write the circuit to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`](std::io::ErrorKind::InvalidInput) if the
+    /// circuit contains lookup gates, which can not be expressed in Bristol-fashion.
+    pub fn write_bristol<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        if self.lut_count > 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "lookup gates are not supported by the bristol format",
+            ));
+        }
+
+        let mut wire_map: Vec<Option<usize>> = vec![None; self.feed_count];
+        let mut next_wire = 0;
+
+        for input in &self.inputs {
+            for node in input.iter() {
+                wire_map[node.id] = Some(next_wire);
+                next_wire += 1;
+            }
+        }
+        let input_count = next_wire;
+
+        let mut is_gate_output = vec![false; self.feed_count];
+        for gate in &self.gates {
+            is_gate_output[gate.z().id] = true;
+        }
+
+        // Outputs which can not be assigned directly to the output of a gate
+        // must be copied.
+        let mut is_assigned = vec![false; self.feed_count];
+        let mut copy_count = 0;
+        for node in self.outputs.iter().flat_map(|output| output.iter()) {
+            if is_gate_output[node.id] && !is_assigned[node.id] {
+                is_assigned[node.id] = true;
+            } else {
+                copy_count += 1;
+            }
+        }
+
+        let output_count: usize = self.outputs.iter().map(|output| output.len()).sum();
+        let gate_count = self.gates.len() + 2 * copy_count;
+        let wire_count = input_count + gate_count;
+        let output_start = wire_count - output_count;
+
+        // Maps the copied feed ids to their output wire.
+        let mut copies = Vec::with_capacity(copy_count);
+        let mut is_assigned = vec![false; self.feed_count];
+        for (i, node) in self
+            .outputs
+            .iter()
+            .flat_map(|output| output.iter())
+            .enumerate()
+        {
+            if is_gate_output[node.id] && !is_assigned[node.id] {
+                is_assigned[node.id] = true;
+                wire_map[node.id] = Some(output_start + i);
+            } else {
+                copies.push((node.id, output_start + i));
+            }
+        }
+
+        write!(writer, "{gate_count} {wire_count}\n{}", self.inputs.len())?;
+        for input in &self.inputs {
+            write!(writer, " {}", input.len())?;
+        }
+        write!(writer, "\n{}", self.outputs.len())?;
+        for output in &self.outputs {
+            write!(writer, " {}", output.len())?;
+        }
+        writeln!(writer, "\n")?;
+
+        for gate in &self.gates {
+            let z = *wire_map[gate.z().id].get_or_insert_with(|| {
+                let wire = next_wire;
+                next_wire += 1;
+                wire
+            });
+
+            match gate {
+                Gate::Xor { x, y, .. } => {
+                    let x = wire_map[x.id].expect("feed should be assigned a wire");
+                    let y = wire_map[y.id].expect("feed should be assigned a wire");
+                    writeln!(writer, "2 1 {x} {y} {z} XOR")?;
+                }
+                Gate::And { x, y, .. } => {
+                    let x = wire_map[x.id].expect("feed should be assigned a wire");
+                    let y = wire_map[y.id].expect("feed should be assigned a wire");
+                    writeln!(writer, "2 1 {x} {y} {z} AND")?;
+                }
+                Gate::Inv { x, .. } => {
+                    let x = wire_map[x.id].expect("feed should be assigned a wire");
+                    writeln!(writer, "1 1 {x} {z} INV")?;
+                }
+                Gate::Lut(_) => unreachable!("lookup gates are rejected above"),
+            }
+        }
+
+        for (id, z) in copies {
+            let x = wire_map[id].expect("feed should be assigned a wire");
+            let tmp = next_wire;
+            next_wire += 1;
+            writeln!(writer, "1 1 {x} {tmp} INV")?;
+            writeln!(writer, "1 1 {tmp} {z} INV")?;
+        }
+
+        debug_assert_eq!(next_wire, output_start);
+
+        writer.flush()
+    }
+
+    /// Returns the circuit in Bristol-fashion format.
+    ///
+    /// See [`Circuit::write_bristol`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the circuit contains lookup gates.
+    pub fn to_bristol(&self) -> String {
+        let mut bytes = Vec::new();
+        self.write_bristol(&mut bytes)
+            .expect("circuit should not contain lookup gates");
+
+        String::from_utf8(bytes).expect("bristol format should be valid utf8")
+    }
+}
+
+#[cfg(all(test, feature = "parse"))]
+mod tests {
+    use mpz_circuits_macros::evaluate;
+
+    use crate::{ops::WrappingAdd, types::ValueType, CircuitBuilder};
+
+    use super::*;
+
+    #[test]
+    fn test_write_bristol_adder() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a.wrapping_add(b);
+        builder.add_output(c);
+        let circ = builder.build().unwrap();
+
+        let bristol = circ.to_bristol();
+
+        let mut lines = bristol.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            format!("{} {}", circ.gates().len(), 16 + circ.gates().len())
+        );
+        assert_eq!(lines.next().unwrap(), "2 8 8");
+        assert_eq!(lines.next().unwrap(), "1 8");
+
+        let circ = Circuit::parse_str(&bristol, &[ValueType::U8, ValueType::U8], &[ValueType::U8])
+            .unwrap();
+
+        let output: u8 = evaluate!(circ, fn(200u8, 100u8) -> u8).unwrap();
+
+        assert_eq!(output, 200u8.wrapping_add(100u8));
+    }
+
+    #[test]
+    fn test_write_bristol_copied_outputs() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let c = a.wrapping_add(b);
+        builder.add_output(c);
+        builder.add_output(a);
+        builder.add_output(c);
+        let circ = builder.build().unwrap();
+
+        let bristol = circ.to_bristol();
+
+        let circ = Circuit::parse_str(
+            &bristol,
+            &[ValueType::U8, ValueType::U8],
+            &[ValueType::U8, ValueType::U8, ValueType::U8],
+        )
+        .unwrap();
+
+        let output = evaluate!(circ, fn(3u8, 4u8) -> (u8, u8, u8)).unwrap();
+
+        assert_eq!(output, (7u8, 3u8, 7u8));
+    }
+
+    #[test]
+    fn test_bristol_round_trip_adder_64() {
+        let circ = Circuit::parse(
+            "circuits/bristol/adder64_reverse.txt",
+            &[ValueType::U64, ValueType::U64],
+            &[ValueType::U64],
+        )
+        .unwrap();
+
+        let bristol = circ.to_bristol();
+
+        assert!(bristol.starts_with("376 504\n2 64 64\n1 64\n\n"));
+
+        let circ = Circuit::parse_str(
+            &bristol,
+            &[ValueType::U64, ValueType::U64],
+            &[ValueType::U64],
+        )
+        .unwrap();
+
+        let output: u64 = evaluate!(circ, fn(u64::MAX, 2u64) -> u64).unwrap();
+
+        assert_eq!(output, u64::MAX.wrapping_add(2));
+    }
+
+    #[test]
+    #[ignore = "expensive"]
+    fn test_bristol_round_trip_aes() {
+        let inputs = [
+            ValueType::Array(Box::new(ValueType::U8), 16),
+            ValueType::Array(Box::new(ValueType::U8), 16),
+        ];
+        let outputs = [ValueType::Array(Box::new(ValueType::U8), 16)];
+
+        let circ =
+            Circuit::parse("circuits/bristol/aes_128_reverse.txt", &inputs, &outputs).unwrap();
+        let round_trip = Circuit::parse_str(&circ.to_bristol(), &inputs, &outputs).unwrap();
+
+        assert_eq!(circ.and_count(), round_trip.and_count());
+        assert_eq!(circ.xor_count(), round_trip.xor_count());
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let expected = evaluate!(circ, fn(key, msg) -> [u8; 16]).unwrap();
+        let output = evaluate!(round_trip, fn(key, msg) -> [u8; 16]).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    #[ignore = "expensive"]
+    fn test_bristol_round_trip_sha() {
+        let inputs = [
+            ValueType::Array(Box::new(ValueType::U8), 64),
+            ValueType::Array(Box::new(ValueType::U32), 8),
+        ];
+        let outputs = [ValueType::Array(Box::new(ValueType::U32), 8)];
+
+        let circ =
+            Circuit::parse("circuits/bristol/sha256_reverse.txt", &inputs, &outputs).unwrap();
+        let round_trip = Circuit::parse_str(&circ.to_bristol(), &inputs, &outputs).unwrap();
+
+        assert_eq!(circ.and_count(), round_trip.and_count());
+        assert_eq!(circ.xor_count(), round_trip.xor_count());
+
+        let msg = [69u8; 64];
+        let state = [42u32; 8];
+
+        let expected = evaluate!(circ, fn(msg, state) -> [u32; 8]).unwrap();
+        let output = evaluate!(round_trip, fn(msg, state) -> [u32; 8]).unwrap();
+
+        assert_eq!(output, expected);
+    }
+}
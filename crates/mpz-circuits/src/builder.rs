@@ -1,8 +1,8 @@
-use itybity::{BitIterable, IntoBits};
+use itybity::IntoBits;
 
 use crate::{
-    components::{Feed, Gate, Node},
-    types::{BinaryLength, BinaryRepr, ToBinaryRepr, ValueType},
+    components::{fold_table, Feed, Gate, LookupTable, Node},
+    types::{BinaryLength, BinaryRepr, ToBinaryRepr, Value, ValueType},
     Circuit, Tracer,
 };
 use std::{cell::RefCell, collections::HashMap, mem::discriminant};
@@ -15,6 +15,8 @@ pub enum BuilderError {
     MissingWire(usize),
     #[error("error appending circuit: {0}")]
     AppendError(String),
+    #[error("constant output, but the circuit has no input to compute it from")]
+    ConstantOutput,
 }
 
 /// A circuit builder.
@@ -148,7 +150,7 @@ impl CircuitBuilder {
     }
 
     /// Returns a tracer for a constant value
-    pub fn get_constant<T: ToBinaryRepr + BitIterable>(&self, value: T) -> Tracer<'_, T::Repr> {
+    pub fn get_constant<T: ToBinaryRepr>(&self, value: T) -> Tracer<'_, T::Repr> {
         let mut state = self.state.borrow_mut();
 
         let value = state.get_constant(value);
@@ -179,6 +181,49 @@ impl CircuitBuilder {
     }
 }
 
+/// A circuit under construction to which gates can be added.
+pub(crate) trait AddGates {
+    /// Adds an XOR gate, returning its output.
+    fn add_xor_gate(&mut self, x: Node<Feed>, y: Node<Feed>) -> Node<Feed>;
+
+    /// Adds an INV gate, returning its output.
+    fn add_inv_gate(&mut self, x: Node<Feed>) -> Node<Feed>;
+}
+
+/// Constant outputs of a circuit.
+///
+/// Outputs can not be constant, so constant outputs are computed from an input `x` instead, as
+/// `x ^ x` and `!(x ^ x)`. The gates are only added once a constant output is requested.
+#[derive(Debug)]
+pub(crate) struct ConstantOutputs {
+    input: Option<Node<Feed>>,
+    nodes: Option<(Node<Feed>, Node<Feed>)>,
+}
+
+impl ConstantOutputs {
+    /// Creates a new instance which computes the constants from `input`, if the circuit has one.
+    pub(crate) fn new(input: Option<Node<Feed>>) -> Self {
+        Self { input, nodes: None }
+    }
+
+    /// Returns the node which outputs the constant `value`, adding the gates to `circ` on first use.
+    ///
+    /// Returns `None` if the circuit has no input to compute the constant from.
+    pub(crate) fn get<C: AddGates>(&mut self, circ: &mut C, value: bool) -> Option<Node<Feed>> {
+        let (zero, one) = match self.nodes {
+            Some(nodes) => nodes,
+            None => {
+                let x = self.input?;
+                let zero = circ.add_xor_gate(x, x);
+                let one = circ.add_inv_gate(zero);
+                *self.nodes.insert((zero, one))
+            }
+        };
+
+        Some(if value { one } else { zero })
+    }
+}
+
 /// The internal state of the [`CircuitBuilder`]
 #[derive(Debug)]
 pub struct BuilderState {
@@ -189,6 +234,7 @@ pub struct BuilderState {
 
     and_count: usize,
     xor_count: usize,
+    lut_count: usize,
 }
 
 impl Default for BuilderState {
@@ -201,6 +247,7 @@ impl Default for BuilderState {
             gates: vec![],
             and_count: 0,
             xor_count: 0,
+            lut_count: 0,
         }
     }
 }
@@ -221,11 +268,11 @@ impl BuilderState {
     /// # Arguments
     ///
     /// * `value` - The value to encode.
-    pub fn get_constant<T: ToBinaryRepr + BitIterable>(&mut self, value: T) -> T::Repr {
+    pub fn get_constant<T: ToBinaryRepr>(&mut self, value: T) -> T::Repr {
         let zero = self.get_const_zero();
         let one = self.get_const_one();
 
-        let nodes: Vec<_> = value
+        let nodes: Vec<_> = Into::<Value>::into(value)
             .into_iter_lsb0()
             .map(|bit| if bit { one } else { zero })
             .collect();
@@ -358,6 +405,89 @@ impl BuilderState {
         }
     }
 
+    /// Adds a lookup gate to the circuit, see [`LookupTable`].
+    ///
+    /// Constant inputs are folded into the table, and outputs which are constant are returned
+    /// as constant nodes. If no outputs depend on the inputs, no gate is added.
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The inputs to the gate, input `i` is bit `i` of the row index.
+    /// * `table` - The rows of the table, output `j` is bit `j` of a row.
+    /// * `output_count` - The number of outputs of the gate.
+    ///
+    /// # Returns
+    ///
+    /// The outputs of the gate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`LookupTable::MAX_INPUTS`] inputs, more than
+    /// [`LookupTable::MAX_OUTPUTS`] outputs, or the table does not have `2^k` rows for `k`
+    /// inputs.
+    pub(crate) fn add_lut_gate(
+        &mut self,
+        inputs: &[Node<Feed>],
+        table: &[u64],
+        output_count: usize,
+    ) -> Vec<Node<Feed>> {
+        assert!(
+            inputs.len() <= LookupTable::MAX_INPUTS,
+            "lookup table has too many inputs: {}",
+            inputs.len()
+        );
+        assert!(
+            output_count <= LookupTable::MAX_OUTPUTS,
+            "lookup table has too many outputs: {output_count}"
+        );
+        assert_eq!(
+            table.len(),
+            1 << inputs.len(),
+            "lookup table should have a row for every input"
+        );
+
+        let folded = fold_table(
+            table,
+            &inputs
+                .iter()
+                .map(|node| match node.id() {
+                    0 => Some(false),
+                    1 => Some(true),
+                    _ => None,
+                })
+                .collect::<Vec<_>>(),
+            output_count,
+        );
+
+        let outputs: Vec<Node<Feed>> = folded
+            .outputs
+            .iter()
+            .map(|value| match value {
+                Some(false) => self.get_const_zero(),
+                Some(true) => self.get_const_one(),
+                None => self.add_feed(),
+            })
+            .collect();
+
+        if folded.outputs.iter().all(Option::is_some) {
+            return outputs;
+        }
+
+        self.gates.push(Gate::Lut(Box::new(LookupTable::new(
+            folded.inputs.iter().map(|i| inputs[*i].into()).collect(),
+            outputs
+                .iter()
+                .zip(&folded.outputs)
+                .filter(|(_, value)| value.is_none())
+                .map(|(node, _)| *node)
+                .collect(),
+            folded.table,
+        ))));
+        self.lut_count += 1;
+
+        outputs
+    }
+
     /// Appends an existing circuit
     ///
     /// # Arguments
@@ -415,6 +545,18 @@ impl BuilderState {
                     let new_z = self.add_inv_gate(*new_x);
                     feed_map.insert(*z, new_z);
                 }
+                Gate::Lut(lut) => {
+                    let new_inputs: Vec<_> = lut
+                        .inputs()
+                        .iter()
+                        .map(|x| *feed_map.get(&(*x).into()).expect("feed should exist"))
+                        .collect();
+                    let new_outputs =
+                        self.add_lut_gate(&new_inputs, lut.table(), lut.outputs().len());
+                    for (z, new_z) in lut.outputs().iter().zip(new_outputs) {
+                        feed_map.insert(*z, new_z);
+                    }
+                }
             }
         }
 
@@ -431,6 +573,24 @@ impl BuilderState {
 
     /// Builds the circuit.
     pub(crate) fn build(mut self) -> Result<Circuit, BuilderError> {
+        // Replace outputs which are constant nodes, as those are removed below.
+        let input = self
+            .inputs
+            .iter()
+            .flat_map(|input| input.iter())
+            .next()
+            .copied();
+        let mut constants = ConstantOutputs::new(input);
+        let mut outputs = std::mem::take(&mut self.outputs);
+        for node in outputs.iter_mut().flat_map(|output| output.iter_mut()) {
+            if node.id() < 2 {
+                *node = constants
+                    .get(&mut self, node.id() == 1)
+                    .ok_or(BuilderError::ConstantOutput)?;
+            }
+        }
+        self.outputs = outputs;
+
         // Shift all the node ids to the left by 2 to eliminate
         // the reserved constant nodes (which should be factored out during building)
         self.inputs.iter_mut().for_each(|input| input.shift_left(2));
@@ -446,10 +606,21 @@ impl BuilderState {
             feed_count: self.feed_id,
             and_count: self.and_count,
             xor_count: self.xor_count,
+            lut_count: self.lut_count,
         })
     }
 }
 
+impl AddGates for BuilderState {
+    fn add_xor_gate(&mut self, x: Node<Feed>, y: Node<Feed>) -> Node<Feed> {
+        BuilderState::add_xor_gate(self, x, y)
+    }
+
+    fn add_inv_gate(&mut self, x: Node<Feed>) -> Node<Feed> {
+        BuilderState::add_inv_gate(self, x)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use mpz_circuits_macros::evaluate;
@@ -510,4 +681,29 @@ mod test {
         // a + (a + b) = 2a + b
         assert_eq!(d, 3u8);
     }
+
+    #[test]
+    fn test_constant_output() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+
+        builder.add_output(a);
+        builder.add_output(builder.get_constant(0x0fu8));
+
+        let circ = builder.build().unwrap();
+
+        let output = evaluate!(circ, fn(42u8) -> (u8, u8)).unwrap();
+
+        assert_eq!(output, (42, 0x0f));
+    }
+
+    #[test]
+    fn test_constant_output_without_inputs() {
+        let builder = CircuitBuilder::new();
+
+        builder.add_output(builder.get_constant(1u8));
+
+        assert!(matches!(builder.build(), Err(BuilderError::ConstantOutput)));
+    }
 }
@@ -19,7 +19,11 @@ pub enum CircuitError {
 
 /// A binary circuit.
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "CircuitRepr")
+)]
 pub struct Circuit {
     pub(crate) inputs: Vec<BinaryRepr>,
     pub(crate) outputs: Vec<BinaryRepr>,
@@ -28,6 +32,43 @@ pub struct Circuit {
 
     pub(crate) and_count: usize,
     pub(crate) xor_count: usize,
+    // Not serialized, so that circuits serialized before lookup gates were added can still be
+    // deserialized. It is recounted from the gates instead.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) lut_count: usize,
+}
+
+/// The serialized form of a [`Circuit`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct CircuitRepr {
+    inputs: Vec<BinaryRepr>,
+    outputs: Vec<BinaryRepr>,
+    gates: Vec<Gate>,
+    feed_count: usize,
+    and_count: usize,
+    xor_count: usize,
+}
+
+#[cfg(feature = "serde")]
+impl From<CircuitRepr> for Circuit {
+    fn from(repr: CircuitRepr) -> Self {
+        let lut_count = repr
+            .gates
+            .iter()
+            .filter(|gate| matches!(gate, Gate::Lut(_)))
+            .count();
+
+        Self {
+            inputs: repr.inputs,
+            outputs: repr.outputs,
+            gates: repr.gates,
+            feed_count: repr.feed_count,
+            and_count: repr.and_count,
+            xor_count: repr.xor_count,
+            lut_count,
+        }
+    }
 }
 
 impl Circuit {
@@ -61,6 +102,11 @@ impl Circuit {
         self.xor_count
     }
 
+    /// Returns the number of lookup gates in the circuit.
+    pub fn lut_count(&self) -> usize {
+        self.lut_count
+    }
+
     /// Reverses the order of the inputs.
     pub fn reverse_inputs(mut self) -> Self {
         self.inputs.reverse();
@@ -160,6 +206,16 @@ impl Circuit {
 
                     feeds[z.id] = Some(!x);
                 }
+                Gate::Lut(lut) => {
+                    let idx = lut.inputs().iter().enumerate().fold(0, |idx, (i, x)| {
+                        idx | (feeds[x.id].expect("Feed should be set") as usize) << i
+                    });
+                    let row = lut.table()[idx];
+
+                    for (j, z) in lut.outputs().iter().enumerate() {
+                        feeds[z.id] = Some((row >> j) & 1 == 1);
+                    }
+                }
             }
         }
 
@@ -221,4 +277,25 @@ mod tests {
 
         assert_eq!(out, 3u8);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_lut_count() {
+        use crate::ops::Lookup;
+
+        let table: [u8; 256] = std::array::from_fn(|i| (i as u8).reverse_bits());
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a.lookup(&table));
+        let circ = builder.build().unwrap();
+
+        let circ: Circuit = bincode::deserialize(&bincode::serialize(&circ).unwrap()).unwrap();
+
+        assert_eq!(circ.lut_count(), 1);
+
+        let out = evaluate!(circ, fn(1u8) -> u8).unwrap();
+
+        assert_eq!(out, 0x80);
+    }
 }
@@ -0,0 +1,482 @@
+//! AES-128 gadgets which are traced at the bit level.
+//!
+//! The S-box is computed as an inversion in GF(2^8) followed by an affine transformation. The
+//! inversion is computed in the isomorphic tower field GF(((2^2)^2)^2), which requires 32 AND
+//! gates, with the change of basis merged into the affine transformation. Alternatively, the
+//! S-box can be computed using a single lookup gate.
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    components::{Feed, Node},
+    types::U8,
+    BuilderState,
+};
+
+/// A byte in LSB0 bit order.
+type Byte = [Node<Feed>; 8];
+
+/// An implementation of the S-box.
+type SBox = fn(&mut BuilderState, Byte) -> Byte;
+
+/// Round constants of the AES key schedule.
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// The AES S-box as a table.
+const SBOX: [u8; 256] = sbox_table();
+
+/// Encrypts a block using AES-128, computing the S-box using lookup gates.
+pub(crate) fn aes128_lut(state: &mut BuilderState, key: [U8; 16], msg: [U8; 16]) -> [U8; 16] {
+    let round_keys = expand_key(state, key.map(|byte| byte.nodes()), sbox_lut);
+
+    encrypt(state, &round_keys, msg.map(|byte| byte.nodes()), sbox_lut).map(U8::new)
+}
+
+/// Encrypts blocks using AES-128, expanding the key once for all of the blocks.
+pub(crate) fn aes128_blocks(
+    state: &mut BuilderState,
+    key: [U8; 16],
+    blocks: Vec<[U8; 16]>,
+) -> Vec<[U8; 16]> {
+    let round_keys = expand_key(state, key.map(|byte| byte.nodes()), sbox);
+
+    blocks
+        .into_iter()
+        .map(|block| encrypt(state, &round_keys, block.map(|byte| byte.nodes()), sbox).map(U8::new))
+        .collect()
+}
+
+/// Encrypts a block using the expanded round keys.
+fn encrypt(
+    state: &mut BuilderState,
+    round_keys: &[[Byte; 16]; 11],
+    msg: [Byte; 16],
+    sbox: SBox,
+) -> [Byte; 16] {
+    let mut block = add_round_key(state, msg, round_keys[0]);
+    for round_key in &round_keys[1..10] {
+        block = block.map(|byte| sbox(state, byte));
+        block = shift_rows(block);
+        block = mix_columns(state, block);
+        block = add_round_key(state, block, *round_key);
+    }
+    block = block.map(|byte| sbox(state, byte));
+    block = shift_rows(block);
+
+    add_round_key(state, block, round_keys[10])
+}
+
+/// Decrypts a block using AES-128.
+pub(crate) fn aes128_decrypt(
+    state: &mut BuilderState,
+    key: [U8; 16],
+    ciphertext: [U8; 16],
+) -> [U8; 16] {
+    let round_keys = expand_key(state, key.map(|byte| byte.nodes()), sbox);
+
+    let mut block = add_round_key(state, ciphertext.map(|byte| byte.nodes()), round_keys[10]);
+    for round_key in round_keys[1..10].iter().rev() {
+        block = inv_shift_rows(block);
+        block = block.map(|byte| inv_sbox(state, byte));
+        block = add_round_key(state, block, *round_key);
+        block = inv_mix_columns(state, block);
+    }
+    block = inv_shift_rows(block);
+    block = block.map(|byte| inv_sbox(state, byte));
+    block = add_round_key(state, block, round_keys[0]);
+
+    block.map(U8::new)
+}
+
+/// Expands the key into the 11 round keys.
+fn expand_key(state: &mut BuilderState, key: [Byte; 16], sbox: SBox) -> [[Byte; 16]; 11] {
+    let mut words: Vec<[Byte; 4]> = key.chunks(4).map(|word| word.try_into().unwrap()).collect();
+
+    for i in 4..44 {
+        let mut temp = words[i - 1];
+        if i % 4 == 0 {
+            // SubWord(RotWord(temp)) ^ Rcon
+            temp.rotate_left(1);
+            temp = temp.map(|byte| sbox(state, byte));
+            temp[0] = xor_const(state, temp[0], RCON[i / 4 - 1]);
+        }
+
+        let prev = words[i - 4];
+        words.push(std::array::from_fn(|j| xor(state, prev[j], temp[j])));
+    }
+
+    std::array::from_fn(|round| std::array::from_fn(|i| words[4 * round + i / 4][i % 4]))
+}
+
+fn add_round_key(state: &mut BuilderState, block: [Byte; 16], key: [Byte; 16]) -> [Byte; 16] {
+    std::array::from_fn(|i| xor(state, block[i], key[i]))
+}
+
+/// The bytes of the block are in column-major order, so row `r` is rotated left by `r`.
+fn shift_rows(block: [Byte; 16]) -> [Byte; 16] {
+    std::array::from_fn(|i| {
+        let (row, col) = (i % 4, i / 4);
+        block[row + 4 * ((col + row) % 4)]
+    })
+}
+
+/// The bytes of the block are in column-major order, so row `r` is rotated right by `r`.
+fn inv_shift_rows(block: [Byte; 16]) -> [Byte; 16] {
+    std::array::from_fn(|i| {
+        let (row, col) = (i % 4, i / 4);
+        block[row + 4 * ((col + 4 - row) % 4)]
+    })
+}
+
+fn mix_columns(state: &mut BuilderState, block: [Byte; 16]) -> [Byte; 16] {
+    const COEFFS: [[u8; 4]; 4] = [[2, 3, 1, 1], [1, 2, 3, 1], [1, 1, 2, 3], [3, 1, 1, 2]];
+
+    mix(state, block, &COEFFS)
+}
+
+fn inv_mix_columns(state: &mut BuilderState, block: [Byte; 16]) -> [Byte; 16] {
+    const COEFFS: [[u8; 4]; 4] = [
+        [14, 11, 13, 9],
+        [9, 14, 11, 13],
+        [13, 9, 14, 11],
+        [11, 13, 9, 14],
+    ];
+
+    mix(state, block, &COEFFS)
+}
+
+/// Multiplies each column of the block by the matrix of coefficients.
+fn mix(state: &mut BuilderState, block: [Byte; 16], matrix: &[[u8; 4]; 4]) -> [Byte; 16] {
+    let mut out = block;
+    for (col, column) in block.chunks(4).enumerate() {
+        for (row, coeffs) in matrix.iter().enumerate() {
+            let mut byte = mul_const(state, column[0], coeffs[0]);
+            for (value, coeff) in column.iter().zip(coeffs).skip(1) {
+                let product = mul_const(state, *value, *coeff);
+                byte = xor(state, byte, product);
+            }
+            out[4 * col + row] = byte;
+        }
+    }
+
+    out
+}
+
+/// AES S-box.
+fn sbox(state: &mut BuilderState, x: Byte) -> Byte {
+    let tower = &*TOWER;
+
+    let x = linear(state, &x, |v| tower.to_tower[v as usize]);
+    let b = inv_gf256(state, x.try_into().unwrap());
+
+    // B'_i = B_i ⊕ B_i+4 ⊕ B_i+5 ⊕ B_i+6 ⊕ B_i+7 ⊕ C_i
+    let affine = linear(state, &b, |v| {
+        let b = tower.from_tower[v as usize];
+        b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4)
+    });
+
+    xor_const(state, affine.try_into().unwrap(), 0x63)
+}
+
+/// AES S-box, computed using a single lookup gate.
+fn sbox_lut(state: &mut BuilderState, x: Byte) -> Byte {
+    state
+        .add_lut_gate(&x, &SBOX.map(u64::from), 8)
+        .try_into()
+        .expect("S-box should have 8 outputs")
+}
+
+/// Inverse AES S-box.
+fn inv_sbox(state: &mut BuilderState, y: Byte) -> Byte {
+    let tower = &*TOWER;
+
+    // B_i = Y_i+2 ⊕ Y_i+5 ⊕ Y_i+7 ⊕ D_i
+    let affine = linear(state, &y, |v| {
+        tower.to_tower[(v.rotate_left(1) ^ v.rotate_left(3) ^ v.rotate_left(6)) as usize]
+    });
+    let b = xor_const(state, affine.try_into().unwrap(), tower.to_tower[0x05]);
+
+    let x = inv_gf256(state, b);
+
+    linear(state, &x, |v| tower.from_tower[v as usize])
+        .try_into()
+        .unwrap()
+}
+
+/// The tower field GF(((2^2)^2)^2), which is isomorphic to the AES field.
+///
+/// An element of GF(2^2) is `a_0 + a_1 W` with `W^2 = W + 1`, an element of GF(2^4) is
+/// `a_0 + a_1 Z` with `Z^2 = Z + W`, and an element of GF(2^8) is `a_0 + a_1 Y` with
+/// `Y^2 = Y + λ`. In each case `a_0` is stored in the low bits.
+struct Tower {
+    /// The constant `λ` in GF(2^4).
+    lambda: u8,
+    /// Maps an element of the AES field to the tower field.
+    to_tower: [u8; 256],
+    /// Maps an element of the tower field to the AES field.
+    from_tower: [u8; 256],
+}
+
+static TOWER: Lazy<Tower> = Lazy::new(|| {
+    // Y^2 + Y + λ must be irreducible over GF(2^4).
+    let lambda = (1..16)
+        .find(|&lambda| (0..16).all(|t| gf16_mul(t, t) ^ t ^ lambda != 0))
+        .expect("irreducible polynomial should exist");
+
+    // The isomorphism maps x to a root β of the AES polynomial x^8 + x^4 + x^3 + x + 1.
+    let pow = |b: u8, e: usize| (0..e).fold(1, |acc, _| gf256_mul(acc, b, lambda));
+    let beta = (2..=255)
+        .find(|&b| pow(b, 8) ^ pow(b, 4) ^ pow(b, 3) ^ b ^ 1 == 0)
+        .expect("AES polynomial should have a root in the tower field");
+
+    let mut to_tower = [0u8; 256];
+    let mut from_tower = [0u8; 256];
+    for a in 0..=255u8 {
+        let t = (0..8)
+            .filter(|i| (a >> i) & 1 == 1)
+            .fold(0, |acc, i| acc ^ pow(beta, i));
+        to_tower[a as usize] = t;
+        from_tower[t as usize] = a;
+    }
+
+    Tower {
+        lambda,
+        to_tower,
+        from_tower,
+    }
+});
+
+/// Inversion in GF(2^8) of the tower field, mapping 0 to 0.
+///
+/// Requires 32 AND gates.
+fn inv_gf256(state: &mut BuilderState, a: Byte) -> Byte {
+    let lambda = TOWER.lambda;
+    let (a_0, a_1): ([_; 4], [_; 4]) = (a[..4].try_into().unwrap(), a[4..].try_into().unwrap());
+
+    // d = a_0^2 + a_0 a_1 + λ a_1^2, then a^-1 = a_1 d^-1 Y + (a_0 + a_1) d^-1
+    let d = linear(state, &a, |v| {
+        let (a_0, a_1) = (v & 0xf, v >> 4);
+        gf16_mul(a_0, a_0) ^ gf16_mul(lambda, gf16_mul(a_1, a_1))
+    });
+    let a_0_1 = mul_gf16(state, a_0, a_1);
+    let d = std::array::from_fn(|i| state.add_xor_gate(d[i], a_0_1[i]));
+    let d_inv = inv_gf16(state, d);
+
+    let a_sum = std::array::from_fn(|i| state.add_xor_gate(a_0[i], a_1[i]));
+    let c_0 = mul_gf16(state, a_sum, d_inv);
+    let c_1 = mul_gf16(state, a_1, d_inv);
+
+    std::array::from_fn(|i| if i < 4 { c_0[i] } else { c_1[i - 4] })
+}
+
+/// Inversion in GF(2^4) of the tower field, mapping 0 to 0.
+///
+/// Requires 5 AND gates, using the inversion of Boyar and Peralta, "A depth-16 circuit for the
+/// AES S-box" (<https://eprint.iacr.org/2011/332>), in the basis of the tower field.
+fn inv_gf16(state: &mut BuilderState, a: [Node<Feed>; 4]) -> [Node<Feed>; 4] {
+    // Change of basis to the inputs of the circuit.
+    let t21 = state.add_xor_gate(a[0], a[1]);
+    let (t22, t23, t24) = (a[1], a[3], a[2]);
+
+    let t25 = state.add_xor_gate(t21, t22);
+    let t26 = state.add_and_gate(t21, t23);
+    let t27 = state.add_xor_gate(t24, t26);
+    let t28 = state.add_and_gate(t25, t27);
+    let t29 = state.add_xor_gate(t28, t22);
+    let t30 = state.add_xor_gate(t23, t24);
+    let t31 = state.add_xor_gate(t22, t26);
+    let t32 = state.add_and_gate(t31, t30);
+    let t33 = state.add_xor_gate(t32, t24);
+    let t34 = state.add_xor_gate(t23, t33);
+    let t35 = state.add_xor_gate(t27, t33);
+    let t36 = state.add_and_gate(t24, t35);
+    let t37 = state.add_xor_gate(t36, t34);
+    let t38 = state.add_xor_gate(t27, t36);
+    let t39 = state.add_and_gate(t29, t38);
+    let t40 = state.add_xor_gate(t25, t39);
+
+    // Change of basis from the outputs of the circuit.
+    let t29_33 = state.add_xor_gate(t29, t33);
+    [
+        state.add_xor_gate(t29_33, t40),
+        state.add_xor_gate(t29, t37),
+        t33,
+        t37,
+    ]
+}
+
+/// Multiplication in GF(2^4) of the tower field, requiring 9 AND gates.
+fn mul_gf16(state: &mut BuilderState, a: [Node<Feed>; 4], b: [Node<Feed>; 4]) -> [Node<Feed>; 4] {
+    let (a_0, a_1) = ([a[0], a[1]], [a[2], a[3]]);
+    let (b_0, b_1) = ([b[0], b[1]], [b[2], b[3]]);
+
+    // (a_0 + a_1 Z)(b_0 + b_1 Z) = (p + W q) + (r + p) Z, where p = a_0 b_0, q = a_1 b_1
+    // and r = (a_0 + a_1)(b_0 + b_1).
+    let p = mul_gf4(state, a_0, b_0);
+    let q = mul_gf4(state, a_1, b_1);
+    let a_sum = [
+        state.add_xor_gate(a_0[0], a_1[0]),
+        state.add_xor_gate(a_0[1], a_1[1]),
+    ];
+    let b_sum = [
+        state.add_xor_gate(b_0[0], b_1[0]),
+        state.add_xor_gate(b_0[1], b_1[1]),
+    ];
+    let r = mul_gf4(state, a_sum, b_sum);
+
+    // W q = q_1 + (q_0 + q_1) W
+    let q_w = [q[1], state.add_xor_gate(q[0], q[1])];
+
+    [
+        state.add_xor_gate(p[0], q_w[0]),
+        state.add_xor_gate(p[1], q_w[1]),
+        state.add_xor_gate(r[0], p[0]),
+        state.add_xor_gate(r[1], p[1]),
+    ]
+}
+
+/// Multiplication in GF(2^2) of the tower field, requiring 3 AND gates.
+fn mul_gf4(state: &mut BuilderState, a: [Node<Feed>; 2], b: [Node<Feed>; 2]) -> [Node<Feed>; 2] {
+    // (a_0 + a_1 W)(b_0 + b_1 W) = (p + q) + (r + p) W, where p = a_0 b_0, q = a_1 b_1
+    // and r = (a_0 + a_1)(b_0 + b_1).
+    let p = state.add_and_gate(a[0], b[0]);
+    let q = state.add_and_gate(a[1], b[1]);
+    let a_sum = state.add_xor_gate(a[0], a[1]);
+    let b_sum = state.add_xor_gate(b[0], b[1]);
+    let r = state.add_and_gate(a_sum, b_sum);
+
+    [state.add_xor_gate(p, q), state.add_xor_gate(r, p)]
+}
+
+/// Applies a linear map `f` over GF(2) to the bits of `x`, returning as many bits as `x` has.
+fn linear(state: &mut BuilderState, x: &[Node<Feed>], f: impl Fn(u8) -> u8) -> Vec<Node<Feed>> {
+    let columns: Vec<u8> = (0..x.len()).map(|i| f(1 << i)).collect();
+
+    (0..x.len())
+        .map(|j| {
+            x.iter()
+                .zip(&columns)
+                .filter(|(_, column)| (*column >> j) & 1 == 1)
+                .fold(None, |acc, (bit, _)| match acc {
+                    None => Some(*bit),
+                    Some(acc) => Some(state.add_xor_gate(acc, *bit)),
+                })
+                .unwrap_or_else(|| state.get_const_zero())
+        })
+        .collect()
+}
+
+/// Multiplication by a constant in GF(2^8), which is linear.
+fn mul_const(state: &mut BuilderState, a: Byte, c: u8) -> Byte {
+    let mut product = [state.get_const_zero(); 8];
+    let mut a = a;
+    for i in 0..8 {
+        if (c >> i) & 1 == 1 {
+            product = xor(state, product, a);
+        }
+        a = xtime(state, a);
+    }
+
+    product
+}
+
+/// Multiplication by x in GF(2^8).
+fn xtime(state: &mut BuilderState, a: Byte) -> Byte {
+    [
+        a[7],
+        state.add_xor_gate(a[0], a[7]),
+        a[1],
+        state.add_xor_gate(a[2], a[7]),
+        state.add_xor_gate(a[3], a[7]),
+        a[4],
+        a[5],
+        a[6],
+    ]
+}
+
+/// Computes the table of the S-box, see [`sbox`].
+const fn sbox_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let x = i as u8;
+
+        // x^254 = x^240 * x^14
+        let x_2 = gf_mul(x, x);
+        let x_3 = gf_mul(x_2, x);
+        let x_12 = gf_mul(gf_mul(x_3, x_3), gf_mul(x_3, x_3));
+        let x_15 = gf_mul(x_12, x_3);
+        let x_14 = gf_mul(x_12, x_2);
+        let mut x_240 = x_15;
+        let mut j = 0;
+        while j < 4 {
+            x_240 = gf_mul(x_240, x_240);
+            j += 1;
+        }
+        let b = gf_mul(x_240, x_14);
+
+        table[i] =
+            b ^ b.rotate_left(1) ^ b.rotate_left(2) ^ b.rotate_left(3) ^ b.rotate_left(4) ^ 0x63;
+        i += 1;
+    }
+
+    table
+}
+
+/// Multiplication in GF(2^8) of bytes.
+const fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 == 1 {
+            product ^= a;
+        }
+        a = (a << 1) ^ if a & 0x80 != 0 { 0x1b } else { 0 };
+        b >>= 1;
+    }
+
+    product
+}
+
+/// Multiplication in GF(2^2) of the tower field.
+fn gf4_mul(a: u8, b: u8) -> u8 {
+    let bit = |x: u8, i: u8| (x >> i) & 1;
+    let p = bit(a, 0) & bit(b, 0);
+    let q = bit(a, 1) & bit(b, 1);
+    let r = (bit(a, 0) ^ bit(a, 1)) & (bit(b, 0) ^ bit(b, 1));
+
+    (p ^ q) | ((r ^ p) << 1)
+}
+
+/// Multiplication in GF(2^4) of the tower field.
+fn gf16_mul(a: u8, b: u8) -> u8 {
+    let (a_0, a_1, b_0, b_1) = (a & 0x3, a >> 2, b & 0x3, b >> 2);
+    let p = gf4_mul(a_0, b_0);
+    let q = gf4_mul(a_1, b_1);
+    let r = gf4_mul(a_0 ^ a_1, b_0 ^ b_1);
+
+    (p ^ gf4_mul(0x2, q)) | ((r ^ p) << 2)
+}
+
+/// Multiplication in GF(2^8) of the tower field with constant `λ`.
+fn gf256_mul(a: u8, b: u8, lambda: u8) -> u8 {
+    let (a_0, a_1, b_0, b_1) = (a & 0xf, a >> 4, b & 0xf, b >> 4);
+    let p = gf16_mul(a_0, b_0);
+    let q = gf16_mul(a_1, b_1);
+    let r = gf16_mul(a_0 ^ a_1, b_0 ^ b_1);
+
+    (p ^ gf16_mul(lambda, q)) | ((r ^ p) << 4)
+}
+
+fn xor(state: &mut BuilderState, a: Byte, b: Byte) -> Byte {
+    std::array::from_fn(|i| state.add_xor_gate(a[i], b[i]))
+}
+
+fn xor_const(state: &mut BuilderState, a: Byte, c: u8) -> Byte {
+    std::array::from_fn(|i| {
+        if (c >> i) & 1 == 1 {
+            state.add_inv_gate(a[i])
+        } else {
+            a[i]
+        }
+    })
+}
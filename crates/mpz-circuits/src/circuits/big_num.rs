@@ -5,9 +5,11 @@ use std::cell::RefCell;
 use itybity::IntoBits;
 
 use crate::{
-    ops::binary::{switch_nbit, wrapping_add_nbit, wrapping_sub_nbit},
+    ops::binary::{
+        div_rem_nbit, karatsuba_mul_nbit, switch_nbit, wrapping_add_nbit, wrapping_sub_nbit,
+    },
     types::U8,
-    BuilderState, Node, Tracer,
+    BuilderState, Feed, Node, Tracer,
 };
 
 /// Add two numbers modulo a constant modulus.
@@ -79,9 +81,127 @@ pub fn nbyte_add_mod_trace<'a, const N: usize>(
     sum_reduced.map(|v| Tracer::new(state, v))
 }
 
+/// Subtract two numbers modulo a constant modulus.
+///
+/// This circuit assumes that the operands are in the range [0, modulus).
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The minuend encoded as an array of bytes in big-endian order.
+/// * `b` - The subtrahend encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (a - b) % modulus
+pub fn nbyte_sub_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    let a_bits = to_bits(a);
+    let b_bits = to_bits(b);
+    let modulus_bits = const_to_bits(modulus);
+
+    let mut state_mut = state.borrow_mut();
+
+    let (diff, underflow) = wrapping_sub_nbit(&mut state_mut, &a_bits, &b_bits);
+
+    let diff_wrapped = wrapping_add_nbit(&mut state_mut, &diff, &modulus_bits);
+
+    // if a < b { a - b + modulus } else { a - b }
+    let diff_reduced = switch_nbit(&mut state_mut, &diff, &diff_wrapped, underflow);
+
+    drop(state_mut);
+
+    from_bits(state, &diff_reduced)
+}
+
+/// Multiply two numbers modulo a constant modulus.
+///
+/// The full product is computed using Karatsuba multiplication and then reduced, so the
+/// operands do not need to be reduced.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `a` - The first number encoded as an array of bytes in big-endian order.
+/// * `b` - The second number encoded as an array of bytes in big-endian order.
+/// * `modulus` - The modulus encoded as an array of bytes in big-endian order.
+///
+/// # Returns
+///
+/// (a * b) % modulus
+///
+/// # Panics
+///
+/// Panics if the modulus is zero.
+pub fn nbyte_mul_mod_trace<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    a: [Tracer<'a, U8>; N],
+    b: [Tracer<'a, U8>; N],
+    modulus: [u8; N],
+) -> [Tracer<'a, U8>; N] {
+    assert!(modulus.iter().any(|byte| *byte != 0), "modulus is zero");
+
+    let a_bits = to_bits(a);
+    let b_bits = to_bits(b);
+    let modulus_bits = const_to_bits(modulus);
+
+    let mut state_mut = state.borrow_mut();
+
+    let product = karatsuba_mul_nbit(&mut state_mut, &a_bits, &b_bits);
+
+    let (_, rem) = div_rem_nbit(&mut state_mut, &product, &modulus_bits);
+
+    drop(state_mut);
+
+    from_bits(state, &rem)
+}
+
+/// Converts a big-endian byte array into bits in little-endian order.
+fn to_bits<const N: usize>(bytes: [Tracer<'_, U8>; N]) -> Vec<Node<Feed>> {
+    bytes
+        .into_iter()
+        .rev()
+        .flat_map(|byte| byte.to_inner().nodes())
+        .collect()
+}
+
+/// Converts a constant big-endian byte array into bits in little-endian order.
+fn const_to_bits<const N: usize>(bytes: [u8; N]) -> Vec<Node<Feed>> {
+    bytes
+        .into_iter()
+        .rev()
+        .flat_map(|byte| byte.into_iter_lsb0())
+        .map(|bit| Node::new(bit as usize))
+        .collect()
+}
+
+/// Converts bits in little-endian order into a big-endian byte array.
+fn from_bits<'a, const N: usize>(
+    state: &'a RefCell<BuilderState>,
+    bits: &[Node<Feed>],
+) -> [Tracer<'a, U8>; N] {
+    let mut bytes: [U8; N] = bits
+        .chunks(8)
+        .map(|chunk| U8::new(chunk.try_into().unwrap()))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+
+    bytes.reverse();
+
+    bytes.map(|v| Tracer::new(state, v))
+}
+
 #[cfg(test)]
 mod tests {
     use mpz_circuits_macros::evaluate;
+    use num_bigint::BigUint;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
 
     use crate::CircuitBuilder;
 
@@ -112,4 +232,68 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_nbyte_sub_mod() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 2>();
+        let b = builder.add_array_input::<u8, 2>();
+        let modulus = [0u8, 239u8];
+
+        let diff = nbyte_sub_mod_trace(builder.state(), a, b, modulus).map(|v| v.to_inner());
+
+        builder.add_output(diff);
+
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..modulus[1] {
+            for b in 0u8..modulus[1] {
+                let expected_diff =
+                    ((a as u16 + modulus[1] as u16 - b as u16) % modulus[1] as u16) as u8;
+
+                let diff: [u8; 2] = evaluate!(circ, fn([0u8, a], [0u8, b]) -> [u8; 2]).unwrap();
+                let diff = u16::from_be_bytes(diff) as u8;
+
+                assert_eq!(diff, expected_diff);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nbyte_mul_mod() {
+        // P-256 base field prime
+        let modulus: [u8; 32] = BigUint::parse_bytes(
+            b"ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+            16,
+        )
+        .unwrap()
+        .to_bytes_be()
+        .try_into()
+        .unwrap();
+
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_array_input::<u8, 32>();
+        let b = builder.add_array_input::<u8, 32>();
+
+        let product = nbyte_mul_mod_trace(builder.state(), a, b, modulus).map(|v| v.to_inner());
+
+        builder.add_output(product);
+
+        let circ = builder.build().unwrap();
+
+        let p = BigUint::from_bytes_be(&modulus);
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..8 {
+            let a: [u8; 32] = rng.gen();
+            let b: [u8; 32] = rng.gen();
+
+            let expected = (BigUint::from_bytes_be(&a) * BigUint::from_bytes_be(&b)) % &p;
+
+            let product: [u8; 32] = evaluate!(circ, fn(a, b) -> [u8; 32]).unwrap();
+
+            assert_eq!(BigUint::from_bytes_be(&product), expected);
+        }
+    }
 }
@@ -1,5 +1,7 @@
 //! Pre-built circuits for MPC.
 
+#[cfg(feature = "aes")]
+mod aes_gadgets;
 pub mod big_num;
 
 use once_cell::sync::Lazy;
@@ -21,6 +23,95 @@ pub static AES128: Lazy<Arc<Circuit>> = Lazy::new(|| {
     Arc::new(bincode::deserialize(bytes).unwrap())
 });
 
+/// AES-128 circuit which computes the S-box using lookup gates.
+///
+/// This circuit has no AND gates, but requires a protocol which supports lookup gates, see
+/// [`LookupTable`](crate::LookupTable). Its garbled size is about 6.5MB, compared to about
+/// 205KB for [`AES128`]. It has the same signature as [`AES128`]:
+///
+/// `fn(key: [u8; 16], msg: [u8; 16]) -> [u8; 16]`
+#[cfg(feature = "aes")]
+pub static AES128_LUT: Lazy<Arc<Circuit>> = Lazy::new(|| {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 16>();
+    let msg = builder.add_array_input::<u8, 16>();
+
+    let ciphertext = aes_gadgets::aes128_lut(
+        &mut builder.state().borrow_mut(),
+        key.map(|byte| byte.to_inner()),
+        msg.map(|byte| byte.to_inner()),
+    );
+
+    builder.add_output(ciphertext);
+
+    Arc::new(builder.build().expect("circuit is valid"))
+});
+
+/// AES-128 decryption circuit.
+///
+/// The circuit has the following signature:
+///
+/// `fn(key: [u8; 16], ciphertext: [u8; 16]) -> [u8; 16]`
+#[cfg(feature = "aes")]
+pub static AES128_DECRYPT: Lazy<Arc<Circuit>> = Lazy::new(|| {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 16>();
+    let ciphertext = builder.add_array_input::<u8, 16>();
+
+    let msg = aes_gadgets::aes128_decrypt(
+        &mut builder.state().borrow_mut(),
+        key.map(|byte| byte.to_inner()),
+        ciphertext.map(|byte| byte.to_inner()),
+    );
+
+    builder.add_output(msg);
+
+    Arc::new(builder.build().expect("circuit is valid"))
+});
+
+/// AES-128-GCM setup circuit.
+///
+/// Computes the GHASH key `H = AES(key, 0^128)` and the encrypted pre-counter block
+/// `AES(key, J0)` which masks the authentication tag, where `J0 = iv || 0^31 || 1`.
+///
+/// The circuit has the following signature:
+///
+/// `fn(key: [u8; 16], iv: [u8; 12]) -> ([u8; 16], [u8; 16])`
+#[cfg(feature = "aes")]
+pub static AES128_GCM_SETUP: Lazy<Arc<Circuit>> = Lazy::new(|| {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 16>();
+    let iv = builder.add_array_input::<u8, 12>();
+
+    let (h, j0) = aes128_gcm_setup_trace(builder.state(), key, iv);
+
+    builder.add_output(h);
+    builder.add_output(j0);
+
+    Arc::new(builder.build().expect("circuit is valid"))
+});
+
+/// ChaCha20 block function circuit.
+///
+/// Computes a 64-byte block of keystream as specified in RFC 8439.
+///
+/// The circuit has the following signature:
+///
+/// `fn(key: [u8; 32], nonce: [u8; 12], counter: u32) -> [u8; 64]`
+#[cfg(feature = "chacha20")]
+pub static CHACHA20: Lazy<Arc<Circuit>> = Lazy::new(|| {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 32>();
+    let nonce = builder.add_array_input::<u8, 12>();
+    let counter = builder.add_input::<u32>();
+
+    let block = chacha20_block_trace(builder.state(), key, nonce, counter);
+
+    builder.add_output(block);
+
+    Arc::new(builder.build().expect("circuit is valid"))
+});
+
 /// SHA-256 circuit.
 ///
 /// The circuit has the following signature:
@@ -32,6 +123,12 @@ pub static SHA256_COMPRESS: Lazy<Arc<Circuit>> = Lazy::new(|| {
     Arc::new(bincode::deserialize(bytes).unwrap())
 });
 
+/// The SHA-256 initial state.
+#[cfg(feature = "sha2")]
+pub const SHA256_INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
 /// AES-128 circuit trace.
 ///
 /// This function is a wrapper around the AES-128 circuit that can be used to append
@@ -66,6 +163,239 @@ pub fn aes128_trace<'a>(
     ciphertext.map(|value| Tracer::new(state, value.try_into().unwrap()))
 }
 
+/// AES-128 decryption circuit trace.
+///
+/// This function is a wrapper around the AES-128 decryption circuit that can be used to append
+/// it to other circuits.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `key` - The key to use.
+/// * `ciphertext` - The ciphertext to decrypt.
+///
+/// # Returns
+///
+/// The message.
+#[cfg(feature = "aes")]
+pub fn aes128_decrypt_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    key: [Tracer<'a, U8>; 16],
+    ciphertext: [Tracer<'a, U8>; 16],
+) -> [Tracer<'a, U8>; 16] {
+    let mut outputs = state
+        .borrow_mut()
+        .append(&AES128_DECRYPT, &[key.into(), ciphertext.into()])
+        .expect("aes 128 decrypt should append successfully");
+
+    let BinaryRepr::Array(msg) = outputs.pop().unwrap() else {
+        panic!("aes 128 decrypt should have array output");
+    };
+
+    let msg: [_; 16] = msg.try_into().unwrap();
+
+    msg.map(|value| Tracer::new(state, value.try_into().unwrap()))
+}
+
+/// AES-128-CTR keystream circuit trace.
+///
+/// The counter block of the `i`-th block of the keystream is `nonce || ctr + i`, where the
+/// counter is encoded as a 32-bit big-endian integer which wraps on overflow. For AES-GCM, the
+/// nonce is the IV and the counter of the first block is 2.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `key` - The key to use.
+/// * `nonce` - The nonce.
+/// * `ctr` - The counter of the first block.
+/// * `block_count` - The number of blocks of keystream.
+///
+/// # Returns
+///
+/// The keystream, which is `16 * block_count` bytes long.
+#[cfg(feature = "aes")]
+pub fn aes128_ctr_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    key: [Tracer<'a, U8>; 16],
+    nonce: [Tracer<'a, U8>; 12],
+    ctr: Tracer<'a, U32>,
+    block_count: usize,
+) -> Vec<Tracer<'a, U8>> {
+    use crate::ops::WrappingAdd;
+
+    let blocks = (0..block_count)
+        .map(|i| {
+            let ctr = ctr.wrapping_add(i as u32).to_be_bytes();
+            std::array::from_fn(|j| {
+                if j < 12 {
+                    nonce[j].to_inner()
+                } else {
+                    ctr[j - 12].to_inner()
+                }
+            })
+        })
+        .collect();
+
+    // The key is expanded once, rather than once per block as in `aes128_trace`.
+    let keystream = aes_gadgets::aes128_blocks(
+        &mut state.borrow_mut(),
+        key.map(|byte| byte.to_inner()),
+        blocks,
+    );
+
+    keystream
+        .into_iter()
+        .flatten()
+        .map(|byte| Tracer::new(state, byte))
+        .collect()
+}
+
+/// Builds a circuit to compute the AES-128-CTR keystream.
+///
+/// See [`aes128_ctr_trace`] for more information.
+///
+/// # Arguments
+///
+/// * `block_count` - The number of blocks of keystream.
+///
+/// # Returns a circuit with the following signature:
+///
+/// `fn(key: [u8; 16], nonce: [u8; 12], ctr: u32) -> [u8; 16 * block_count]`
+#[cfg(feature = "aes")]
+pub fn build_aes128_ctr(block_count: usize) -> Circuit {
+    let builder = CircuitBuilder::new();
+    let key = builder.add_array_input::<u8, 16>();
+    let nonce = builder.add_array_input::<u8, 12>();
+    let ctr = builder.add_input::<u32>();
+
+    let keystream = aes128_ctr_trace(builder.state(), key, nonce, ctr, block_count);
+
+    builder.add_output(keystream);
+
+    builder.build().expect("circuit is valid")
+}
+
+/// AES-128-GCM setup circuit trace.
+///
+/// See [`AES128_GCM_SETUP`] for more information.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `key` - The key to use.
+/// * `iv` - The IV.
+///
+/// # Returns
+///
+/// The GHASH key and the encrypted pre-counter block.
+#[cfg(feature = "aes")]
+pub fn aes128_gcm_setup_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    key: [Tracer<'a, U8>; 16],
+    iv: [Tracer<'a, U8>; 12],
+) -> ([Tracer<'a, U8>; 16], [Tracer<'a, U8>; 16]) {
+    let zero = state.borrow_mut().get_constant(0u8);
+    let one = state.borrow_mut().get_constant(1u8);
+
+    let j0 = std::array::from_fn(|i| match i {
+        0..=11 => iv[i].to_inner(),
+        15 => one,
+        _ => zero,
+    });
+
+    // Both blocks are encrypted with the same key, so the key is only expanded once.
+    let mut blocks = aes_gadgets::aes128_blocks(
+        &mut state.borrow_mut(),
+        key.map(|byte| byte.to_inner()),
+        vec![[zero; 16], j0],
+    );
+
+    let j0 = blocks.pop().expect("j0 should be encrypted");
+    let h = blocks.pop().expect("h should be encrypted");
+
+    (
+        h.map(|byte| Tracer::new(state, byte)),
+        j0.map(|byte| Tracer::new(state, byte)),
+    )
+}
+
+/// ChaCha20 block function circuit trace.
+///
+/// See [`CHACHA20`] for more information.
+///
+/// # Arguments
+///
+/// * `state` - The builder state to append the circuit to.
+/// * `key` - The key to use.
+/// * `nonce` - The nonce.
+/// * `counter` - The block counter.
+///
+/// # Returns
+///
+/// The block of keystream.
+#[cfg(feature = "chacha20")]
+pub fn chacha20_block_trace<'a>(
+    state: &'a RefCell<BuilderState>,
+    key: [Tracer<'a, U8>; 32],
+    nonce: [Tracer<'a, U8>; 12],
+    counter: Tracer<'a, U32>,
+) -> [Tracer<'a, U8>; 64] {
+    use crate::ops::WrappingAdd;
+
+    // "expand 32-byte k"
+    const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+    let word = |bytes: &[Tracer<'a, U8>]| {
+        Tracer::<U32>::from_le_bytes(bytes.try_into().expect("word is 4 bytes"))
+    };
+
+    let mut initial_state = Vec::with_capacity(16);
+    initial_state
+        .extend(CONSTANTS.map(|value| Tracer::new(state, state.borrow_mut().get_constant(value))));
+    initial_state.extend(key.chunks(4).map(word));
+    initial_state.push(counter);
+    initial_state.extend(nonce.chunks(4).map(word));
+
+    let initial_state: [_; 16] = initial_state.try_into().expect("state is 16 words");
+
+    let mut x = initial_state;
+    for _ in 0..10 {
+        // Column rounds
+        chacha20_quarter_round(&mut x, 0, 4, 8, 12);
+        chacha20_quarter_round(&mut x, 1, 5, 9, 13);
+        chacha20_quarter_round(&mut x, 2, 6, 10, 14);
+        chacha20_quarter_round(&mut x, 3, 7, 11, 15);
+        // Diagonal rounds
+        chacha20_quarter_round(&mut x, 0, 5, 10, 15);
+        chacha20_quarter_round(&mut x, 1, 6, 11, 12);
+        chacha20_quarter_round(&mut x, 2, 7, 8, 13);
+        chacha20_quarter_round(&mut x, 3, 4, 9, 14);
+    }
+
+    let block = x
+        .into_iter()
+        .zip(initial_state)
+        .flat_map(|(x, initial)| x.wrapping_add(initial).to_le_bytes())
+        .collect::<Vec<_>>();
+
+    block.try_into().expect("block is 64 bytes")
+}
+
+#[cfg(feature = "chacha20")]
+fn chacha20_quarter_round(x: &mut [Tracer<'_, U32>; 16], a: usize, b: usize, c: usize, d: usize) {
+    use crate::ops::WrappingAdd;
+
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
 /// SHA-256 compression circuit trace.
 ///
 /// This function is a wrapper around the SHA256 compression circuit that can be used to append
@@ -195,6 +525,182 @@ pub fn sha256_trace<'a>(
     hash.map(|value| Tracer::new(builder_state, value.try_into().unwrap()))
 }
 
+/// HMAC-SHA256 pad states.
+///
+/// These are the SHA-256 states after compressing the key XORed with the inner and outer pads.
+/// They only depend on the key, so they can be computed once with [`hmac_sha256_pads_trace`]
+/// and reused to authenticate many messages.
+#[cfg(feature = "sha2")]
+#[derive(Debug, Clone, Copy)]
+pub struct HmacSha256Pads<'a> {
+    /// The state after compressing `key ^ ipad`.
+    pub inner: [Tracer<'a, U32>; 8],
+    /// The state after compressing `key ^ opad`.
+    pub outer: [Tracer<'a, U32>; 8],
+}
+
+/// HMAC-SHA256 pad states circuit trace.
+///
+/// Keys longer than the block size are hashed first, as specified in RFC 2104.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `key` - The HMAC key.
+#[cfg(feature = "sha2")]
+pub fn hmac_sha256_pads_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    key: &[Tracer<'a, U8>],
+) -> HmacSha256Pads<'a> {
+    const IPAD: u8 = 0x36;
+    const OPAD: u8 = 0x5c;
+
+    let initial_state = SHA256_INITIAL_STATE.map(|value| {
+        Tracer::new(
+            builder_state,
+            builder_state.borrow_mut().get_constant(value),
+        )
+    });
+
+    let key = if key.len() > 64 {
+        sha256_trace(builder_state, initial_state, 0, key).to_vec()
+    } else {
+        key.to_vec()
+    };
+
+    let zero = Tracer::new(builder_state, builder_state.borrow_mut().get_constant(0u8));
+    let block = |pad: u8| std::array::from_fn(|i| key.get(i).copied().unwrap_or(zero) ^ pad);
+
+    HmacSha256Pads {
+        inner: sha256_compress_trace(builder_state, initial_state, block(IPAD)),
+        outer: sha256_compress_trace(builder_state, initial_state, block(OPAD)),
+    }
+}
+
+/// HMAC-SHA256 circuit trace using precomputed pad states.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `pads` - The pad states of the key, see [`hmac_sha256_pads_trace`].
+/// * `msg` - The message to authenticate.
+#[cfg(feature = "sha2")]
+pub fn hmac_sha256_with_pads_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    pads: HmacSha256Pads<'a>,
+    msg: &[Tracer<'a, U8>],
+) -> [Tracer<'a, U8>; 32] {
+    let inner = sha256_trace(builder_state, pads.inner, 64, msg);
+    sha256_trace(builder_state, pads.outer, 64, &inner)
+}
+
+/// HMAC-SHA256 circuit trace.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `key` - The HMAC key.
+/// * `msg` - The message to authenticate.
+#[cfg(feature = "sha2")]
+pub fn hmac_sha256_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    key: &[Tracer<'a, U8>],
+    msg: &[Tracer<'a, U8>],
+) -> [Tracer<'a, U8>; 32] {
+    let pads = hmac_sha256_pads_trace(builder_state, key);
+    hmac_sha256_with_pads_trace(builder_state, pads, msg)
+}
+
+/// HKDF-SHA256 extract circuit trace, see RFC 5869.
+///
+/// An empty salt is equivalent to a salt of 32 zero bytes.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `salt` - The salt.
+/// * `ikm` - The input keying material.
+///
+/// # Returns
+///
+/// The pseudorandom key.
+#[cfg(feature = "sha2")]
+pub fn hkdf_extract_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    salt: &[Tracer<'a, U8>],
+    ikm: &[Tracer<'a, U8>],
+) -> [Tracer<'a, U8>; 32] {
+    hmac_sha256_trace(builder_state, salt, ikm)
+}
+
+/// HKDF-SHA256 expand circuit trace using the precomputed pad states of the pseudorandom key.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `pads` - The pad states of the pseudorandom key, see [`hmac_sha256_pads_trace`].
+/// * `info` - The context and application specific information.
+/// * `len` - The length of the output keying material in bytes.
+///
+/// # Panics
+///
+/// Panics if `len` is greater than `255 * 32`.
+#[cfg(feature = "sha2")]
+pub fn hkdf_expand_with_pads_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    pads: HmacSha256Pads<'a>,
+    info: &[Tracer<'a, U8>],
+    len: usize,
+) -> Vec<Tracer<'a, U8>> {
+    assert!(
+        len <= 255 * 32,
+        "output length must be at most 255 * 32 bytes"
+    );
+
+    let mut okm = Vec::with_capacity(len);
+    let mut block: Vec<Tracer<'a, U8>> = Vec::new();
+    for i in 1..=len.div_ceil(32) {
+        let ctr = Tracer::new(
+            builder_state,
+            builder_state.borrow_mut().get_constant(i as u8),
+        );
+
+        // T(i) = HMAC(PRK, T(i - 1) || info || i)
+        let mut msg = block;
+        msg.extend_from_slice(info);
+        msg.push(ctr);
+
+        block = hmac_sha256_with_pads_trace(builder_state, pads, &msg).to_vec();
+        okm.extend_from_slice(&block);
+    }
+    okm.truncate(len);
+
+    okm
+}
+
+/// HKDF-SHA256 expand circuit trace, see RFC 5869.
+///
+/// # Arguments
+///
+/// * `builder_state` - The builder state to append the circuit to.
+/// * `prk` - The pseudorandom key.
+/// * `info` - The context and application specific information.
+/// * `len` - The length of the output keying material in bytes.
+///
+/// # Panics
+///
+/// Panics if `len` is greater than `255 * 32`.
+#[cfg(feature = "sha2")]
+pub fn hkdf_expand_trace<'a>(
+    builder_state: &'a RefCell<BuilderState>,
+    prk: &[Tracer<'a, U8>],
+    info: &[Tracer<'a, U8>],
+    len: usize,
+) -> Vec<Tracer<'a, U8>> {
+    let pads = hmac_sha256_pads_trace(builder_state, prk);
+    hkdf_expand_with_pads_trace(builder_state, pads, info, len)
+}
+
 /// Reference SHA256 compression function implementation.
 ///
 /// # Arguments
@@ -244,11 +750,6 @@ mod tests {
 
     use mpz_circuits_macros::test_circ;
 
-    static SHA2_INITIAL_STATE: [u32; 8] = [
-        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
-        0x5be0cd19,
-    ];
-
     #[test]
     #[cfg(feature = "aes")]
     fn test_aes128() {
@@ -270,13 +771,169 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_lut() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        fn aes_128(key: [u8; 16], msg: [u8; 16]) -> [u8; 16] {
+            use aes::Aes128;
+
+            let aes = Aes128::new_from_slice(&key).unwrap();
+            let mut ciphertext = msg.into();
+            aes.encrypt_block(&mut ciphertext);
+            ciphertext.into()
+        }
+
+        // 16 S-boxes in each of the 10 rounds, and 4 in each round of the key schedule.
+        assert_eq!(AES128_LUT.lut_count(), 200);
+        assert_eq!(AES128_LUT.and_count(), 0);
+
+        test_circ!(
+            AES128_LUT,
+            aes_128,
+            fn([0u8; 16], [69u8; 16]) -> [u8; 16]
+        );
+
+        test_circ!(
+            AES128_LUT,
+            aes_128,
+            fn([42u8; 16], [0u8; 16]) -> [u8; 16]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_decrypt() {
+        use aes::cipher::{BlockDecrypt, KeyInit};
+
+        fn aes_128_decrypt(key: [u8; 16], ciphertext: [u8; 16]) -> [u8; 16] {
+            use aes::Aes128;
+
+            let aes = Aes128::new_from_slice(&key).unwrap();
+            let mut msg = ciphertext.into();
+            aes.decrypt_block(&mut msg);
+            msg.into()
+        }
+
+        // 200 S-boxes, each requiring 32 AND gates, the same as encryption.
+        assert_eq!(AES128_DECRYPT.and_count(), 200 * 32);
+        assert_eq!(AES128_DECRYPT.and_count(), AES128.and_count());
+
+        test_circ!(
+            AES128_DECRYPT,
+            aes_128_decrypt,
+            fn([0u8; 16], [69u8; 16]) -> [u8; 16]
+        );
+
+        test_circ!(
+            AES128_DECRYPT,
+            aes_128_decrypt,
+            fn([42u8; 16], [0u8; 16]) -> [u8; 16]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_ctr() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        fn aes_128_ctr(key: [u8; 16], nonce: [u8; 12], ctr: u32) -> [u8; 48] {
+            use aes::Aes128;
+
+            let aes = Aes128::new_from_slice(&key).unwrap();
+            let mut keystream = [0u8; 48];
+            for (i, chunk) in keystream.chunks_mut(16).enumerate() {
+                let mut block = [0u8; 16];
+                block[..12].copy_from_slice(&nonce);
+                block[12..].copy_from_slice(&ctr.wrapping_add(i as u32).to_be_bytes());
+
+                let mut block = block.into();
+                aes.encrypt_block(&mut block);
+                chunk.copy_from_slice(&block);
+            }
+            keystream
+        }
+
+        let circ = build_aes128_ctr(3);
+
+        // The key schedule is shared by all of the blocks.
+        assert!(circ.and_count() < 3 * AES128.and_count());
+
+        test_circ!(circ, aes_128_ctr, fn([0u8; 16], [69u8; 12], 2u32) -> [u8; 48]);
+        test_circ!(circ, aes_128_ctr, fn([42u8; 16], [1u8; 12], u32::MAX) -> [u8; 48]);
+    }
+
+    #[test]
+    #[cfg(feature = "aes")]
+    fn test_aes128_gcm_setup() {
+        use aes::cipher::{BlockEncrypt, KeyInit};
+
+        fn aes_128_gcm_setup(key: [u8; 16], iv: [u8; 12]) -> ([u8; 16], [u8; 16]) {
+            use aes::Aes128;
+
+            let aes = Aes128::new_from_slice(&key).unwrap();
+
+            let mut h = [0u8; 16].into();
+            aes.encrypt_block(&mut h);
+
+            let mut j0 = [0u8; 16];
+            j0[..12].copy_from_slice(&iv);
+            j0[15] = 1;
+
+            let mut j0 = j0.into();
+            aes.encrypt_block(&mut j0);
+
+            (h.into(), j0.into())
+        }
+
+        // The key schedule is shared, so 40 + 2 * 160 S-boxes.
+        assert_eq!(AES128_GCM_SETUP.and_count(), 360 * 32);
+
+        test_circ!(
+            AES128_GCM_SETUP,
+            aes_128_gcm_setup,
+            fn([42u8; 16], [69u8; 12]) -> ([u8; 16], [u8; 16])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "chacha20")]
+    fn test_chacha20() {
+        use mpz_circuits_macros::evaluate;
+
+        // RFC 8439 section 2.3.2 and appendix A.1 test vector 1.
+        let test_vectors: [([u8; 32], [u8; 12], u32, &str); 2] = [
+            (
+                std::array::from_fn(|i| i as u8),
+                [0, 0, 0, 0x09, 0, 0, 0, 0x4a, 0, 0, 0, 0],
+                1,
+                "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+                 d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e",
+            ),
+            (
+                [0; 32],
+                [0; 12],
+                0,
+                "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+                 da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+            ),
+        ];
+
+        for (key, nonce, counter, expected) in test_vectors {
+            let block = evaluate!(CHACHA20, fn(key, nonce, counter) -> [u8; 64]).unwrap();
+
+            assert_eq!(hex::encode(block), expected);
+        }
+    }
+
     #[test]
     #[cfg(feature = "sha2")]
     fn test_sha256_compress() {
         test_circ!(
             SHA256_COMPRESS,
             sha256_compress,
-            fn(SHA2_INITIAL_STATE, [69u8; 64]) -> [u32; 8]
+            fn(SHA256_INITIAL_STATE, [69u8; 64]) -> [u32; 8]
         );
     }
 
@@ -288,7 +945,135 @@ mod tests {
             let circ = build_sha256(0, len);
             let reference = |state, msg| sha256(state, 0, msg);
 
-            test_circ!(circ, reference, fn(SHA2_INITIAL_STATE, msg.as_slice()) -> [u8; 32]);
+            test_circ!(circ, reference, fn(SHA256_INITIAL_STATE, msg.as_slice()) -> [u8; 32]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_hmac_sha256() {
+        use mpz_circuits_macros::evaluate;
+
+        // RFC 4231 test cases 1, 2, 3 and 6.
+        let test_vectors: [(Vec<u8>, Vec<u8>, &str); 4] = [
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+        ];
+
+        for (key, msg, expected) in test_vectors {
+            let builder = CircuitBuilder::new();
+            let key_input = builder.add_vec_input::<u8>(key.len());
+            let msg_input = builder.add_vec_input::<u8>(msg.len());
+
+            let mac = hmac_sha256_trace(builder.state(), &key_input, &msg_input);
+
+            builder.add_output(mac);
+            let circ = builder.build().unwrap();
+
+            let mac = evaluate!(circ, fn(key.as_slice(), msg.as_slice()) -> [u8; 32]).unwrap();
+
+            assert_eq!(hex::encode(mac), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_hmac_sha256_with_pads() {
+        use mpz_circuits_macros::evaluate;
+
+        let builder = CircuitBuilder::new();
+        let key = builder.add_array_input::<u8, 4>();
+        let msg_0 = builder.add_array_input::<u8, 8>();
+        let msg_1 = builder.add_array_input::<u8, 28>();
+
+        let pads = hmac_sha256_pads_trace(builder.state(), &key);
+        let mac_0 = hmac_sha256_with_pads_trace(builder.state(), pads, &msg_0);
+        let mac_1 = hmac_sha256_with_pads_trace(builder.state(), pads, &msg_1);
+
+        builder.add_output(mac_0);
+        builder.add_output(mac_1);
+        let circ = builder.build().unwrap();
+
+        let (mac_0, mac_1) = evaluate!(
+            circ,
+            fn(*b"Jefe", *b"Hi There", *b"what do ya want for nothing?") -> ([u8; 32], [u8; 32])
+        )
+        .unwrap();
+
+        assert_eq!(
+            hex::encode(mac_0),
+            "6bfb115ca30df3be0dfdffe79a51cbee88186db55acc287af148d7ff6220f92e"
+        );
+        assert_eq!(
+            hex::encode(mac_1),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_hkdf() {
+        use mpz_circuits_macros::evaluate;
+
+        // RFC 5869 test cases 1 and 3.
+        let test_vectors: [(Vec<u8>, Vec<u8>, &str, &str); 2] = [
+            (
+                (0x00..=0x0c).collect(),
+                (0xf0..=0xf9).collect(),
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+            ),
+            (
+                vec![],
+                vec![],
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+            ),
+        ];
+
+        let ikm = [0x0bu8; 22];
+
+        for (salt, info, expected_prk, expected_okm) in test_vectors {
+            let builder = CircuitBuilder::new();
+            let ikm_input = builder.add_array_input::<u8, 22>();
+            let salt = salt
+                .into_iter()
+                .map(|byte| builder.get_constant(byte))
+                .collect::<Vec<_>>();
+            let info = info
+                .into_iter()
+                .map(|byte| builder.get_constant(byte))
+                .collect::<Vec<_>>();
+
+            let prk = hkdf_extract_trace(builder.state(), &salt, &ikm_input);
+            let okm = hkdf_expand_trace(builder.state(), &prk, &info, 42);
+
+            builder.add_output(prk);
+            builder.add_output(okm);
+            let circ = builder.build().unwrap();
+
+            let (prk, okm) = evaluate!(circ, fn(ikm) -> ([u8; 32], Vec<u8>)).unwrap();
+
+            assert_eq!(hex::encode(prk), expected_prk);
+            assert_eq!(hex::encode(okm), expected_okm);
         }
     }
 }
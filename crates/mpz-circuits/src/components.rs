@@ -1,7 +1,7 @@
 use std::{fmt::Display, marker::PhantomData};
 
 /// A binary logic gate.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 #[allow(missing_docs)]
@@ -20,6 +20,8 @@ pub enum Gate {
     },
     /// Inverter gate.
     Inv { x: Node<Sink>, z: Node<Feed> },
+    /// Lookup gate, see [`LookupTable`].
+    Lut(Box<LookupTable>),
 }
 
 impl Gate {
@@ -29,36 +31,67 @@ impl Gate {
             Gate::Xor { .. } => GateType::Xor,
             Gate::And { .. } => GateType::And,
             Gate::Inv { .. } => GateType::Inv,
+            Gate::Lut(_) => GateType::Lut,
         }
     }
 
     /// Returns the x input of the gate.
+    ///
+    /// For lookup gates this is the first input, see [`Gate::inputs`].
     pub fn x(&self) -> Node<Sink> {
         match self {
             Gate::Xor { x, .. } => *x,
             Gate::And { x, .. } => *x,
             Gate::Inv { x, .. } => *x,
+            Gate::Lut(lut) => lut.inputs[0],
         }
     }
 
     /// Returns the y input of the gate.
+    ///
+    /// For lookup gates this is always `None`, see [`Gate::inputs`].
     pub fn y(&self) -> Option<Node<Sink>> {
         match self {
             Gate::Xor { y, .. } => Some(*y),
             Gate::And { y, .. } => Some(*y),
             Gate::Inv { .. } => None,
+            Gate::Lut(_) => None,
         }
     }
 
     /// Returns the z output of the gate.
+    ///
+    /// For lookup gates this is the first output, see [`Gate::outputs`].
     pub fn z(&self) -> Node<Feed> {
         match self {
             Gate::Xor { z, .. } => *z,
             Gate::And { z, .. } => *z,
             Gate::Inv { z, .. } => *z,
+            Gate::Lut(lut) => lut.outputs[0],
         }
     }
 
+    /// Returns an iterator over all inputs of the gate.
+    pub fn inputs(&self) -> impl Iterator<Item = Node<Sink>> + '_ {
+        let (xy, lut): ([Option<Node<Sink>>; 2], &[Node<Sink>]) = match self {
+            Gate::Xor { x, y, .. } | Gate::And { x, y, .. } => ([Some(*x), Some(*y)], &[]),
+            Gate::Inv { x, .. } => ([Some(*x), None], &[]),
+            Gate::Lut(lut) => ([None, None], &lut.inputs),
+        };
+
+        xy.into_iter().flatten().chain(lut.iter().copied())
+    }
+
+    /// Returns an iterator over all outputs of the gate.
+    pub fn outputs(&self) -> impl Iterator<Item = Node<Feed>> + '_ {
+        let (z, lut): (Option<Node<Feed>>, &[Node<Feed>]) = match self {
+            Gate::Lut(lut) => (None, &lut.outputs),
+            gate => (Some(gate.z()), &[]),
+        };
+
+        z.into_iter().chain(lut.iter().copied())
+    }
+
     /// Shifts all the node IDs of the gate by the given offset.
     #[inline]
     pub(crate) fn shift_left(&mut self, offset: usize) {
@@ -77,8 +110,145 @@ impl Gate {
                 x.id -= offset;
                 z.id -= offset;
             }
+            Gate::Lut(lut) => {
+                lut.inputs.iter_mut().for_each(|x| x.id -= offset);
+                lut.outputs.iter_mut().for_each(|z| z.id -= offset);
+            }
+        }
+    }
+}
+
+/// A lookup table which maps `k` input bits to `m` output bits.
+///
+/// A lookup gate computes a small function such as an S-box as a single gate with an AND depth
+/// of one, so it takes fewer gates and rounds than a circuit of AND gates. In exchange it
+/// requires far more communication, as it is garbled as `(2^k - 1) * m` ciphertexts: an 8-bit
+/// S-box is 2040 ciphertexts (about 32KB), whereas a circuit of about 32 AND gates is about
+/// 1KB using half-gates.
+///
+/// The table has `2^k` rows: input `i` is bit `i` of the row index, and output `j` is bit `j`
+/// of the row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LookupTable {
+    inputs: Vec<Node<Sink>>,
+    outputs: Vec<Node<Feed>>,
+    table: Vec<u64>,
+}
+
+impl LookupTable {
+    /// Maximum number of inputs of a lookup table.
+    pub const MAX_INPUTS: usize = 8;
+    /// Maximum number of outputs of a lookup table.
+    pub const MAX_OUTPUTS: usize = 64;
+
+    pub(crate) fn new(inputs: Vec<Node<Sink>>, outputs: Vec<Node<Feed>>, table: Vec<u64>) -> Self {
+        debug_assert!(!inputs.is_empty() && inputs.len() <= Self::MAX_INPUTS);
+        debug_assert!(!outputs.is_empty() && outputs.len() <= Self::MAX_OUTPUTS);
+        debug_assert_eq!(table.len(), 1 << inputs.len());
+
+        Self {
+            inputs,
+            outputs,
+            table,
         }
     }
+
+    /// Returns the inputs of the table.
+    pub fn inputs(&self) -> &[Node<Sink>] {
+        &self.inputs
+    }
+
+    /// Returns the outputs of the table.
+    pub fn outputs(&self) -> &[Node<Feed>] {
+        &self.outputs
+    }
+
+    /// Returns the rows of the table.
+    pub fn table(&self) -> &[u64] {
+        &self.table
+    }
+}
+
+/// A table with its constant inputs and outputs folded, see [`fold_table`].
+#[derive(Debug)]
+pub(crate) struct FoldedTable {
+    /// Indices of the inputs which are not constant.
+    pub(crate) inputs: Vec<usize>,
+    /// The value of each output if it is constant.
+    pub(crate) outputs: Vec<Option<bool>>,
+    /// Rows of the table for the remaining inputs and outputs.
+    pub(crate) table: Vec<u64>,
+}
+
+/// Folds the constant inputs of a table, and removes the outputs which become constant.
+///
+/// # Arguments
+///
+/// * `table` - The rows of the table.
+/// * `inputs` - The value of each input if it is constant.
+/// * `output_count` - The number of outputs of the table.
+pub(crate) fn fold_table(
+    table: &[u64],
+    inputs: &[Option<bool>],
+    output_count: usize,
+) -> FoldedTable {
+    let mask = u64::MAX
+        .checked_shr((u64::BITS as usize - output_count) as u32)
+        .unwrap_or(0);
+
+    let vars: Vec<usize> = (0..inputs.len()).filter(|i| inputs[*i].is_none()).collect();
+    let fixed = inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value == Some(true))
+        .fold(0, |idx, (i, _)| idx | 1 << i);
+
+    // Restrict the table to the rows which match the constant inputs.
+    let table: Vec<u64> = (0..1usize << vars.len())
+        .map(|row| {
+            let idx = vars
+                .iter()
+                .enumerate()
+                .fold(fixed, |idx, (j, &i)| idx | ((row >> j) & 1) << i);
+            table[idx] & mask
+        })
+        .collect();
+
+    // Outputs which are the same in every row are constant.
+    let ones = table.iter().fold(mask, |acc, row| acc & row);
+    let zeros = table.iter().fold(mask, |acc, row| acc & !row);
+
+    let outputs: Vec<Option<bool>> = (0..output_count)
+        .map(|j| {
+            if (ones >> j) & 1 == 1 {
+                Some(true)
+            } else if (zeros >> j) & 1 == 1 {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let columns: Vec<usize> = (0..output_count)
+        .filter(|j| outputs[*j].is_none())
+        .collect();
+    let table = table
+        .into_iter()
+        .map(|row| {
+            columns
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (t, &j)| acc | ((row >> j) & 1) << t)
+        })
+        .collect();
+
+    FoldedTable {
+        inputs: vars,
+        outputs,
+        table,
+    }
 }
 
 /// The type of a gate.
@@ -90,6 +260,8 @@ pub enum GateType {
     And,
     /// Inverter gate.
     Inv,
+    /// Lookup gate.
+    Lut,
 }
 
 /// A feed in a circuit.
@@ -3,11 +3,14 @@
 
 extern crate self as mpz_circuits;
 
+pub mod analysis;
+mod bristol;
 mod builder;
 mod circuit;
 pub mod circuits;
 pub(crate) mod components;
 pub mod ops;
+pub mod optimize;
 #[cfg(feature = "parse")]
 mod parse;
 mod tracer;
@@ -19,7 +22,7 @@ pub use builder::{BuilderError, CircuitBuilder};
 pub use circuit::{Circuit, CircuitError};
 #[doc(hidden)]
 pub use components::{Feed, Node, Sink};
-pub use components::{Gate, GateType};
+pub use components::{Gate, GateType, LookupTable};
 pub use tracer::Tracer;
 
 pub use once_cell;
@@ -109,6 +112,50 @@ pub use once_cell;
 ///    assert_eq!(output, bitxor_and(a, b));
 /// }
 /// ```
+/// # Lookup tables
+///
+/// Small tables indexed by a byte, such as S-boxes, can be traced as a single lookup gate
+/// using [`Lookup`](ops::Lookup).
+///
+/// ```
+/// use mpz_circuits::{ops::Lookup, trace, evaluate, CircuitBuilder};
+///
+/// const TABLE: [u8; 256] = {
+///     let mut table = [0u8; 256];
+///     let mut i = 0;
+///     while i < 256 {
+///         table[i] = (i as u8).reverse_bits();
+///         i += 1;
+///     }
+///     table
+/// };
+///
+/// #[trace]
+/// fn reverse(a: [u8; 4]) -> [u8; 4] {
+///     a.map(|a| a.lookup(&TABLE))
+/// }
+///
+/// fn main() {
+///     let builder = CircuitBuilder::new();
+///     let a = builder.add_array_input::<u8, 4>();
+///
+///     let b = reverse_trace(&mut builder.state(), a);
+///
+///     builder.add_output(b);
+///
+///     let circ = builder.build().unwrap();
+///
+///     assert_eq!(circ.lut_count(), 4);
+///     assert_eq!(circ.and_count(), 0);
+///
+///     let a = [1u8, 2, 3, 4];
+///
+///     let output = evaluate!(circ, fn(a) -> [u8; 4]).unwrap();
+///
+///     assert_eq!(output, reverse(a));
+/// }
+/// ```
+///
 /// # Cache
 ///
 /// The macro can optionally be configured with the `cache` argument which will cache the circuit
@@ -148,6 +148,134 @@ pub(crate) fn wrapping_sub_nbit(
     (diff, underflow)
 }
 
+/// Multiplies two values using schoolbook multiplication.
+///
+/// Returns the `len` least significant bits of the product, so `len` can be set to the length
+/// of the operands for wrapping multiplication, or to the sum of their lengths for the full
+/// product.
+pub(crate) fn mul_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+    len: usize,
+) -> Vec<Node<Feed>> {
+    assert!(len <= a.len() + b.len());
+
+    let const_zero = state.get_const_zero();
+
+    let mut product = vec![const_zero; len];
+    for (i, b) in b.iter().enumerate().take(len) {
+        // Partial products of bits which would be shifted out are skipped.
+        let row = (0..len - i)
+            .map(|j| match a.get(j) {
+                Some(a) => state.add_and_gate(*a, *b),
+                None => const_zero,
+            })
+            .collect::<Vec<_>>();
+
+        let sum = wrapping_add_nbit(state, &product[i..], &row);
+        product[i..].copy_from_slice(&sum);
+    }
+
+    product
+}
+
+/// Below this length, Karatsuba multiplication falls back to schoolbook multiplication.
+const KARATSUBA_THRESHOLD: usize = 16;
+
+/// Multiplies two values of the same length using Karatsuba multiplication.
+///
+/// Returns the full product, which is twice the length of the operands.
+pub(crate) fn karatsuba_mul_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> Vec<Node<Feed>> {
+    assert_eq!(a.len(), b.len());
+
+    let n = a.len();
+    if n <= KARATSUBA_THRESHOLD {
+        return mul_nbit(state, a, b, 2 * n);
+    }
+
+    let const_zero = state.get_const_zero();
+    let pad = |v: &[Node<Feed>], len: usize| {
+        let mut v = v.to_vec();
+        v.resize(len, const_zero);
+        v
+    };
+
+    // A = A_1 * 2^h + A_0, B = B_1 * 2^h + B_0
+    let h = n / 2;
+    let (a_0, a_1) = a.split_at(h);
+    let (b_0, b_1) = b.split_at(h);
+
+    // Z_0 = A_0 * B_0, Z_2 = A_1 * B_1
+    let z_0 = karatsuba_mul_nbit(state, a_0, b_0);
+    let z_2 = karatsuba_mul_nbit(state, a_1, b_1);
+
+    // Z_1 = (A_0 + A_1) * (B_0 + B_1) - Z_0 - Z_2, the additions have an extra bit for the carry.
+    let len = n - h + 1;
+    let a_sum = wrapping_add_nbit(state, &pad(a_0, len), &pad(a_1, len));
+    let b_sum = wrapping_add_nbit(state, &pad(b_0, len), &pad(b_1, len));
+    let z_1 = karatsuba_mul_nbit(state, &a_sum, &b_sum);
+    let (z_1, _) = wrapping_sub_nbit(state, &z_1, &pad(&z_0, 2 * len));
+    let (z_1, _) = wrapping_sub_nbit(state, &z_1, &pad(&z_2, 2 * len));
+
+    // A * B = Z_2 * 2^2h + Z_1 * 2^h + Z_0
+    let mut z_1_shifted = vec![const_zero; h];
+    z_1_shifted.extend_from_slice(&z_1);
+    z_1_shifted.resize(2 * n, const_zero);
+
+    let mut z_2_shifted = vec![const_zero; 2 * h];
+    z_2_shifted.extend_from_slice(&z_2);
+
+    let product = wrapping_add_nbit(state, &pad(&z_0, 2 * n), &z_1_shifted);
+
+    wrapping_add_nbit(state, &product, &z_2_shifted)
+}
+
+/// Divides two unsigned values using restoring division.
+///
+/// Returns the quotient, which has the length of `a`, and the remainder, which has the
+/// length of `b`.
+///
+/// Division by zero results in a quotient with all bits set, and a remainder equal to `a`
+/// truncated to the length of `b`.
+pub(crate) fn div_rem_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> (Vec<Node<Feed>>, Vec<Node<Feed>>) {
+    let n = b.len();
+    let const_zero = state.get_const_zero();
+
+    // The divisor has an extra bit to match the shifted remainder.
+    let mut b = b.to_vec();
+    b.push(const_zero);
+
+    let mut quotient = vec![const_zero; a.len()];
+    let mut rem = vec![const_zero; n];
+    for (i, a) in a.iter().enumerate().rev() {
+        // REM = (REM << 1) | A_i
+        let mut shifted = Vec::with_capacity(n + 1);
+        shifted.push(*a);
+        shifted.extend_from_slice(&rem);
+
+        // if REM < B { REM } else { REM - B }
+        let (diff, underflow) = wrapping_sub_nbit(state, &shifted, &b);
+        let mut rem_next = switch_nbit(state, &diff, &shifted, underflow);
+
+        // The remainder is always less than the divisor, so the MSB is zero.
+        rem_next.pop();
+        rem = rem_next;
+
+        quotient[i] = state.add_inv_gate(underflow);
+    }
+
+    (quotient, rem)
+}
+
 /// Switch between two nbit values.
 ///
 /// If `toggle` is 0, the result is `a`, otherwise it is `b`.
@@ -171,6 +299,76 @@ pub(crate) fn switch_nbit(
         .collect()
 }
 
+/// Returns a bit indicating whether `a == b`.
+///
+/// Requires `n - 1` AND gates, which are arranged in a tree of depth `log2(n)`.
+pub(crate) fn eq_nbit(state: &mut BuilderState, a: &[Node<Feed>], b: &[Node<Feed>]) -> Node<Feed> {
+    assert_eq!(a.len(), b.len());
+    assert!(!a.is_empty());
+
+    let mut bits: Vec<_> = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| {
+            // EQ = ¬(A ⊕ B)
+            let a_b = state.add_xor_gate(*a, *b);
+            state.add_inv_gate(a_b)
+        })
+        .collect();
+
+    while bits.len() > 1 {
+        bits = bits
+            .chunks(2)
+            .map(|pair| match pair {
+                [x, y] => state.add_and_gate(*x, *y),
+                [x] => *x,
+                _ => unreachable!("chunks are never empty"),
+            })
+            .collect();
+    }
+
+    bits[0]
+}
+
+/// Returns a bit indicating whether `a < b`, where `a` and `b` are unsigned nbit values.
+///
+/// Requires one AND gate per bit.
+pub(crate) fn lt_nbit(state: &mut BuilderState, a: &[Node<Feed>], b: &[Node<Feed>]) -> Node<Feed> {
+    assert_eq!(a.len(), b.len());
+
+    let const_zero = state.get_const_zero();
+
+    // The result is determined by the most significant bit in which `a` and `b` differ,
+    // so starting from the LSB we carry the result of the lower bits.
+    a.iter().zip(b).fold(const_zero, |lt, (a, b)| {
+        // LT_OUT = LT_IN ⊕ ((A ⊕ B) ^ (B ⊕ LT_IN))
+        let a_b = state.add_xor_gate(*a, *b);
+        let b_lt = state.add_xor_gate(*b, lt);
+        let and = state.add_and_gate(a_b, b_lt);
+        state.add_xor_gate(lt, and)
+    })
+}
+
+/// Returns a bit indicating whether `a < b`, where `a` and `b` are nbit values in two's
+/// complement.
+pub(crate) fn signed_lt_nbit(
+    state: &mut BuilderState,
+    a: &[Node<Feed>],
+    b: &[Node<Feed>],
+) -> Node<Feed> {
+    assert_eq!(a.len(), b.len());
+
+    // Flipping the sign bits maps the signed range onto the unsigned range
+    // while preserving the order.
+    let msb = a.len() - 1;
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a[msb] = state.add_inv_gate(a[msb]);
+    b[msb] = state.add_inv_gate(b[msb]);
+
+    lt_nbit(state, &a, &b)
+}
+
 /// Bitwise XOR of two nbit values.
 pub(crate) fn xor_nbit<const N: usize>(
     state: &mut BuilderState,
@@ -248,6 +446,28 @@ impl<'a> Not for Tracer<'a, Bit> {
     }
 }
 
+impl<'a> Tracer<'a, Bit> {
+    /// Selects between two values using this bit.
+    ///
+    /// If the bit is 0, the result is `a`, otherwise it is `b`.
+    pub fn mux<T>(self, a: Tracer<'a, T>, b: Tracer<'a, T>) -> Tracer<'a, T>
+    where
+        T: AsRef<[Node<Feed>]> + AsMut<[Node<Feed>]>,
+    {
+        let nodes = switch_nbit(
+            &mut self.state.borrow_mut(),
+            a.value.as_ref(),
+            b.value.as_ref(),
+            self.node(),
+        );
+
+        let mut value = a.to_inner();
+        value.as_mut().copy_from_slice(&nodes);
+
+        Tracer::new(self.state, value)
+    }
+}
+
 impl<'a> BitOr for Tracer<'a, Bit> {
     type Output = Tracer<'a, Bit>;
 
@@ -355,4 +575,26 @@ mod tests {
         let out: u8 = evaluate!(circ, fn(a, b, true) -> u8).unwrap();
         assert_eq!(out, b);
     }
+
+    #[test]
+    fn test_mux() {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+        let toggle = builder.add_input::<bool>();
+
+        builder.add_output(toggle.mux(a, b));
+
+        let circ = builder.build().unwrap();
+
+        // Two AND gates per bit.
+        assert_eq!(circ.and_count(), 64);
+
+        let out: u32 = evaluate!(circ, fn(42u32, 69u32, false) -> u32).unwrap();
+        assert_eq!(out, 42);
+
+        let out: u32 = evaluate!(circ, fn(42u32, 69u32, true) -> u32).unwrap();
+        assert_eq!(out, 69);
+    }
 }
@@ -34,3 +34,81 @@ pub trait WrappingSub<Rhs> {
     /// ```
     fn wrapping_sub(self, rhs: Rhs) -> Self::Output;
 }
+
+/// Multiplication of two integers using so called "wrapping multiplication", which
+/// allows bit overflow.
+pub trait WrappingMul<Rhs> {
+    /// The result type after wrapping multiplication.
+    type Output;
+
+    /// Multiplies two integers with wrapping multiplication, using schoolbook multiplication.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// assert_eq!(16u8.wrapping_mul(17u8), 16u8);
+    /// ```
+    fn wrapping_mul(self, rhs: Rhs) -> Self::Output;
+
+    /// Multiplies two integers with wrapping multiplication, using Karatsuba multiplication.
+    ///
+    /// Karatsuba multiplication computes the full product before truncating it, so it only
+    /// requires fewer AND gates than [`WrappingMul::wrapping_mul`] for large integers.
+    fn wrapping_mul_karatsuba(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Comparison of two integers, producing the result as a bit.
+pub trait Compare<Rhs> {
+    /// The result type of the comparison.
+    type Output;
+
+    /// Returns whether `self` is less than `rhs`.
+    fn lt(self, rhs: Rhs) -> Self::Output;
+
+    /// Returns whether `self` is less than or equal to `rhs`.
+    fn le(self, rhs: Rhs) -> Self::Output;
+
+    /// Returns whether `self` is greater than `rhs`.
+    fn gt(self, rhs: Rhs) -> Self::Output;
+
+    /// Returns whether `self` is greater than or equal to `rhs`.
+    fn ge(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Equality of two integers, producing the result as a bit.
+pub trait Equal<Rhs> {
+    /// The result type of the comparison.
+    type Output;
+
+    /// Returns whether `self` is equal to `rhs`.
+    fn eq(self, rhs: Rhs) -> Self::Output;
+
+    /// Returns whether `self` is not equal to `rhs`.
+    fn ne(self, rhs: Rhs) -> Self::Output;
+}
+
+/// Lookup of an entry in a table, indexed by a byte.
+///
+/// When tracing a circuit the lookup is computed using a single lookup gate, see
+/// [`LookupTable`](crate::LookupTable), which requires fewer gates and rounds than computing
+/// the function using AND gates, but far more communication.
+pub trait Lookup<T> {
+    /// The result type of the lookup.
+    type Output;
+
+    /// Returns the entry of the table at index `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use mpz_circuits::ops::Lookup;
+    ///
+    /// let table: [u8; 256] = std::array::from_fn(|i| (i as u8).reverse_bits());
+    /// assert_eq!(1u8.lookup(&table), 128u8);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the table does not have 256 entries.
+    fn lookup(self, table: &[T]) -> Self::Output;
+}
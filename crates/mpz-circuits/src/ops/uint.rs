@@ -1,11 +1,11 @@
-use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+use std::ops::{BitAnd, BitOr, BitXor, Div, Not, Rem, Shl, Shr};
 
 use crate::{
-    types::{BinaryRepr, U128, U16, U32, U64, U8},
+    types::{BinaryRepr, Bit, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8},
     Tracer,
 };
 
-use super::{binary, WrappingAdd, WrappingSub};
+use super::{binary, Compare, Equal, Lookup, WrappingAdd, WrappingMul, WrappingSub};
 
 macro_rules! impl_wrapping_add_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -56,6 +56,11 @@ impl_wrapping_add_uint!(U16, u16, 16);
 impl_wrapping_add_uint!(U32, u32, 32);
 impl_wrapping_add_uint!(U64, u64, 64);
 impl_wrapping_add_uint!(U128, u128, 128);
+impl_wrapping_add_uint!(I8, i8, 8);
+impl_wrapping_add_uint!(I16, i16, 16);
+impl_wrapping_add_uint!(I32, i32, 32);
+impl_wrapping_add_uint!(I64, i64, 64);
+impl_wrapping_add_uint!(I128, i128, 128);
 
 macro_rules! impl_wrapping_sub_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -108,6 +113,141 @@ impl_wrapping_sub_uint!(U16, u16, 16);
 impl_wrapping_sub_uint!(U32, u32, 32);
 impl_wrapping_sub_uint!(U64, u64, 64);
 impl_wrapping_sub_uint!(U128, u128, 128);
+impl_wrapping_sub_uint!(I8, i8, 8);
+impl_wrapping_sub_uint!(I16, i16, 16);
+impl_wrapping_sub_uint!(I32, i32, 32);
+impl_wrapping_sub_uint!(I64, i64, 64);
+impl_wrapping_sub_uint!(I128, i128, 128);
+
+macro_rules! impl_wrapping_mul_uint {
+    ($ty:ident, $const_ty:ident, $len:expr) => {
+        impl<'a> WrappingMul<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn wrapping_mul(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let nodes = binary::mul_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                    $len,
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, <$ty>::new(nodes.try_into().unwrap()))
+            }
+
+            fn wrapping_mul_karatsuba(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let mut nodes = binary::karatsuba_mul_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+                nodes.truncate($len);
+
+                drop(state);
+
+                Tracer::new(self.state, <$ty>::new(nodes.try_into().unwrap()))
+            }
+        }
+
+        impl<'a> WrappingMul<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn wrapping_mul(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.wrapping_mul(Tracer::new(self.state, rhs))
+            }
+
+            fn wrapping_mul_karatsuba(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.wrapping_mul_karatsuba(Tracer::new(self.state, rhs))
+            }
+        }
+    };
+}
+
+impl_wrapping_mul_uint!(U8, u8, 8);
+impl_wrapping_mul_uint!(U16, u16, 16);
+impl_wrapping_mul_uint!(U32, u32, 32);
+impl_wrapping_mul_uint!(U64, u64, 64);
+impl_wrapping_mul_uint!(U128, u128, 128);
+impl_wrapping_mul_uint!(I8, i8, 8);
+impl_wrapping_mul_uint!(I16, i16, 16);
+impl_wrapping_mul_uint!(I32, i32, 32);
+impl_wrapping_mul_uint!(I64, i64, 64);
+impl_wrapping_mul_uint!(I128, i128, 128);
+
+macro_rules! impl_div_rem_uint {
+    ($ty:ident, $const_ty:ident) => {
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns the quotient and remainder of an unsigned division.
+            ///
+            /// Division by zero results in a quotient with all bits set, and a remainder equal
+            /// to the dividend.
+            pub fn div_rem(self, rhs: Tracer<'a, $ty>) -> (Tracer<'a, $ty>, Tracer<'a, $ty>) {
+                let mut state = self.state.borrow_mut();
+
+                let (quotient, rem) = binary::div_rem_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                drop(state);
+
+                (
+                    Tracer::new(self.state, <$ty>::new(quotient.try_into().unwrap())),
+                    Tracer::new(self.state, <$ty>::new(rem.try_into().unwrap())),
+                )
+            }
+        }
+
+        impl<'a> Div<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn div(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                self.div_rem(rhs).0
+            }
+        }
+
+        impl<'a> Div<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn div(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.div_rem(Tracer::new(self.state, rhs)).0
+            }
+        }
+
+        impl<'a> Rem<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn rem(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                self.div_rem(rhs).1
+            }
+        }
+
+        impl<'a> Rem<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            fn rem(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.div_rem(Tracer::new(self.state, rhs)).1
+            }
+        }
+    };
+}
+
+impl_div_rem_uint!(U8, u8);
+impl_div_rem_uint!(U16, u16);
+impl_div_rem_uint!(U32, u32);
+impl_div_rem_uint!(U64, u64);
+impl_div_rem_uint!(U128, u128);
 
 impl<'a> BitXor for Tracer<'a, BinaryRepr> {
     type Output = Tracer<'a, BinaryRepr>;
@@ -138,6 +278,26 @@ impl<'a> BitXor for Tracer<'a, BinaryRepr> {
                 let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
                 Tracer::new(self.state, c.into())
             }
+            (BinaryRepr::I8(a), BinaryRepr::I8(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I16(a), BinaryRepr::I16(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I32(a), BinaryRepr::I32(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I64(a), BinaryRepr::I64(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
+            (BinaryRepr::I128(a), BinaryRepr::I128(b)) => {
+                let c = Tracer::new(self.state, a) ^ Tracer::new(self.state, b);
+                Tracer::new(self.state, c.into())
+            }
             (BinaryRepr::Array(a), BinaryRepr::Array(b)) => Tracer::new(
                 self.state,
                 BinaryRepr::Array(
@@ -202,6 +362,11 @@ impl_bitxor_uint!(U16, u16, 16);
 impl_bitxor_uint!(U32, u32, 32);
 impl_bitxor_uint!(U64, u64, 64);
 impl_bitxor_uint!(U128, u128, 128);
+impl_bitxor_uint!(I8, i8, 8);
+impl_bitxor_uint!(I16, i16, 16);
+impl_bitxor_uint!(I32, i32, 32);
+impl_bitxor_uint!(I64, i64, 64);
+impl_bitxor_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_and_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -250,6 +415,11 @@ impl_bit_and_uint!(U16, u16, 16);
 impl_bit_and_uint!(U32, u32, 32);
 impl_bit_and_uint!(U64, u64, 64);
 impl_bit_and_uint!(U128, u128, 128);
+impl_bit_and_uint!(I8, i8, 8);
+impl_bit_and_uint!(I16, i16, 16);
+impl_bit_and_uint!(I32, i32, 32);
+impl_bit_and_uint!(I64, i64, 64);
+impl_bit_and_uint!(I128, i128, 128);
 
 macro_rules! impl_bit_or_uint {
     ($ty:ident, $const_ty:ident, $len:expr) => {
@@ -298,6 +468,11 @@ impl_bit_or_uint!(U16, u16, 16);
 impl_bit_or_uint!(U32, u32, 32);
 impl_bit_or_uint!(U64, u64, 64);
 impl_bit_or_uint!(U128, u128, 128);
+impl_bit_or_uint!(I8, i8, 8);
+impl_bit_or_uint!(I16, i16, 16);
+impl_bit_or_uint!(I32, i32, 32);
+impl_bit_or_uint!(I64, i64, 64);
+impl_bit_or_uint!(I128, i128, 128);
 
 macro_rules! impl_shl_uint {
     ($ty:ident, $len:expr) => {
@@ -330,6 +505,11 @@ impl_shl_uint!(U16, 16);
 impl_shl_uint!(U32, 32);
 impl_shl_uint!(U64, 64);
 impl_shl_uint!(U128, 128);
+impl_shl_uint!(I8, 8);
+impl_shl_uint!(I16, 16);
+impl_shl_uint!(I32, 32);
+impl_shl_uint!(I64, 64);
+impl_shl_uint!(I128, 128);
 
 macro_rules! impl_shr_uint {
     ($ty:ident, $len:expr) => {
@@ -347,7 +527,7 @@ macro_rules! impl_shr_uint {
                 // Bits are LSB0, so we rotate left
                 nodes.rotate_left(rhs);
                 // Replace the msbs with 0s
-                nodes[$len - rhs - 1..]
+                nodes[$len - rhs..]
                     .iter_mut()
                     .for_each(|node| *node = const_zero);
 
@@ -365,6 +545,203 @@ impl_shr_uint!(U32, 32);
 impl_shr_uint!(U64, 64);
 impl_shr_uint!(U128, 128);
 
+macro_rules! impl_shr_int {
+    ($ty:ident, $len:expr) => {
+        impl<'a> Shr<usize> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, $ty>;
+
+            /// Arithmetic shift right, which preserves the sign of the value.
+            fn shr(self, rhs: usize) -> Self::Output {
+                assert!(rhs < $len);
+
+                let mut nodes = self.to_inner().nodes();
+                let msb = nodes[$len - 1];
+                // Bits are LSB0, so we rotate left
+                nodes.rotate_left(rhs);
+                // Replace the msbs with the sign bit
+                nodes[$len - rhs..].iter_mut().for_each(|node| *node = msb);
+
+                let value = <$ty>::new(nodes);
+
+                Tracer::new(self.state, value)
+            }
+        }
+    };
+}
+
+impl_shr_int!(I8, 8);
+impl_shr_int!(I16, 16);
+impl_shr_int!(I32, 32);
+impl_shr_int!(I64, 64);
+impl_shr_int!(I128, 128);
+
+macro_rules! impl_rotate_uint {
+    ($ty:ident, $len:expr) => {
+        impl<'a> Tracer<'a, $ty> {
+            /// Shifts the bits to the left by `n`, wrapping the truncated bits to the end.
+            pub fn rotate_left(self, n: usize) -> Tracer<'a, $ty> {
+                let mut nodes = self.to_inner().nodes();
+                // Bits are LSB0, so we rotate right
+                nodes.rotate_right(n % $len);
+
+                Tracer::new(self.state, <$ty>::new(nodes))
+            }
+
+            /// Shifts the bits to the right by `n`, wrapping the truncated bits to the beginning.
+            pub fn rotate_right(self, n: usize) -> Tracer<'a, $ty> {
+                let mut nodes = self.to_inner().nodes();
+                // Bits are LSB0, so we rotate left
+                nodes.rotate_left(n % $len);
+
+                Tracer::new(self.state, <$ty>::new(nodes))
+            }
+        }
+    };
+}
+
+impl_rotate_uint!(U8, 8);
+impl_rotate_uint!(U16, 16);
+impl_rotate_uint!(U32, 32);
+impl_rotate_uint!(U64, 64);
+impl_rotate_uint!(U128, 128);
+impl_rotate_uint!(I8, 8);
+impl_rotate_uint!(I16, 16);
+impl_rotate_uint!(I32, 32);
+impl_rotate_uint!(I64, 64);
+impl_rotate_uint!(I128, 128);
+
+macro_rules! impl_compare_int {
+    ($ty:ident, $const_ty:ident, $lt:ident) => {
+        impl<'a> Compare<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, Bit>;
+
+            fn lt(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let out = binary::$lt(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, Bit::new([out]))
+            }
+
+            fn le(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                !rhs.lt(self)
+            }
+
+            fn gt(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                rhs.lt(self)
+            }
+
+            fn ge(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                !self.lt(rhs)
+            }
+        }
+
+        impl<'a> Tracer<'a, $ty> {
+            /// Returns the minimum of two values.
+            pub fn min(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, $ty> {
+                self.lt(rhs).mux(rhs, self)
+            }
+
+            /// Returns the maximum of two values.
+            pub fn max(self, rhs: Tracer<'a, $ty>) -> Tracer<'a, $ty> {
+                self.lt(rhs).mux(self, rhs)
+            }
+        }
+
+        impl<'a> Compare<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, Bit>;
+
+            fn lt(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.lt(Tracer::new(self.state, rhs))
+            }
+
+            fn le(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.le(Tracer::new(self.state, rhs))
+            }
+
+            fn gt(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.gt(Tracer::new(self.state, rhs))
+            }
+
+            fn ge(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.ge(Tracer::new(self.state, rhs))
+            }
+        }
+    };
+}
+
+impl_compare_int!(U8, u8, lt_nbit);
+impl_compare_int!(U16, u16, lt_nbit);
+impl_compare_int!(U32, u32, lt_nbit);
+impl_compare_int!(U64, u64, lt_nbit);
+impl_compare_int!(U128, u128, lt_nbit);
+impl_compare_int!(I8, i8, signed_lt_nbit);
+impl_compare_int!(I16, i16, signed_lt_nbit);
+impl_compare_int!(I32, i32, signed_lt_nbit);
+impl_compare_int!(I64, i64, signed_lt_nbit);
+impl_compare_int!(I128, i128, signed_lt_nbit);
+
+macro_rules! impl_equal_int {
+    ($ty:ident, $const_ty:ident) => {
+        impl<'a> Equal<Tracer<'a, $ty>> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, Bit>;
+
+            fn eq(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                let mut state = self.state.borrow_mut();
+
+                let out = binary::eq_nbit(
+                    &mut state,
+                    &self.to_inner().nodes(),
+                    &rhs.to_inner().nodes(),
+                );
+
+                drop(state);
+
+                Tracer::new(self.state, Bit::new([out]))
+            }
+
+            fn ne(self, rhs: Tracer<'a, $ty>) -> Self::Output {
+                !self.eq(rhs)
+            }
+        }
+
+        impl<'a> Equal<$const_ty> for Tracer<'a, $ty> {
+            type Output = Tracer<'a, Bit>;
+
+            fn eq(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.eq(Tracer::new(self.state, rhs))
+            }
+
+            fn ne(self, rhs: $const_ty) -> Self::Output {
+                let rhs = self.state.borrow_mut().get_constant::<$const_ty>(rhs);
+                self.ne(Tracer::new(self.state, rhs))
+            }
+        }
+    };
+}
+
+impl_equal_int!(U8, u8);
+impl_equal_int!(U16, u16);
+impl_equal_int!(U32, u32);
+impl_equal_int!(U64, u64);
+impl_equal_int!(U128, u128);
+impl_equal_int!(I8, i8);
+impl_equal_int!(I16, i16);
+impl_equal_int!(I32, i32);
+impl_equal_int!(I64, i64);
+impl_equal_int!(I128, i128);
+
 macro_rules! impl_neg_uint {
     ($ty:ident) => {
         impl<'a> Not for Tracer<'a, $ty> {
@@ -386,6 +763,11 @@ impl_neg_uint!(U16);
 impl_neg_uint!(U32);
 impl_neg_uint!(U64);
 impl_neg_uint!(U128);
+impl_neg_uint!(I8);
+impl_neg_uint!(I16);
+impl_neg_uint!(I32);
+impl_neg_uint!(I64);
+impl_neg_uint!(I128);
 
 macro_rules! impl_convert_bytes {
     ($ty:ident, $len:expr) => {
@@ -424,3 +806,386 @@ impl_convert_bytes!(U16, 2);
 impl_convert_bytes!(U32, 4);
 impl_convert_bytes!(U64, 8);
 impl_convert_bytes!(U128, 16);
+impl_convert_bytes!(I8, 1);
+impl_convert_bytes!(I16, 2);
+impl_convert_bytes!(I32, 4);
+impl_convert_bytes!(I64, 8);
+impl_convert_bytes!(I128, 16);
+
+macro_rules! impl_lookup_uint {
+    ($ty:ident, $const_ty:ident, $len:expr) => {
+        impl Lookup<$const_ty> for u8 {
+            type Output = $const_ty;
+
+            fn lookup(self, table: &[$const_ty]) -> Self::Output {
+                assert_eq!(table.len(), 256, "table should have 256 entries");
+
+                table[self as usize]
+            }
+        }
+
+        impl<'a> Lookup<$const_ty> for Tracer<'a, U8> {
+            type Output = Tracer<'a, $ty>;
+
+            fn lookup(self, table: &[$const_ty]) -> Self::Output {
+                assert_eq!(table.len(), 256, "table should have 256 entries");
+
+                #[allow(clippy::unnecessary_cast)]
+                let table: Vec<u64> = table.iter().map(|value| *value as u64).collect();
+
+                let mut state = self.state.borrow_mut();
+
+                let nodes = state.add_lut_gate(&self.to_inner().nodes(), &table, $len);
+
+                drop(state);
+
+                let nodes: [_; $len] = nodes
+                    .try_into()
+                    .expect("lookup should have an output per bit");
+
+                Tracer::new(self.state, <$ty>::new(nodes))
+            }
+        }
+    };
+}
+
+impl_lookup_uint!(U8, u8, 8);
+impl_lookup_uint!(U16, u16, 16);
+impl_lookup_uint!(U32, u32, 32);
+impl_lookup_uint!(U64, u64, 64);
+impl_lookup_uint!(I8, i8, 8);
+impl_lookup_uint!(I16, i16, 16);
+impl_lookup_uint!(I32, i32, 32);
+impl_lookup_uint!(I64, i64, 64);
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits_macros::evaluate;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::{CircuitBuilder, Gate};
+
+    use super::*;
+
+    #[test]
+    fn test_signed_wrapping_add_sub() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i8>();
+        let b = builder.add_input::<i8>();
+        builder.add_output(a.wrapping_add(b));
+        builder.add_output(a.wrapping_sub(b));
+        let circ = builder.build().unwrap();
+
+        for a in i8::MIN..=i8::MAX {
+            for b in [i8::MIN, -42, -1, 0, 1, 69, i8::MAX] {
+                let (sum, diff) = evaluate!(circ, fn(a, b) -> (i8, i8)).unwrap();
+
+                assert_eq!(sum, a.wrapping_add(b));
+                assert_eq!(diff, a.wrapping_sub(b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrapping_mul() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_mul(b));
+        builder.add_output(a.wrapping_mul(42u8));
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let output = evaluate!(circ, fn(a, b) -> (u8, u8)).unwrap();
+
+                assert_eq!(output, (a.wrapping_mul(b), a.wrapping_mul(42)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_wrapping_mul_karatsuba() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u64>();
+        let b = builder.add_input::<u64>();
+        let c = builder.add_input::<i32>();
+        let d = builder.add_input::<i32>();
+        builder.add_output(a.wrapping_mul_karatsuba(b));
+        builder.add_output(c.wrapping_mul_karatsuba(d));
+        let circ = builder.build().unwrap();
+
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..32 {
+            let (a, b, c, d): (u64, u64, i32, i32) = rng.gen();
+
+            let output = evaluate!(circ, fn(a, b, c, d) -> (u64, i32)).unwrap();
+
+            assert_eq!(output, (a.wrapping_mul(b), c.wrapping_mul(d)));
+        }
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a / b);
+        builder.add_output(a % b);
+        builder.add_output(a % 7u8);
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let output = evaluate!(circ, fn(a, b) -> (u8, u8, u8)).unwrap();
+
+                let expected = if b == 0 {
+                    (u8::MAX, a, a % 7)
+                } else {
+                    (a / b, a % b, a % 7)
+                };
+
+                assert_eq!(output, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shr() {
+        for rhs in 0..=8 {
+            let builder = CircuitBuilder::new();
+            let a = builder.add_input::<u8>();
+            builder.add_output(a >> rhs);
+            let circ = builder.build().unwrap();
+
+            for a in [0u8, 1, 0x55, 0x80, 0xff] {
+                let a_shr: u8 = evaluate!(circ, fn(a) -> u8).unwrap();
+
+                assert_eq!(a_shr, a.checked_shr(rhs as u32).unwrap_or(0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_shr_signed() {
+        for rhs in 0..8 {
+            let builder = CircuitBuilder::new();
+            let a = builder.add_input::<i8>();
+            builder.add_output(a >> rhs);
+            let circ = builder.build().unwrap();
+
+            for a in [0i8, 1, -1, 0x55, i8::MIN, i8::MAX] {
+                let a_shr: i8 = evaluate!(circ, fn(a) -> i8).unwrap();
+
+                assert_eq!(a_shr, a >> rhs);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        let square: [u16; 256] = std::array::from_fn(|i| (i * i) as u16);
+        let neg: [i8; 256] = std::array::from_fn(|i| (i as u8 as i8).wrapping_neg());
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a.lookup(&square));
+        builder.add_output(a.lookup(&neg));
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.lut_count(), 2);
+        assert_eq!(circ.and_count(), 0);
+
+        for a in 0u8..=255 {
+            let output = evaluate!(circ, fn(a) -> (u16, i8)).unwrap();
+
+            assert_eq!(output, (a.lookup(&square), a.lookup(&neg)));
+        }
+    }
+
+    #[test]
+    fn test_lookup_constant() {
+        let table: [u8; 256] = std::array::from_fn(|i| (i as u8).reverse_bits() | 0xf0);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        // The low bits of the index are constant, which makes the high bits of the entry
+        // constant.
+        builder.add_output((a & 0xf0).lookup(&table));
+        // A constant index does not require a gate.
+        builder.add_output(builder.get_constant(42u8).lookup(&table) ^ a);
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.lut_count(), 1);
+
+        let lut = circ
+            .gates()
+            .iter()
+            .find_map(|gate| match gate {
+                Gate::Lut(lut) => Some(lut),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(lut.inputs().len(), 4);
+        assert_eq!(lut.outputs().len(), 4);
+
+        for a in 0u8..=255 {
+            let output = evaluate!(circ, fn(a) -> (u8, u8)).unwrap();
+
+            assert_eq!(output, (table[(a & 0xf0) as usize], table[42] ^ a));
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        for n in 0..=32 {
+            let builder = CircuitBuilder::new();
+            let a = builder.add_input::<u32>();
+            builder.add_output(a.rotate_left(n));
+            builder.add_output(a.rotate_right(n));
+            let circ = builder.build().unwrap();
+
+            assert_eq!(circ.and_count(), 0);
+
+            for a in [0u32, 1, 0x8000_0001, 0xdead_beef] {
+                let (left, right) = evaluate!(circ, fn(a) -> (u32, u32)).unwrap();
+
+                assert_eq!(left, a.rotate_left(n as u32));
+                assert_eq!(right, a.rotate_right(n as u32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.lt(b));
+        builder.add_output(a.le(b));
+        builder.add_output(a.gt(b));
+        builder.add_output(a.ge(b));
+        let circ = builder.build().unwrap();
+
+        // One AND gate per bit for each comparison.
+        assert_eq!(circ.and_count(), 32);
+
+        for a in 0u8..=255 {
+            for b in 0u8..=255 {
+                let output = evaluate!(circ, fn(a, b) -> (bool, bool, bool, bool)).unwrap();
+
+                assert_eq!(output, (a < b, a <= b, a > b, a >= b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_const() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u64>();
+        builder.add_output(a.le(42u64));
+        builder.add_output(a.gt(42u64));
+        let circ = builder.build().unwrap();
+
+        for a in [0u64, 41, 42, 43, u64::MAX] {
+            let output = evaluate!(circ, fn(a) -> (bool, bool)).unwrap();
+
+            assert_eq!(output, (a <= 42, a > 42));
+        }
+    }
+
+    #[test]
+    fn test_equal() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.eq(b));
+        builder.add_output(a.ne(b));
+        builder.add_output(a.eq(42u8));
+        let circ = builder.build().unwrap();
+
+        for a in 0u8..=255 {
+            for b in [0u8, 1, 42, 128, 255] {
+                let output = evaluate!(circ, fn(a, b) -> (bool, bool, bool)).unwrap();
+
+                assert_eq!(output, (a == b, a != b, a == 42));
+            }
+        }
+    }
+
+    #[test]
+    fn test_equal_and_count() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u32>();
+        let b = builder.add_input::<u32>();
+        builder.add_output(a.eq(b));
+        let circ = builder.build().unwrap();
+
+        assert_eq!(circ.and_count(), 31);
+        assert_eq!(circ.and_depth(), 5);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u16>();
+        let b = builder.add_input::<u16>();
+        let c = builder.add_input::<i16>();
+        let d = builder.add_input::<i16>();
+        builder.add_output(a.min(b));
+        builder.add_output(a.max(b));
+        builder.add_output(c.min(d));
+        builder.add_output(c.max(d));
+        let circ = builder.build().unwrap();
+
+        for (a, b, c, d) in [
+            (0u16, 0u16, 0i16, 0i16),
+            (1, 2, -1, 2),
+            (2, 1, 2, -1),
+            (u16::MAX, 42, i16::MIN, i16::MAX),
+        ] {
+            let output = evaluate!(circ, fn(a, b, c, d) -> (u16, u16, i16, i16)).unwrap();
+
+            assert_eq!(output, (a.min(b), a.max(b), c.min(d), c.max(d)));
+        }
+    }
+
+    #[test]
+    fn test_signed_compare() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i8>();
+        let b = builder.add_input::<i8>();
+        builder.add_output(a.lt(b));
+        builder.add_output(a.le(b));
+        builder.add_output(a.gt(b));
+        builder.add_output(a.ge(b));
+        let circ = builder.build().unwrap();
+
+        // One AND gate per bit for each comparison.
+        assert_eq!(circ.and_count(), 32);
+
+        for a in i8::MIN..=i8::MAX {
+            for b in i8::MIN..=i8::MAX {
+                let output = evaluate!(circ, fn(a, b) -> (bool, bool, bool, bool)).unwrap();
+
+                assert_eq!(output, (a < b, a <= b, a > b, a >= b));
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_compare_const() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i32>();
+        builder.add_output(a.lt(-1i32));
+        builder.add_output(a.ge(-1i32));
+        let circ = builder.build().unwrap();
+
+        for a in [i32::MIN, -2, -1, 0, 1, i32::MAX] {
+            let output = evaluate!(circ, fn(a) -> (bool, bool)).unwrap();
+
+            assert_eq!(output, (a < -1, a >= -1));
+        }
+    }
+}
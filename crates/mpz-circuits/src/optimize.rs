@@ -0,0 +1,581 @@
+//! Optimization passes for circuits.
+//!
+//! # Example
+//!
+//! ```
+//! use mpz_circuits::{optimize::optimize, CircuitBuilder};
+//!
+//! let builder = CircuitBuilder::new();
+//! let a = builder.add_input::<u8>();
+//! let b = builder.add_input::<u8>();
+//!
+//! // `a ^ a` is always zero, so the AND gates are redundant.
+//! let c = (a ^ a) & b;
+//! builder.add_output(c ^ b);
+//!
+//! let circ = builder.build().unwrap();
+//! let (optimized, reports) = optimize(&circ).unwrap();
+//!
+//! assert_eq!(optimized.and_count(), 0);
+//! assert_eq!(reports[0].and_count_after, 0);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::{
+    builder::{AddGates, ConstantOutputs},
+    components::{fold_table, Feed, Gate, LookupTable, Node, Sink},
+    Circuit,
+};
+
+/// An error that can occur when optimizing a circuit.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum OptimizeError {
+    #[error("constant output, but the circuit has no input to compute it from")]
+    ConstantOutput,
+}
+
+/// An optimization pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pass {
+    /// Folds constants through the circuit, as well as trivial identities such as
+    /// `x ^ x = 0` and `x & x = x`.
+    ConstantPropagation,
+    /// Removes gates which do not contribute to an output of the circuit.
+    DeadGateElimination,
+    /// Collapses chains of INV gates, eg `!!x = x`.
+    InvChainCollapse,
+    /// Merges gates which compute the same function of the same inputs, also
+    /// known as common subexpression elimination.
+    StructuralHashing,
+}
+
+impl Pass {
+    /// All passes, in the order they are applied by [`optimize`].
+    pub const ALL: [Pass; 4] = [
+        Pass::ConstantPropagation,
+        Pass::InvChainCollapse,
+        Pass::StructuralHashing,
+        Pass::DeadGateElimination,
+    ];
+
+    /// Runs the pass on a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to optimize.
+    ///
+    /// # Returns
+    ///
+    /// The optimized circuit and a report of the pass.
+    pub fn run(&self, circ: &Circuit) -> Result<(Circuit, PassReport), OptimizeError> {
+        let optimized = match self {
+            Pass::ConstantPropagation => constant_propagation(circ),
+            Pass::DeadGateElimination => dead_gate_elimination(circ),
+            Pass::InvChainCollapse => inv_chain_collapse(circ),
+            Pass::StructuralHashing => structural_hashing(circ),
+        }?;
+
+        let report = PassReport {
+            pass: *self,
+            and_count_before: circ.and_count(),
+            and_count_after: optimized.and_count(),
+            xor_count_before: circ.xor_count(),
+            xor_count_after: optimized.xor_count(),
+        };
+
+        Ok((optimized, report))
+    }
+}
+
+/// A report of an optimization pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassReport {
+    /// The pass which was run.
+    pub pass: Pass,
+    /// The number of AND gates before the pass.
+    pub and_count_before: usize,
+    /// The number of AND gates after the pass.
+    pub and_count_after: usize,
+    /// The number of XOR gates before the pass.
+    pub xor_count_before: usize,
+    /// The number of XOR gates after the pass.
+    pub xor_count_after: usize,
+}
+
+/// Optimizes a circuit by running all passes in [`Pass::ALL`].
+///
+/// # Arguments
+///
+/// * `circ` - The circuit to optimize.
+///
+/// # Returns
+///
+/// The optimized circuit and a report of each pass.
+pub fn optimize(circ: &Circuit) -> Result<(Circuit, Vec<PassReport>), OptimizeError> {
+    optimize_with(circ, &Pass::ALL)
+}
+
+/// Optimizes a circuit by running the provided passes in order.
+///
+/// # Arguments
+///
+/// * `circ` - The circuit to optimize.
+/// * `passes` - The passes to run.
+///
+/// # Returns
+///
+/// The optimized circuit and a report of each pass.
+pub fn optimize_with(
+    circ: &Circuit,
+    passes: &[Pass],
+) -> Result<(Circuit, Vec<PassReport>), OptimizeError> {
+    let mut circ = circ.clone();
+    let mut reports = Vec::with_capacity(passes.len());
+    for pass in passes {
+        let (optimized, report) = pass.run(&circ)?;
+        circ = optimized;
+        reports.push(report);
+    }
+
+    Ok((circ, reports))
+}
+
+fn constant_propagation(circ: &Circuit) -> Result<Circuit, OptimizeError> {
+    let mut rewriter = Rewriter::new(circ);
+    for gate in circ.gates() {
+        rewriter.add_gate(gate);
+    }
+
+    rewriter.finish()
+}
+
+fn dead_gate_elimination(circ: &Circuit) -> Result<Circuit, OptimizeError> {
+    let mut live = vec![false; circ.feed_count()];
+    for node in circ.outputs().iter().flat_map(|output| output.iter()) {
+        live[node.id] = true;
+    }
+
+    // A lookup gate is live if any of its outputs is live.
+    let is_live = |live: &[bool], gate: &Gate| gate.outputs().any(|z| live[z.id]);
+
+    for gate in circ.gates().iter().rev() {
+        if is_live(&live, gate) {
+            for x in gate.inputs() {
+                live[x.id] = true;
+            }
+        }
+    }
+
+    let mut rewriter = Rewriter::new(circ);
+    for gate in circ.gates().iter().filter(|gate| is_live(&live, gate)) {
+        rewriter.add_gate(gate);
+    }
+
+    rewriter.finish()
+}
+
+fn inv_chain_collapse(circ: &Circuit) -> Result<Circuit, OptimizeError> {
+    // Maps the output of an INV gate to its input.
+    let mut inverse: Vec<Option<usize>> = vec![None; circ.feed_count()];
+
+    let mut rewriter = Rewriter::new(circ);
+    for gate in circ.gates() {
+        let Gate::Inv { x, z } = gate else {
+            rewriter.add_gate(gate);
+            continue;
+        };
+
+        match rewriter.signal(x) {
+            Signal::Feed(x) => {
+                if let Some(w) = inverse[x] {
+                    rewriter.alias(z, Signal::Feed(w));
+                } else {
+                    rewriter.add_inv(Signal::Feed(x), z);
+                    inverse[z.id] = Some(x);
+                }
+            }
+            x => rewriter.add_inv(x, z),
+        }
+    }
+
+    rewriter.finish()
+}
+
+fn structural_hashing(circ: &Circuit) -> Result<Circuit, OptimizeError> {
+    #[derive(PartialEq, Eq, Hash)]
+    enum Key {
+        Xor(usize, usize),
+        And(usize, usize),
+        Inv(usize),
+    }
+
+    let mut table: HashMap<Key, usize> = HashMap::with_capacity(circ.gates().len());
+
+    let mut rewriter = Rewriter::new(circ);
+    for gate in circ.gates() {
+        let x = rewriter.signal(&gate.x());
+        let y = gate.y().map(|y| rewriter.signal(&y));
+
+        let key = match (gate, x, y) {
+            (Gate::Xor { .. }, Signal::Feed(x), Some(Signal::Feed(y))) => {
+                Key::Xor(x.min(y), x.max(y))
+            }
+            (Gate::And { .. }, Signal::Feed(x), Some(Signal::Feed(y))) => {
+                Key::And(x.min(y), x.max(y))
+            }
+            (Gate::Inv { .. }, Signal::Feed(x), None) => Key::Inv(x),
+            _ => {
+                rewriter.add_gate(gate);
+                continue;
+            }
+        };
+
+        let z = gate.z();
+        if let Some(id) = table.get(&key) {
+            rewriter.alias(&z, Signal::Feed(*id));
+        } else {
+            rewriter.add_gate(gate);
+            if let Signal::Feed(id) = rewriter.signal(&z.into()) {
+                table.insert(key, id);
+            }
+        }
+    }
+
+    rewriter.finish()
+}
+
+/// A signal in a circuit which is being rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Signal {
+    Const(bool),
+    Feed(usize),
+}
+
+/// Rewrites the gates of a circuit, folding any constants.
+///
+/// The feeds of the original circuit are mapped to signals in the rewritten circuit. Gates
+/// which are added keep the id of their output feed, so ids remain unique.
+struct Rewriter<'a> {
+    circ: &'a Circuit,
+    map: Vec<Signal>,
+    gates: Vec<Gate>,
+    feed_count: usize,
+    and_count: usize,
+    xor_count: usize,
+    lut_count: usize,
+}
+
+impl<'a> Rewriter<'a> {
+    fn new(circ: &'a Circuit) -> Self {
+        Self {
+            circ,
+            map: (0..circ.feed_count()).map(Signal::Feed).collect(),
+            gates: Vec::with_capacity(circ.gates().len()),
+            feed_count: circ.feed_count(),
+            and_count: 0,
+            xor_count: 0,
+            lut_count: 0,
+        }
+    }
+
+    /// Returns the signal of a node in the original circuit.
+    fn signal(&self, node: &Node<Sink>) -> Signal {
+        self.map[node.id]
+    }
+
+    /// Maps a feed of the original circuit to a signal.
+    fn alias(&mut self, z: &Node<Feed>, signal: Signal) {
+        self.map[z.id] = signal;
+    }
+
+    fn add_gate(&mut self, gate: &Gate) {
+        match gate {
+            Gate::Xor { x, y, z } => self.add_xor(self.signal(x), self.signal(y), z),
+            Gate::And { x, y, z } => self.add_and(self.signal(x), self.signal(y), z),
+            Gate::Inv { x, z } => self.add_inv(self.signal(x), z),
+            Gate::Lut(lut) => self.add_lut(lut),
+        }
+    }
+
+    fn add_xor(&mut self, x: Signal, y: Signal, z: &Node<Feed>) {
+        match (x, y) {
+            (Signal::Const(x), Signal::Const(y)) => self.alias(z, Signal::Const(x ^ y)),
+            (Signal::Const(false), s) | (s, Signal::Const(false)) => self.alias(z, s),
+            (Signal::Const(true), s) | (s, Signal::Const(true)) => self.add_inv(s, z),
+            (Signal::Feed(x), Signal::Feed(y)) if x == y => self.alias(z, Signal::Const(false)),
+            (Signal::Feed(x), Signal::Feed(y)) => {
+                self.gates.push(Gate::Xor {
+                    x: Node::new(x),
+                    y: Node::new(y),
+                    z: *z,
+                });
+                self.xor_count += 1;
+                self.alias(z, Signal::Feed(z.id));
+            }
+        }
+    }
+
+    fn add_and(&mut self, x: Signal, y: Signal, z: &Node<Feed>) {
+        match (x, y) {
+            (Signal::Const(x), Signal::Const(y)) => self.alias(z, Signal::Const(x & y)),
+            (Signal::Const(false), _) | (_, Signal::Const(false)) => {
+                self.alias(z, Signal::Const(false))
+            }
+            (Signal::Const(true), s) | (s, Signal::Const(true)) => self.alias(z, s),
+            (Signal::Feed(x), Signal::Feed(y)) if x == y => self.alias(z, Signal::Feed(x)),
+            (Signal::Feed(x), Signal::Feed(y)) => {
+                self.gates.push(Gate::And {
+                    x: Node::new(x),
+                    y: Node::new(y),
+                    z: *z,
+                });
+                self.and_count += 1;
+                self.alias(z, Signal::Feed(z.id));
+            }
+        }
+    }
+
+    fn add_inv(&mut self, x: Signal, z: &Node<Feed>) {
+        match x {
+            Signal::Const(x) => self.alias(z, Signal::Const(!x)),
+            Signal::Feed(x) => {
+                self.gates.push(Gate::Inv {
+                    x: Node::new(x),
+                    z: *z,
+                });
+                self.alias(z, Signal::Feed(z.id));
+            }
+        }
+    }
+
+    fn add_lut(&mut self, lut: &LookupTable) {
+        let inputs: Vec<Signal> = lut.inputs().iter().map(|x| self.signal(x)).collect();
+        let folded = fold_table(
+            lut.table(),
+            &inputs
+                .iter()
+                .map(|x| match x {
+                    Signal::Const(value) => Some(*value),
+                    Signal::Feed(_) => None,
+                })
+                .collect::<Vec<_>>(),
+            lut.outputs().len(),
+        );
+
+        let mut outputs = Vec::with_capacity(lut.outputs().len());
+        for (z, value) in lut.outputs().iter().zip(folded.outputs) {
+            match value {
+                Some(value) => self.alias(z, Signal::Const(value)),
+                None => {
+                    outputs.push(*z);
+                    self.alias(z, Signal::Feed(z.id));
+                }
+            }
+        }
+
+        if outputs.is_empty() {
+            return;
+        }
+
+        let inputs = folded
+            .inputs
+            .into_iter()
+            .map(|i| match inputs[i] {
+                Signal::Feed(x) => Node::new(x),
+                Signal::Const(_) => unreachable!("constant inputs should be folded"),
+            })
+            .collect();
+
+        self.gates.push(Gate::Lut(Box::new(LookupTable::new(
+            inputs,
+            outputs,
+            folded.table,
+        ))));
+        self.lut_count += 1;
+    }
+
+    /// Builds the rewritten circuit.
+    ///
+    /// Returns an error if an output is folded to a constant but the circuit has no input to
+    /// compute it from.
+    fn finish(mut self) -> Result<Circuit, OptimizeError> {
+        let mut outputs = self.circ.outputs().to_vec();
+
+        let input = self
+            .circ
+            .inputs()
+            .iter()
+            .flat_map(|input| input.iter())
+            .next()
+            .copied();
+        let mut constants = ConstantOutputs::new(input);
+        for node in outputs.iter_mut().flat_map(|output| output.iter_mut()) {
+            node.id = match self.map[node.id] {
+                Signal::Feed(id) => id,
+                Signal::Const(value) => {
+                    constants
+                        .get(&mut self, value)
+                        .ok_or(OptimizeError::ConstantOutput)?
+                        .id
+                }
+            };
+        }
+
+        Ok(Circuit {
+            inputs: self.circ.inputs().to_vec(),
+            outputs,
+            gates: self.gates,
+            feed_count: self.feed_count,
+            and_count: self.and_count,
+            xor_count: self.xor_count,
+            lut_count: self.lut_count,
+        })
+    }
+}
+
+impl AddGates for Rewriter<'_> {
+    fn add_xor_gate(&mut self, x: Node<Feed>, y: Node<Feed>) -> Node<Feed> {
+        let z = Node::new(self.feed_count);
+        self.feed_count += 1;
+
+        self.gates.push(Gate::Xor {
+            x: x.into(),
+            y: y.into(),
+            z,
+        });
+        self.xor_count += 1;
+
+        z
+    }
+
+    fn add_inv_gate(&mut self, x: Node<Feed>) -> Node<Feed> {
+        let z = Node::new(self.feed_count);
+        self.feed_count += 1;
+
+        self.gates.push(Gate::Inv { x: x.into(), z });
+
+        z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{ops::WrappingAdd, types::Value, CircuitBuilder};
+
+    use super::*;
+
+    fn assert_equivalent(a: &Circuit, b: &Circuit) {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..16 {
+            let inputs: Vec<Value> = a
+                .inputs()
+                .iter()
+                .map(|input| Value::random(&mut rng, &input.value_type()))
+                .collect();
+
+            assert_eq!(a.evaluate(&inputs).unwrap(), b.evaluate(&inputs).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_constant_propagation() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let zero = a ^ a;
+        builder.add_output(zero | b);
+        builder.add_output(!zero & b);
+        builder.add_output(zero);
+        let circ = builder.build().unwrap();
+
+        let (optimized, report) = Pass::ConstantPropagation.run(&circ).unwrap();
+
+        assert_eq!(report.and_count_before, circ.and_count());
+        assert_eq!(report.and_count_after, 0);
+        assert_eq!(optimized.and_count(), 0);
+        assert_equivalent(&circ, &optimized);
+    }
+
+    #[test]
+    fn test_dead_gate_elimination() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        let _unused = a.wrapping_add(b);
+        builder.add_output(a & b);
+        let circ = builder.build().unwrap();
+
+        let (optimized, report) = Pass::DeadGateElimination.run(&circ).unwrap();
+
+        assert_eq!(report.and_count_after, 8);
+        assert_eq!(report.xor_count_after, 0);
+        assert_eq!(optimized.gates().len(), 8);
+        assert_equivalent(&circ, &optimized);
+    }
+
+    #[test]
+    fn test_inv_chain_collapse() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(!!a & !!!b);
+        let circ = builder.build().unwrap();
+
+        let (optimized, _) =
+            optimize_with(&circ, &[Pass::InvChainCollapse, Pass::DeadGateElimination]).unwrap();
+
+        // 8 AND gates and 8 INV gates for `!b`.
+        assert_eq!(optimized.gates().len(), 16);
+        assert_equivalent(&circ, &optimized);
+    }
+
+    #[test]
+    fn test_structural_hashing() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_add(b));
+        builder.add_output(b.wrapping_add(a));
+        let circ = builder.build().unwrap();
+
+        let (optimized, report) = Pass::StructuralHashing.run(&circ).unwrap();
+
+        assert_eq!(report.and_count_after * 2, report.and_count_before);
+        assert_eq!(report.xor_count_after * 2, report.xor_count_before);
+        assert_equivalent(&circ, &optimized);
+    }
+
+    #[test]
+    #[ignore = "expensive"]
+    #[cfg(feature = "aes")]
+    fn test_optimize_aes() {
+        use crate::circuits::AES128;
+
+        let (optimized, reports) = optimize(&AES128).unwrap();
+
+        assert_eq!(reports.len(), Pass::ALL.len());
+        for report in reports {
+            assert!(report.and_count_after <= report.and_count_before);
+            assert!(report.xor_count_after <= report.xor_count_before);
+        }
+        assert_equivalent(&AES128, &optimized);
+    }
+
+    #[test]
+    #[ignore = "expensive"]
+    #[cfg(feature = "sha2")]
+    fn test_optimize_sha256() {
+        use crate::circuits::build_sha256;
+
+        let circ = build_sha256(0, 32);
+
+        let (optimized, _) = optimize(&circ).unwrap();
+
+        assert!(optimized.and_count() <= circ.and_count());
+        assert_equivalent(&circ, &optimized);
+    }
+}
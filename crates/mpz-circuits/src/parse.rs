@@ -1,12 +1,14 @@
 use crate::{
-    components::{Feed, GateType, Node},
-    types::ValueType,
+    builder::ConstantOutputs,
+    components::{Feed, Node},
+    types::{BinaryRepr, ValueType},
     Circuit, CircuitBuilder,
 };
 use regex::{Captures, Regex};
 use std::collections::HashMap;
 
-static GATE_PATTERN: &str = r"(?P<input_count>\d+)\s(?P<output_count>\d+)\s(?P<xref>\d+)\s(?:(?P<yref>\d+)\s)?(?P<zref>\d+)\s(?P<gate>INV|AND|XOR)";
+static GATE_PATTERN: &str =
+    r"^(?P<input_count>\d+)\s+(?P<output_count>\d+)\s+(?P<wires>(?:\d+\s+)+)(?P<gate>[A-Z]+)$";
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -14,10 +16,21 @@ pub enum ParseError {
     IOError(#[from] std::io::Error),
     #[error(transparent)]
     ParseIntError(#[from] std::num::ParseIntError),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("invalid gate: {0}")]
+    InvalidGate(String),
+    #[error("types do not match header: expected bit lengths {expected:?}, got {actual:?}")]
+    TypeMismatch {
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
     #[error("uninitialized feed: {0}")]
     UninitializedFeed(usize),
     #[error("unsupported gate type: {0}")]
     UnsupportedGateType(String),
+    #[error("constant output in a circuit without inputs")]
+    ConstantOutput,
     #[error(transparent)]
     BuilderError(#[from] crate::BuilderError),
 }
@@ -27,6 +40,14 @@ impl Circuit {
     ///
     /// See `https://homes.esat.kuleuven.be/~nsmart/MPC/` for more information.
     ///
+    /// In addition to `XOR`, `AND` and `INV` gates, the extended gates `EQ` (constant),
+    /// `EQW` (wire copy) and `MAND` (multi-AND) are supported. The legacy Bristol format,
+    /// which has a single line header for two inputs and one output, is also supported.
+    ///
+    /// If no input or output types are provided, they are derived from the bit lengths
+    /// in the header. Lengths which match a primitive type, eg 32 bits, are parsed as that
+    /// type, otherwise they are parsed as an array of bits.
+    ///
     /// # Arguments
     ///
     /// * `filename` - The path to the file to parse.
@@ -43,75 +64,141 @@ impl Circuit {
     ) -> Result<Self, ParseError> {
         let file = std::fs::read_to_string(filename)?;
 
+        Self::parse_str(&file, inputs, outputs)
+    }
+
+    /// Parses a circuit in Bristol-fashion format from a string.
+    ///
+    /// See [`Circuit::parse`] for more information.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit in Bristol-fashion format.
+    /// * `inputs` - The types of the inputs to the circuit.
+    /// * `outputs` - The types of the outputs to the circuit.
+    ///
+    /// # Returns
+    ///
+    /// The parsed circuit.
+    pub fn parse_str(
+        circ: &str,
+        inputs: &[ValueType],
+        outputs: &[ValueType],
+    ) -> Result<Self, ParseError> {
+        let mut lines = circ
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .peekable();
+
+        let header = Header::parse(&mut lines)?;
+        let inputs = value_types(&header.inputs, inputs)?;
+        let outputs = value_types(&header.outputs, outputs)?;
+
         let builder = CircuitBuilder::new();
 
-        let mut feed_ids: Vec<usize> = Vec::new();
+        // Maps wire ids to feeds in the new circuit.
         let mut feed_map: HashMap<usize, Node<Feed>> = HashMap::default();
 
-        let mut input_len = 0;
+        let mut wire_id = 0;
         for input in inputs {
-            let input = builder.add_input_by_type(input.clone());
-            for (node, old_id) in input.iter().zip(input_len..input_len + input.len()) {
-                feed_map.insert(old_id, *node);
+            let input = builder.add_input_by_type(input);
+            for node in input.iter() {
+                feed_map.insert(wire_id, *node);
+                wire_id += 1;
             }
-            input_len += input.len();
         }
 
         let mut state = builder.state().borrow_mut();
         let pattern = Regex::new(GATE_PATTERN).unwrap();
-        for cap in pattern.captures_iter(&file) {
-            let UncheckedGate {
-                xref,
-                yref,
-                zref,
-                gate_type,
-            } = UncheckedGate::parse(cap)?;
-            feed_ids.push(zref);
-
-            match gate_type {
-                GateType::Xor => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_xor_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
+        for line in lines {
+            let cap = pattern
+                .captures(line)
+                .ok_or_else(|| ParseError::InvalidGate(line.to_string()))?;
+
+            match UncheckedGate::parse(cap)? {
+                UncheckedGate::Xor { x, y, z } => {
+                    let new_x = get_feed(&feed_map, x)?;
+                    let new_y = get_feed(&feed_map, y)?;
+                    let new_z = state.add_xor_gate(new_x, new_y);
+                    feed_map.insert(z, new_z);
+                }
+                UncheckedGate::And { x, y, z } => {
+                    let new_x = get_feed(&feed_map, x)?;
+                    let new_y = get_feed(&feed_map, y)?;
+                    let new_z = state.add_and_gate(new_x, new_y);
+                    feed_map.insert(z, new_z);
+                }
+                UncheckedGate::Inv { x, z } => {
+                    let new_x = get_feed(&feed_map, x)?;
+                    let new_z = state.add_inv_gate(new_x);
+                    feed_map.insert(z, new_z);
+                }
+                UncheckedGate::Eq { value, z } => {
+                    let new_z = if value {
+                        state.get_const_one()
+                    } else {
+                        state.get_const_zero()
+                    };
+                    feed_map.insert(z, new_z);
+                }
+                UncheckedGate::Eqw { x, z } => {
+                    let new_x = get_feed(&feed_map, x)?;
+                    feed_map.insert(z, new_x);
                 }
-                GateType::And => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_y = feed_map
-                        .get(&yref.unwrap())
-                        .ok_or(ParseError::UninitializedFeed(yref.unwrap()))?;
-                    let new_z = state.add_and_gate(*new_x, *new_y);
-                    feed_map.insert(zref, new_z);
+                UncheckedGate::Mand { x, y, z } => {
+                    for ((x, y), z) in x.into_iter().zip(y).zip(z) {
+                        let new_x = get_feed(&feed_map, x)?;
+                        let new_y = get_feed(&feed_map, y)?;
+                        let new_z = state.add_and_gate(new_x, new_y);
+                        feed_map.insert(z, new_z);
+                    }
                 }
-                GateType::Inv => {
-                    let new_x = feed_map
-                        .get(&xref)
-                        .ok_or(ParseError::UninitializedFeed(xref))?;
-                    let new_z = state.add_inv_gate(*new_x);
-                    feed_map.insert(zref, new_z);
+            }
+        }
+
+        // The output wires are the last wires of the circuit.
+        let output_len: usize = outputs.iter().map(|output| output.len()).sum();
+        let mut wire_id = header.wire_count.checked_sub(output_len).ok_or_else(|| {
+            ParseError::InvalidHeader(format!(
+                "output length {output_len} exceeds wire count {}",
+                header.wire_count
+            ))
+        })?;
+
+        // Any constant outputs are computed from the first input instead.
+        let first_input = feed_map
+            .get(&0)
+            .copied()
+            .filter(|_| !header.inputs.is_empty());
+        let mut constants = ConstantOutputs::new(first_input);
+
+        let mut output_reprs: Vec<BinaryRepr> = Vec::with_capacity(outputs.len());
+        for output in outputs {
+            let mut feeds = Vec::with_capacity(output.len());
+            for _ in 0..output.len() {
+                let mut feed = get_feed(&feed_map, wire_id)?;
+                wire_id += 1;
+
+                if feed == state.get_const_zero() || feed == state.get_const_one() {
+                    let value = feed == state.get_const_one();
+                    feed = constants
+                        .get(&mut *state, value)
+                        .ok_or(ParseError::ConstantOutput)?;
                 }
+
+                feeds.push(feed);
             }
+
+            output_reprs.push(
+                output
+                    .to_bin_repr(&feeds)
+                    .expect("output should have correct bit length"),
+            );
         }
         drop(state);
-        feed_ids.sort();
-
-        for output in outputs.iter().rev() {
-            let feeds = feed_ids
-                .drain(feed_ids.len() - output.len()..)
-                .map(|id| {
-                    *feed_map
-                        .get(&id)
-                        .expect("Old feed should be mapped to new feed")
-                })
-                .collect::<Vec<Node<Feed>>>();
-
-            let output = output.to_bin_repr(&feeds).unwrap();
+
+        for output in output_reprs {
             builder.add_output(output);
         }
 
@@ -119,36 +206,191 @@ impl Circuit {
     }
 }
 
-struct UncheckedGate {
-    xref: usize,
-    yref: Option<usize>,
-    zref: usize,
-    gate_type: GateType,
+fn get_feed(feed_map: &HashMap<usize, Node<Feed>>, id: usize) -> Result<Node<Feed>, ParseError> {
+    feed_map
+        .get(&id)
+        .copied()
+        .ok_or(ParseError::UninitializedFeed(id))
+}
+
+/// Returns the value types of the inputs or outputs of a circuit, deriving them from
+/// the header if none are provided.
+fn value_types(lens: &[usize], types: &[ValueType]) -> Result<Vec<ValueType>, ParseError> {
+    if types.is_empty() {
+        return Ok(lens
+            .iter()
+            .map(|len| match len {
+                1 => ValueType::Bit,
+                8 => ValueType::U8,
+                16 => ValueType::U16,
+                32 => ValueType::U32,
+                64 => ValueType::U64,
+                128 => ValueType::U128,
+                len => ValueType::Array(Box::new(ValueType::Bit), *len),
+            })
+            .collect());
+    }
+
+    let actual: Vec<usize> = types.iter().map(ValueType::len).collect();
+    if actual != lens {
+        return Err(ParseError::TypeMismatch {
+            expected: lens.to_vec(),
+            actual,
+        });
+    }
+
+    Ok(types.to_vec())
+}
+
+fn parse_numbers(line: &str) -> Option<Vec<usize>> {
+    line.split_whitespace()
+        .map(|number| number.parse().ok())
+        .collect()
+}
+
+/// The header of a Bristol-fashion circuit.
+struct Header {
+    wire_count: usize,
+    /// The bit length of each input.
+    inputs: Vec<usize>,
+    /// The bit length of each output.
+    outputs: Vec<usize>,
+}
+
+impl Header {
+    fn parse<'a>(
+        lines: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+    ) -> Result<Self, ParseError> {
+        let invalid =
+            |line: Option<&str>| ParseError::InvalidHeader(line.unwrap_or_default().into());
+
+        let line = lines.next();
+        let wire_count = match line.and_then(parse_numbers).as_deref() {
+            Some([_, wire_count]) => *wire_count,
+            _ => return Err(invalid(line)),
+        };
+
+        let line = lines.next();
+        let io = line.and_then(parse_numbers).ok_or_else(|| invalid(line))?;
+
+        // The legacy format has a single line for the inputs and output, which
+        // is followed directly by the gates.
+        let (inputs, outputs) = match lines.peek().copied().and_then(parse_numbers) {
+            Some(outputs) => {
+                lines.next();
+                (
+                    parse_lengths(&io).ok_or_else(|| invalid(line))?,
+                    parse_lengths(&outputs).ok_or_else(|| invalid(line))?,
+                )
+            }
+            None => match io.as_slice() {
+                [a, b, out] => (vec![*a, *b], vec![*out]),
+                _ => return Err(invalid(line)),
+            },
+        };
+
+        Ok(Self {
+            wire_count,
+            inputs,
+            outputs,
+        })
+    }
+}
+
+/// Parses a line of the form `count len_1 .. len_count`.
+fn parse_lengths(numbers: &[usize]) -> Option<Vec<usize>> {
+    match numbers {
+        [count, lens @ ..] if *count == lens.len() => Some(lens.to_vec()),
+        _ => None,
+    }
+}
+
+enum UncheckedGate {
+    Xor {
+        x: usize,
+        y: usize,
+        z: usize,
+    },
+    And {
+        x: usize,
+        y: usize,
+        z: usize,
+    },
+    Inv {
+        x: usize,
+        z: usize,
+    },
+    Eq {
+        value: bool,
+        z: usize,
+    },
+    Eqw {
+        x: usize,
+        z: usize,
+    },
+    Mand {
+        x: Vec<usize>,
+        y: Vec<usize>,
+        z: Vec<usize>,
+    },
 }
 
 impl UncheckedGate {
     fn parse(captures: Captures) -> Result<Self, ParseError> {
-        let xref: usize = captures.name("xref").unwrap().as_str().parse()?;
-        let yref: Option<usize> = captures
-            .name("yref")
-            .map(|yref| yref.as_str().parse())
-            .transpose()?;
-        let zref: usize = captures.name("zref").unwrap().as_str().parse()?;
+        let input_count: usize = captures.name("input_count").unwrap().as_str().parse()?;
+        let output_count: usize = captures.name("output_count").unwrap().as_str().parse()?;
+        let wires = captures
+            .name("wires")
+            .unwrap()
+            .as_str()
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<usize>, _>>()?;
         let gate_type = captures.name("gate").unwrap().as_str();
 
-        let gate_type = match gate_type {
-            "XOR" => GateType::Xor,
-            "AND" => GateType::And,
-            "INV" => GateType::Inv,
+        let invalid = || ParseError::InvalidGate(captures[0].to_string());
+
+        if wires.len() != input_count + output_count {
+            return Err(invalid());
+        }
+
+        let gate = match (gate_type, input_count, output_count) {
+            ("XOR", 2, 1) => UncheckedGate::Xor {
+                x: wires[0],
+                y: wires[1],
+                z: wires[2],
+            },
+            ("AND", 2, 1) => UncheckedGate::And {
+                x: wires[0],
+                y: wires[1],
+                z: wires[2],
+            },
+            ("INV", 1, 1) => UncheckedGate::Inv {
+                x: wires[0],
+                z: wires[1],
+            },
+            ("EQ", 1, 1) => UncheckedGate::Eq {
+                value: match wires[0] {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(invalid()),
+                },
+                z: wires[1],
+            },
+            ("EQW", 1, 1) => UncheckedGate::Eqw {
+                x: wires[0],
+                z: wires[1],
+            },
+            ("MAND", n, m) if n == 2 * m => UncheckedGate::Mand {
+                x: wires[..m].to_vec(),
+                y: wires[m..n].to_vec(),
+                z: wires[n..].to_vec(),
+            },
+            ("XOR" | "AND" | "INV" | "EQ" | "EQW" | "MAND", ..) => return Err(invalid()),
             _ => return Err(ParseError::UnsupportedGateType(gate_type.to_string())),
         };
 
-        Ok(Self {
-            xref,
-            yref,
-            zref,
-            gate_type,
-        })
+        Ok(gate)
     }
 }
 
@@ -172,6 +414,98 @@ mod tests {
         assert_eq!(output, 3);
     }
 
+    #[test]
+    fn test_parse_header_types() {
+        let circ = Circuit::parse("circuits/bristol/adder64_reverse.txt", &[], &[]).unwrap();
+
+        assert_eq!(
+            circ.inputs()
+                .iter()
+                .map(|input| input.value_type())
+                .collect::<Vec<_>>(),
+            vec![ValueType::U64, ValueType::U64]
+        );
+        assert_eq!(circ.outputs()[0].value_type(), ValueType::U64);
+
+        let output: u64 = evaluate!(circ, fn(1u64, 2u64) -> u64).unwrap();
+
+        assert_eq!(output, 3);
+    }
+
+    #[test]
+    fn test_parse_type_mismatch() {
+        let err = Circuit::parse(
+            "circuits/bristol/adder64_reverse.txt",
+            &[ValueType::U32, ValueType::U32],
+            &[ValueType::U64],
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, ParseError::TypeMismatch { .. }));
+    }
+
+    #[test]
+    fn test_parse_extended_gates() {
+        // Wires 7, 8 and 9 are the outputs, where wire 9 is a constant.
+        let circ = "\
+            5 10
+            2 2 2
+            2 1 2
+
+            4 2 0 1 2 3 4 5 MAND
+            1 1 1 6 EQ
+            2 1 4 6 7 XOR
+            1 1 5 8 EQW
+            1 1 0 9 EQ
+        ";
+
+        let circ = Circuit::parse_str(circ, &[], &[]).unwrap();
+
+        assert_eq!(circ.and_count(), 2);
+
+        for (a, b) in [
+            ([true, true], [true, true]),
+            ([true, true], [false, true]),
+            ([false, true], [true, false]),
+        ] {
+            let (c, d) = evaluate!(circ, fn(a, b) -> (bool, [bool; 2])).unwrap();
+
+            assert_eq!(c, !(a[0] & b[0]));
+            assert_eq!(d, [a[1] & b[1], false]);
+        }
+    }
+
+    #[test]
+    fn test_parse_legacy_header() {
+        let circ = "\
+            1 3
+            1 1 1
+
+            2 1 0 1 2 AND
+        ";
+
+        let circ = Circuit::parse_str(circ, &[], &[]).unwrap();
+
+        let output = evaluate!(circ, fn(true, true) -> bool).unwrap();
+
+        assert!(output);
+    }
+
+    #[test]
+    fn test_parse_unsupported_gate() {
+        let circ = "\
+            1 3
+            2 1 1
+            1 1
+
+            2 1 0 1 2 OR
+        ";
+
+        let err = Circuit::parse_str(circ, &[], &[]).unwrap_err();
+
+        assert!(matches!(err, ParseError::UnsupportedGateType(gate) if gate == "OR"));
+    }
+
     #[test]
     #[cfg(feature = "aes")]
     #[ignore = "expensive"]
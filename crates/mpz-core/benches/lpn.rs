@@ -1,5 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mpz_core::{lpn::LpnEncoder, prg::Prg, Block};
+use mpz_core::{
+    lpn::{ExpandAccumulateEncoder, ExpandConvoluteEncoder, LpnEncoder},
+    prg::Prg,
+    Block,
+};
 use std::time::Duration;
 
 fn criterion_benchmark(c: &mut Criterion) {
@@ -50,6 +54,36 @@ fn criterion_benchmark(c: &mut Criterion) {
             black_box(lpn.compute(&mut y, &x));
         });
     });
+
+    c.bench_function("expand-accumulate-rayon-medium", move |bench| {
+        let seed = Block::ZERO;
+        let n = 2_097_152;
+        let m = 1_048_576;
+        let lpn = ExpandAccumulateEncoder::<21>::new(seed, n);
+        let mut x = vec![Block::ZERO; n as usize];
+        let mut y = vec![Block::ZERO; m];
+        let mut prg = Prg::new();
+        prg.random_blocks(&mut x);
+        bench.iter(|| {
+            #[allow(clippy::unit_arg)]
+            black_box(lpn.compute(&mut y, &x));
+        });
+    });
+
+    c.bench_function("expand-convolute-rayon-medium", move |bench| {
+        let seed = Block::ZERO;
+        let n = 2_097_152;
+        let m = 1_048_576;
+        let lpn = ExpandConvoluteEncoder::<7>::new(seed, n);
+        let mut x = vec![Block::ZERO; n as usize];
+        let mut y = vec![Block::ZERO; m];
+        let mut prg = Prg::new();
+        prg.random_blocks(&mut x);
+        bench.iter(|| {
+            #[allow(clippy::unit_arg)]
+            black_box(lpn.compute(&mut y, &x));
+        });
+    });
 }
 
 criterion_group! {
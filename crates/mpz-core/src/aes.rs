@@ -21,7 +21,19 @@ pub struct FixedKeyAes {
     aes: Aes128Enc,
 }
 
+opaque_debug::implement!(FixedKeyAes);
+
 impl FixedKeyAes {
+    /// Creates a new cipher with the provided key.
+    ///
+    /// The hash functions are usually instantiated using [`FIXED_KEY_AES`]. Using a fresh key,
+    /// eg for each session, improves their security when many instances are attacked at once.
+    pub fn new(key: [u8; 16]) -> Self {
+        Self {
+            aes: Aes128Enc::new_from_slice(&key).unwrap(),
+        }
+    }
+
     /// Tweakable circular correlation-robust hash function instantiated
     /// using fixed-key AES.
     ///
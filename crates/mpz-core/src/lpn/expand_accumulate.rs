@@ -0,0 +1,112 @@
+//! Implement the [`expand-accumulate`](https://eprint.iacr.org/2022/1014.pdf) code for dual LPN.
+
+use crate::{
+    lpn::{expander::Expander, LpnCode},
+    Block,
+};
+
+/// An expand-accumulate encoder for dual LPN.
+///
+/// The `seed` defines a random sparse binary matrix `B` with `D` non-zero values in each row, and
+/// `A` is the accumulator matrix, i.e., the lower triangular matrix of ones.
+///
+/// Given a vector `x`, compute `y = BAx + y`.
+///
+/// `x` - is a `F_{2^128}` vector with length `n`, usually the noisy vector of a PCG.
+///
+/// `y` - is a `F_{2^128}` vector, whose length determines the number of rows of `B`. It is
+/// usually shorter than `x`, so the code compresses `x`.
+pub struct ExpandAccumulateEncoder<const D: usize> {
+    expander: Expander<D>,
+}
+
+impl<const D: usize> ExpandAccumulateEncoder<D> {
+    /// Create a new encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed to generate the expander matrix.
+    /// * `n` - The length of the input vector.
+    pub fn new(seed: Block, n: u32) -> Self {
+        Self {
+            expander: Expander::new(seed, n),
+        }
+    }
+
+    /// Compute `BAx + y`, writing the result in-place into `y`.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The vector added to the result, with the length of the output.
+    /// * `x` - The vector to encode, with length `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() != n`.
+    pub fn compute(&self, y: &mut [Block], x: &[Block]) {
+        let mut acc = x.to_vec();
+        for i in 1..acc.len() {
+            let prev = acc[i - 1];
+            acc[i] ^= prev;
+        }
+
+        self.expander.expand(y, &acc);
+    }
+}
+
+impl<const D: usize> LpnCode for ExpandAccumulateEncoder<D> {
+    fn compute(&self, y: &mut [Block], x: &[Block]) {
+        ExpandAccumulateEncoder::compute(self, y, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prg::Prg, prp::Prp};
+
+    #[test]
+    fn test_expand_accumulate() {
+        let n = 1000;
+        let m = 300;
+        let encoder = ExpandAccumulateEncoder::<7>::new(Block::ONES, n as u32);
+        let prp = Prp::new(Block::ONES);
+
+        // Encoding the j-th unit vector sets a row to the parity of its indices which are at
+        // least j.
+        for j in [0, 1, 500, n - 1] {
+            let mut x = vec![Block::ZERO; n];
+            x[j] = Block::ONE;
+            let mut y = vec![Block::ZERO; m];
+            encoder.compute(&mut y, &x);
+
+            for (i, y) in y.iter().enumerate() {
+                let parity = encoder
+                    .expander
+                    .row(&prp, i)
+                    .into_iter()
+                    .filter(|index| *index >= j)
+                    .count()
+                    % 2;
+                assert_eq!(y.lsb(), parity);
+            }
+        }
+
+        // The code is linear.
+        let mut prg = Prg::new();
+        let mut x = vec![Block::ZERO; n];
+        let mut x_ = vec![Block::ZERO; n];
+        prg.random_blocks(&mut x);
+        prg.random_blocks(&mut x_);
+        let sum = x.iter().zip(&x_).map(|(a, b)| *a ^ *b).collect::<Vec<_>>();
+
+        let mut y = vec![Block::ZERO; m];
+        encoder.compute(&mut y, &x);
+        encoder.compute(&mut y, &x_);
+
+        let mut z = vec![Block::ZERO; m];
+        encoder.compute(&mut z, &sum);
+
+        assert_eq!(y, z);
+    }
+}
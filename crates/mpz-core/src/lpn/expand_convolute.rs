@@ -0,0 +1,139 @@
+//! Implement the [`expand-convolute`](https://eprint.iacr.org/2023/882.pdf) code for dual LPN.
+
+use rand::RngCore;
+use rand_core::SeedableRng;
+
+use crate::{
+    lpn::{expander::Expander, LpnCode},
+    prg::Prg,
+    Block,
+};
+
+/// The number of random taps of the convolution, in addition to the accumulator.
+const WINDOW: u32 = 24;
+
+/// An expand-convolute encoder for dual LPN.
+///
+/// The `seed` defines a random sparse binary matrix `B` with `D` non-zero values in each row, and
+/// a random convolution `C`. The convolution is an accumulator which additionally adds `WINDOW`
+/// randomly selected previous outputs, i.e.,
+/// `(Cx)_i = x_i + (Cx)_{i-1} + sum_{j=1}^{WINDOW} c_{i,j} (Cx)_{i-1-j}` for random bits `c_{i,j}`.
+///
+/// Given a vector `x`, compute `y = BCx + y`.
+///
+/// `x` - is a `F_{2^128}` vector with length `n`, usually the noisy vector of a PCG.
+///
+/// `y` - is a `F_{2^128}` vector, whose length determines the number of rows of `B`. It is
+/// usually shorter than `x`, so the code compresses `x`.
+///
+/// Compared to [`ExpandAccumulateEncoder`](super::ExpandAccumulateEncoder), the convolution
+/// allows a smaller expander weight `D` for the same minimum distance.
+pub struct ExpandConvoluteEncoder<const D: usize> {
+    expander: Expander<D>,
+    /// The seed to generate the convolution.
+    seed: Block,
+}
+
+impl<const D: usize> ExpandConvoluteEncoder<D> {
+    /// Create a new encoder.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed to generate the expander matrix and the convolution.
+    /// * `n` - The length of the input vector.
+    pub fn new(seed: Block, n: u32) -> Self {
+        let mut prg = Prg::from_seed(seed);
+
+        Self {
+            expander: Expander::new(prg.random_block(), n),
+            seed: prg.random_block(),
+        }
+    }
+
+    /// Compute `BCx + y`, writing the result in-place into `y`.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The vector added to the result, with the length of the output.
+    /// * `x` - The vector to encode, with length `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() != n`.
+    pub fn compute(&self, y: &mut [Block], x: &[Block]) {
+        let mut acc = x.to_vec();
+        self.convolute(&mut acc);
+
+        self.expander.expand(y, &acc);
+    }
+
+    /// Computes `Cx`, writing the result in-place into `x`.
+    fn convolute(&self, x: &mut [Block]) {
+        let mut prg = Prg::from_seed(self.seed);
+
+        for i in 1..x.len() {
+            let mut sum = x[i - 1];
+
+            let mut taps = prg.next_u32() & ((1 << WINDOW) - 1);
+            while taps != 0 {
+                let j = taps.trailing_zeros() as usize + 2;
+                if j <= i {
+                    sum ^= x[i - j];
+                }
+                taps &= taps - 1;
+            }
+
+            x[i] ^= sum;
+        }
+    }
+}
+
+impl<const D: usize> LpnCode for ExpandConvoluteEncoder<D> {
+    fn compute(&self, y: &mut [Block], x: &[Block]) {
+        ExpandConvoluteEncoder::compute(self, y, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_convolute() {
+        let n = 1000;
+        let m = 300;
+        let encoder = ExpandConvoluteEncoder::<7>::new(Block::ONES, n as u32);
+
+        // The convolution is invertible, so it maps a unit vector to a non-zero vector whose
+        // first non-zero value is at the same position.
+        let mut x = vec![Block::ZERO; n];
+        x[500] = Block::ONE;
+        encoder.convolute(&mut x);
+        assert!(x[..500].iter().all(|x| *x == Block::ZERO));
+        assert_eq!(x[500], Block::ONE);
+
+        // The code is linear.
+        let mut prg = Prg::new();
+        let mut x = vec![Block::ZERO; n];
+        let mut x_ = vec![Block::ZERO; n];
+        prg.random_blocks(&mut x);
+        prg.random_blocks(&mut x_);
+        let sum = x.iter().zip(&x_).map(|(a, b)| *a ^ *b).collect::<Vec<_>>();
+
+        let mut y = vec![Block::ZERO; m];
+        encoder.compute(&mut y, &x);
+        encoder.compute(&mut y, &x_);
+
+        let mut z = vec![Block::ZERO; m];
+        encoder.compute(&mut z, &sum);
+
+        assert_eq!(y, z);
+
+        // A different seed defines a different code.
+        let other = ExpandConvoluteEncoder::<7>::new(Block::ZERO, n as u32);
+        let mut z = vec![Block::ZERO; m];
+        other.compute(&mut z, &sum);
+
+        assert_ne!(y, z);
+    }
+}
@@ -0,0 +1,66 @@
+//! A random sparse expander matrix, shared by the dual LPN codes.
+
+use crate::{prp::Prp, Block};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// A random binary matrix with `D` non-zero values in each row and `n` columns.
+///
+/// The rows are generated on-the-fly from the seed, so the number of rows is determined by the
+/// output length.
+pub(crate) struct Expander<const D: usize> {
+    /// The seed to generate the matrix.
+    seed: Block,
+    /// The number of columns.
+    n: u32,
+}
+
+impl<const D: usize> Expander<D> {
+    /// Creates a new expander with `n` columns.
+    pub(crate) fn new(seed: Block, n: u32) -> Self {
+        Self { seed, n }
+    }
+
+    /// Returns the column indices of the non-zero values in the given row.
+    #[inline]
+    pub(crate) fn row(&self, prp: &Prp, row: usize) -> [usize; D] {
+        let mut indices = [0usize; D];
+        for (i, chunk) in indices.chunks_mut(4).enumerate() {
+            let block = prp.permute_block(Block::from(bytemuck::cast::<_, [u8; 16]>([
+                row as u64, i as u64,
+            ])));
+            let words = bytemuck::cast::<_, [u32; 4]>(block);
+
+            for (index, word) in chunk.iter_mut().zip(words) {
+                // Maps the word uniformly into [0, n).
+                *index = ((word as u64 * self.n as u64) >> 32) as usize;
+            }
+        }
+        indices
+    }
+
+    /// Computes `Bx + y`, writing the result in-place into `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() != n`.
+    pub(crate) fn expand(&self, y: &mut [Block], x: &[Block]) {
+        assert_eq!(x.len() as u32, self.n);
+        let prp = Prp::new(self.seed);
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")]{
+                let iter = y.par_iter_mut().enumerate();
+            }else{
+                let iter = y.iter_mut().enumerate();
+            }
+        }
+
+        iter.for_each(|(i, y)| {
+            for index in self.row(&prp, i) {
+                *y ^= x[index];
+            }
+        });
+    }
+}
@@ -1,9 +1,39 @@
 //! Implement LPN with local linear code.
 //! More specifically, a local linear code is a random boolean matrix with at most D non-zero values in each row.
+//!
+//! This module also provides codes for dual LPN, which compress a long noisy vector into a shorter
+//! one: [`ExpandAccumulateEncoder`] and [`ExpandConvoluteEncoder`]. All codes implement [`LpnCode`].
+
+mod expand_accumulate;
+mod expand_convolute;
+mod expander;
+
+pub use expand_accumulate::ExpandAccumulateEncoder;
+pub use expand_convolute::ExpandConvoluteEncoder;
 
 use crate::{prp::Prp, Block};
 use rand::{seq::SliceRandom, thread_rng};
 use rayon::prelude::*;
+use std::ops::Add;
+
+/// A binary linear code used to compute LPN samples.
+///
+/// The code defines a binary matrix `M` with `y.len()` rows and `x.len()` columns, which is
+/// applied to vectors of `F_{2^128}` elements.
+pub trait LpnCode {
+    /// Computes `Mx + y`, writing the result in-place into `y`.
+    ///
+    /// # Arguments
+    ///
+    /// * `y` - The vector added to the result.
+    /// * `x` - The vector to encode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `x` is not the one the code was created with.
+    fn compute(&self, y: &mut [Block], x: &[Block]);
+}
+
 /// An LPN encoder.
 ///
 /// The `seed` defines a sparse binary matrix `A` with at most `D` non-zero values in each row.
@@ -41,7 +71,14 @@ impl<const D: usize> LpnEncoder<D> {
 
     /// Compute 4 rows as a batch, this is for the `compute` function.
     #[inline]
-    fn compute_four_rows_indep(&self, y: &mut [Block], x: &[Block], pos: usize, prp: &Prp) {
+    fn compute_four_rows_indep<T: Copy>(
+        &self,
+        y: &mut [T],
+        x: &[T],
+        pos: usize,
+        prp: &Prp,
+        add: &impl Fn(T, T) -> T,
+    ) {
         let mut cnt = 0u64;
         let mut index: [Block; D] = std::array::from_fn(|_| {
             let i = cnt;
@@ -57,13 +94,20 @@ impl<const D: usize> LpnEncoder<D> {
                 *ind &= self.mask;
                 *ind = if *ind >= self.k { *ind - self.k } else { *ind };
 
-                *y ^= x[*ind as usize];
+                *y = add(*y, x[*ind as usize]);
             }
         }
     }
 
     #[inline]
-    fn compute_one_row(&self, y: &mut [Block], x: &[Block], pos: usize, prp: &Prp) {
+    fn compute_one_row<T: Copy>(
+        &self,
+        y: &mut [T],
+        x: &[T],
+        pos: usize,
+        prp: &Prp,
+        add: &impl Fn(T, T) -> T,
+    ) {
         let block_size = (D + 4 - 1) / 4;
         let mut index = (0..block_size)
             .map(|i| Block::from(bytemuck::cast::<_, [u8; 16]>([pos as u64, i as u64])))
@@ -74,7 +118,7 @@ impl<const D: usize> LpnEncoder<D> {
         for ind in index.iter_mut().take(D) {
             *ind &= self.mask;
             *ind = if *ind >= self.k { *ind - self.k } else { *ind };
-            y[pos] ^= x[*ind as usize];
+            y[pos] = add(y[pos], x[*ind as usize]);
         }
     }
 
@@ -89,6 +133,35 @@ impl<const D: usize> LpnEncoder<D> {
     ///
     /// Panics if `x.len() !=k` or `y.len() != n`.
     pub fn compute(&self, y: &mut [Block], x: &[Block]) {
+        self.compute_with(y, x, |a, b| a ^ b)
+    }
+
+    /// Compute `Ax + e` over an additive group, e.g. a prime field, writing the result in-place
+    /// into `y`.
+    ///
+    /// `A` is generated as in [`LpnEncoder::compute`], which computes over `F_{2^128}`. A row
+    /// which selects the same element of `x` twice adds it twice, so the results agree modulo 2.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - Secret vector with length `k`.
+    /// * `y` - Error vector with length `n`, this is actually `e` in LPN.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x.len() !=k` or `y.len() != n`.
+    pub fn compute_add<T>(&self, y: &mut [T], x: &[T])
+    where
+        T: Copy + Send + Sync + Add<Output = T>,
+    {
+        self.compute_with(y, x, |a, b| a + b)
+    }
+
+    fn compute_with<T, A>(&self, y: &mut [T], x: &[T], add: A)
+    where
+        T: Copy + Send + Sync,
+        A: Fn(T, T) -> T + Sync,
+    {
         assert_eq!(x.len() as u32, self.k);
         assert!(x.len() >= D);
         let prp = Prp::new(self.seed);
@@ -103,15 +176,21 @@ impl<const D: usize> LpnEncoder<D> {
         }
 
         iter.for_each(|(i, y)| {
-            self.compute_four_rows_indep(y, x, i * 4, &prp);
+            self.compute_four_rows_indep(y, x, i * 4, &prp, &add);
         });
 
         for i in size..y.len() {
-            self.compute_one_row(y, x, i, &prp);
+            self.compute_one_row(y, x, i, &prp, &add);
         }
     }
 }
 
+impl<const D: usize> LpnCode for LpnEncoder<D> {
+    fn compute(&self, y: &mut [Block], x: &[Block]) {
+        LpnEncoder::compute(self, y, x)
+    }
+}
+
 /// Lpn paramters
 #[derive(Copy, Clone, Debug)]
 pub struct LpnParameters {
@@ -196,7 +275,7 @@ mod tests {
             }
 
             for i in batch_size * 4..y.len() {
-                self.compute_one_row(y, x, i, &prp);
+                self.compute_one_row(y, x, i, &prp, &|a, b| a ^ b);
             }
         }
     }
@@ -222,4 +301,35 @@ mod tests {
 
         assert_eq!(y, z);
     }
+
+    #[test]
+    fn lpn_add_test() {
+        use crate::lpn::LpnEncoder;
+        use crate::prg::Prg;
+        use crate::Block;
+        use std::num::Wrapping;
+
+        let k = 20;
+        let n = 202;
+        let lpn = LpnEncoder::<10>::new(Block::ZERO, k);
+        let mut prg = Prg::new();
+        let mut bits = vec![false; k as usize];
+        prg.random_bools(&mut bits);
+
+        // Over the integers, the parity of each row matches the binary computation.
+        let x = bits
+            .iter()
+            .map(|b| if *b { Block::ONE } else { Block::ZERO })
+            .collect::<Vec<_>>();
+        let mut y = vec![Block::ZERO; n];
+        lpn.compute(&mut y, &x);
+
+        let x = bits.iter().map(|b| Wrapping(*b as u64)).collect::<Vec<_>>();
+        let mut z = vec![Wrapping(0u64); n];
+        lpn.compute_add(&mut z, &x);
+
+        y.iter()
+            .zip(z)
+            .for_each(|(y, z)| assert_eq!(y.lsb() as u64, z.0 % 2));
+    }
 }
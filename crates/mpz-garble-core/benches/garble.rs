@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mpz_circuits::circuits::AES128;
-use mpz_garble_core::{ChaChaEncoder, Encoder, Evaluator, Generator};
+use mpz_circuits::circuits::{AES128, AES128_LUT};
+use mpz_garble_core::{ChaChaEncoder, Encoder, Evaluator, Generator, ThreeHalves};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let mut gb_group = c.benchmark_group("garble");
@@ -58,6 +58,47 @@ fn criterion_benchmark(c: &mut Criterion) {
         })
     });
 
+    gb_group.bench_function("aes128_parallel", |b| {
+        let mut gen = Generator::default();
+        b.iter(|| {
+            let mut gen_iter = gen
+                .generate_parallel(&AES128, encoder.delta(), full_inputs.clone())
+                .unwrap();
+
+            let _: Vec<_> = gen_iter.by_ref().collect();
+
+            black_box(gen_iter.finish().unwrap())
+        })
+    });
+
+    gb_group.bench_function("aes128_three_halves", |b| {
+        let mut gen = Generator::<ThreeHalves>::new();
+        b.iter(|| {
+            let mut gen_iter = gen
+                .generate(&AES128, encoder.delta(), full_inputs.clone())
+                .unwrap();
+
+            let _: Vec<_> = gen_iter.by_ref().collect();
+
+            black_box(gen_iter.finish().unwrap())
+        })
+    });
+
+    // The S-boxes are computed using lookup gates instead of AND gates, which is far more
+    // communication.
+    gb_group.bench_function("aes128_lut", |b| {
+        let mut gen = Generator::default();
+        b.iter(|| {
+            let mut gen_iter = gen
+                .generate(&AES128_LUT, encoder.delta(), full_inputs.clone())
+                .unwrap();
+
+            let _: Vec<_> = gen_iter.by_ref().collect();
+
+            black_box(gen_iter.finish().unwrap())
+        })
+    });
+
     drop(gb_group);
 
     let mut ev_group = c.benchmark_group("evaluate");
@@ -80,6 +121,44 @@ fn criterion_benchmark(c: &mut Criterion) {
             black_box(ev_consumer.finish().unwrap());
         })
     });
+
+    ev_group.bench_function("aes128_lut", |b| {
+        let mut gen = Generator::default();
+        let mut gen_iter = gen
+            .generate(&AES128_LUT, encoder.delta(), full_inputs.clone())
+            .unwrap();
+        let gates: Vec<_> = gen_iter.by_ref().collect();
+
+        let mut ev = Evaluator::default();
+        b.iter(|| {
+            let mut ev_consumer = ev.evaluate(&AES128_LUT, active_inputs.clone()).unwrap();
+
+            for gate in &gates {
+                ev_consumer.next(*gate);
+            }
+
+            black_box(ev_consumer.finish().unwrap());
+        })
+    });
+
+    ev_group.bench_function("aes128_three_halves", |b| {
+        let mut gen = Generator::<ThreeHalves>::new();
+        let mut gen_iter = gen
+            .generate(&AES128, encoder.delta(), full_inputs.clone())
+            .unwrap();
+        let gates: Vec<_> = gen_iter.by_ref().collect();
+
+        let mut ev = Evaluator::<ThreeHalves>::new();
+        b.iter(|| {
+            let mut ev_consumer = ev.evaluate(&AES128, active_inputs.clone()).unwrap();
+
+            for gate in &gates {
+                ev_consumer.next(*gate);
+            }
+
+            black_box(ev_consumer.finish().unwrap());
+        })
+    });
 }
 
 criterion_group!(benches, criterion_benchmark);
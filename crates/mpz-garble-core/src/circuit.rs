@@ -3,7 +3,7 @@ use std::ops::Index;
 use mpz_core::Block;
 use serde::{Deserialize, Serialize};
 
-use crate::{EncodingCommitment, DEFAULT_BATCH_SIZE};
+use crate::{EncodingCommitment, DEFAULT_BATCH_SIZE, THREE_HALVES_BATCH_SIZE};
 
 /// Encrypted gate truth table
 ///
@@ -25,6 +25,14 @@ impl EncryptedGate {
         bytes[16..].copy_from_slice(&self.0[1].to_bytes());
         bytes
     }
+
+    pub(crate) fn from_bytes(bytes: [u8; 32]) -> Self {
+        let mut blocks = [Block::ZERO; 2];
+        for (block, chunk) in blocks.iter_mut().zip(bytes.chunks_exact(16)) {
+            *block = Block::new(chunk.try_into().expect("chunk is 16 bytes"));
+        }
+        Self(blocks)
+    }
 }
 
 impl Index<usize> for EncryptedGate {
@@ -35,33 +43,86 @@ impl Index<usize> for EncryptedGate {
     }
 }
 
+/// Encrypted gate of the three-halves garbling scheme
+///
+/// Consists of 3 ciphertexts of half the size of a label, and the encrypted control bits of
+/// the 4 rows of the gate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ThreeHalvesGate {
+    pub(crate) halves: [u64; 3],
+    pub(crate) control: u16,
+}
+
+impl ThreeHalvesGate {
+    pub(crate) fn new(halves: [u64; 3], control: u16) -> Self {
+        Self { halves, control }
+    }
+
+    pub(crate) fn to_bytes(self) -> [u8; 26] {
+        let mut bytes = [0u8; 26];
+        for (chunk, half) in bytes.chunks_exact_mut(8).zip(self.halves) {
+            chunk.copy_from_slice(&half.to_le_bytes());
+        }
+        bytes[24..].copy_from_slice(&self.control.to_le_bytes());
+        bytes
+    }
+
+    pub(crate) fn from_bytes(bytes: [u8; 26]) -> Self {
+        let mut halves = [0u64; 3];
+        for (half, chunk) in halves.iter_mut().zip(bytes.chunks_exact(8)) {
+            *half = u64::from_le_bytes(chunk.try_into().expect("chunk is 8 bytes"));
+        }
+        let control = u16::from_le_bytes([bytes[24], bytes[25]]);
+
+        Self { halves, control }
+    }
+}
+
 /// A batch of encrypted gates.
 ///
 /// # Parameters
 ///
 /// - `N`: The size of a batch.
+/// - `G`: The type of the encrypted gates, see [`GarblingScheme`](crate::GarblingScheme).
 #[derive(Debug, Serialize, Deserialize)]
-pub struct EncryptedGateBatch<const N: usize = DEFAULT_BATCH_SIZE>(
-    #[serde(with = "serde_arrays")] [EncryptedGate; N],
+#[serde(bound(serialize = "G: Serialize", deserialize = "G: Deserialize<'de>"))]
+pub struct EncryptedGateBatch<const N: usize = DEFAULT_BATCH_SIZE, G = EncryptedGate>(
+    #[serde(with = "serde_arrays")] [G; N],
 );
 
-impl<const N: usize> EncryptedGateBatch<N> {
+impl<const N: usize, G> EncryptedGateBatch<N, G> {
     /// Creates a new batch of encrypted gates.
-    pub fn new(batch: [EncryptedGate; N]) -> Self {
+    pub fn new(batch: [G; N]) -> Self {
         Self(batch)
     }
 
+    /// Returns a reference to the inner array.
+    pub fn as_array(&self) -> &[G; N] {
+        &self.0
+    }
+
     /// Returns the inner array.
-    pub fn into_array(self) -> [EncryptedGate; N] {
+    pub fn into_array(self) -> [G; N] {
         self.0
     }
 }
 
+/// A batch of three-halves encrypted gates.
+///
+/// The default size is chosen such that a batch takes up about as many bytes as a default
+/// [`EncryptedGateBatch`].
+pub type ThreeHalvesGateBatch<const N: usize = THREE_HALVES_BATCH_SIZE> =
+    EncryptedGateBatch<N, ThreeHalvesGate>;
+
 /// A garbled circuit
+///
+/// # Parameters
+///
+/// - `G`: The type of the encrypted gates, see [`GarblingScheme`](crate::GarblingScheme).
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct GarbledCircuit {
+pub struct GarbledCircuit<G = EncryptedGate> {
     /// Encrypted gates of the circuit
-    pub gates: Vec<EncryptedGate>,
+    pub gates: Vec<G>,
     /// Encoding commitments of the circuit outputs
     pub commitments: Option<Vec<EncodingCommitment>>,
 }
@@ -1,13 +1,18 @@
+use aes::Aes256;
+use cipher::{KeyIvInit, StreamCipher};
 use mpz_circuits::types::{BinaryLength, ValueType};
 use mpz_core::Block;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
 
 use super::{state, value::Encode, Delta, EncodedValue, Label};
 
 const DELTA_STREAM_ID: u64 = u64::MAX;
 
+type Aes256Ctr = ctr::Ctr64BE<Aes256>;
+
 /// This trait is used to encode values using a global offset (delta).
 ///
 /// Implementations of this trait should be _idempotent_, meaning that calling
@@ -105,6 +110,11 @@ impl Encoder for ChaChaEncoder {
             ValueType::U32 => self.encode::<u32>(id).into(),
             ValueType::U64 => self.encode::<u64>(id).into(),
             ValueType::U128 => self.encode::<u128>(id).into(),
+            ValueType::I8 => self.encode::<i8>(id).into(),
+            ValueType::I16 => self.encode::<i16>(id).into(),
+            ValueType::I32 => self.encode::<i32>(id).into(),
+            ValueType::I64 => self.encode::<i64>(id).into(),
+            ValueType::I128 => self.encode::<i128>(id).into(),
             ValueType::Array(_, _) => {
                 let mut rng = self.get_rng(id);
 
@@ -121,6 +131,156 @@ impl Encoder for ChaChaEncoder {
     }
 }
 
+/// Encodes values using AES-256 in counter mode.
+///
+/// The labels of a value are the keystream of AES-CTR keyed with the seed, using the id of the
+/// value as the nonce.
+#[derive(Debug)]
+pub struct AesCtrEncoder {
+    seed: [u8; 32],
+    delta: Delta,
+}
+
+impl Default for AesCtrEncoder {
+    fn default() -> Self {
+        Self::new(OsRng.gen())
+    }
+}
+
+impl AesCtrEncoder {
+    /// Creates a new encoder with the provided seed
+    ///
+    /// * `seed` - 32-byte AES-256 key
+    pub fn new(seed: [u8; 32]) -> Self {
+        // Nonce u64::MAX is reserved to generate delta.
+        // This way there is only ever 1 delta per seed
+        let [delta] = Self::keystream(&seed, DELTA_STREAM_ID, 1)
+            .try_into()
+            .expect("keystream should contain 1 block");
+
+        Self {
+            seed,
+            delta: Delta::from_block(delta),
+        }
+    }
+
+    /// Returns `len` blocks of the keystream for the provided nonce.
+    fn keystream(seed: &[u8; 32], nonce: u64, len: usize) -> Vec<Block> {
+        let mut iv = [0u8; 16];
+        iv[..8].copy_from_slice(&nonce.to_be_bytes());
+
+        let mut cipher = Aes256Ctr::new(seed.into(), &iv.into());
+        let mut blocks = vec![Block::ZERO; len];
+        blocks
+            .iter_mut()
+            .for_each(|block| cipher.apply_keystream(block.as_mut()));
+
+        blocks
+    }
+
+    /// Returns the labels for the provided id
+    ///
+    /// * `id` - Id of value
+    /// * `len` - Number of labels
+    fn labels(&self, id: u64, len: usize) -> Vec<Label> {
+        if id == DELTA_STREAM_ID {
+            panic!("stream id {} is reserved", DELTA_STREAM_ID);
+        }
+
+        Self::keystream(&self.seed, id, len)
+            .into_iter()
+            .map(Label::new)
+            .collect()
+    }
+}
+
+impl Encoder for AesCtrEncoder {
+    fn seed(&self) -> Vec<u8> {
+        self.seed.to_vec()
+    }
+
+    fn delta(&self) -> Delta {
+        self.delta
+    }
+
+    fn encode<T: Encode + BinaryLength>(&self, id: u64) -> T::Encoded {
+        T::encode(self.delta, &self.labels(id, T::LEN)).expect("encoding should not fail")
+    }
+
+    fn encode_by_type(&self, id: u64, ty: &ValueType) -> EncodedValue<state::Full> {
+        EncodedValue::<state::Full>::from_labels(ty.clone(), self.delta, &self.labels(id, ty.len()))
+            .expect("bit length should be correct")
+    }
+}
+
+/// Selects an [`Encoder`] at runtime, eg from a configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EncoderKind {
+    /// See [`ChaChaEncoder`].
+    #[default]
+    ChaCha,
+    /// See [`AesCtrEncoder`].
+    AesCtr,
+}
+
+/// An [`Encoder`] which was selected at runtime, see [`EncoderKind`].
+#[derive(Debug)]
+pub enum AnyEncoder {
+    /// ChaCha encoder.
+    ChaCha(ChaChaEncoder),
+    /// AES-CTR encoder.
+    AesCtr(AesCtrEncoder),
+}
+
+impl Default for AnyEncoder {
+    fn default() -> Self {
+        Self::ChaCha(ChaChaEncoder::default())
+    }
+}
+
+impl AnyEncoder {
+    /// Creates a new encoder of the provided kind.
+    ///
+    /// * `kind` - Kind of encoder
+    /// * `seed` - 32-byte seed of the encoder
+    pub fn new(kind: EncoderKind, seed: [u8; 32]) -> Self {
+        match kind {
+            EncoderKind::ChaCha => Self::ChaCha(ChaChaEncoder::new(seed)),
+            EncoderKind::AesCtr => Self::AesCtr(AesCtrEncoder::new(seed)),
+        }
+    }
+}
+
+impl Encoder for AnyEncoder {
+    fn seed(&self) -> Vec<u8> {
+        match self {
+            Self::ChaCha(encoder) => encoder.seed(),
+            Self::AesCtr(encoder) => encoder.seed(),
+        }
+    }
+
+    fn delta(&self) -> Delta {
+        match self {
+            Self::ChaCha(encoder) => encoder.delta(),
+            Self::AesCtr(encoder) => encoder.delta(),
+        }
+    }
+
+    fn encode<T: Encode + BinaryLength>(&self, id: u64) -> T::Encoded {
+        match self {
+            Self::ChaCha(encoder) => encoder.encode::<T>(id),
+            Self::AesCtr(encoder) => encoder.encode::<T>(id),
+        }
+    }
+
+    fn encode_by_type(&self, id: u64, ty: &ValueType) -> EncodedValue<state::Full> {
+        match self {
+            Self::ChaCha(encoder) => encoder.encode_by_type(id, ty),
+            Self::AesCtr(encoder) => encoder.encode_by_type(id, ty),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::marker::PhantomData;
@@ -140,6 +300,11 @@ mod test {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
     fn test_encoder_idempotent<T: Encode + BinaryLength + Default>(
         encoder: ChaChaEncoder,
         #[case] _pd: PhantomData<T>,
@@ -151,4 +316,19 @@ mod test {
 
         assert_eq!(encoded, encoded2);
     }
+
+    #[rstest]
+    #[case::chacha(EncoderKind::ChaCha)]
+    #[case::aes_ctr(EncoderKind::AesCtr)]
+    fn test_any_encoder(#[case] kind: EncoderKind) {
+        let encoder = AnyEncoder::new(kind, [0u8; 32]);
+
+        let encoded: EncodedValue<_> = encoder.encode::<u32>(1).into();
+
+        assert_eq!(encoder.encode_by_type(1, &ValueType::U32), encoded);
+        assert_ne!(encoder.encode_by_type(2, &ValueType::U32), encoded);
+        assert_eq!(AnyEncoder::new(kind, [0u8; 32]).delta(), encoder.delta());
+        assert_ne!(AnyEncoder::new(kind, [1u8; 32]).delta(), encoder.delta());
+        assert_eq!(encoder.delta().lsb(), 1);
+    }
 }
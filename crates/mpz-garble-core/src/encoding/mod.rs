@@ -26,7 +26,7 @@ use mpz_core::Block;
 use rand::{CryptoRng, Rng};
 use serde::{Deserialize, Deserializer, Serialize};
 
-pub use encoder::{ChaChaEncoder, Encoder};
+pub use encoder::{AesCtrEncoder, AnyEncoder, ChaChaEncoder, Encoder, EncoderKind};
 pub use equality::EqualityCheck;
 pub use value::{Decoding, Encode, EncodedValue, EncodingCommitment, ValueError};
 
@@ -46,6 +46,12 @@ impl Delta {
         Self(block)
     }
 
+    /// Creates a new Delta from a block, setting its LSB.
+    pub(crate) fn from_block(mut block: Block) -> Self {
+        block.set_lsb();
+        Self(block)
+    }
+
     /// Returns the inner block
     #[inline]
     pub(crate) fn into_inner(self) -> Block {
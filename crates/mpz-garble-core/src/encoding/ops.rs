@@ -20,6 +20,11 @@ macro_rules! impl_encoded_xor {
                     (EncodedValue::U32(a), EncodedValue::U32(b)) => Ok(EncodedValue::U32(a ^ b)),
                     (EncodedValue::U64(a), EncodedValue::U64(b)) => Ok(EncodedValue::U64(a ^ b)),
                     (EncodedValue::U128(a), EncodedValue::U128(b)) => Ok(EncodedValue::U128(a ^ b)),
+                    (EncodedValue::I8(a), EncodedValue::I8(b)) => Ok(EncodedValue::I8(a ^ b)),
+                    (EncodedValue::I16(a), EncodedValue::I16(b)) => Ok(EncodedValue::I16(a ^ b)),
+                    (EncodedValue::I32(a), EncodedValue::I32(b)) => Ok(EncodedValue::I32(a ^ b)),
+                    (EncodedValue::I64(a), EncodedValue::I64(b)) => Ok(EncodedValue::I64(a ^ b)),
+                    (EncodedValue::I128(a), EncodedValue::I128(b)) => Ok(EncodedValue::I128(a ^ b)),
                     (EncodedValue::Array(a), EncodedValue::Array(b))
                         if self.value_type() == rhs.value_type() =>
                     {
@@ -49,6 +54,11 @@ macro_rules! impl_encoded_xor {
                     (EncodedValue::U32(a), EncodedValue::U32(b)) => Ok(EncodedValue::U32(a ^ b)),
                     (EncodedValue::U64(a), EncodedValue::U64(b)) => Ok(EncodedValue::U64(a ^ b)),
                     (EncodedValue::U128(a), EncodedValue::U128(b)) => Ok(EncodedValue::U128(a ^ b)),
+                    (EncodedValue::I8(a), EncodedValue::I8(b)) => Ok(EncodedValue::I8(a ^ b)),
+                    (EncodedValue::I16(a), EncodedValue::I16(b)) => Ok(EncodedValue::I16(a ^ b)),
+                    (EncodedValue::I32(a), EncodedValue::I32(b)) => Ok(EncodedValue::I32(a ^ b)),
+                    (EncodedValue::I64(a), EncodedValue::I64(b)) => Ok(EncodedValue::I64(a ^ b)),
+                    (EncodedValue::I128(a), EncodedValue::I128(b)) => Ok(EncodedValue::I128(a ^ b)),
                     (EncodedValue::Array(a), EncodedValue::Array(b))
                         if self.value_type() == rhs.value_type() =>
                     {
@@ -78,6 +88,11 @@ macro_rules! impl_encoded_xor {
                     (EncodedValue::U32(a), EncodedValue::U32(b)) => Ok(EncodedValue::U32(a ^ b)),
                     (EncodedValue::U64(a), EncodedValue::U64(b)) => Ok(EncodedValue::U64(a ^ b)),
                     (EncodedValue::U128(a), EncodedValue::U128(b)) => Ok(EncodedValue::U128(a ^ b)),
+                    (EncodedValue::I8(a), EncodedValue::I8(b)) => Ok(EncodedValue::I8(a ^ b)),
+                    (EncodedValue::I16(a), EncodedValue::I16(b)) => Ok(EncodedValue::I16(a ^ b)),
+                    (EncodedValue::I32(a), EncodedValue::I32(b)) => Ok(EncodedValue::I32(a ^ b)),
+                    (EncodedValue::I64(a), EncodedValue::I64(b)) => Ok(EncodedValue::I64(a ^ b)),
+                    (EncodedValue::I128(a), EncodedValue::I128(b)) => Ok(EncodedValue::I128(a ^ b)),
                     (EncodedValue::Array(a), EncodedValue::Array(b))
                         if self.value_type() == rhs.value_type() =>
                     {
@@ -107,6 +122,11 @@ macro_rules! impl_encoded_xor {
                     (EncodedValue::U32(a), EncodedValue::U32(b)) => Ok(EncodedValue::U32(a ^ b)),
                     (EncodedValue::U64(a), EncodedValue::U64(b)) => Ok(EncodedValue::U64(a ^ b)),
                     (EncodedValue::U128(a), EncodedValue::U128(b)) => Ok(EncodedValue::U128(a ^ b)),
+                    (EncodedValue::I8(a), EncodedValue::I8(b)) => Ok(EncodedValue::I8(a ^ b)),
+                    (EncodedValue::I16(a), EncodedValue::I16(b)) => Ok(EncodedValue::I16(a ^ b)),
+                    (EncodedValue::I32(a), EncodedValue::I32(b)) => Ok(EncodedValue::I32(a ^ b)),
+                    (EncodedValue::I64(a), EncodedValue::I64(b)) => Ok(EncodedValue::I64(a ^ b)),
+                    (EncodedValue::I128(a), EncodedValue::I128(b)) => Ok(EncodedValue::I128(a ^ b)),
                     (EncodedValue::Array(a), EncodedValue::Array(b))
                         if self.value_type() == rhs.value_type() =>
                     {
@@ -161,6 +181,11 @@ mod tests {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
     fn test_encoded_xor<T>(encoder: ChaChaEncoder, #[case] _pd: PhantomData<T>)
     where
         Standard: Distribution<T>,
@@ -192,6 +217,11 @@ mod tests {
     #[case::u32_array(PhantomData::<[u32; 16]>)]
     #[case::u64_array(PhantomData::<[u64; 16]>)]
     #[case::u128_array(PhantomData::<[u128; 16]>)]
+    #[case::i8_array(PhantomData::<[i8; 16]>)]
+    #[case::i16_array(PhantomData::<[i16; 16]>)]
+    #[case::i32_array(PhantomData::<[i32; 16]>)]
+    #[case::i64_array(PhantomData::<[i64; 16]>)]
+    #[case::i128_array(PhantomData::<[i128; 16]>)]
     fn test_encoded_xor_array<T>(encoder: ChaChaEncoder, #[case] _pd: PhantomData<[T; 16]>)
     where
         Standard: Distribution<[T; 16]>,
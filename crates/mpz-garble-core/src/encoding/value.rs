@@ -1,4 +1,4 @@
-use itybity::{FromBitIterator, ToBits};
+use itybity::{FromBitIterator, IntoBits};
 use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
 use std::ops::BitXor;
@@ -306,10 +306,10 @@ macro_rules! define_encoded_value {
     };
 }
 
-define_encoded_value!(Bit, U8, U16, U32, U64, U128);
+define_encoded_value!(Bit, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128);
 
 macro_rules! define_encoded_variant {
-    ($EncodedTy:ident, $PlaintextTy:ty, $len:expr) => {
+    ($EncodedTy:ident, $PlaintextTy:ty, $BitsTy:ty, $len:expr) => {
         #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
         pub struct $EncodedTy<S: LabelState>(Labels<$len, S>);
 
@@ -320,7 +320,7 @@ macro_rules! define_encoded_variant {
 
             /// Returns the active encoding of the plaintext value
             pub(crate) fn select(&self, value: $PlaintextTy) -> $EncodedTy<state::Active> {
-                let mut bits = value.iter_lsb0();
+                let mut bits = (value as $BitsTy).into_iter_lsb0();
                 let delta = self.0.delta();
                 $EncodedTy::<state::Active>::new(self.0.labels.map(|label| {
                     if bits.next().expect("bit length should match") {
@@ -453,12 +453,17 @@ macro_rules! define_encoded_variant {
     };
 }
 
-define_encoded_variant!(Bit, bool, 1);
-define_encoded_variant!(U8, u8, 8);
-define_encoded_variant!(U16, u16, 16);
-define_encoded_variant!(U32, u32, 32);
-define_encoded_variant!(U64, u64, 64);
-define_encoded_variant!(U128, u128, 128);
+define_encoded_variant!(Bit, bool, bool, 1);
+define_encoded_variant!(U8, u8, u8, 8);
+define_encoded_variant!(U16, u16, u16, 16);
+define_encoded_variant!(U32, u32, u32, 32);
+define_encoded_variant!(U64, u64, u64, 64);
+define_encoded_variant!(U128, u128, u128, 128);
+define_encoded_variant!(I8, i8, u8, 8);
+define_encoded_variant!(I16, i16, u16, 16);
+define_encoded_variant!(I32, i32, u32, 32);
+define_encoded_variant!(I64, i64, u64, 64);
+define_encoded_variant!(I128, i128, u128, 128);
 
 macro_rules! define_decoding {
     ($( ($EncodedTy:ident, $DecodingTy:ident) ),*) => {
@@ -503,7 +508,12 @@ define_decoding!(
     (U16, U16Decoding),
     (U32, U32Decoding),
     (U64, U64Decoding),
-    (U128, U128Decoding)
+    (U128, U128Decoding),
+    (I8, I8Decoding),
+    (I16, I16Decoding),
+    (I32, I32Decoding),
+    (I64, I64Decoding),
+    (I128, I128Decoding)
 );
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -544,22 +554,22 @@ impl Bit<state::Active> {
 }
 
 macro_rules! define_decoding_info_variant {
-    ($name:ident, $value:ident, $ty:ty) => {
+    ($name:ident, $value:ident, $ty:ty, $bits:ty) => {
         #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
         pub struct $name($ty);
 
         impl $value<state::Full> {
             pub(crate) fn decoding(&self) -> $name {
-                $name(<$ty>::from_lsb0_iter(
-                    self.0.iter().map(|label| label.pointer_bit()),
-                ))
+                $name(
+                    <$bits>::from_lsb0_iter(self.0.iter().map(|label| label.pointer_bit())) as $ty,
+                )
             }
         }
 
         impl $value<state::Active> {
             /// Recovers the full encoding of this value using the decoding information and delta.
             pub(crate) fn recover(&self, decoding: &$name, delta: Delta) -> $value<state::Full> {
-                let mut decoding = decoding.0.iter_lsb0();
+                let mut decoding = (decoding.0 as $bits).into_iter_lsb0();
                 $value::<state::Full>::new(
                     delta,
                     self.0.labels.map(|label| {
@@ -578,7 +588,7 @@ macro_rules! define_decoding_info_variant {
                 value: $ty,
                 delta: Delta,
             ) -> $value<state::Full> {
-                let mut value = value.iter_lsb0();
+                let mut value = (value as $bits).into_iter_lsb0();
                 $value::<state::Full>::new(
                     delta,
                     self.0.labels.map(|label| {
@@ -593,23 +603,27 @@ macro_rules! define_decoding_info_variant {
 
             /// Decodes this value using the decoding information.
             pub(crate) fn decode(&self, decoding: &$name) -> $ty {
-                <$ty>::from_lsb0_iter(
+                <$bits>::from_lsb0_iter(
                     self.0
                         .iter()
-                        .zip(decoding.0.iter_lsb0())
+                        .zip((decoding.0 as $bits).into_iter_lsb0())
                         .map(|(label, dec)| label.pointer_bit() ^ dec),
-                )
-                .into()
+                ) as $ty
             }
         }
     };
 }
 
-define_decoding_info_variant!(U8Decoding, U8, u8);
-define_decoding_info_variant!(U16Decoding, U16, u16);
-define_decoding_info_variant!(U32Decoding, U32, u32);
-define_decoding_info_variant!(U64Decoding, U64, u64);
-define_decoding_info_variant!(U128Decoding, U128, u128);
+define_decoding_info_variant!(U8Decoding, U8, u8, u8);
+define_decoding_info_variant!(U16Decoding, U16, u16, u16);
+define_decoding_info_variant!(U32Decoding, U32, u32, u32);
+define_decoding_info_variant!(U64Decoding, U64, u64, u64);
+define_decoding_info_variant!(U128Decoding, U128, u128, u128);
+define_decoding_info_variant!(I8Decoding, I8, i8, u8);
+define_decoding_info_variant!(I16Decoding, I16, i16, u16);
+define_decoding_info_variant!(I32Decoding, I32, i32, u32);
+define_decoding_info_variant!(I64Decoding, I64, i64, u64);
+define_decoding_info_variant!(I128Decoding, I128, i128, u128);
 
 #[derive(Serialize)]
 struct LabelCommit(Label);
@@ -701,7 +715,12 @@ define_encoding_commitment!(
     (U16, U16Commitment),
     (U32, U32Commitment),
     (U64, U64Commitment),
-    (U128, U128Commitment)
+    (U128, U128Commitment),
+    (I8, I8Commitment),
+    (I16, I16Commitment),
+    (I32, I32Commitment),
+    (I64, I64Commitment),
+    (I128, I128Commitment)
 );
 
 macro_rules! define_encoding_commitment_variant {
@@ -776,6 +795,11 @@ define_encoding_commitment_variant!(U16Commitment, U16, 16);
 define_encoding_commitment_variant!(U32Commitment, U32, 32);
 define_encoding_commitment_variant!(U64Commitment, U64, 64);
 define_encoding_commitment_variant!(U128Commitment, U128, 128);
+define_encoding_commitment_variant!(I8Commitment, I8, 8);
+define_encoding_commitment_variant!(I16Commitment, I16, 16);
+define_encoding_commitment_variant!(I32Commitment, I32, 32);
+define_encoding_commitment_variant!(I64Commitment, I64, 64);
+define_encoding_commitment_variant!(I128Commitment, I128, 128);
 
 #[cfg(test)]
 mod tests {
@@ -804,12 +828,22 @@ mod tests {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
     #[case::bit_array(PhantomData::<[bool; 16]>)]
     #[case::u8_array(PhantomData::<[u8; 16]>)]
     #[case::u16_array(PhantomData::<[u16; 16]>)]
     #[case::u32_array(PhantomData::<[u32; 16]>)]
     #[case::u64_array(PhantomData::<[u64; 16]>)]
     #[case::u128_array(PhantomData::<[u128; 16]>)]
+    #[case::i8_array(PhantomData::<[i8; 16]>)]
+    #[case::i16_array(PhantomData::<[i16; 16]>)]
+    #[case::i32_array(PhantomData::<[i32; 16]>)]
+    #[case::i64_array(PhantomData::<[i64; 16]>)]
+    #[case::i128_array(PhantomData::<[i128; 16]>)]
     fn test_encoding<T>(encoder: ChaChaEncoder, #[case] _pd: PhantomData<T>)
     where
         Standard: Distribution<T>,
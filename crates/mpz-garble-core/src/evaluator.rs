@@ -1,18 +1,21 @@
-use core::fmt;
+use core::{fmt, marker::PhantomData};
 
 use blake3::Hasher;
 
 use crate::{
     circuit::EncryptedGate,
     encoding::{state, EncodedValue, Label},
-    EncryptedGateBatch, DEFAULT_BATCH_SIZE,
+    hash::TccrHash,
+    lut::{self, encrypted_gate_count},
+    EncryptedGateBatch, GarblingScheme, HalfGates, ThreeHalves, DEFAULT_BATCH_SIZE,
+    THREE_HALVES_BATCH_SIZE,
 };
 use mpz_circuits::{
     types::{BinaryRepr, TypeError},
-    Circuit, CircuitError, Gate,
+    Circuit, CircuitError, Gate, LookupTable,
 };
 use mpz_core::{
-    aes::{FixedKeyAes, FIXED_KEY_AES},
+    aes::{FixedKeyAes, FIXED_KEY},
     hash::Hash,
     Block,
 };
@@ -31,8 +34,8 @@ pub enum EvaluatorError {
 
 /// Evaluates half-gate garbled AND gate
 #[inline]
-pub(crate) fn and_gate(
-    cipher: &FixedKeyAes,
+pub(crate) fn and_gate<H: TccrHash>(
+    cipher: &H,
     x: &Label,
     y: &Label,
     encrypted_gate: &EncryptedGate,
@@ -68,13 +71,49 @@ pub struct EvaluatorOutput {
 }
 
 /// Garbled circuit evaluator.
-#[derive(Debug, Default)]
-pub struct Evaluator {
+///
+/// # Parameters
+///
+/// - `S`: The garbling scheme, defaults to [`HalfGates`].
+/// - `H`: The hash function used to encrypt the gates, defaults to fixed-key AES.
+#[derive(Debug)]
+pub struct Evaluator<S = HalfGates, H = FixedKeyAes> {
+    /// Hash function used to encrypt the gates.
+    hash: H,
     /// Buffer for the active labels.
     buffer: Vec<Label>,
+    _scheme: PhantomData<S>,
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: GarblingScheme> Evaluator<S> {
+    /// Creates a new evaluator.
+    pub fn new() -> Self {
+        Self {
+            hash: FixedKeyAes::new(FIXED_KEY),
+            buffer: Vec::new(),
+            _scheme: PhantomData,
+        }
+    }
 }
 
-impl Evaluator {
+impl<S: GarblingScheme, H: TccrHash> Evaluator<S, H> {
+    /// Sets the hash function used to encrypt the gates.
+    ///
+    /// The generator must use the same hash function.
+    pub fn with_hash<T: TccrHash>(self, hash: T) -> Evaluator<S, T> {
+        Evaluator {
+            hash,
+            buffer: self.buffer,
+            _scheme: PhantomData,
+        }
+    }
+
     /// Returns a consumer over the encrypted gates of a circuit.
     ///
     /// # Arguments
@@ -85,7 +124,7 @@ impl Evaluator {
         &'a mut self,
         circ: &'a Circuit,
         inputs: Vec<EncodedValue<state::Active>>,
-    ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+    ) -> Result<EncryptedGateConsumer<'_, std::slice::Iter<'_, Gate>, S, H>, EvaluatorError> {
         if inputs.len() != circ.inputs().len() {
             return Err(CircuitError::InvalidInputCount(
                 circ.inputs().len(),
@@ -112,13 +151,16 @@ impl Evaluator {
         }
 
         Ok(EncryptedGateConsumer::new(
+            &self.hash,
             circ.gates().iter(),
             circ.outputs(),
             &mut self.buffer,
-            circ.and_count(),
+            encrypted_gate_count::<S>(circ),
         ))
     }
+}
 
+impl<H: TccrHash> Evaluator<HalfGates, H> {
     /// Returns a consumer over batched encrypted gates of a circuit.
     ///
     /// # Arguments
@@ -129,15 +171,49 @@ impl Evaluator {
         &'a mut self,
         circ: &'a Circuit,
         inputs: Vec<EncodedValue<state::Active>>,
-    ) -> Result<EncryptedGateBatchConsumer<'_, std::slice::Iter<'_, Gate>>, EvaluatorError> {
+    ) -> Result<
+        EncryptedGateBatchConsumer<
+            '_,
+            std::slice::Iter<'_, Gate>,
+            DEFAULT_BATCH_SIZE,
+            HalfGates,
+            H,
+        >,
+        EvaluatorError,
+    > {
+        self.evaluate(circ, inputs).map(EncryptedGateBatchConsumer)
+    }
+}
+
+impl<H: TccrHash> Evaluator<ThreeHalves, H> {
+    /// Returns a consumer over batched encrypted gates of a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to evaluate.
+    /// * `inputs` - The input values to the circuit.
+    pub fn evaluate_batched<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        inputs: Vec<EncodedValue<state::Active>>,
+    ) -> Result<
+        EncryptedGateBatchConsumer<
+            '_,
+            std::slice::Iter<'_, Gate>,
+            THREE_HALVES_BATCH_SIZE,
+            ThreeHalves,
+            H,
+        >,
+        EvaluatorError,
+    > {
         self.evaluate(circ, inputs).map(EncryptedGateBatchConsumer)
     }
 }
 
 /// Consumer over the encrypted gates of a circuit.
-pub struct EncryptedGateConsumer<'a, I: Iterator> {
+pub struct EncryptedGateConsumer<'a, I: Iterator, S: GarblingScheme = HalfGates, H = FixedKeyAes> {
     /// Cipher to use to encrypt the gates.
-    cipher: &'static FixedKeyAes,
+    cipher: &'a H,
     /// Buffer for the active labels.
     labels: &'a mut [Label],
     /// Iterator over the gates.
@@ -148,34 +224,48 @@ pub struct EncryptedGateConsumer<'a, I: Iterator> {
     gid: usize,
     /// Hasher to use to hash the encrypted gates.
     hasher: Option<Hasher>,
-    /// Number of AND gates evaluated.
+    /// Lookup gate which is waiting for encrypted gates.
+    lut: Option<&'a LookupTable>,
+    /// Encrypted gates received for the current lookup gate.
+    lut_gates: Vec<S::EncryptedGate>,
+    /// Number of encrypted gates evaluated.
     counter: usize,
-    /// Total number of AND gates in the circuit.
-    and_count: usize,
+    /// Total number of encrypted gates of the circuit.
+    gate_count: usize,
     /// Whether the entire circuit has been garbled.
     complete: bool,
 }
 
-impl<'a, I: Iterator> fmt::Debug for EncryptedGateConsumer<'a, I> {
+impl<'a, I: Iterator, S: GarblingScheme, H> fmt::Debug for EncryptedGateConsumer<'a, I, S, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "EncryptedGateConsumer {{ .. }}")
     }
 }
 
-impl<'a, I> EncryptedGateConsumer<'a, I>
+impl<'a, I, S, H> EncryptedGateConsumer<'a, I, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
-    fn new(gates: I, outputs: &'a [BinaryRepr], labels: &'a mut [Label], and_count: usize) -> Self {
+    fn new(
+        cipher: &'a H,
+        gates: I,
+        outputs: &'a [BinaryRepr],
+        labels: &'a mut [Label],
+        gate_count: usize,
+    ) -> Self {
         Self {
-            cipher: &(*FIXED_KEY_AES),
+            cipher,
             gates,
             outputs,
             labels,
             gid: 1,
             hasher: None,
+            lut: None,
+            lut_gates: Vec::new(),
             counter: 0,
-            and_count,
+            gate_count,
             complete: false,
         }
     }
@@ -188,12 +278,17 @@ where
     /// Returns `true` if the evaluator wants more encrypted gates.
     #[inline]
     pub fn wants_gates(&self) -> bool {
-        self.counter != self.and_count
+        self.counter != self.gate_count
     }
 
     /// Evaluates the next encrypted gate in the circuit.
     #[inline]
-    pub fn next(&mut self, encrypted_gate: EncryptedGate) {
+    pub fn next(&mut self, encrypted_gate: S::EncryptedGate) {
+        // Continue with the current lookup gate, if any.
+        if self.lut.is_some() && (!self.push_lut_gate(encrypted_gate) || self.wants_gates()) {
+            return;
+        }
+
         while let Some(gate) = self.gates.next() {
             match gate {
                 Gate::Xor {
@@ -212,14 +307,14 @@ where
                 } => {
                     let x = self.labels[node_x.id()];
                     let y = self.labels[node_y.id()];
-                    let z = and_gate(self.cipher, &x, &y, &encrypted_gate, self.gid);
+                    let z = S::evaluate_and(self.cipher, &x, &y, &encrypted_gate, self.gid);
                     self.labels[node_z.id()] = z;
 
-                    self.gid += 2;
+                    self.gid += S::TWEAKS_PER_GATE;
                     self.counter += 1;
 
                     if let Some(hasher) = &mut self.hasher {
-                        hasher.update(&encrypted_gate.to_bytes());
+                        S::hash_gate(hasher, &encrypted_gate);
                     }
 
                     // If we have more AND gates to evaluate, return.
@@ -234,19 +329,56 @@ where
                     let x = self.labels[node_x.id()];
                     self.labels[node_z.id()] = x;
                 }
+                Gate::Lut(table) => {
+                    self.lut = Some(table.as_ref());
+
+                    // If we need more gates for this lookup gate, or have more
+                    // encrypted gates to evaluate, return.
+                    if !self.push_lut_gate(encrypted_gate) || self.wants_gates() {
+                        return;
+                    }
+                }
             }
         }
 
         self.complete = true;
     }
 
+    /// Pushes an encrypted gate of the current lookup gate, evaluating it once all of its
+    /// encrypted gates have been received.
+    ///
+    /// Returns `true` if the lookup gate was evaluated.
+    #[inline]
+    fn push_lut_gate(&mut self, encrypted_gate: S::EncryptedGate) -> bool {
+        let table = self.lut.expect("lookup gate should be set");
+
+        self.counter += 1;
+
+        if let Some(hasher) = &mut self.hasher {
+            S::hash_gate(hasher, &encrypted_gate);
+        }
+
+        self.lut_gates.push(encrypted_gate);
+        if self.lut_gates.len() < lut::gate_count::<S>(table) {
+            return false;
+        }
+
+        lut::evaluate::<S, H>(self.cipher, table, self.labels, &self.lut_gates, self.gid);
+
+        self.gid += table.outputs().len();
+        self.lut_gates.clear();
+        self.lut = None;
+
+        true
+    }
+
     /// Returns the encoded outputs of the circuit.
     pub fn finish(mut self) -> Result<EvaluatorOutput, EvaluatorError> {
         if self.wants_gates() {
             return Err(EvaluatorError::NotFinished);
         }
 
-        // If there were 0 AND gates in the circuit, we need to evaluate the "free" gates now.
+        // If there were 0 encrypted gates in the circuit, we need to evaluate the "free" gates now.
         if !self.complete {
             self.next(Default::default());
         }
@@ -274,13 +406,19 @@ where
 
 /// Consumer returned by [`Evaluator::evaluate_batched`].
 #[derive(Debug)]
-pub struct EncryptedGateBatchConsumer<'a, I: Iterator, const N: usize = DEFAULT_BATCH_SIZE>(
-    EncryptedGateConsumer<'a, I>,
-);
-
-impl<'a, I, const N: usize> EncryptedGateBatchConsumer<'a, I, N>
+pub struct EncryptedGateBatchConsumer<
+    'a,
+    I: Iterator,
+    const N: usize = DEFAULT_BATCH_SIZE,
+    S: GarblingScheme = HalfGates,
+    H = FixedKeyAes,
+>(EncryptedGateConsumer<'a, I, S, H>);
+
+impl<'a, I, const N: usize, S, H> EncryptedGateBatchConsumer<'a, I, N, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
     /// Enables hashing of the encrypted gates.
     pub fn enable_hasher(&mut self) {
@@ -294,7 +432,7 @@ where
 
     /// Evaluates the next batch of gates in the circuit.
     #[inline]
-    pub fn next(&mut self, batch: EncryptedGateBatch<N>) {
+    pub fn next(&mut self, batch: EncryptedGateBatch<N, S::EncryptedGate>) {
         for encrypted_gate in batch.into_array() {
             self.0.next(encrypted_gate);
             if !self.0.wants_gates() {
@@ -1,22 +1,29 @@
-use core::fmt;
+use core::{fmt, marker::PhantomData};
+use std::collections::VecDeque;
 
 use blake3::Hasher;
 
 use crate::{
     circuit::EncryptedGate,
     encoding::{state, Delta, EncodedValue, Label},
-    EncryptedGateBatch, DEFAULT_BATCH_SIZE,
+    hash::TccrHash,
+    lut::{self, encrypted_gate_count},
+    EncryptedGateBatch, GarblingScheme, HalfGates, ThreeHalves, DEFAULT_BATCH_SIZE,
+    THREE_HALVES_BATCH_SIZE,
 };
 use mpz_circuits::{
     types::{BinaryRepr, TypeError},
     Circuit, CircuitError, Gate,
 };
 use mpz_core::{
-    aes::{FixedKeyAes, FIXED_KEY_AES},
+    aes::{FixedKeyAes, FIXED_KEY},
     hash::Hash,
     Block,
 };
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
 /// Errors that can occur during garbled circuit generation.
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -31,8 +38,8 @@ pub enum GeneratorError {
 
 /// Computes half-gate garbled AND gate
 #[inline]
-pub(crate) fn and_gate(
-    cipher: &FixedKeyAes,
+pub(crate) fn and_gate<H: TccrHash>(
+    cipher: &H,
     x_0: &Label,
     y_0: &Label,
     delta: &Delta,
@@ -77,13 +84,49 @@ pub struct GeneratorOutput {
 }
 
 /// Garbled circuit generator.
-#[derive(Debug, Default)]
-pub struct Generator {
+///
+/// # Parameters
+///
+/// - `S`: The garbling scheme, defaults to [`HalfGates`].
+/// - `H`: The hash function used to encrypt the gates, defaults to fixed-key AES.
+#[derive(Debug)]
+pub struct Generator<S = HalfGates, H = FixedKeyAes> {
+    /// Hash function used to encrypt the gates.
+    hash: H,
     /// Buffer for the 0-bit labels.
     buffer: Vec<Label>,
+    _scheme: PhantomData<S>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: GarblingScheme> Generator<S> {
+    /// Creates a new generator.
+    pub fn new() -> Self {
+        Self {
+            hash: FixedKeyAes::new(FIXED_KEY),
+            buffer: Vec::new(),
+            _scheme: PhantomData,
+        }
+    }
 }
 
-impl Generator {
+impl<S: GarblingScheme, H: TccrHash> Generator<S, H> {
+    /// Sets the hash function used to encrypt the gates.
+    ///
+    /// The evaluator must use the same hash function.
+    pub fn with_hash<T: TccrHash>(self, hash: T) -> Generator<S, T> {
+        Generator {
+            hash,
+            buffer: self.buffer,
+            _scheme: PhantomData,
+        }
+    }
+
     /// Returns an iterator over the encrypted gates of a circuit.
     ///
     /// # Arguments
@@ -96,7 +139,59 @@ impl Generator {
         circ: &'a Circuit,
         delta: Delta,
         inputs: Vec<EncodedValue<state::Full>>,
-    ) -> Result<EncryptedGateIter<'_, std::slice::Iter<'_, Gate>>, GeneratorError> {
+    ) -> Result<EncryptedGateIter<'_, std::slice::Iter<'_, Gate>, S, H>, GeneratorError> {
+        self.load_inputs(circ, inputs)?;
+
+        Ok(EncryptedGateIter::new(
+            &self.hash,
+            delta,
+            circ.gates().iter(),
+            circ.outputs(),
+            &mut self.buffer,
+            encrypted_gate_count::<S>(circ),
+        ))
+    }
+
+    /// Returns an iterator over the encrypted gates of a circuit, which garbles independent AND
+    /// gates on multiple threads.
+    ///
+    /// The gates are garbled in windows of consecutive gates, each of which is split into layers
+    /// of AND gates which only depend on previous layers, see [`Circuit::and_layers`]. A window
+    /// holds about [`PARALLEL_WINDOW_SIZE`] encrypted gates, which bounds the memory used
+    /// regardless of the size of the circuit. The encrypted gates are identical to those of
+    /// [`Generator::generate`] and are returned in the same order. Without the `rayon` feature
+    /// the layers are garbled on the current thread.
+    ///
+    /// This pays off for large circuits with wide layers, for narrow circuits prefer
+    /// [`Generator::generate`].
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    pub fn generate_parallel<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<ParallelEncryptedGateIter<'a, S, H>, GeneratorError> {
+        self.load_inputs(circ, inputs)?;
+
+        Ok(ParallelEncryptedGateIter::new(
+            &self.hash,
+            delta,
+            circ,
+            &mut self.buffer,
+        ))
+    }
+
+    /// Writes the input labels to the buffer.
+    fn load_inputs(
+        &mut self,
+        circ: &Circuit,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<(), GeneratorError> {
         if inputs.len() != circ.inputs().len() {
             return Err(CircuitError::InvalidInputCount(
                 circ.inputs().len(),
@@ -122,15 +217,33 @@ impl Generator {
             }
         }
 
-        Ok(EncryptedGateIter::new(
-            delta,
-            circ.gates().iter(),
-            circ.outputs(),
-            &mut self.buffer,
-            circ.and_count(),
-        ))
+        Ok(())
+    }
+}
+
+impl<H: TccrHash> Generator<HalfGates, H> {
+    /// Returns an iterator over batched encrypted gates of a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to garble.
+    /// * `delta` - The delta value to use for garbling.
+    /// * `inputs` - The input values to the circuit.
+    pub fn generate_batched<'a>(
+        &'a mut self,
+        circ: &'a Circuit,
+        delta: Delta,
+        inputs: Vec<EncodedValue<state::Full>>,
+    ) -> Result<
+        EncryptedGateBatchIter<'_, std::slice::Iter<'_, Gate>, DEFAULT_BATCH_SIZE, HalfGates, H>,
+        GeneratorError,
+    > {
+        self.generate(circ, delta, inputs)
+            .map(EncryptedGateBatchIter)
     }
+}
 
+impl<H: TccrHash> Generator<ThreeHalves, H> {
     /// Returns an iterator over batched encrypted gates of a circuit.
     ///
     /// # Arguments
@@ -143,16 +256,25 @@ impl Generator {
         circ: &'a Circuit,
         delta: Delta,
         inputs: Vec<EncodedValue<state::Full>>,
-    ) -> Result<EncryptedGateBatchIter<'_, std::slice::Iter<'_, Gate>>, GeneratorError> {
+    ) -> Result<
+        EncryptedGateBatchIter<
+            '_,
+            std::slice::Iter<'_, Gate>,
+            THREE_HALVES_BATCH_SIZE,
+            ThreeHalves,
+            H,
+        >,
+        GeneratorError,
+    > {
         self.generate(circ, delta, inputs)
             .map(EncryptedGateBatchIter)
     }
 }
 
 /// Iterator over encrypted gates of a garbled circuit.
-pub struct EncryptedGateIter<'a, I> {
+pub struct EncryptedGateIter<'a, I, S: GarblingScheme = HalfGates, H = FixedKeyAes> {
     /// Cipher to use to encrypt the gates.
-    cipher: &'static FixedKeyAes,
+    cipher: &'a H,
     /// Global offset.
     delta: Delta,
     /// Buffer for the 0-bit labels.
@@ -165,41 +287,47 @@ pub struct EncryptedGateIter<'a, I> {
     gid: usize,
     /// Hasher to use to hash the encrypted gates.
     hasher: Option<Hasher>,
-    /// Number of AND gates generated.
+    /// Encrypted gates of a lookup gate which have not been returned yet.
+    pending: VecDeque<S::EncryptedGate>,
+    /// Number of encrypted gates generated.
     counter: usize,
-    /// Number of AND gates in the circuit.
-    and_count: usize,
+    /// Number of encrypted gates of the circuit.
+    gate_count: usize,
     /// Whether the entire circuit has been garbled.
     complete: bool,
 }
 
-impl<'a, I> fmt::Debug for EncryptedGateIter<'a, I> {
+impl<'a, I, S: GarblingScheme, H> fmt::Debug for EncryptedGateIter<'a, I, S, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "EncryptedGateIter {{ .. }}")
     }
 }
 
-impl<'a, I> EncryptedGateIter<'a, I>
+impl<'a, I, S, H> EncryptedGateIter<'a, I, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
     fn new(
+        cipher: &'a H,
         delta: Delta,
         gates: I,
         outputs: &'a [BinaryRepr],
         labels: &'a mut [Label],
-        and_count: usize,
+        gate_count: usize,
     ) -> Self {
         Self {
-            cipher: &(*FIXED_KEY_AES),
+            cipher,
             delta,
             gates,
             outputs,
             labels,
             gid: 1,
             hasher: None,
+            pending: VecDeque::new(),
             counter: 0,
-            and_count,
+            gate_count,
             complete: false,
         }
     }
@@ -212,7 +340,7 @@ where
     /// Returns `true` if the generator has more encrypted gates to generate.
     #[inline]
     pub fn has_gates(&self) -> bool {
-        self.counter != self.and_count
+        self.counter != self.gate_count
     }
 
     /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
@@ -247,15 +375,52 @@ where
     }
 }
 
-impl<'a, I> Iterator for EncryptedGateIter<'a, I>
+impl<'a, I, S, H> Iterator for EncryptedGateIter<'a, I, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
-    type Item = EncryptedGate;
+    type Item = S::EncryptedGate;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(gate) = self.gates.next() {
+        let encrypted_gate = match self.pending.pop_front() {
+            Some(encrypted_gate) => encrypted_gate,
+            None => self.garble_next()?,
+        };
+
+        self.counter += 1;
+
+        if let Some(hasher) = &mut self.hasher {
+            S::hash_gate(hasher, &encrypted_gate);
+        }
+
+        // If we have generated all encrypted gates, we can compute
+        // the rest of the "free" gates.
+        if !self.has_gates() {
+            assert!(self.garble_next().is_none());
+
+            self.complete = true;
+        }
+
+        Some(encrypted_gate)
+    }
+}
+
+impl<'a, I, S, H> EncryptedGateIter<'a, I, S, H>
+where
+    I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
+{
+    /// Garbles gates until the next encrypted gate, returning `None` if the circuit has been
+    /// garbled.
+    ///
+    /// The remaining encrypted gates of a lookup gate are pushed to `pending`.
+    #[inline]
+    fn garble_next(&mut self) -> Option<S::EncryptedGate> {
+        for gate in self.gates.by_ref() {
             match gate {
                 Gate::Xor {
                     x: node_x,
@@ -274,23 +439,10 @@ where
                     let x_0 = self.labels[node_x.id()];
                     let y_0 = self.labels[node_y.id()];
                     let (z_0, encrypted_gate) =
-                        and_gate(self.cipher, &x_0, &y_0, &self.delta, self.gid);
+                        S::garble_and(self.cipher, &x_0, &y_0, &self.delta, self.gid);
                     self.labels[node_z.id()] = z_0;
 
-                    self.gid += 2;
-                    self.counter += 1;
-
-                    if let Some(hasher) = &mut self.hasher {
-                        hasher.update(&encrypted_gate.to_bytes());
-                    }
-
-                    // If we have generated all AND gates, we can compute
-                    // the rest of the "free" gates.
-                    if !self.has_gates() {
-                        assert!(self.next().is_none());
-
-                        self.complete = true;
-                    }
+                    self.gid += S::TWEAKS_PER_GATE;
 
                     return Some(encrypted_gate);
                 }
@@ -301,6 +453,21 @@ where
                     let x_0 = self.labels[node_x.id()];
                     self.labels[node_z.id()] = x_0 ^ self.delta;
                 }
+                Gate::Lut(table) => {
+                    let (outputs, encrypted_gates) =
+                        lut::garble::<S, H>(self.cipher, table, self.labels, &self.delta, self.gid);
+                    for (node, z_0) in table.outputs().iter().zip(outputs) {
+                        self.labels[node.id()] = z_0;
+                    }
+
+                    self.gid += table.outputs().len();
+
+                    let mut encrypted_gates = encrypted_gates.into_iter();
+                    let encrypted_gate = encrypted_gates.next();
+                    self.pending.extend(encrypted_gates);
+
+                    return encrypted_gate;
+                }
             }
         }
 
@@ -310,13 +477,19 @@ where
 
 /// Iterator returned by [`Generator::generate_batched`].
 #[derive(Debug)]
-pub struct EncryptedGateBatchIter<'a, I: Iterator, const N: usize = DEFAULT_BATCH_SIZE>(
-    EncryptedGateIter<'a, I>,
-);
-
-impl<'a, I, const N: usize> EncryptedGateBatchIter<'a, I, N>
+pub struct EncryptedGateBatchIter<
+    'a,
+    I: Iterator,
+    const N: usize = DEFAULT_BATCH_SIZE,
+    S: GarblingScheme = HalfGates,
+    H = FixedKeyAes,
+>(EncryptedGateIter<'a, I, S, H>);
+
+impl<'a, I, const N: usize, S, H> EncryptedGateBatchIter<'a, I, N, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
     /// Enables hashing of the encrypted gates.
     pub fn enable_hasher(&mut self) {
@@ -334,11 +507,13 @@ where
     }
 }
 
-impl<'a, I, const N: usize> Iterator for EncryptedGateBatchIter<'a, I, N>
+impl<'a, I, const N: usize, S, H> Iterator for EncryptedGateBatchIter<'a, I, N, S, H>
 where
     I: Iterator<Item = &'a Gate>,
+    S: GarblingScheme,
+    H: TccrHash,
 {
-    type Item = EncryptedGateBatch<N>;
+    type Item = EncryptedGateBatch<N, S::EncryptedGate>;
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -346,7 +521,7 @@ where
             return None;
         }
 
-        let mut batch = [EncryptedGate::default(); N];
+        let mut batch = [<S::EncryptedGate>::default(); N];
         let mut i = 0;
         for gate in self.0.by_ref() {
             batch[i] = gate;
@@ -361,11 +536,303 @@ where
     }
 }
 
+/// Number of encrypted gates garbled at once by [`Generator::generate_parallel`].
+///
+/// The encrypted gates of a window are buffered until they are returned, and wider windows
+/// allow more gates to be garbled in parallel.
+pub const PARALLEL_WINDOW_SIZE: usize = 1 << 16;
+
+/// Iterator returned by [`Generator::generate_parallel`].
+pub struct ParallelEncryptedGateIter<'a, S: GarblingScheme = HalfGates, H = FixedKeyAes> {
+    /// Cipher to use to encrypt the gates.
+    cipher: &'a H,
+    /// Global offset.
+    delta: Delta,
+    /// Buffer for the 0-bit labels.
+    labels: &'a mut [Label],
+    /// Gates of the circuit.
+    gates: &'a [Gate],
+    /// Number of encrypted gates in a window.
+    window_size: usize,
+    /// Index of the first gate of the next window.
+    next_gate: usize,
+    /// AND depth of each feed, counted across all windows.
+    feed_depths: Vec<usize>,
+    /// AND depth of the first layer of the next window.
+    base_depth: usize,
+    /// Gate id and position of the first encrypted gate of each AND and lookup gate, indexed by
+    /// gate.
+    positions: Vec<(usize, usize)>,
+    /// Circuit outputs.
+    outputs: &'a [BinaryRepr],
+    /// Encrypted gates which have been garbled, starting at encrypted gate `counter`.
+    pending: VecDeque<Option<S::EncryptedGate>>,
+    /// Hasher to use to hash the encrypted gates.
+    hasher: Option<Hasher>,
+    /// Number of encrypted gates returned.
+    counter: usize,
+    /// Number of encrypted gates of the circuit.
+    gate_count: usize,
+}
+
+impl<'a, S: GarblingScheme, H> fmt::Debug for ParallelEncryptedGateIter<'a, S, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParallelEncryptedGateIter {{ .. }}")
+    }
+}
+
+impl<'a, S, H> ParallelEncryptedGateIter<'a, S, H>
+where
+    S: GarblingScheme,
+    H: TccrHash,
+{
+    fn new(cipher: &'a H, delta: Delta, circ: &'a Circuit, labels: &'a mut [Label]) -> Self {
+        let mut positions = vec![(0, 0); circ.gates().len()];
+        let (mut gid, mut pos) = (1, 0);
+        for (idx, gate) in circ.gates().iter().enumerate() {
+            match gate {
+                Gate::And { .. } => {
+                    positions[idx] = (gid, pos);
+                    gid += S::TWEAKS_PER_GATE;
+                    pos += 1;
+                }
+                Gate::Lut(table) => {
+                    positions[idx] = (gid, pos);
+                    gid += table.outputs().len();
+                    pos += lut::gate_count::<S>(table);
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            cipher,
+            delta,
+            labels,
+            gates: circ.gates(),
+            window_size: PARALLEL_WINDOW_SIZE,
+            next_gate: 0,
+            feed_depths: vec![0; circ.feed_count()],
+            base_depth: 0,
+            positions,
+            outputs: circ.outputs(),
+            pending: VecDeque::new(),
+            hasher: None,
+            counter: 0,
+            gate_count: pos,
+        }
+    }
+
+    /// Enables hashing of the encrypted gates.
+    pub fn enable_hasher(&mut self) {
+        self.hasher = Some(Hasher::new());
+    }
+
+    /// Returns `true` if the generator has more encrypted gates to generate.
+    #[inline]
+    pub fn has_gates(&self) -> bool {
+        self.counter != self.gate_count
+    }
+
+    /// Returns the encoded outputs of the circuit, and the hash of the encrypted gates if present.
+    pub fn finish(mut self) -> Result<GeneratorOutput, GeneratorError> {
+        if self.has_gates() {
+            return Err(GeneratorError::NotFinished);
+        }
+
+        // Finish computing any "free" gates.
+        while self.garble_window() {}
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|output| {
+                let labels: Vec<Label> = output.iter().map(|node| self.labels[node.id()]).collect();
+
+                EncodedValue::<state::Full>::from_labels(output.value_type(), self.delta, &labels)
+                    .expect("encoding should be correct")
+            })
+            .collect();
+
+        Ok(GeneratorOutput {
+            outputs,
+            hash: self.hasher.as_ref().map(|hasher| {
+                let hash: [u8; 32] = hasher.finalize().into();
+                Hash::from(hash)
+            }),
+        })
+    }
+
+    /// Garbles the next window of gates, returning `false` if all gates have been garbled.
+    fn garble_window(&mut self) -> bool {
+        if self.next_gate == self.gates.len() {
+            return false;
+        }
+
+        // Split the window into layers by AND depth, where the outputs of previous windows are
+        // treated as inputs. Layer `i` holds the AND and lookup gates at depth `i` followed by
+        // the free gates which depend on them.
+        let gates = self.gates;
+        let mut layers: Vec<(Vec<usize>, Vec<usize>)> = Vec::new();
+        let mut count = 0;
+        while self.next_gate < gates.len() && count < self.window_size {
+            let idx = self.next_gate;
+            let gate = &gates[idx];
+            self.next_gate += 1;
+
+            let depth = gate
+                .inputs()
+                .map(|x| self.feed_depths[x.id()])
+                .fold(self.base_depth, usize::max);
+            let depth = match gate {
+                Gate::And { .. } => {
+                    count += 1;
+                    depth + 1
+                }
+                Gate::Lut(table) => {
+                    count += lut::gate_count::<S>(table);
+                    depth + 1
+                }
+                Gate::Xor { .. } | Gate::Inv { .. } => depth,
+            };
+
+            for z in gate.outputs() {
+                self.feed_depths[z.id()] = depth;
+            }
+
+            let layer = depth - self.base_depth;
+            if layer >= layers.len() {
+                layers.resize_with(layer + 1, Default::default);
+            }
+            match gate {
+                Gate::And { .. } | Gate::Lut(_) => layers[layer].0.push(idx),
+                Gate::Xor { .. } | Gate::Inv { .. } => layers[layer].1.push(idx),
+            }
+        }
+        self.base_depth += layers.len();
+
+        for (garbled_gates, free_gates) in layers {
+            self.garble_layer(&garbled_gates, &free_gates);
+        }
+
+        true
+    }
+
+    /// Garbles a layer of AND and lookup gates, followed by the free gates which depend on them.
+    fn garble_layer(&mut self, garbled_gates: &[usize], free_gates: &[usize]) {
+        let (cipher, delta, gates, positions) =
+            (self.cipher, &self.delta, self.gates, &self.positions);
+        let labels = &*self.labels;
+        let garble = |&idx: &usize| {
+            let (gid, _) = positions[idx];
+            match &gates[idx] {
+                Gate::And { x, y, .. } => {
+                    let (z_0, encrypted_gate) =
+                        S::garble_and(cipher, &labels[x.id()], &labels[y.id()], delta, gid);
+                    Garbled::And(z_0, encrypted_gate)
+                }
+                Gate::Lut(table) => {
+                    let (outputs, encrypted_gates) =
+                        lut::garble::<S, H>(cipher, table, labels, delta, gid);
+                    Garbled::Lut(outputs, encrypted_gates)
+                }
+                _ => unreachable!("only AND and lookup gates should be garbled"),
+            }
+        };
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let garbled: Vec<_> = garbled_gates.par_iter().map(garble).collect();
+            } else {
+                let garbled: Vec<_> = garbled_gates.iter().map(garble).collect();
+            }
+        }
+
+        for (&idx, garbled) in garbled_gates.iter().zip(garbled) {
+            let pos = self.positions[idx].1 - self.counter;
+            let encrypted_gates = match garbled {
+                Garbled::And(z_0, encrypted_gate) => {
+                    self.labels[self.gates[idx].z().id()] = z_0;
+                    vec![encrypted_gate]
+                }
+                Garbled::Lut(outputs, encrypted_gates) => {
+                    for (node, z_0) in self.gates[idx].outputs().zip(outputs) {
+                        self.labels[node.id()] = z_0;
+                    }
+                    encrypted_gates
+                }
+            };
+
+            if pos + encrypted_gates.len() > self.pending.len() {
+                self.pending.resize(pos + encrypted_gates.len(), None);
+            }
+            for (slot, encrypted_gate) in self.pending.range_mut(pos..).zip(encrypted_gates) {
+                *slot = Some(encrypted_gate);
+            }
+        }
+
+        for &idx in free_gates {
+            match &self.gates[idx] {
+                Gate::Xor { x, y, z } => {
+                    self.labels[z.id()] = self.labels[x.id()] ^ self.labels[y.id()];
+                }
+                Gate::Inv { x, z } => {
+                    self.labels[z.id()] = self.labels[x.id()] ^ self.delta;
+                }
+                Gate::And { .. } | Gate::Lut(_) => {
+                    unreachable!("AND and lookup gates should be garbled first")
+                }
+            }
+        }
+    }
+}
+
+/// Result of garbling an AND or lookup gate.
+enum Garbled<G> {
+    And(Label, G),
+    Lut(Vec<Label>, Vec<G>),
+}
+
+impl<'a, S, H> Iterator for ParallelEncryptedGateIter<'a, S, H>
+where
+    S: GarblingScheme,
+    H: TccrHash,
+{
+    type Item = S::EncryptedGate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(Some(_)) = self.pending.front() {
+                let encrypted_gate = self
+                    .pending
+                    .pop_front()
+                    .flatten()
+                    .expect("front gate should be garbled");
+                self.counter += 1;
+
+                if let Some(hasher) = &mut self.hasher {
+                    S::hash_gate(hasher, &encrypted_gate);
+                }
+
+                return Some(encrypted_gate);
+            }
+
+            if !self.has_gates() || !self.garble_window() {
+                return None;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{ChaChaEncoder, Encoder};
-    use mpz_circuits::{circuits::AES128, CircuitBuilder};
+    use mpz_circuits::{
+        circuits::{AES128, AES128_LUT, SHA256_COMPRESS},
+        CircuitBuilder,
+    };
     use pretty_assertions::assert_eq;
+    use rstest::rstest;
 
     use super::*;
 
@@ -417,4 +884,66 @@ mod tests {
 
         assert!(enc_gates.is_empty());
     }
+
+    fn garble_parallel<S: GarblingScheme>(circ: &Circuit, window_size: usize) {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let inputs: Vec<_> = circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let mut gen = Generator::<S>::new();
+        let mut gate_iter = gen.generate(circ, encoder.delta(), inputs.clone()).unwrap();
+        gate_iter.enable_hasher();
+        let expected_gates: Vec<_> = gate_iter.by_ref().collect();
+        let expected = gate_iter.finish().unwrap();
+
+        let mut gen = Generator::<S>::new();
+        let mut gate_iter = gen
+            .generate_parallel(circ, encoder.delta(), inputs)
+            .unwrap();
+        gate_iter.window_size = window_size;
+        gate_iter.enable_hasher();
+        let gates: Vec<_> = gate_iter.by_ref().collect();
+
+        assert!(!gate_iter.has_gates());
+
+        let output = gate_iter.finish().unwrap();
+
+        assert_eq!(gates, expected_gates);
+        assert_eq!(output.outputs, expected.outputs);
+        assert_eq!(output.hash, expected.hash);
+    }
+
+    #[rstest]
+    #[case::aes128(&AES128)]
+    #[case::aes128_lut(&AES128_LUT)]
+    #[case::sha256(&SHA256_COMPRESS)]
+    fn test_generator_parallel(#[case] circ: &Circuit) {
+        garble_parallel::<HalfGates>(circ, PARALLEL_WINDOW_SIZE);
+        garble_parallel::<ThreeHalves>(circ, PARALLEL_WINDOW_SIZE);
+    }
+
+    #[rstest]
+    #[case::aes128(&AES128)]
+    #[case::aes128_lut(&AES128_LUT)]
+    fn test_generator_parallel_windows(#[case] circ: &Circuit) {
+        // The circuit spans many windows.
+        garble_parallel::<HalfGates>(circ, 1000);
+        garble_parallel::<ThreeHalves>(circ, 1000);
+    }
+
+    #[test]
+    fn test_generator_parallel_no_and() {
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = !(a ^ b);
+        builder.add_output(c);
+
+        garble_parallel::<HalfGates>(&builder.build().unwrap(), PARALLEL_WINDOW_SIZE);
+    }
 }
@@ -0,0 +1,135 @@
+//! Hash functions used to encrypt the gates of a garbled circuit.
+
+use mpz_core::{
+    aes::{FixedKeyAes, FIXED_KEY_AES},
+    Block,
+};
+use serde::{Deserialize, Serialize};
+
+/// A tweakable circular correlation-robust (TCCR) hash function.
+///
+/// The generator and evaluator must use the same hash function, otherwise the evaluator will
+/// compute invalid labels.
+pub trait TccrHash: Send + Sync {
+    /// Hashes the blocks in-place.
+    ///
+    /// # Arguments
+    ///
+    /// * `tweaks` - The tweaks to use for each block in `blocks`.
+    /// * `blocks` - The blocks to hash in-place.
+    fn tccr_many<const N: usize>(&self, tweaks: &[Block; N], blocks: &mut [Block; N]);
+}
+
+impl TccrHash for FixedKeyAes {
+    #[inline]
+    fn tccr_many<const N: usize>(&self, tweaks: &[Block; N], blocks: &mut [Block; N]) {
+        FixedKeyAes::tccr_many(self, tweaks, blocks)
+    }
+}
+
+/// TCCR hash function instantiated using BLAKE3.
+///
+/// `H(i, x) = BLAKE3(i || x)`, truncated to 128 bits.
+///
+/// It is considerably slower than fixed-key AES, but its security does not degrade with the
+/// number of hashed blocks in the same way.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Blake3Tccr;
+
+impl TccrHash for Blake3Tccr {
+    #[inline]
+    fn tccr_many<const N: usize>(&self, tweaks: &[Block; N], blocks: &mut [Block; N]) {
+        for (tweak, block) in tweaks.iter().zip(blocks.iter_mut()) {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&tweak.to_bytes());
+            hasher.update(&block.to_bytes());
+
+            let hash = hasher.finalize();
+            *block = Block::new(
+                hash.as_bytes()[..16]
+                    .try_into()
+                    .expect("hash is at least 16 bytes"),
+            );
+        }
+    }
+}
+
+/// Selects a [`TccrHash`] at runtime, eg from a configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GarbleHash {
+    /// Fixed-key AES using the public [`FIXED_KEY_AES`].
+    #[default]
+    FixedKeyAes,
+    /// Fixed-key AES using the provided key, eg a fresh key for each session.
+    ReKeyedAes([u8; 16]),
+    /// See [`Blake3Tccr`].
+    Blake3,
+}
+
+/// A [`TccrHash`] which was selected at runtime, see [`GarbleHash`].
+#[derive(Debug)]
+pub enum AnyTccr {
+    /// Fixed-key AES using the public key.
+    FixedKeyAes(&'static FixedKeyAes),
+    /// Fixed-key AES using a custom key.
+    ReKeyedAes(Box<FixedKeyAes>),
+    /// BLAKE3.
+    Blake3(Blake3Tccr),
+}
+
+impl Default for AnyTccr {
+    fn default() -> Self {
+        GarbleHash::default().into()
+    }
+}
+
+impl From<GarbleHash> for AnyTccr {
+    fn from(hash: GarbleHash) -> Self {
+        match hash {
+            GarbleHash::FixedKeyAes => Self::FixedKeyAes(&FIXED_KEY_AES),
+            GarbleHash::ReKeyedAes(key) => Self::ReKeyedAes(Box::new(FixedKeyAes::new(key))),
+            GarbleHash::Blake3 => Self::Blake3(Blake3Tccr),
+        }
+    }
+}
+
+impl TccrHash for AnyTccr {
+    #[inline]
+    fn tccr_many<const N: usize>(&self, tweaks: &[Block; N], blocks: &mut [Block; N]) {
+        match self {
+            Self::FixedKeyAes(cipher) => cipher.tccr_many(tweaks, blocks),
+            Self::ReKeyedAes(cipher) => cipher.tccr_many(tweaks, blocks),
+            Self::Blake3(hash) => hash.tccr_many(tweaks, blocks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash<H: TccrHash>(hash: &H) -> [Block; 2] {
+        let tweaks = [Block::new([1; 16]), Block::new([2; 16])];
+        let mut blocks = [Block::new([3; 16]), Block::new([4; 16])];
+        hash.tccr_many(&tweaks, &mut blocks);
+        blocks
+    }
+
+    #[test]
+    fn test_any_tccr() {
+        let fixed_key = hash(&(*FIXED_KEY_AES));
+        let re_keyed = hash(&FixedKeyAes::new([42; 16]));
+        let blake3 = hash(&Blake3Tccr);
+
+        assert_ne!(fixed_key, re_keyed);
+        assert_ne!(fixed_key, blake3);
+
+        for (kind, expected) in [
+            (GarbleHash::FixedKeyAes, fixed_key),
+            (GarbleHash::ReKeyedAes([42; 16]), re_keyed),
+            (GarbleHash::Blake3, blake3),
+        ] {
+            assert_eq!(hash(&AnyTccr::from(kind)), expected);
+        }
+    }
+}
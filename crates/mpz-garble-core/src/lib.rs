@@ -2,6 +2,13 @@
 //!
 //! This module implements "half-gate" garbled circuits from the [Two Halves Make a Whole \[ZRE15\]](https://eprint.iacr.org/2014/756) paper.
 //!
+//! The "three-halves" garbling scheme from the [Three Halves Make a Whole? \[RR21\]](https://eprint.iacr.org/2021/749)
+//! paper is supported as an alternative, see [`GarblingScheme`].
+//!
+//! Lookup gates of a circuit, see [`LookupTable`](mpz_circuits::LookupTable), are garbled as
+//! row-reduced tables which are transferred alongside the encrypted AND gates, see
+//! [`encrypted_gate_count`].
+//!
 //! # Example
 //!
 //! ```
@@ -51,21 +58,34 @@ pub(crate) mod circuit;
 pub mod encoding;
 mod evaluator;
 mod generator;
-
-pub use circuit::{EncryptedGate, EncryptedGateBatch, GarbledCircuit};
+mod hash;
+mod lut;
+mod scheme;
+pub mod stream;
+mod three_halves;
+
+pub use circuit::{
+    EncryptedGate, EncryptedGateBatch, GarbledCircuit, ThreeHalvesGate, ThreeHalvesGateBatch,
+};
 pub use encoding::{
-    state as encoding_state, ChaChaEncoder, Decoding, Delta, Encode, EncodedValue, Encoder,
-    EncodingCommitment, EqualityCheck, Label, ValueError,
+    state as encoding_state, AesCtrEncoder, AnyEncoder, ChaChaEncoder, Decoding, Delta, Encode,
+    EncodedValue, Encoder, EncoderKind, EncodingCommitment, EqualityCheck, Label, ValueError,
 };
 pub use evaluator::{
     EncryptedGateBatchConsumer, EncryptedGateConsumer, Evaluator, EvaluatorError, EvaluatorOutput,
 };
 pub use generator::{
     EncryptedGateBatchIter, EncryptedGateIter, Generator, GeneratorError, GeneratorOutput,
+    ParallelEncryptedGateIter, PARALLEL_WINDOW_SIZE,
 };
+pub use hash::{AnyTccr, Blake3Tccr, GarbleHash, TccrHash};
+pub use lut::encrypted_gate_count;
+pub use scheme::{GarblingScheme, HalfGates, ThreeHalves};
+pub use stream::{GarbledCircuitReader, GarbledCircuitWriter, StreamError};
 
 const KB: usize = 1024;
 const BYTES_PER_GATE: usize = 32;
+const THREE_HALVES_BYTES_PER_GATE: usize = 26;
 
 /// Maximum size of a batch in bytes.
 const MAX_BATCH_SIZE: usize = 4 * KB;
@@ -79,16 +99,25 @@ const MAX_BATCH_SIZE: usize = 4 * KB;
 /// waste.
 pub(crate) const DEFAULT_BATCH_SIZE: usize = MAX_BATCH_SIZE / BYTES_PER_GATE;
 
+/// Default amount of three-halves encrypted gates per batch, see [`DEFAULT_BATCH_SIZE`].
+pub(crate) const THREE_HALVES_BATCH_SIZE: usize = MAX_BATCH_SIZE / THREE_HALVES_BYTES_PER_GATE;
+
 #[cfg(test)]
 mod tests {
     use aes::{
         cipher::{BlockEncrypt, KeyInit},
         Aes128,
     };
-    use mpz_circuits::{circuits::AES128, types::Value, CircuitBuilder};
-    use mpz_core::aes::FIXED_KEY_AES;
+    use mpz_circuits::{
+        circuits::{AES128, AES128_LUT},
+        ops::Lookup,
+        types::Value,
+        Circuit, CircuitBuilder, Gate,
+    };
+    use mpz_core::aes::{FixedKeyAes, FIXED_KEY, FIXED_KEY_AES};
     use rand::SeedableRng;
     use rand_chacha::ChaCha12Rng;
+    use rstest::rstest;
 
     use super::*;
 
@@ -115,6 +144,98 @@ mod tests {
         assert_eq!(ev::and_gate(cipher, &x_1, &y_1, &encrypted_gate, gid), z_1);
     }
 
+    #[test]
+    fn test_three_halves_and_gate() {
+        use crate::three_halves::{evaluate_and_gate, generate_and_gate};
+
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let cipher = &(*FIXED_KEY_AES);
+
+        // Repeat to cover all combinations of the permute bits.
+        for gid in 1..64 {
+            let delta = Delta::random(&mut rng);
+            let x_0 = Label::random(&mut rng);
+            let x_1 = x_0 ^ delta;
+            let y_0 = Label::random(&mut rng);
+            let y_1 = y_0 ^ delta;
+
+            let (z_0, encrypted_gate) = generate_and_gate(cipher, &x_0, &y_0, &delta, gid);
+            let z_1 = z_0 ^ delta;
+
+            assert_eq!(
+                evaluate_and_gate(cipher, &x_0, &y_0, &encrypted_gate, gid),
+                z_0
+            );
+            assert_eq!(
+                evaluate_and_gate(cipher, &x_0, &y_1, &encrypted_gate, gid),
+                z_0
+            );
+            assert_eq!(
+                evaluate_and_gate(cipher, &x_1, &y_0, &encrypted_gate, gid),
+                z_0
+            );
+            assert_eq!(
+                evaluate_and_gate(cipher, &x_1, &y_1, &encrypted_gate, gid),
+                z_1
+            );
+        }
+    }
+
+    fn lut_gate<S: GarblingScheme>() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let cipher = &(*FIXED_KEY_AES);
+
+        let table: [u8; 256] = std::array::from_fn(|i| (i as u8).wrapping_mul(167) ^ 0x5a);
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        builder.add_output(a.lookup(&table));
+        let circ = builder.build().unwrap();
+
+        let Gate::Lut(lut) = &circ.gates()[0] else {
+            panic!("expected a lookup gate");
+        };
+
+        // Repeat to cover different permute bits.
+        for gid in 1..8 {
+            let delta = Delta::random(&mut rng);
+            let zero: Vec<Label> = (0..circ.feed_count())
+                .map(|_| Label::random(&mut rng))
+                .collect();
+
+            let (z_0, encrypted_gates) = lut::garble::<S, _>(cipher, lut, &zero, &delta, gid);
+
+            assert_eq!(encrypted_gates.len(), encrypted_gate_count::<S>(&circ));
+
+            for value in 0..=255u8 {
+                let mut labels = zero.clone();
+                for (i, node) in lut.inputs().iter().enumerate() {
+                    if (value >> i) & 1 == 1 {
+                        labels[node.id()] = labels[node.id()] ^ delta;
+                    }
+                }
+
+                lut::evaluate::<S, _>(cipher, lut, &mut labels, &encrypted_gates, gid);
+
+                let expected = table[value as usize];
+                for (j, (node, z_0)) in lut.outputs().iter().zip(&z_0).enumerate() {
+                    let z = if (expected >> j) & 1 == 1 {
+                        z_0 ^ delta
+                    } else {
+                        *z_0
+                    };
+                    assert_eq!(labels[node.id()], z);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lut_gate() {
+        lut_gate::<HalfGates>();
+        lut_gate::<ThreeHalves>();
+    }
+
     #[test]
     fn test_garble() {
         let encoder = ChaChaEncoder::new([0; 32]);
@@ -244,4 +365,245 @@ mod tests {
         assert_eq!(actual, a ^ b);
         assert_eq!(gen_hash, ev_hash);
     }
+
+    #[test]
+    fn test_garble_three_halves() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let expected: [u8; 16] = {
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            let mut out = msg.into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .map(|input| encoder.encode_by_type(0, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        let mut gen = Generator::<ThreeHalves>::new();
+        let mut ev = Evaluator::<ThreeHalves>::new();
+
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs.clone())
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&AES128, active_inputs).unwrap();
+
+        gen_iter.enable_hasher();
+        ev_consumer.enable_hasher();
+
+        for batch in gen_iter.by_ref() {
+            ev_consumer.next(batch);
+        }
+
+        let GeneratorOutput {
+            outputs: full_outputs,
+            hash: gen_hash,
+        } = gen_iter.finish().unwrap();
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            hash: ev_hash,
+        } = ev_consumer.finish().unwrap();
+
+        let outputs: Vec<Value> = active_outputs
+            .iter()
+            .zip(full_outputs)
+            .map(|(active_output, full_output)| {
+                full_output.commit().verify(active_output).unwrap();
+                active_output.decode(&full_output.decoding()).unwrap()
+            })
+            .collect();
+
+        let actual: [u8; 16] = outputs[0].clone().try_into().unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(gen_hash, ev_hash);
+
+        // Garbling is deterministic, which is required to verify a garbled circuit.
+        let mut gen = Generator::<ThreeHalves>::new();
+        let mut gen_iter = gen.generate(&AES128, encoder.delta(), full_inputs).unwrap();
+        gen_iter.enable_hasher();
+        for _ in gen_iter.by_ref() {}
+
+        assert_eq!(gen_iter.finish().unwrap().hash, gen_hash);
+    }
+
+    fn garble_aes_lut<S: GarblingScheme>() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+
+        let key = [69u8; 16];
+        let msg = [42u8; 16];
+
+        let expected: [u8; 16] = {
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            let mut out = msg.into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let circ: &Circuit = &AES128_LUT;
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = circ
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select(key).unwrap(),
+            full_inputs[1].clone().select(msg).unwrap(),
+        ];
+
+        let mut gen = Generator::<S>::new();
+        let mut ev = Evaluator::<S>::new();
+
+        let mut gen_iter = gen.generate(circ, encoder.delta(), full_inputs).unwrap();
+        let mut ev_consumer = ev.evaluate(circ, active_inputs).unwrap();
+
+        gen_iter.enable_hasher();
+        ev_consumer.enable_hasher();
+
+        let mut count = 0;
+        for encrypted_gate in gen_iter.by_ref() {
+            ev_consumer.next(encrypted_gate);
+            count += 1;
+        }
+
+        assert_eq!(count, encrypted_gate_count::<S>(circ));
+        assert!(!ev_consumer.wants_gates());
+
+        let GeneratorOutput {
+            outputs: full_outputs,
+            hash: gen_hash,
+        } = gen_iter.finish().unwrap();
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            hash: ev_hash,
+        } = ev_consumer.finish().unwrap();
+
+        full_outputs[0].commit().verify(&active_outputs[0]).unwrap();
+        let actual: [u8; 16] = active_outputs[0]
+            .decode(&full_outputs[0].decoding())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+        assert_eq!(gen_hash, ev_hash);
+    }
+
+    #[test]
+    fn test_garble_lut() {
+        garble_aes_lut::<HalfGates>();
+        garble_aes_lut::<ThreeHalves>();
+    }
+
+    /// Garbles and evaluates AES-128, returning the full and active outputs, and the hash of the
+    /// encrypted gates.
+    fn garble_aes<H: TccrHash, T: TccrHash>(
+        encoder: &impl Encoder,
+        gen_hash: H,
+        ev_hash: T,
+    ) -> (
+        Vec<EncodedValue<encoding_state::Full>>,
+        Vec<EncodedValue<encoding_state::Active>>,
+        Option<mpz_core::hash::Hash>,
+    ) {
+        let full_inputs: Vec<EncodedValue<encoding_state::Full>> = AES128
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let active_inputs: Vec<EncodedValue<encoding_state::Active>> = vec![
+            full_inputs[0].clone().select([69u8; 16]).unwrap(),
+            full_inputs[1].clone().select([42u8; 16]).unwrap(),
+        ];
+
+        let mut gen = Generator::default().with_hash(gen_hash);
+        let mut ev = Evaluator::default().with_hash(ev_hash);
+
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut ev_consumer = ev.evaluate_batched(&AES128, active_inputs).unwrap();
+
+        gen_iter.enable_hasher();
+
+        for batch in gen_iter.by_ref() {
+            ev_consumer.next(batch);
+        }
+
+        let GeneratorOutput {
+            outputs: full_outputs,
+            hash,
+        } = gen_iter.finish().unwrap();
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            ..
+        } = ev_consumer.finish().unwrap();
+
+        (full_outputs, active_outputs, hash)
+    }
+
+    #[rstest]
+    fn test_garble_hash_compat(
+        #[values(
+            GarbleHash::FixedKeyAes,
+            GarbleHash::ReKeyedAes([42; 16]),
+            GarbleHash::Blake3
+        )]
+        hash: GarbleHash,
+        #[values(EncoderKind::ChaCha, EncoderKind::AesCtr)] encoder: EncoderKind,
+    ) {
+        let encoder = AnyEncoder::new(encoder, [0; 32]);
+
+        let expected: [u8; 16] = {
+            let cipher = Aes128::new_from_slice(&[69u8; 16]).unwrap();
+            let mut out = [42u8; 16].into();
+            cipher.encrypt_block(&mut out);
+            out.into()
+        };
+
+        let (full_outputs, active_outputs, gen_hash) =
+            garble_aes(&encoder, AnyTccr::from(hash), AnyTccr::from(hash));
+
+        full_outputs[0].commit().verify(&active_outputs[0]).unwrap();
+        let actual: [u8; 16] = active_outputs[0]
+            .decode(&full_outputs[0].decoding())
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(actual, expected);
+
+        // Only the default hash produces the same garbled circuit as the default generator.
+        let (_, _, default_hash) = garble_aes(
+            &encoder,
+            FixedKeyAes::new(FIXED_KEY),
+            FixedKeyAes::new(FIXED_KEY),
+        );
+        assert_eq!(gen_hash == default_hash, hash == GarbleHash::FixedKeyAes);
+
+        // The evaluator can not evaluate the circuit using a different hash.
+        let other = match hash {
+            GarbleHash::Blake3 => GarbleHash::FixedKeyAes,
+            _ => GarbleHash::Blake3,
+        };
+        let (full_outputs, active_outputs, _) =
+            garble_aes(&encoder, AnyTccr::from(hash), AnyTccr::from(other));
+
+        assert!(full_outputs[0].commit().verify(&active_outputs[0]).is_err());
+    }
 }
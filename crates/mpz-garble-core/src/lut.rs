@@ -0,0 +1,217 @@
+//! Garbled lookup gates.
+//!
+//! A lookup gate with `k` inputs and `m` outputs is garbled as a table with a row for each
+//! combination of the point-and-permute bits of the input labels. Row `c` encrypts the `m`
+//! output labels under a key derived from the input labels with color `c`. The output labels
+//! are chosen such that the ciphertexts of the first row are zero, so that row is not sent,
+//! leaving `(2^k - 1) * m` ciphertexts. The output labels are free-XOR compatible.
+//!
+//! The key of a row is `K = Σ α^i · X_i` in GF(2^128) for the input labels `X_i`. As the `α^i`
+//! are linearly independent, the keys of distinct rows differ by distinct multiples of delta.
+//!
+//! The ciphertexts are packed into the encrypted gates of the garbling scheme, so lookup gates
+//! are transferred in the same batches as AND gates.
+
+use mpz_circuits::{Circuit, Gate, LookupTable};
+use mpz_core::Block;
+
+use crate::{
+    encoding::{Delta, Label},
+    hash::TccrHash,
+    GarblingScheme,
+};
+
+/// Returns the number of encrypted gates of a garbled circuit.
+///
+/// This is the number of AND gates of the circuit, plus the encrypted gates which are used to
+/// transfer the lookup gates, see [`LookupTable`].
+pub fn encrypted_gate_count<S: GarblingScheme>(circ: &Circuit) -> usize {
+    if circ.lut_count() == 0 {
+        return circ.and_count();
+    }
+
+    circ.and_count()
+        + circ
+            .gates()
+            .iter()
+            .map(|gate| match gate {
+                Gate::Lut(lut) => gate_count::<S>(lut),
+                _ => 0,
+            })
+            .sum::<usize>()
+}
+
+/// Returns the number of encrypted gates used to transfer a lookup gate.
+pub(crate) fn gate_count<S: GarblingScheme>(lut: &LookupTable) -> usize {
+    (ciphertext_count(lut) * 16).div_ceil(S::BYTES_PER_GATE)
+}
+
+/// Returns the number of ciphertexts of a lookup gate.
+fn ciphertext_count(lut: &LookupTable) -> usize {
+    ((1 << lut.inputs().len()) - 1) * lut.outputs().len()
+}
+
+/// Returns the tweak for output `gid` in row `row`.
+#[inline]
+fn tweak(gid: usize, row: usize) -> Block {
+    Block::new((((row as u128) << 64) | gid as u128).to_be_bytes())
+}
+
+/// Returns the key of a row, see the module documentation.
+#[inline]
+fn combine(labels: impl Iterator<Item = Block>) -> Block {
+    labels
+        .enumerate()
+        .fold(Block::ZERO, |key, (i, label)| key ^ label.gfmul(alpha(i)))
+}
+
+/// Returns `α^i`.
+#[inline]
+fn alpha(i: usize) -> Block {
+    Block::new((1u128 << i).to_le_bytes())
+}
+
+/// Hashes the blocks in-place, in chunks to make use of instruction level parallelism.
+#[inline]
+fn hash_blocks<H: TccrHash>(cipher: &H, tweaks: &[Block], blocks: &mut [Block]) {
+    const CHUNK: usize = 8;
+
+    let mut tweaks = tweaks.chunks_exact(CHUNK);
+    let mut blocks = blocks.chunks_exact_mut(CHUNK);
+    for (tweaks, blocks) in tweaks.by_ref().zip(blocks.by_ref()) {
+        cipher.tccr_many::<CHUNK>(
+            tweaks.try_into().expect("chunk should be exact"),
+            blocks.try_into().expect("chunk should be exact"),
+        );
+    }
+
+    for (tweak, block) in tweaks.remainder().iter().zip(blocks.into_remainder()) {
+        let mut hash = [*block];
+        cipher.tccr_many(&[*tweak], &mut hash);
+        *block = hash[0];
+    }
+}
+
+/// Garbles a lookup gate.
+///
+/// # Arguments
+///
+/// * `cipher` - The hash function used to encrypt the gate.
+/// * `lut` - The lookup gate.
+/// * `labels` - The 0-bit labels of the circuit.
+/// * `delta` - The global offset.
+/// * `gid` - The gate id of the first output, each output consumes one gate id.
+///
+/// # Returns
+///
+/// The 0-bit labels of the outputs and the encrypted gates.
+pub(crate) fn garble<S: GarblingScheme, H: TccrHash>(
+    cipher: &H,
+    lut: &LookupTable,
+    labels: &[Label],
+    delta: &Delta,
+    gid: usize,
+) -> (Vec<Label>, Vec<S::EncryptedGate>) {
+    let delta = delta.into_inner();
+    let k = lut.inputs().len();
+    let m = lut.outputs().len();
+
+    let inputs = lut.inputs().iter().map(|node| labels[node.id()].to_inner());
+    let permute = inputs
+        .clone()
+        .enumerate()
+        .fold(0, |acc, (i, label)| acc | label.lsb() << i);
+    let key_0 = combine(inputs);
+    // Offset of the key when the value of input `i` is 1.
+    let offsets: Vec<Block> = (0..k).map(|i| delta.gfmul(alpha(i))).collect();
+
+    let tweaks: Vec<Vec<Block>> = (0..1 << k)
+        .map(|row| (0..m).map(|j| tweak(gid + j, row)).collect())
+        .collect();
+
+    let mut outputs = vec![Block::ZERO; m];
+    let mut bytes = Vec::with_capacity(gate_count::<S>(lut) * S::BYTES_PER_GATE);
+    let mut hashes = vec![Block::ZERO; m];
+    for (row, tweaks) in tweaks.iter().enumerate() {
+        // The evaluator holds the labels of `value` if their colors are `row`.
+        let value = row ^ permute;
+        let key = (0..k)
+            .filter(|i| (value >> i) & 1 == 1)
+            .fold(key_0, |key, i| key ^ offsets[i]);
+
+        hashes.fill(key);
+        hash_blocks(cipher, tweaks, &mut hashes);
+
+        let entry = lut.table()[value];
+        for (j, (hash, output)) in hashes.iter().zip(outputs.iter_mut()).enumerate() {
+            let bit = Block::SELECT_MASK[((entry >> j) & 1) as usize] & delta;
+            if row == 0 {
+                // Row reduction, the evaluator uses the hash as the output label.
+                *output = *hash ^ bit;
+            } else {
+                bytes.extend_from_slice(&(*hash ^ *output ^ bit).to_bytes());
+            }
+        }
+    }
+
+    bytes.resize(gate_count::<S>(lut) * S::BYTES_PER_GATE, 0);
+
+    (
+        outputs.into_iter().map(Label::new).collect(),
+        bytes
+            .chunks_exact(S::BYTES_PER_GATE)
+            .map(S::read_gate)
+            .collect(),
+    )
+}
+
+/// Evaluates a garbled lookup gate, writing the active labels of the outputs.
+///
+/// # Arguments
+///
+/// * `cipher` - The hash function used to encrypt the gate.
+/// * `lut` - The lookup gate.
+/// * `labels` - The active labels of the circuit.
+/// * `encrypted_gates` - The encrypted gates of the lookup gate.
+/// * `gid` - The gate id of the first output.
+pub(crate) fn evaluate<S: GarblingScheme, H: TccrHash>(
+    cipher: &H,
+    lut: &LookupTable,
+    labels: &mut [Label],
+    encrypted_gates: &[S::EncryptedGate],
+    gid: usize,
+) {
+    debug_assert_eq!(encrypted_gates.len(), gate_count::<S>(lut));
+
+    let m = lut.outputs().len();
+
+    let inputs = lut.inputs().iter().map(|node| labels[node.id()].to_inner());
+    let row = inputs
+        .clone()
+        .enumerate()
+        .fold(0, |acc, (i, label)| acc | label.lsb() << i);
+    let key = combine(inputs);
+
+    let tweaks: Vec<Block> = (0..m).map(|j| tweak(gid + j, row)).collect();
+    let mut hashes = vec![key; m];
+    hash_blocks(cipher, &tweaks, &mut hashes);
+
+    if row != 0 {
+        let mut bytes = vec![0u8; encrypted_gates.len() * S::BYTES_PER_GATE];
+        for (encrypted_gate, bytes) in encrypted_gates
+            .iter()
+            .zip(bytes.chunks_exact_mut(S::BYTES_PER_GATE))
+        {
+            S::write_gate(encrypted_gate, bytes);
+        }
+
+        let ciphertexts = bytes[(row - 1) * m * 16..row * m * 16].chunks_exact(16);
+        for (hash, ciphertext) in hashes.iter_mut().zip(ciphertexts) {
+            *hash ^= Block::new(ciphertext.try_into().expect("ciphertext is 16 bytes"));
+        }
+    }
+
+    for (node, hash) in lut.outputs().iter().zip(hashes) {
+        labels[node.id()] = Label::new(hash);
+    }
+}
@@ -0,0 +1,203 @@
+//! Garbling schemes for AND gates.
+//!
+//! XOR and INV gates are garbled for free in every scheme, so a scheme only determines how
+//! AND gates are encrypted, and thus the size of the garbled circuit.
+
+use core::fmt::Debug;
+
+use blake3::Hasher;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    circuit::{EncryptedGate, ThreeHalvesGate},
+    encoding::{Delta, Label},
+    evaluator, generator,
+    hash::TccrHash,
+    three_halves, BYTES_PER_GATE, THREE_HALVES_BYTES_PER_GATE,
+};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::HalfGates {}
+    impl Sealed for super::ThreeHalves {}
+}
+
+/// A garbling scheme for AND gates.
+///
+/// This trait is sealed, see [`HalfGates`] and [`ThreeHalves`] for the available schemes.
+pub trait GarblingScheme:
+    sealed::Sealed + Debug + Default + Clone + Copy + Send + Sync + 'static
+{
+    /// Encrypted AND gate of the scheme.
+    type EncryptedGate: Debug
+        + Default
+        + Clone
+        + Copy
+        + PartialEq
+        + Serialize
+        + DeserializeOwned
+        + Send
+        + Sync
+        + 'static;
+
+    /// Number of hash tweaks consumed by each AND gate.
+    #[doc(hidden)]
+    const TWEAKS_PER_GATE: usize;
+
+    /// Identifier of the scheme in serialized garbled circuits.
+    #[doc(hidden)]
+    const ID: u8;
+
+    /// Number of bytes of a serialized encrypted gate.
+    #[doc(hidden)]
+    const BYTES_PER_GATE: usize;
+
+    /// Garbles an AND gate, returning the 0-bit label of the output and the encrypted gate.
+    #[doc(hidden)]
+    fn garble_and<H: TccrHash>(
+        cipher: &H,
+        x_0: &Label,
+        y_0: &Label,
+        delta: &Delta,
+        gid: usize,
+    ) -> (Label, Self::EncryptedGate);
+
+    /// Evaluates an encrypted AND gate, returning the active label of the output.
+    #[doc(hidden)]
+    fn evaluate_and<H: TccrHash>(
+        cipher: &H,
+        x: &Label,
+        y: &Label,
+        encrypted_gate: &Self::EncryptedGate,
+        gid: usize,
+    ) -> Label;
+
+    /// Updates the hasher with an encrypted gate.
+    #[doc(hidden)]
+    fn hash_gate(hasher: &mut Hasher, encrypted_gate: &Self::EncryptedGate);
+
+    /// Writes an encrypted gate to `bytes`, which has a length of [`Self::BYTES_PER_GATE`].
+    #[doc(hidden)]
+    fn write_gate(encrypted_gate: &Self::EncryptedGate, bytes: &mut [u8]);
+
+    /// Reads an encrypted gate from `bytes`, which has a length of [`Self::BYTES_PER_GATE`].
+    #[doc(hidden)]
+    fn read_gate(bytes: &[u8]) -> Self::EncryptedGate;
+}
+
+/// The half-gates scheme from [Two Halves Make a Whole \[ZRE15\]](https://eprint.iacr.org/2014/756).
+///
+/// Each AND gate is encrypted using 2 ciphertexts of 128 bits.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HalfGates;
+
+impl GarblingScheme for HalfGates {
+    type EncryptedGate = EncryptedGate;
+
+    const TWEAKS_PER_GATE: usize = 2;
+
+    const ID: u8 = 0;
+
+    const BYTES_PER_GATE: usize = BYTES_PER_GATE;
+
+    #[inline]
+    fn garble_and<H: TccrHash>(
+        cipher: &H,
+        x_0: &Label,
+        y_0: &Label,
+        delta: &Delta,
+        gid: usize,
+    ) -> (Label, EncryptedGate) {
+        generator::and_gate(cipher, x_0, y_0, delta, gid)
+    }
+
+    #[inline]
+    fn evaluate_and<H: TccrHash>(
+        cipher: &H,
+        x: &Label,
+        y: &Label,
+        encrypted_gate: &EncryptedGate,
+        gid: usize,
+    ) -> Label {
+        evaluator::and_gate(cipher, x, y, encrypted_gate, gid)
+    }
+
+    #[inline]
+    fn hash_gate(hasher: &mut Hasher, encrypted_gate: &EncryptedGate) {
+        hasher.update(&encrypted_gate.to_bytes());
+    }
+
+    #[inline]
+    fn write_gate(encrypted_gate: &EncryptedGate, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&encrypted_gate.to_bytes());
+    }
+
+    #[inline]
+    fn read_gate(bytes: &[u8]) -> EncryptedGate {
+        EncryptedGate::from_bytes(
+            bytes
+                .try_into()
+                .expect("gate should have the correct length"),
+        )
+    }
+}
+
+/// The three-halves scheme from [Three Halves Make a Whole? \[RR21\]](https://eprint.iacr.org/2021/749).
+///
+/// Each AND gate is encrypted using 3 ciphertexts of 64 bits and 16 encrypted control bits,
+/// 26 bytes in total compared to the 32 bytes of [`HalfGates`]. The paper achieves 1.5κ + 5
+/// bits per gate by packing the control bits more tightly, we round them up to 2 bytes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ThreeHalves;
+
+impl GarblingScheme for ThreeHalves {
+    type EncryptedGate = ThreeHalvesGate;
+
+    const TWEAKS_PER_GATE: usize = 3;
+
+    const ID: u8 = 1;
+
+    const BYTES_PER_GATE: usize = THREE_HALVES_BYTES_PER_GATE;
+
+    #[inline]
+    fn garble_and<H: TccrHash>(
+        cipher: &H,
+        x_0: &Label,
+        y_0: &Label,
+        delta: &Delta,
+        gid: usize,
+    ) -> (Label, ThreeHalvesGate) {
+        three_halves::generate_and_gate(cipher, x_0, y_0, delta, gid)
+    }
+
+    #[inline]
+    fn evaluate_and<H: TccrHash>(
+        cipher: &H,
+        x: &Label,
+        y: &Label,
+        encrypted_gate: &ThreeHalvesGate,
+        gid: usize,
+    ) -> Label {
+        three_halves::evaluate_and_gate(cipher, x, y, encrypted_gate, gid)
+    }
+
+    #[inline]
+    fn hash_gate(hasher: &mut Hasher, encrypted_gate: &ThreeHalvesGate) {
+        hasher.update(&encrypted_gate.to_bytes());
+    }
+
+    #[inline]
+    fn write_gate(encrypted_gate: &ThreeHalvesGate, bytes: &mut [u8]) {
+        bytes.copy_from_slice(&encrypted_gate.to_bytes());
+    }
+
+    #[inline]
+    fn read_gate(bytes: &[u8]) -> ThreeHalvesGate {
+        ThreeHalvesGate::from_bytes(
+            bytes
+                .try_into()
+                .expect("gate should have the correct length"),
+        )
+    }
+}
@@ -0,0 +1,623 @@
+//! Streaming format for storing garbled circuits, eg on disk.
+//!
+//! A garbled circuit is written batch by batch, so it never has to be held in memory at once.
+//! This allows a generator to garble large circuits ahead of time and replay them later.
+//!
+//! # Format
+//!
+//! All integers are little-endian.
+//!
+//! | Field              | Size                                 |
+//! |--------------------|--------------------------------------|
+//! | Magic              | 8                                    |
+//! | Version            | 1                                    |
+//! | Scheme id          | 1                                    |
+//! | Batch size         | 4                                    |
+//! | Encrypted gates    | 8                                    |
+//! | Circuit hash       | 32                                   |
+//! | Delta commitment   | 32                                   |
+//! | Batches            | batch count × batch size × gate size |
+//! | Gate digest        | 32                                   |
+//! | Commitments length | 8                                    |
+//! | Commitments (BCS)  | commitments length                   |
+//!
+//! The number of encrypted gates includes the gates used to transfer lookup gates, see
+//! [`encrypted_gate_count`]. The last batch is padded with default gates, as produced by
+//! [`EncryptedGateBatchIter`](crate::EncryptedGateBatchIter). The gate digest is the BLAKE3 hash
+//! of all the batches and is checked by the reader once all batches have been read.
+
+use std::{
+    io::{Read, Write},
+    marker::PhantomData,
+};
+
+use mpz_circuits::{Circuit, Gate};
+use mpz_core::hash::Hash;
+
+use crate::{
+    circuit::EncryptedGateBatch, encrypted_gate_count, EncodingCommitment, GarblingScheme,
+    HalfGates, DEFAULT_BATCH_SIZE,
+};
+
+const MAGIC: [u8; 8] = *b"MPZGC\0\0\0";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 8 + 1 + 1 + 4 + 8 + 32 + 32;
+
+/// Errors that can occur when writing or reading a garbled circuit stream.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum StreamError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("circuit does not match the header")]
+    CircuitMismatch,
+    #[error("invalid batch count: expected {expected}, got {actual}")]
+    InvalidBatchCount { expected: usize, actual: usize },
+    #[error("gate digest does not match the batches")]
+    InvalidDigest,
+    #[error("invalid commitments: {0}")]
+    InvalidCommitments(String),
+}
+
+/// Header of a garbled circuit stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GarbledCircuitHeader {
+    scheme: u8,
+    batch_size: usize,
+    gate_count: usize,
+    circuit_hash: Hash,
+    delta_commitment: Hash,
+}
+
+impl GarbledCircuitHeader {
+    /// Returns the hash of the garbled circuit, see [`circuit_hash`].
+    pub fn circuit_hash(&self) -> Hash {
+        self.circuit_hash
+    }
+
+    /// Returns the commitment to the delta used to garble the circuit.
+    pub fn delta_commitment(&self) -> Hash {
+        self.delta_commitment
+    }
+
+    /// Returns the number of encrypted gates of the circuit.
+    pub fn gate_count(&self) -> usize {
+        self.gate_count
+    }
+
+    /// Returns the number of encrypted gates per batch.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the number of batches in the stream.
+    pub fn batch_count(&self) -> usize {
+        self.gate_count.div_ceil(self.batch_size)
+    }
+
+    fn to_bytes(self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[..8].copy_from_slice(&MAGIC);
+        bytes[8] = VERSION;
+        bytes[9] = self.scheme;
+        bytes[10..14].copy_from_slice(&(self.batch_size as u32).to_le_bytes());
+        bytes[14..22].copy_from_slice(&(self.gate_count as u64).to_le_bytes());
+        bytes[22..54].copy_from_slice(self.circuit_hash.as_bytes());
+        bytes[54..].copy_from_slice(self.delta_commitment.as_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: [u8; HEADER_LEN]) -> Result<Self, StreamError> {
+        if bytes[..8] != MAGIC {
+            return Err(StreamError::InvalidHeader("invalid magic".to_string()));
+        }
+
+        if bytes[8] != VERSION {
+            return Err(StreamError::InvalidHeader(format!(
+                "unsupported version {}",
+                bytes[8]
+            )));
+        }
+
+        let batch_size = u32::from_le_bytes(bytes[10..14].try_into().unwrap()) as usize;
+        let gate_count = u64::from_le_bytes(bytes[14..22].try_into().unwrap());
+        let circuit_hash: [u8; 32] = bytes[22..54].try_into().unwrap();
+        let delta_commitment: [u8; 32] = bytes[54..].try_into().unwrap();
+
+        if batch_size == 0 {
+            return Err(StreamError::InvalidHeader("batch size is 0".to_string()));
+        }
+
+        Ok(Self {
+            scheme: bytes[9],
+            batch_size,
+            gate_count: usize::try_from(gate_count).map_err(|_| {
+                StreamError::InvalidHeader(format!("gate count {gate_count} is too large"))
+            })?,
+            circuit_hash: circuit_hash.into(),
+            delta_commitment: delta_commitment.into(),
+        })
+    }
+}
+
+/// Computes the hash of a circuit, which identifies it in a garbled circuit stream.
+pub fn circuit_hash(circ: &Circuit) -> Hash {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(b"MPZ_GARBLED_CIRCUIT");
+
+    let mut update = |value: usize| {
+        hasher.update(&(value as u64).to_le_bytes());
+    };
+
+    for repr in circ.inputs().iter().chain(circ.outputs()) {
+        update(repr.len());
+        repr.iter().for_each(|node| update(node.id()));
+    }
+
+    for gate in circ.gates() {
+        match gate {
+            Gate::Xor { x, y, z } => [0, x.id(), y.id(), z.id()]
+                .into_iter()
+                .for_each(&mut update),
+            Gate::And { x, y, z } => [1, x.id(), y.id(), z.id()]
+                .into_iter()
+                .for_each(&mut update),
+            Gate::Inv { x, z } => [2, x.id(), z.id()].into_iter().for_each(&mut update),
+            Gate::Lut(lut) => {
+                [3, lut.inputs().len(), lut.outputs().len()]
+                    .into_iter()
+                    .chain(lut.inputs().iter().map(|node| node.id()))
+                    .chain(lut.outputs().iter().map(|node| node.id()))
+                    // Rows are split into 32-bit halves to fit into `usize` on all targets.
+                    .chain(
+                        lut.table()
+                            .iter()
+                            .flat_map(|row| [*row as u32 as usize, (*row >> 32) as usize]),
+                    )
+                    .for_each(&mut update);
+            }
+        }
+    }
+
+    Hash::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+/// Writes a garbled circuit to a stream, batch by batch.
+///
+/// # Parameters
+///
+/// - `W`: The writer.
+/// - `N`: The size of a batch.
+/// - `S`: The garbling scheme.
+#[derive(Debug)]
+pub struct GarbledCircuitWriter<W, const N: usize = DEFAULT_BATCH_SIZE, S = HalfGates> {
+    writer: W,
+    header: GarbledCircuitHeader,
+    batches: usize,
+    hasher: blake3::Hasher,
+    buf: Vec<u8>,
+    _scheme: PhantomData<S>,
+}
+
+impl<W: Write, const N: usize, S: GarblingScheme> GarbledCircuitWriter<W, N, S> {
+    /// Creates a new writer, writing the header to the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer.
+    /// * `circ` - The circuit which is garbled.
+    /// * `delta_commitment` - A commitment to the delta used to garble the circuit.
+    pub fn new(mut writer: W, circ: &Circuit, delta_commitment: Hash) -> Result<Self, StreamError> {
+        let header = GarbledCircuitHeader {
+            scheme: S::ID,
+            batch_size: N,
+            gate_count: encrypted_gate_count::<S>(circ),
+            circuit_hash: circuit_hash(circ),
+            delta_commitment,
+        };
+
+        writer.write_all(&header.to_bytes())?;
+
+        Ok(Self {
+            writer,
+            header,
+            batches: 0,
+            hasher: blake3::Hasher::new(),
+            buf: vec![0u8; N * S::BYTES_PER_GATE],
+            _scheme: PhantomData,
+        })
+    }
+
+    /// Returns the header of the stream.
+    pub fn header(&self) -> &GarbledCircuitHeader {
+        &self.header
+    }
+
+    /// Writes a batch of encrypted gates to the stream.
+    pub fn write_batch(
+        &mut self,
+        batch: &EncryptedGateBatch<N, S::EncryptedGate>,
+    ) -> Result<(), StreamError> {
+        if self.batches == self.header.batch_count() {
+            return Err(StreamError::InvalidBatchCount {
+                expected: self.header.batch_count(),
+                actual: self.batches + 1,
+            });
+        }
+
+        for (gate, bytes) in batch
+            .as_array()
+            .iter()
+            .zip(self.buf.chunks_exact_mut(S::BYTES_PER_GATE))
+        {
+            S::write_gate(gate, bytes);
+        }
+
+        self.hasher.update(&self.buf);
+        self.writer.write_all(&self.buf)?;
+        self.batches += 1;
+
+        Ok(())
+    }
+
+    /// Finishes the stream, returning the writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `commitments` - Encoding commitments of the circuit outputs.
+    pub fn finish(
+        mut self,
+        commitments: Option<Vec<EncodingCommitment>>,
+    ) -> Result<W, StreamError> {
+        if self.batches != self.header.batch_count() {
+            return Err(StreamError::InvalidBatchCount {
+                expected: self.header.batch_count(),
+                actual: self.batches,
+            });
+        }
+
+        let commitments = bcs::to_bytes(&commitments)
+            .map_err(|err| StreamError::InvalidCommitments(err.to_string()))?;
+
+        self.writer.write_all(self.hasher.finalize().as_bytes())?;
+        self.writer
+            .write_all(&(commitments.len() as u64).to_le_bytes())?;
+        self.writer.write_all(&commitments)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Reads a garbled circuit from a stream, batch by batch.
+///
+/// The batches can be passed directly to an
+/// [`EncryptedGateBatchConsumer`](crate::EncryptedGateBatchConsumer).
+///
+/// # Parameters
+///
+/// - `R`: The reader.
+/// - `N`: The size of a batch.
+/// - `S`: The garbling scheme.
+#[derive(Debug)]
+pub struct GarbledCircuitReader<R, const N: usize = DEFAULT_BATCH_SIZE, S = HalfGates> {
+    reader: R,
+    header: GarbledCircuitHeader,
+    batches: usize,
+    hasher: blake3::Hasher,
+    buf: Vec<u8>,
+    _scheme: PhantomData<S>,
+}
+
+impl<R: Read, const N: usize, S: GarblingScheme> GarbledCircuitReader<R, N, S> {
+    /// Creates a new reader, reading the header from the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The reader.
+    /// * `circ` - The circuit which is expected to be garbled.
+    pub fn new(mut reader: R, circ: &Circuit) -> Result<Self, StreamError> {
+        let mut bytes = [0u8; HEADER_LEN];
+        reader.read_exact(&mut bytes)?;
+
+        let header = GarbledCircuitHeader::from_bytes(bytes)?;
+
+        if header.scheme != S::ID {
+            return Err(StreamError::InvalidHeader(format!(
+                "expected scheme {}, got {}",
+                S::ID,
+                header.scheme
+            )));
+        }
+
+        if header.batch_size != N {
+            return Err(StreamError::InvalidHeader(format!(
+                "expected batch size {N}, got {}",
+                header.batch_size
+            )));
+        }
+
+        if header.gate_count != encrypted_gate_count::<S>(circ)
+            || header.circuit_hash != circuit_hash(circ)
+        {
+            return Err(StreamError::CircuitMismatch);
+        }
+
+        Ok(Self {
+            reader,
+            header,
+            batches: 0,
+            hasher: blake3::Hasher::new(),
+            buf: vec![0u8; N * S::BYTES_PER_GATE],
+            _scheme: PhantomData,
+        })
+    }
+
+    /// Returns the header of the stream.
+    pub fn header(&self) -> &GarbledCircuitHeader {
+        &self.header
+    }
+
+    /// Returns `true` if the stream has more batches.
+    pub fn has_batches(&self) -> bool {
+        self.batches < self.header.batch_count()
+    }
+
+    /// Reads the next batch of encrypted gates, returning `None` if all batches have been read.
+    pub fn read_batch(
+        &mut self,
+    ) -> Result<Option<EncryptedGateBatch<N, S::EncryptedGate>>, StreamError> {
+        if !self.has_batches() {
+            return Ok(None);
+        }
+
+        self.reader.read_exact(&mut self.buf)?;
+        self.hasher.update(&self.buf);
+        self.batches += 1;
+
+        let buf = &self.buf;
+        Ok(Some(EncryptedGateBatch::new(std::array::from_fn(|i| {
+            S::read_gate(&buf[i * S::BYTES_PER_GATE..(i + 1) * S::BYTES_PER_GATE])
+        }))))
+    }
+
+    /// Finishes reading the stream, returning the encoding commitments of the circuit outputs.
+    ///
+    /// Returns an error if not all batches were read, or if the batches do not match the gate
+    /// digest.
+    pub fn finish(mut self) -> Result<Option<Vec<EncodingCommitment>>, StreamError> {
+        if self.has_batches() {
+            return Err(StreamError::InvalidBatchCount {
+                expected: self.header.batch_count(),
+                actual: self.batches,
+            });
+        }
+
+        let mut digest = [0u8; 32];
+        self.reader.read_exact(&mut digest)?;
+
+        if *self.hasher.finalize().as_bytes() != digest {
+            return Err(StreamError::InvalidDigest);
+        }
+
+        let mut len = [0u8; 8];
+        self.reader.read_exact(&mut len)?;
+
+        let mut commitments = Vec::new();
+        self.reader
+            .by_ref()
+            .take(u64::from_le_bytes(len))
+            .read_to_end(&mut commitments)?;
+
+        bcs::from_bytes(&commitments)
+            .map_err(|err| StreamError::InvalidCommitments(err.to_string()))
+    }
+}
+
+impl<R: Read, const N: usize, S: GarblingScheme> Iterator for GarbledCircuitReader<R, N, S> {
+    type Item = Result<EncryptedGateBatch<N, S::EncryptedGate>, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_batch().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{BufReader, BufWriter, Cursor},
+    };
+
+    use mpz_circuits::circuits::{AES128, AES128_LUT};
+    use mpz_core::commit::HashCommit;
+
+    use super::*;
+    use crate::{
+        ChaChaEncoder, Encoder, Evaluator, EvaluatorOutput, Generator, GeneratorOutput, ThreeHalves,
+    };
+
+    #[test]
+    fn test_stream_file() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let (_, delta_commitment) = encoder.delta().hash_commit();
+        let full_inputs: Vec<_> = AES128
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+        let active_inputs: Vec<_> = full_inputs
+            .iter()
+            .map(|input| input.clone().select([42u8; 16]).unwrap())
+            .collect();
+
+        let path = std::env::temp_dir().join(format!("mpz-gc-{}.bin", std::process::id()));
+
+        let mut gen = Generator::default();
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut writer: GarbledCircuitWriter<_> = GarbledCircuitWriter::new(
+            BufWriter::new(File::create(&path).unwrap()),
+            &AES128,
+            delta_commitment,
+        )
+        .unwrap();
+        for batch in gen_iter.by_ref() {
+            writer.write_batch(&batch).unwrap();
+        }
+        let GeneratorOutput { outputs, .. } = gen_iter.finish().unwrap();
+        let commitments: Vec<_> = outputs.iter().map(|output| output.commit()).collect();
+        writer.finish(Some(commitments.clone())).unwrap();
+
+        let mut reader: GarbledCircuitReader<_> =
+            GarbledCircuitReader::new(BufReader::new(File::open(&path).unwrap()), &AES128).unwrap();
+        assert_eq!(reader.header().delta_commitment(), delta_commitment);
+
+        let mut ev = Evaluator::default();
+        let mut ev_consumer = ev.evaluate_batched(&AES128, active_inputs).unwrap();
+        for batch in reader.by_ref() {
+            ev_consumer.next(batch.unwrap());
+        }
+        let EvaluatorOutput {
+            outputs: active_outputs,
+            ..
+        } = ev_consumer.finish().unwrap();
+        let read_commitments = reader.finish().unwrap().unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read_commitments, commitments);
+        assert_eq!(
+            active_outputs[0].decode(&outputs[0].decoding()).unwrap(),
+            AES128
+                .evaluate(&[[42u8; 16].into(), [42u8; 16].into()])
+                .unwrap()[0]
+        );
+        read_commitments[0].verify(&active_outputs[0]).unwrap();
+    }
+
+    fn write_three_halves() -> Vec<u8> {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let full_inputs: Vec<_> = AES128
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+        let (_, delta_commitment) = encoder.delta().hash_commit();
+
+        let mut gen = Generator::<ThreeHalves>::new();
+        let mut gen_iter = gen
+            .generate_batched(&AES128, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut writer =
+            GarbledCircuitWriter::<_, { crate::THREE_HALVES_BATCH_SIZE }, ThreeHalves>::new(
+                Vec::new(),
+                &AES128,
+                delta_commitment,
+            )
+            .unwrap();
+        for batch in gen_iter.by_ref() {
+            writer.write_batch(&batch).unwrap();
+        }
+
+        writer.finish(None).unwrap()
+    }
+
+    #[test]
+    fn test_stream_three_halves() {
+        let bytes = write_three_halves();
+
+        let mut reader =
+            GarbledCircuitReader::<_, { crate::THREE_HALVES_BATCH_SIZE }, ThreeHalves>::new(
+                Cursor::new(&bytes),
+                &AES128,
+            )
+            .unwrap();
+        let batches = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(batches.len(), reader.header().batch_count());
+        assert!(reader.finish().unwrap().is_none());
+
+        // Wrong scheme
+        assert!(matches!(
+            GarbledCircuitReader::<_>::new(Cursor::new(&bytes), &AES128),
+            Err(StreamError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_stream_lut() {
+        let encoder = ChaChaEncoder::new([0; 32]);
+        let (_, delta_commitment) = encoder.delta().hash_commit();
+        let full_inputs: Vec<_> = AES128_LUT
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(id, input)| encoder.encode_by_type(id as u64, &input.value_type()))
+            .collect();
+
+        let mut gen = Generator::default();
+        let mut gen_iter = gen
+            .generate_batched(&AES128_LUT, encoder.delta(), full_inputs)
+            .unwrap();
+        let mut writer: GarbledCircuitWriter<_> =
+            GarbledCircuitWriter::new(Vec::new(), &AES128_LUT, delta_commitment).unwrap();
+        for batch in gen_iter.by_ref() {
+            writer.write_batch(&batch).unwrap();
+        }
+        let bytes = writer.finish(None).unwrap();
+
+        let mut reader: GarbledCircuitReader<_> =
+            GarbledCircuitReader::new(Cursor::new(&bytes), &AES128_LUT).unwrap();
+        assert_eq!(
+            reader.header().gate_count(),
+            encrypted_gate_count::<HalfGates>(&AES128_LUT)
+        );
+        assert_ne!(circuit_hash(&AES128_LUT), circuit_hash(&AES128));
+
+        while reader.read_batch().unwrap().is_some() {}
+        assert!(reader.finish().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stream_invalid() {
+        let bytes = write_three_halves();
+
+        // Wrong circuit
+        let other = mpz_circuits::circuits::AES128_DECRYPT.clone();
+        assert!(matches!(
+            GarbledCircuitReader::<_, { crate::THREE_HALVES_BATCH_SIZE }, ThreeHalves>::new(
+                Cursor::new(&bytes),
+                &other,
+            ),
+            Err(StreamError::CircuitMismatch)
+        ));
+
+        // Corrupted gate
+        let mut corrupted = bytes.clone();
+        corrupted[HEADER_LEN + 1] ^= 1;
+        let mut reader =
+            GarbledCircuitReader::<_, { crate::THREE_HALVES_BATCH_SIZE }, ThreeHalves>::new(
+                Cursor::new(&corrupted),
+                &AES128,
+            )
+            .unwrap();
+        while reader.read_batch().unwrap().is_some() {}
+        assert!(matches!(reader.finish(), Err(StreamError::InvalidDigest)));
+
+        // Truncated stream
+        let truncated = &bytes[..HEADER_LEN + 100];
+        let mut reader =
+            GarbledCircuitReader::<_, { crate::THREE_HALVES_BATCH_SIZE }, ThreeHalves>::new(
+                Cursor::new(truncated),
+                &AES128,
+            )
+            .unwrap();
+        assert!(matches!(reader.read_batch(), Err(StreamError::Io(_))));
+    }
+}
@@ -0,0 +1,213 @@
+//! Three-halves garbling of AND gates, see [`ThreeHalves`](crate::ThreeHalves).
+//!
+//! Labels are split into a left half, containing the color bit, and a right half of 64 bits
+//! each. The output label of the evaluator is computed half-wise as
+//!
+//! `C = H(A, B) ⊕ V_ij · G ⊕ R_ij · (A, B)`
+//!
+//! where `ij` are the colors of the input labels, `H(A, B) = (H(A) ⊕ H(A ⊕ B), H(B) ⊕ H(A ⊕ B))`,
+//! `G` are the 3 half-size ciphertexts of the gate and `R_ij` is a linear combination of the
+//! input halves chosen by the generator using 4 control bits per row. The control bits of each
+//! row are encrypted such that only the evaluator holding the labels of that row can decrypt
+//! them.
+
+use mpz_core::Block;
+
+use crate::{
+    circuit::ThreeHalvesGate,
+    encoding::{Delta, Label},
+    hash::TccrHash,
+};
+
+/// Coefficients of the gate ciphertexts in the left and right output halves for each row.
+///
+/// Bit `k` is set if `G_k` is added to the half.
+const DECODE: [[u8; 2]; 4] = [
+    [0b000, 0b000],
+    [0b100, 0b101],
+    [0b110, 0b100],
+    [0b010, 0b001],
+];
+
+/// Fixed part of the linear combination of the input halves for each row, indexed by the input
+/// half.
+///
+/// Bits 0 and 1 add the half of `A` to the left and right output halves, bits 2 and 3 add the
+/// half of `B`.
+const LINEAR: [[u8; 2]; 4] = [
+    [0b0000, 0b0000],
+    [0b0000, 0b1010],
+    [0b0101, 0b0000],
+    [0b0101, 0b1010],
+];
+
+/// Parts of the linear combination which are selected by the 2 control bits of an input half.
+const CONTROL: [u8; 2] = [0b1000, 0b1110];
+
+/// Control bits of each row which are flipped by the permute bit of `A`.
+const PERMUTE_A: [u8; 4] = [0b0101, 0b0110, 0b0100, 0b0111];
+
+/// Control bits of each row which are flipped by the permute bit of `B`.
+const PERMUTE_B: [u8; 4] = [0b1000, 0b0100, 0b1100, 0b0000];
+
+#[inline]
+fn to_u128(block: Block) -> u128 {
+    u128::from_le_bytes(block.to_bytes())
+}
+
+#[inline]
+fn to_halves(x: u128) -> [u64; 2] {
+    [x as u64, (x >> 64) as u64]
+}
+
+#[inline]
+fn from_halves([left, right]: [u64; 2]) -> Block {
+    Block::new((left as u128 | ((right as u128) << 64)).to_le_bytes())
+}
+
+/// Returns a mask of all ones if `bit` is set.
+#[inline]
+fn mask(bit: u8) -> u64 {
+    0u64.wrapping_sub((bit & 1) as u64)
+}
+
+#[inline]
+fn tweaks(gid: usize) -> [Block; 3] {
+    [gid, gid + 1, gid + 2].map(|tweak| Block::new((tweak as u128).to_be_bytes()))
+}
+
+/// Returns the nibble `idx` of the bits of a hash which are not used as a half.
+#[inline]
+fn nibble(hash: u128, idx: usize) -> u8 {
+    ((hash >> (64 + 4 * idx)) & 0xf) as u8
+}
+
+/// Computes the hash part of the output halves.
+#[inline]
+fn hash_halves(h_a: u128, h_b: u128, h_ab: u128) -> [u64; 2] {
+    [(h_a ^ h_ab) as u64, (h_b ^ h_ab) as u64]
+}
+
+/// Computes the linear combination of the input halves of a row.
+#[inline]
+fn combine(row: usize, control: u8, a: [u64; 2], b: [u64; 2]) -> [u64; 2] {
+    let mut out = [0u64; 2];
+    for (half, (a, b)) in a.into_iter().zip(b).enumerate() {
+        let coeffs = LINEAR[row][half]
+            ^ (CONTROL[0] & mask(control >> (2 * half)) as u8)
+            ^ (CONTROL[1] & mask(control >> (2 * half + 1)) as u8);
+
+        for (idx, out) in out.iter_mut().enumerate() {
+            *out ^= a & mask(coeffs >> idx);
+            *out ^= b & mask(coeffs >> (idx + 2));
+        }
+    }
+    out
+}
+
+/// Computes the gate ciphertext part of the output halves of a row.
+#[inline]
+fn decode(row: usize, halves: &[u64; 3]) -> [u64; 2] {
+    DECODE[row].map(|coeffs| {
+        halves
+            .iter()
+            .enumerate()
+            .fold(0, |acc, (k, half)| acc ^ (half & mask(coeffs >> k)))
+    })
+}
+
+/// Computes three-halves garbled AND gate
+#[inline]
+pub(crate) fn generate_and_gate<H: TccrHash>(
+    cipher: &H,
+    x_0: &Label,
+    y_0: &Label,
+    delta: &Delta,
+    gid: usize,
+) -> (Label, ThreeHalvesGate) {
+    let delta = delta.into_inner();
+    let x_0 = x_0.to_inner();
+    let x_1 = x_0 ^ delta;
+    let y_0 = y_0.to_inner();
+    let y_1 = y_0 ^ delta;
+
+    let p_a = x_0.lsb();
+    let p_b = y_0.lsb();
+    let [t_x, t_y, t_xy] = tweaks(gid);
+
+    let mut h = [x_0, x_1, y_0, y_1, x_0 ^ y_0, x_0 ^ y_1];
+    cipher.tccr_many(&[t_x, t_x, t_y, t_y, t_xy, t_xy], &mut h);
+
+    let [hx_0, hx_1, hy_0, hy_1, hxy_0, hxy_1] = h.map(to_u128);
+    let (hx, hy, hxy) = ([hx_0, hx_1], [hy_0, hy_1], [hxy_0, hxy_1]);
+    let x = [x_0, x_1].map(|x| to_halves(to_u128(x)));
+    let y = [y_0, y_1].map(|y| to_halves(to_u128(y)));
+    let delta = to_halves(to_u128(delta));
+
+    // The random control bits are derived from hashes the evaluator can not both compute,
+    // which keeps garbling deterministic.
+    let r = nibble(hx_0 ^ hx_1, 14);
+
+    let mut rows = [[0u64; 2]; 4];
+    let mut control = 0u16;
+    for i in 0..2 {
+        for j in 0..2 {
+            let row = 2 * i + j;
+            // Truth values of the labels with colors `i` and `j`.
+            let (a, b) = (i ^ p_a, j ^ p_b);
+
+            let row_control = r
+                ^ (PERMUTE_A[row] & mask(p_a as u8) as u8)
+                ^ (PERMUTE_B[row] & mask(p_b as u8) as u8);
+            let hash = hash_halves(hx[a], hy[b], hxy[a ^ b]);
+            let linear = combine(row, row_control, x[a], y[b]);
+            let and = mask((a & b) as u8);
+
+            rows[row] = [0, 1].map(|half| hash[half] ^ linear[half] ^ (delta[half] & and));
+            control |= ((row_control ^ nibble(hx[a], j) ^ nibble(hy[b], i)) as u16) << (4 * row);
+        }
+    }
+
+    let halves = [
+        rows[3][1] ^ rows[0][1],
+        rows[3][0] ^ rows[0][0],
+        rows[1][0] ^ rows[0][0],
+    ];
+
+    let z_0 = Label::new(from_halves(rows[0]));
+
+    (z_0, ThreeHalvesGate::new(halves, control))
+}
+
+/// Evaluates three-halves garbled AND gate
+#[inline]
+pub(crate) fn evaluate_and_gate<H: TccrHash>(
+    cipher: &H,
+    x: &Label,
+    y: &Label,
+    encrypted_gate: &ThreeHalvesGate,
+    gid: usize,
+) -> Label {
+    let x = x.to_inner();
+    let y = y.to_inner();
+
+    let s_a = x.lsb();
+    let s_b = y.lsb();
+    let row = 2 * s_a + s_b;
+
+    let mut h = [x, y, x ^ y];
+    cipher.tccr_many(&tweaks(gid), &mut h);
+
+    let [hx, hy, hxy] = h.map(to_u128);
+
+    let control =
+        ((encrypted_gate.control >> (4 * row)) & 0xf) as u8 ^ nibble(hx, s_b) ^ nibble(hy, s_a);
+
+    let hash = hash_halves(hx, hy, hxy);
+    let ciphertext = decode(row, &encrypted_gate.halves);
+    let linear = combine(row, control, to_halves(to_u128(x)), to_halves(to_u128(y)));
+
+    Label::new(from_halves(
+        [0, 1].map(|half| hash[half] ^ ciphertext[half] ^ linear[half]),
+    ))
+}
@@ -0,0 +1,163 @@
+use std::{cell::RefCell, sync::Arc};
+
+use mpz_circuits::{
+    circuits::big_num::nbyte_add_mod_trace,
+    types::{BinaryRepr, ValueType, U128, U16, U32, U64, U8},
+    BuilderState, Circuit, CircuitBuilder, Tracer,
+};
+use mpz_fields::{gf2_128::Gf2_128, p256::P256, Field};
+
+/// The modulus of the P-256 base field in big-endian order.
+const P256_MODULUS: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+];
+
+/// A field whose elements can be converted to and from garbled values.
+pub trait ConvertibleField: Field {
+    /// Adds two field elements in a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The builder state to append the circuit to.
+    /// * `a` - The first element encoded as [`Field::BYTE_SIZE`] bytes in big-endian order.
+    /// * `b` - The second element encoded as [`Field::BYTE_SIZE`] bytes in big-endian order.
+    fn add_trace<'a>(
+        state: &'a RefCell<BuilderState>,
+        a: Vec<Tracer<'a, U8>>,
+        b: Vec<Tracer<'a, U8>>,
+    ) -> Vec<Tracer<'a, U8>>;
+}
+
+impl ConvertibleField for P256 {
+    fn add_trace<'a>(
+        state: &'a RefCell<BuilderState>,
+        a: Vec<Tracer<'a, U8>>,
+        b: Vec<Tracer<'a, U8>>,
+    ) -> Vec<Tracer<'a, U8>> {
+        let a: [Tracer<'a, U8>; 32] = a.try_into().expect("element is 32 bytes");
+        let b: [Tracer<'a, U8>; 32] = b.try_into().expect("element is 32 bytes");
+
+        nbyte_add_mod_trace(state, a, b, P256_MODULUS).to_vec()
+    }
+}
+
+impl ConvertibleField for Gf2_128 {
+    fn add_trace<'a>(
+        _state: &'a RefCell<BuilderState>,
+        a: Vec<Tracer<'a, U8>>,
+        b: Vec<Tracer<'a, U8>>,
+    ) -> Vec<Tracer<'a, U8>> {
+        a.into_iter().zip(b).map(|(a, b)| a ^ b).collect()
+    }
+}
+
+/// Returns whether values of the provided type can be converted to and from elements of the
+/// field.
+pub(crate) fn is_supported<F: Field>(typ: &ValueType) -> bool {
+    let supported = match typ {
+        ValueType::U8 | ValueType::U16 | ValueType::U32 | ValueType::U64 | ValueType::U128 => true,
+        ValueType::Array(elem, _) => **elem == ValueType::U8,
+        _ => false,
+    };
+
+    supported && typ.len() <= F::BIT_SIZE
+}
+
+/// Returns the type of a field element encoded as bytes.
+pub(crate) fn field_type<F: Field>() -> ValueType {
+    ValueType::new_array::<u8>(F::BYTE_SIZE)
+}
+
+/// Builds a circuit which masks the provided values with field elements.
+///
+/// The inputs are pairs of a value and a mask, and the outputs are the sums of each value and
+/// its mask in the field.
+pub(crate) fn build_mask_circuit<F: ConvertibleField>(types: &[ValueType]) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+    let state = builder.state();
+
+    for typ in types {
+        let value = builder.add_input_by_type(typ.clone());
+        let mask = builder.add_input_by_type(field_type::<F>());
+
+        let value = to_field_bytes::<F>(state, value);
+        let mask = to_field_bytes::<F>(state, mask);
+
+        let masked = F::add_trace(state, value, mask);
+        builder.add_output(BinaryRepr::from(
+            masked
+                .into_iter()
+                .map(|byte| byte.to_inner())
+                .collect::<Vec<_>>(),
+        ));
+    }
+
+    Arc::new(builder.build().expect("circuit should be valid"))
+}
+
+/// Builds a circuit which adds additive shares of field elements.
+///
+/// The inputs are pairs of the shares of the leader and the follower, and the outputs are the
+/// sums of the shares, truncated to the provided types.
+pub(crate) fn build_sum_circuit<F: ConvertibleField>(types: &[ValueType]) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+    let state = builder.state();
+
+    for typ in types {
+        let a = builder.add_input_by_type(field_type::<F>());
+        let b = builder.add_input_by_type(field_type::<F>());
+
+        let a = to_field_bytes::<F>(state, a);
+        let b = to_field_bytes::<F>(state, b);
+
+        let sum = F::add_trace(state, a, b);
+        builder.add_output(from_field_bytes(
+            typ,
+            sum.into_iter().map(|byte| byte.to_inner()).collect(),
+        ));
+    }
+
+    Arc::new(builder.build().expect("circuit should be valid"))
+}
+
+/// Encodes a value as the big-endian bytes of a field element, padding it with zeros.
+fn to_field_bytes<F: Field>(
+    state: &RefCell<BuilderState>,
+    value: BinaryRepr,
+) -> Vec<Tracer<'_, U8>> {
+    let bytes: Vec<U8> = match value {
+        BinaryRepr::U8(v) => v.to_be_bytes().to_vec(),
+        BinaryRepr::U16(v) => v.to_be_bytes().to_vec(),
+        BinaryRepr::U32(v) => v.to_be_bytes().to_vec(),
+        BinaryRepr::U64(v) => v.to_be_bytes().to_vec(),
+        BinaryRepr::U128(v) => v.to_be_bytes().to_vec(),
+        BinaryRepr::Array(v) => v
+            .into_iter()
+            .map(|v| U8::try_from(v).expect("type is checked"))
+            .collect(),
+        _ => unreachable!("type is checked"),
+    };
+
+    let zero = state.borrow_mut().get_constant(0u8);
+    std::iter::repeat(zero)
+        .take(F::BYTE_SIZE - bytes.len())
+        .chain(bytes)
+        .map(|byte| Tracer::new(state, byte))
+        .collect()
+}
+
+/// Decodes a value of the provided type from the big-endian bytes of a field element, dropping
+/// the bytes which do not fit into the type.
+fn from_field_bytes(typ: &ValueType, bytes: Vec<U8>) -> BinaryRepr {
+    let bytes = &bytes[bytes.len() - typ.len() / 8..];
+    match typ {
+        ValueType::U8 => U8::from_be_bytes(bytes.try_into().unwrap()).into(),
+        ValueType::U16 => U16::from_be_bytes(bytes.try_into().unwrap()).into(),
+        ValueType::U32 => U32::from_be_bytes(bytes.try_into().unwrap()).into(),
+        ValueType::U64 => U64::from_be_bytes(bytes.try_into().unwrap()).into(),
+        ValueType::U128 => U128::from_be_bytes(bytes.try_into().unwrap()).into(),
+        ValueType::Array(..) => bytes.to_vec().into(),
+        _ => unreachable!("type is checked"),
+    }
+}
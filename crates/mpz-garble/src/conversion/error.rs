@@ -0,0 +1,19 @@
+use mpz_circuits::types::ValueType;
+
+use crate::{DecodeError, ExecutionError, MemoryError};
+
+/// Errors that can occur when converting between garbled values and additive shares.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ConversionError {
+    #[error(transparent)]
+    MemoryError(#[from] MemoryError),
+    #[error(transparent)]
+    ExecutionError(#[from] ExecutionError),
+    #[error(transparent)]
+    DecodeError(#[from] DecodeError),
+    #[error("unsupported value type: {0:?}")]
+    UnsupportedType(ValueType),
+    #[error("unexpected number of shares: expected {expected}, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+}
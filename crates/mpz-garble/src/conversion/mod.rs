@@ -0,0 +1,320 @@
+//! Conversion between garbled values and additive shares of field elements.
+//!
+//! A garbled value is converted into additive shares by adding a random mask of the leader to it
+//! in a circuit. The masked value is decoded privately to the follower, so the shares are the
+//! masked value for the follower and the negated mask for the leader. Additive shares are
+//! converted into a garbled value by adding the shares of both parties in a circuit.
+//!
+//! Values must be unsigned integers or byte arrays, which are interpreted as big-endian integers.
+//! Values converted into shares must be smaller than the modulus of the field, while values
+//! converted from shares are truncated to the size of their type.
+//!
+//! The conversions work with any VM which implements [`Memory`], [`Execute`] and
+//! [`DecodePrivate`], and both parties must perform them in the same order.
+
+mod circuit;
+mod error;
+
+use itybity::IntoBits;
+use mpz_circuits::types::{Value, ValueType};
+use mpz_common::Counter;
+use mpz_fields::Field;
+use rand::thread_rng;
+
+use crate::{
+    config::{Role, Visibility},
+    value::ValueRef,
+    DecodePrivate, Execute, Memory,
+};
+
+pub use circuit::ConvertibleField;
+pub use error::ConversionError;
+
+use self::circuit::{build_mask_circuit, build_sum_circuit, field_type, is_supported};
+
+/// Converts between garbled values and additive shares of field elements.
+#[derive(Debug)]
+pub struct Converter {
+    id: String,
+    role: Role,
+    counter: Counter,
+}
+
+impl Converter {
+    /// Creates a new converter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the converter, which must be unique within the VM.
+    /// * `role` - The role of this party.
+    pub fn new(id: impl Into<String>, role: Role) -> Self {
+        Self {
+            id: id.into(),
+            role,
+            counter: Counter::default(),
+        }
+    }
+
+    /// Converts garbled values into additive shares of field elements.
+    ///
+    /// # Arguments
+    ///
+    /// * `vm` - The VM which holds the values.
+    /// * `values` - The values to convert.
+    pub async fn to_additive<F, T>(
+        &mut self,
+        vm: &mut T,
+        values: &[ValueRef],
+    ) -> Result<Vec<F>, ConversionError>
+    where
+        F: ConvertibleField,
+        T: Memory + Execute + DecodePrivate + Send,
+    {
+        let types = check_types::<F, _>(vm, values)?;
+        let id = format!("{}/{}", self.id, self.counter.next());
+
+        let masks: Vec<F> = match self.role {
+            Role::Leader => {
+                let mut rng = thread_rng();
+                values.iter().map(|_| F::rand(&mut rng)).collect()
+            }
+            Role::Follower => Vec::new(),
+        };
+
+        let mut inputs = Vec::with_capacity(values.len() * 2);
+        let mut outputs = Vec::with_capacity(values.len());
+        for (idx, value) in values.iter().enumerate() {
+            let mask_id = format!("{id}/{idx}/mask");
+            let mask = match self.role {
+                Role::Leader => {
+                    let mask =
+                        vm.new_input_with_type(&mask_id, field_type::<F>(), Visibility::Private)?;
+                    vm.assign(&mask, to_value(masks[idx]))?;
+                    mask
+                }
+                Role::Follower => {
+                    vm.new_input_with_type(&mask_id, field_type::<F>(), Visibility::Blind)?
+                }
+            };
+
+            inputs.push(value.clone());
+            inputs.push(mask);
+            outputs
+                .push(vm.new_output_with_type(&format!("{id}/{idx}/masked"), field_type::<F>())?);
+        }
+
+        vm.execute(build_mask_circuit::<F>(&types), &inputs, &outputs)
+            .await?;
+
+        match self.role {
+            Role::Leader => {
+                vm.decode_blind(&outputs).await?;
+                Ok(masks.into_iter().map(|mask| -mask).collect())
+            }
+            Role::Follower => Ok(vm
+                .decode_private(&outputs)
+                .await?
+                .into_iter()
+                .map(from_value)
+                .collect()),
+        }
+    }
+
+    /// Converts additive shares of field elements into garbled values.
+    ///
+    /// # Arguments
+    ///
+    /// * `vm` - The VM to hold the values.
+    /// * `shares` - The shares of this party.
+    /// * `outputs` - The output values, which are assigned the sums of the shares.
+    pub async fn from_additive<F, T>(
+        &mut self,
+        vm: &mut T,
+        shares: Vec<F>,
+        outputs: &[ValueRef],
+    ) -> Result<(), ConversionError>
+    where
+        F: ConvertibleField,
+        T: Memory + Execute + Send,
+    {
+        if shares.len() != outputs.len() {
+            return Err(ConversionError::InvalidLength {
+                expected: outputs.len(),
+                actual: shares.len(),
+            });
+        }
+
+        let types = check_types::<F, _>(vm, outputs)?;
+        let id = format!("{}/{}", self.id, self.counter.next());
+
+        let (leader, follower) = match self.role {
+            Role::Leader => (Visibility::Private, Visibility::Blind),
+            Role::Follower => (Visibility::Blind, Visibility::Private),
+        };
+
+        let mut inputs = Vec::with_capacity(shares.len() * 2);
+        for (idx, share) in shares.into_iter().enumerate() {
+            let leader_share =
+                vm.new_input_with_type(&format!("{id}/{idx}/share_0"), field_type::<F>(), leader)?;
+            let follower_share = vm.new_input_with_type(
+                &format!("{id}/{idx}/share_1"),
+                field_type::<F>(),
+                follower,
+            )?;
+
+            let own = match self.role {
+                Role::Leader => &leader_share,
+                Role::Follower => &follower_share,
+            };
+            vm.assign(own, to_value(share))?;
+
+            inputs.push(leader_share);
+            inputs.push(follower_share);
+        }
+
+        vm.execute(build_sum_circuit::<F>(&types), &inputs, outputs)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Returns the types of the values, checking that they can be converted.
+fn check_types<F: ConvertibleField, T: Memory>(
+    vm: &T,
+    values: &[ValueRef],
+) -> Result<Vec<ValueType>, ConversionError> {
+    values
+        .iter()
+        .map(|value| {
+            let typ = vm.get_value_type(value);
+            if is_supported::<F>(&typ) {
+                Ok(typ)
+            } else {
+                Err(ConversionError::UnsupportedType(typ))
+            }
+        })
+        .collect()
+}
+
+/// Encodes a field element as big-endian bytes.
+fn to_value<F: Field>(element: F) -> Value {
+    Value::from(element.to_be_bytes())
+}
+
+/// Decodes a field element from big-endian bytes.
+fn from_value<F: Field>(value: Value) -> F {
+    F::from_msb0_iter(value.into_iter_msb0())
+}
+
+#[cfg(test)]
+mod tests {
+    use itybity::FromBitIterator;
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, UniformRand};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{protocol::deap::mock::create_mock_deap_vm, Decode};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_to_additive() {
+        let (mut leader_vm, mut follower_vm) = create_mock_deap_vm();
+        let mut leader = Converter::new("conversion", Role::Leader);
+        let mut follower = Converter::new("conversion", Role::Follower);
+
+        let x = P256::rand(&mut StdRng::seed_from_u64(0));
+        let x_bytes: [u8; 32] = x.to_be_bytes().try_into().unwrap();
+        let y = 42u32;
+
+        let leader_fut = {
+            let x_ref = leader_vm.new_private_input::<[u8; 32]>("x").unwrap();
+            let y_ref = leader_vm.new_blind_input::<u32>("y").unwrap();
+
+            leader_vm.assign(&x_ref, x_bytes).unwrap();
+
+            async {
+                leader
+                    .to_additive::<P256, _>(&mut leader_vm, &[x_ref, y_ref])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let x_ref = follower_vm.new_blind_input::<[u8; 32]>("x").unwrap();
+            let y_ref = follower_vm.new_private_input::<u32>("y").unwrap();
+
+            follower_vm.assign(&y_ref, y).unwrap();
+
+            async {
+                follower
+                    .to_additive::<P256, _>(&mut follower_vm, &[x_ref, y_ref])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let (leader_shares, follower_shares) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_shares[0] + follower_shares[0], x);
+        assert_eq!(
+            leader_shares[1] + follower_shares[1],
+            P256::from_lsb0_iter(y.into_iter_lsb0())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_additive() {
+        let (mut leader_vm, mut follower_vm) = create_mock_deap_vm();
+        let mut leader = Converter::new("conversion", Role::Leader);
+        let mut follower = Converter::new("conversion", Role::Follower);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = Gf2_128::rand(&mut rng);
+        let b = Gf2_128::rand(&mut rng);
+
+        let leader_fut = {
+            let x_ref = leader_vm.new_output::<u128>("x").unwrap();
+            let y_ref = leader_vm.new_output::<[u8; 4]>("y").unwrap();
+
+            async {
+                leader
+                    .from_additive(&mut leader_vm, vec![a, a], &[x_ref.clone(), y_ref.clone()])
+                    .await
+                    .unwrap();
+
+                leader_vm.decode(&[x_ref, y_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let x_ref = follower_vm.new_output::<u128>("x").unwrap();
+            let y_ref = follower_vm.new_output::<[u8; 4]>("y").unwrap();
+
+            async {
+                follower
+                    .from_additive(
+                        &mut follower_vm,
+                        vec![b, b],
+                        &[x_ref.clone(), y_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower_vm.decode(&[x_ref, y_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_values, follower_values) = futures::join!(leader_fut, follower_fut);
+
+        let sum = (a + b).to_inner();
+        let truncated: [u8; 4] = sum.to_be_bytes()[12..].try_into().unwrap();
+
+        assert_eq!(leader_values, follower_values);
+        assert_eq!(
+            leader_values,
+            vec![Value::from(sum), Value::from(truncated)]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use derive_builder::Builder;
+use mpz_garble_core::{EncoderKind, GarbleHash};
 
 /// Evaluator configuration.
 #[derive(Debug, Clone, Builder)]
@@ -12,6 +13,12 @@ pub struct EvaluatorConfig {
     /// Whether to log decodings.
     #[builder(default = "false", setter(custom))]
     pub(crate) log_decodings: bool,
+    /// Hash function used by the generator to garble circuits.
+    #[builder(default)]
+    pub(crate) hash: GarbleHash,
+    /// Encoder used by the generator to encode values, which is required to verify them.
+    #[builder(default)]
+    pub(crate) encoder: EncoderKind,
 }
 
 impl EvaluatorConfig {
@@ -40,3 +47,9 @@ impl EvaluatorConfigBuilder {
         self
     }
 }
+
+impl Default for EvaluatorConfig {
+    fn default() -> Self {
+        EvaluatorConfigBuilder::default().build().unwrap()
+    }
+}
@@ -17,8 +17,8 @@ use mpz_circuits::{
 use mpz_common::{cpu::CpuBackend, executor::DummyExecutor, scoped, Context};
 use mpz_core::hash::Hash;
 use mpz_garble_core::{
-    encoding_state, Decoding, EncodedValue, EncodingCommitment, EncryptedGateBatch,
-    Evaluator as EvaluatorCore, EvaluatorOutput, GarbledCircuit,
+    encoding_state, encrypted_gate_count, AnyTccr, Decoding, EncodedValue, EncodingCommitment,
+    EncryptedGateBatch, Evaluator as EvaluatorCore, EvaluatorOutput, GarbledCircuit, HalfGates,
 };
 use mpz_ot::TransferId;
 use serio::stream::IoStreamExt;
@@ -312,7 +312,7 @@ impl Evaluator {
             return Err(EvaluatorError::DuplicateCircuit);
         }
 
-        let gate_count = circ.and_count();
+        let gate_count = encrypted_gate_count::<HalfGates>(&circ);
         let mut gates = Vec::with_capacity(gate_count);
 
         while gates.len() < gate_count {
@@ -397,8 +397,9 @@ impl Evaluator {
         } = if let Some(GarbledCircuit { gates, commitments }) = existing_garbled_circuit {
             let circ = circ.clone();
             let hash = self.config.log_circuits;
+            let garble_hash = self.config.hash;
             let output = CpuBackend::blocking(move || {
-                let mut ev = EvaluatorCore::default();
+                let mut ev = EvaluatorCore::default().with_hash(AnyTccr::from(garble_hash));
                 let mut ev_consumer = ev.evaluate(&circ, encoded_inputs)?;
 
                 if hash {
@@ -427,9 +428,10 @@ impl Evaluator {
         } else {
             let circ = circ.clone();
             let hash = self.config.log_circuits;
+            let garble_hash = self.config.hash;
             let output = ctx
                 .blocking(scoped!(move |ctx| async move {
-                    let mut ev = EvaluatorCore::default();
+                    let mut ev = EvaluatorCore::default().with_hash(AnyTccr::from(garble_hash));
                     let mut ev_consumer = ev.evaluate_batched(&circ, encoded_inputs)?;
                     let io = ctx.io_mut();
 
@@ -548,7 +550,11 @@ impl Evaluator {
         // the state during verification.
 
         let gen = Generator::new(
-            GeneratorConfigBuilder::default().build().unwrap(),
+            GeneratorConfigBuilder::default()
+                .hash(self.config.hash)
+                .encoder(self.config.encoder)
+                .build()
+                .unwrap(),
             encoder_seed,
         );
 
@@ -1,4 +1,5 @@
 use derive_builder::Builder;
+use mpz_garble_core::{EncoderKind, GarbleHash};
 
 /// Generator configuration.
 #[derive(Debug, Clone, Builder)]
@@ -6,6 +7,12 @@ pub struct GeneratorConfig {
     /// Whether to send commitments to output encodings.
     #[builder(default = "false", setter(custom))]
     pub(crate) encoding_commitments: bool,
+    /// Hash function used to garble circuits.
+    #[builder(default)]
+    pub(crate) hash: GarbleHash,
+    /// Encoder used to encode values.
+    #[builder(default)]
+    pub(crate) encoder: EncoderKind,
 }
 
 impl GeneratorConfig {
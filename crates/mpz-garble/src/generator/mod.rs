@@ -16,7 +16,7 @@ use mpz_circuits::{
 use mpz_common::{scoped, Context};
 use mpz_core::hash::Hash;
 use mpz_garble_core::{
-    encoding_state, ChaChaEncoder, EncodedValue, Encoder, EncodingCommitment,
+    encoding_state, AnyEncoder, AnyTccr, EncodedValue, Encoder, EncodingCommitment,
     Generator as GeneratorCore, GeneratorOutput,
 };
 use serio::SinkExt;
@@ -42,7 +42,7 @@ pub struct Generator {
 #[derive(Debug, Default)]
 struct State {
     /// The encoder used to encode values
-    encoder: ChaChaEncoder,
+    encoder: AnyEncoder,
     /// Encodings of values
     memory: EncodingMemory<encoding_state::Full>,
     /// Transferred garbled circuits
@@ -61,9 +61,11 @@ struct State {
 impl Generator {
     /// Create a new generator.
     pub fn new(config: GeneratorConfig, encoder_seed: [u8; 32]) -> Self {
+        let encoder = AnyEncoder::new(config.encoder, encoder_seed);
+
         Self {
             config,
-            state: Mutex::new(State::new(ChaChaEncoder::new(encoder_seed))),
+            state: Mutex::new(State::new(encoder)),
         }
     }
 
@@ -304,6 +306,7 @@ impl Generator {
         };
 
         // Garble the circuit in batches, streaming the encrypted gates from the worker thread.
+        let garble_hash = self.config.hash;
         let span = span!(Level::TRACE, "worker");
         let GeneratorOutput {
             outputs: encoded_outputs,
@@ -311,7 +314,7 @@ impl Generator {
         } = ctx
             .blocking(scoped!(move |ctx| async move {
                 let _enter = span.enter();
-                let mut gen = GeneratorCore::default();
+                let mut gen = GeneratorCore::default().with_hash(AnyTccr::from(garble_hash));
                 let mut gen_iter = gen.generate_batched(&circ, delta, inputs)?;
                 let io = ctx.io_mut();
 
@@ -383,7 +386,7 @@ impl Generator {
 }
 
 impl State {
-    fn new(encoder: ChaChaEncoder) -> Self {
+    fn new(encoder: AnyEncoder) -> Self {
         Self {
             encoder,
             ..Default::default()
@@ -15,10 +15,12 @@ use mpz_circuits::{
 };
 
 pub mod config;
+pub mod conversion;
 pub(crate) mod evaluator;
 pub(crate) mod generator;
 pub(crate) mod internal_circuits;
 pub(crate) mod memory;
+pub mod oram;
 pub mod ot;
 pub mod protocol;
 pub mod value;
@@ -27,6 +29,7 @@ pub use evaluator::{Evaluator, EvaluatorConfig, EvaluatorConfigBuilder, Evaluato
 pub use generator::{Generator, GeneratorConfig, GeneratorConfigBuilder, GeneratorError};
 pub use memory::{AssignedValues, ValueMemory};
 
+use oram::ObliviousArray;
 use value::{ArrayRef, ValueId, ValueRef};
 
 /// Errors that can occur when using an implementation of [`Vm`].
@@ -286,6 +289,25 @@ pub trait Memory {
 
         Ok(ValueRef::Array(ArrayRef::new(ids)))
     }
+
+    /// Creates an oblivious array from the provided array, which supports reads and writes at
+    /// secret indices.
+    ///
+    /// The backend is selected with the type parameter, eg
+    /// [`LinearScanArray`](oram::LinearScanArray). See the [`oram`] module for more information.
+    fn new_oblivious_array<A: ObliviousArray>(
+        &self,
+        id: &str,
+        array: &ValueRef,
+    ) -> Result<A, MemoryError> {
+        let ValueType::Array(typ, _) = self.get_value_type(array) else {
+            return Err(MemoryError::InvalidArray(
+                "an oblivious array can only be created from an array".to_string(),
+            ));
+        };
+
+        Ok(A::new(id, array.clone(), *typ))
+    }
 }
 
 /// This trait provides methods for loading a circuit.
@@ -486,12 +486,22 @@ mod tests {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
     #[case::bit_array(PhantomData::<[bool; 16]>)]
     #[case::u8_array(PhantomData::<[u8; 16]>)]
     #[case::u16_array(PhantomData::<[u16; 16]>)]
     #[case::u32_array(PhantomData::<[u32; 16]>)]
     #[case::u64_array(PhantomData::<[u64; 16]>)]
     #[case::u128_array(PhantomData::<[u128; 16]>)]
+    #[case::i8_array(PhantomData::<[i8; 16]>)]
+    #[case::i16_array(PhantomData::<[i16; 16]>)]
+    #[case::i32_array(PhantomData::<[i32; 16]>)]
+    #[case::i64_array(PhantomData::<[i64; 16]>)]
+    #[case::i128_array(PhantomData::<[i128; 16]>)]
     fn test_value_memory_duplicate_fails<T>(#[case] _ty: PhantomData<T>)
     where
         T: StaticValueType + Default + std::fmt::Debug,
@@ -516,12 +526,22 @@ mod tests {
     #[case::u64(PhantomData::<u64>)]
     #[case::u64(PhantomData::<u64>)]
     #[case::u128(PhantomData::<u128>)]
+    #[case::i8(PhantomData::<i8>)]
+    #[case::i16(PhantomData::<i16>)]
+    #[case::i32(PhantomData::<i32>)]
+    #[case::i64(PhantomData::<i64>)]
+    #[case::i128(PhantomData::<i128>)]
     #[case::bit_array(PhantomData::<[bool; 16]>)]
     #[case::u8_array(PhantomData::<[u8; 16]>)]
     #[case::u16_array(PhantomData::<[u16; 16]>)]
     #[case::u32_array(PhantomData::<[u32; 16]>)]
     #[case::u64_array(PhantomData::<[u64; 16]>)]
     #[case::u128_array(PhantomData::<[u128; 16]>)]
+    #[case::i8_array(PhantomData::<[i8; 16]>)]
+    #[case::i16_array(PhantomData::<[i16; 16]>)]
+    #[case::i32_array(PhantomData::<[i32; 16]>)]
+    #[case::i64_array(PhantomData::<[i64; 16]>)]
+    #[case::i128_array(PhantomData::<[i128; 16]>)]
     fn test_encoding_memory_set_duplicate_fails<T>(
         encoder: ChaChaEncoder,
         #[case] _ty: PhantomData<T>,
@@ -0,0 +1,167 @@
+use std::{cell::RefCell, sync::Arc};
+
+use mpz_circuits::{
+    ops::Equal,
+    types::{BinaryRepr, Bit, ValueType},
+    BuilderState, Circuit, CircuitBuilder, Tracer,
+};
+
+macro_rules! impl_primitive {
+    ($($id:ident => $ty:ty),*) => {
+        /// Selects between two values of the same primitive type.
+        ///
+        /// If the bit is 0, the result is `a`, otherwise it is `b`.
+        fn mux<'a>(
+            state: &'a RefCell<BuilderState>,
+            bit: Tracer<'a, Bit>,
+            a: BinaryRepr,
+            b: BinaryRepr,
+        ) -> BinaryRepr {
+            match (a, b) {
+                $(
+                    (BinaryRepr::$id(a), BinaryRepr::$id(b)) => bit
+                        .mux(Tracer::new(state, a), Tracer::new(state, b))
+                        .to_inner()
+                        .into(),
+                )*
+                _ => unreachable!("types are checked"),
+            }
+        }
+
+        /// Returns a zero value of the provided primitive type.
+        fn zero(state: &RefCell<BuilderState>, typ: &ValueType) -> BinaryRepr {
+            match typ {
+                $(ValueType::$id => state.borrow_mut().get_constant(<$ty>::default()).into(),)*
+                _ => unreachable!("types are checked"),
+            }
+        }
+    };
+}
+
+impl_primitive!(
+    Bit => bool,
+    U8 => u8,
+    U16 => u16,
+    U32 => u32,
+    U64 => u64,
+    U128 => u128,
+    I8 => i8,
+    I16 => i16,
+    I32 => i32,
+    I64 => i64,
+    I128 => i128
+);
+
+/// Returns whether values of the provided type can index an array of the provided length.
+pub(crate) fn is_index_type(typ: &ValueType, len: usize) -> bool {
+    let max = match typ {
+        ValueType::U8 => u8::MAX as u64,
+        ValueType::U16 => u16::MAX as u64,
+        ValueType::U32 => u32::MAX as u64,
+        ValueType::U64 => u64::MAX,
+        _ => return false,
+    };
+
+    (len as u64).saturating_sub(1) <= max
+}
+
+/// Returns whether the index is equal to the provided constant, which must fit into the type
+/// of the index.
+fn index_eq<'a>(state: &'a RefCell<BuilderState>, index: &BinaryRepr, i: usize) -> Tracer<'a, Bit> {
+    match index.clone() {
+        BinaryRepr::U8(index) => Tracer::new(state, index).eq(i as u8),
+        BinaryRepr::U16(index) => Tracer::new(state, index).eq(i as u16),
+        BinaryRepr::U32(index) => Tracer::new(state, index).eq(i as u32),
+        BinaryRepr::U64(index) => Tracer::new(state, index).eq(i as u64),
+        _ => unreachable!("types are checked"),
+    }
+}
+
+/// Returns the elements of an array.
+fn elements(array: BinaryRepr) -> Vec<BinaryRepr> {
+    match array {
+        BinaryRepr::Array(elements) => elements,
+        _ => unreachable!("input is an array"),
+    }
+}
+
+/// Builds a circuit which reads an element of an array with a linear scan.
+///
+/// The inputs are the array and the index, and the output is the element.
+pub(crate) fn build_read_circuit(
+    typ: &ValueType,
+    len: usize,
+    index_typ: &ValueType,
+) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+    let state = builder.state();
+
+    let array = builder.add_input_by_type(ValueType::Array(Box::new(typ.clone()), len));
+    let index = builder.add_input_by_type(index_typ.clone());
+
+    let mut value = zero(state, typ);
+    for (i, element) in elements(array).into_iter().enumerate() {
+        value = mux(state, index_eq(state, &index, i), value, element);
+    }
+
+    builder.add_output(value);
+
+    Arc::new(builder.build().expect("circuit should be valid"))
+}
+
+/// Builds a circuit which writes an element of an array with a linear scan.
+///
+/// The inputs are the array, the index and the value, and the output is the updated array.
+pub(crate) fn build_write_circuit(
+    typ: &ValueType,
+    len: usize,
+    index_typ: &ValueType,
+) -> Arc<Circuit> {
+    let builder = CircuitBuilder::new();
+    let state = builder.state();
+
+    let array = builder.add_input_by_type(ValueType::Array(Box::new(typ.clone()), len));
+    let index = builder.add_input_by_type(index_typ.clone());
+    let value = builder.add_input_by_type(typ.clone());
+
+    let array = elements(array)
+        .into_iter()
+        .enumerate()
+        .map(|(i, element)| mux(state, index_eq(state, &index, i), element, value.clone()))
+        .collect();
+
+    builder.add_output(BinaryRepr::Array(array));
+
+    Arc::new(builder.build().expect("circuit should be valid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_read_circuit() {
+        let circ = build_read_circuit(&ValueType::U16, 5, &ValueType::U8);
+        let array = Value::from([1u16, 2, 3, 4, 5]);
+
+        for (index, expected) in [(0u8, 1u16), (3, 4), (4, 5), (5, 0)] {
+            let output = circ.evaluate(&[array.clone(), Value::from(index)]).unwrap();
+            assert_eq!(output, vec![Value::from(expected)]);
+        }
+    }
+
+    #[test]
+    fn test_write_circuit() {
+        let circ = build_write_circuit(&ValueType::U16, 3, &ValueType::U8);
+        let array = Value::from([1u16, 2, 3]);
+
+        for (index, expected) in [(0u8, [9u16, 2, 3]), (2, [1, 2, 9]), (3, [1, 2, 3])] {
+            let output = circ
+                .evaluate(&[array.clone(), Value::from(index), Value::from(9u16)])
+                .unwrap();
+            assert_eq!(output, vec![Value::from(expected)]);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use mpz_circuits::types::ValueType;
+use mpz_common::Counter;
+
+use crate::{value::ValueRef, Execute, Memory};
+
+use super::{
+    circuit::{build_read_circuit, build_write_circuit, is_index_type},
+    ObliviousArray, ObliviousArrayError,
+};
+
+/// An oblivious array which is accessed with a linear scan over all of its elements.
+#[derive(Debug, Clone)]
+pub struct LinearScanArray {
+    id: String,
+    /// The current elements of the array.
+    array: ValueRef,
+    /// The type of the elements.
+    typ: ValueType,
+    counter: Counter,
+}
+
+impl LinearScanArray {
+    /// Returns a reference to the current elements of the array.
+    pub fn array(&self) -> &ValueRef {
+        &self.array
+    }
+
+    fn check_index<T: Memory>(
+        &self,
+        vm: &T,
+        index: &ValueRef,
+    ) -> Result<ValueType, ObliviousArrayError> {
+        let typ = vm.get_value_type(index);
+        if !is_index_type(&typ, self.len()) {
+            return Err(ObliviousArrayError::InvalidIndex {
+                typ,
+                len: self.len(),
+            });
+        }
+
+        Ok(typ)
+    }
+}
+
+#[async_trait]
+impl ObliviousArray for LinearScanArray {
+    fn new(id: &str, array: ValueRef, typ: ValueType) -> Self {
+        Self {
+            id: id.to_string(),
+            array,
+            typ,
+            counter: Counter::default(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn value_type(&self) -> &ValueType {
+        &self.typ
+    }
+
+    async fn read<T: Memory + Execute + Send>(
+        &mut self,
+        vm: &mut T,
+        index: &ValueRef,
+    ) -> Result<ValueRef, ObliviousArrayError> {
+        let index_typ = self.check_index(vm, index)?;
+
+        let value = vm.new_output_with_type(
+            &format!("{}/{}/read", self.id, self.counter.next()),
+            self.typ.clone(),
+        )?;
+
+        vm.execute(
+            build_read_circuit(&self.typ, self.len(), &index_typ),
+            &[self.array.clone(), index.clone()],
+            &[value.clone()],
+        )
+        .await?;
+
+        Ok(value)
+    }
+
+    async fn write<T: Memory + Execute + Send>(
+        &mut self,
+        vm: &mut T,
+        index: &ValueRef,
+        value: &ValueRef,
+    ) -> Result<(), ObliviousArrayError> {
+        let index_typ = self.check_index(vm, index)?;
+
+        let value_typ = vm.get_value_type(value);
+        if value_typ != self.typ {
+            return Err(ObliviousArrayError::InvalidValue {
+                expected: self.typ.clone(),
+                actual: value_typ,
+            });
+        }
+
+        let array = vm.new_output_with_type(
+            &format!("{}/{}/write", self.id, self.counter.next()),
+            ValueType::Array(Box::new(self.typ.clone()), self.len()),
+        )?;
+
+        vm.execute(
+            build_write_circuit(&self.typ, self.len(), &index_typ),
+            &[self.array.clone(), index.clone(), value.clone()],
+            &[array.clone()],
+        )
+        .await?;
+
+        self.array = array;
+
+        Ok(())
+    }
+}
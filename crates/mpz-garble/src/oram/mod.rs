@@ -0,0 +1,169 @@
+//! Oblivious arrays, which support reads and writes at secret indices.
+//!
+//! An oblivious array is created from an array value with [`Memory::new_oblivious_array`], which
+//! selects the backend, and is accessed with an index value of an unsigned integer type. Each
+//! access executes a circuit with the provided VM, so the accesses of both parties must be
+//! performed in the same order.
+//!
+//! The only backend is currently a [`LinearScanArray`], which touches every element on each
+//! access. Backends with sublinear access costs, such as square-root ORAM or FLORAM, can be added
+//! by implementing [`ObliviousArray`].
+//!
+//! [`Memory::new_oblivious_array`]: crate::Memory::new_oblivious_array
+
+mod circuit;
+mod linear;
+
+use async_trait::async_trait;
+use mpz_circuits::types::ValueType;
+
+use crate::{value::ValueRef, Execute, ExecutionError, Memory, MemoryError};
+
+pub use linear::LinearScanArray;
+
+/// Errors that can occur when accessing an oblivious array.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ObliviousArrayError {
+    #[error(transparent)]
+    MemoryError(#[from] MemoryError),
+    #[error(transparent)]
+    ExecutionError(#[from] ExecutionError),
+    #[error("invalid index type {typ:?} for an array of length {len}")]
+    InvalidIndex { typ: ValueType, len: usize },
+    #[error("invalid value type: expected {expected:?}, got {actual:?}")]
+    InvalidValue {
+        expected: ValueType,
+        actual: ValueType,
+    },
+}
+
+/// An array which supports reads and writes at secret indices.
+#[async_trait]
+pub trait ObliviousArray {
+    /// Creates a new oblivious array.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the array, which must be unique within the VM.
+    /// * `array` - The initial elements.
+    /// * `typ` - The type of the elements.
+    fn new(id: &str, array: ValueRef, typ: ValueType) -> Self
+    where
+        Self: Sized;
+
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the array has no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the type of the elements.
+    fn value_type(&self) -> &ValueType;
+
+    /// Reads the element at the provided index, returning a reference to a new value which holds
+    /// it.
+    ///
+    /// Reading an index which is out of bounds returns zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `vm` - The VM which holds the array.
+    /// * `index` - The index to read.
+    async fn read<T: Memory + Execute + Send>(
+        &mut self,
+        vm: &mut T,
+        index: &ValueRef,
+    ) -> Result<ValueRef, ObliviousArrayError>;
+
+    /// Writes the provided value to the element at the provided index.
+    ///
+    /// Writing an index which is out of bounds leaves the array unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `vm` - The VM which holds the array.
+    /// * `index` - The index to write.
+    /// * `value` - The value to write.
+    async fn write<T: Memory + Execute + Send>(
+        &mut self,
+        vm: &mut T,
+        index: &ValueRef,
+        value: &ValueRef,
+    ) -> Result<(), ObliviousArrayError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::types::Value;
+
+    use crate::{protocol::deap::mock::create_mock_deap_vm, Decode};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_linear_scan_array() {
+        let (mut leader_vm, mut follower_vm) = create_mock_deap_vm();
+
+        let array = [1u32, 2, 3, 4, 5, 6, 7, 8];
+
+        let leader_fut = {
+            let array_ref = leader_vm.new_private_input::<[u32; 8]>("array").unwrap();
+            let index_ref = leader_vm.new_blind_input::<u8>("index").unwrap();
+            let value_ref = leader_vm.new_private_input::<u32>("value").unwrap();
+
+            leader_vm.assign(&array_ref, array).unwrap();
+            leader_vm.assign(&value_ref, 42u32).unwrap();
+
+            let mut oram = leader_vm
+                .new_oblivious_array::<LinearScanArray>("oram", &array_ref)
+                .unwrap();
+
+            async move {
+                let read = oram.read(&mut leader_vm, &index_ref).await.unwrap();
+                oram.write(&mut leader_vm, &index_ref, &value_ref)
+                    .await
+                    .unwrap();
+
+                leader_vm
+                    .decode(&[read, oram.array().clone()])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let array_ref = follower_vm.new_blind_input::<[u32; 8]>("array").unwrap();
+            let index_ref = follower_vm.new_private_input::<u8>("index").unwrap();
+            let value_ref = follower_vm.new_blind_input::<u32>("value").unwrap();
+
+            follower_vm.assign(&index_ref, 5u8).unwrap();
+
+            let mut oram = follower_vm
+                .new_oblivious_array::<LinearScanArray>("oram", &array_ref)
+                .unwrap();
+
+            async move {
+                let read = oram.read(&mut follower_vm, &index_ref).await.unwrap();
+                oram.write(&mut follower_vm, &index_ref, &value_ref)
+                    .await
+                    .unwrap();
+
+                follower_vm
+                    .decode(&[read, oram.array().clone()])
+                    .await
+                    .unwrap()
+            }
+        };
+
+        let (leader_values, follower_values) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_values, follower_values);
+        assert_eq!(
+            leader_values,
+            vec![Value::from(6u32), Value::from([1u32, 2, 3, 4, 5, 42, 7, 8])]
+        );
+    }
+}
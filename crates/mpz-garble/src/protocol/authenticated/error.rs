@@ -0,0 +1,66 @@
+use crate::{
+    protocol::common::InvalidLength,
+    value::{ValueId, ValueRef},
+    DecodeError, ExecutionError, LoadError,
+};
+
+/// Errors that can occur during the authenticated garbling protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum AuthenticatedError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    CommitmentError(#[from] mpz_core::commit::CommitmentError),
+    #[error("value is not assigned: {0:?}")]
+    UnassignedValue(ValueId),
+    #[error("value does not match the circuit: {0:?}")]
+    InvalidValue(ValueRef),
+    #[error("lookup gates are not supported")]
+    LookupGate,
+    #[error("unexpected message length: expected {expected}, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    #[error("invalid MAC of an authenticated share")]
+    InvalidMac,
+    #[error("authenticated AND triple check failed")]
+    InvalidTriple,
+    #[error("invalid garbled AND gate: {0}")]
+    InvalidGate(usize),
+    #[error("invalid output label")]
+    InvalidLabel,
+}
+
+impl From<InvalidLength> for AuthenticatedError {
+    fn from(InvalidLength { expected, actual }: InvalidLength) -> Self {
+        AuthenticatedError::InvalidLength { expected, actual }
+    }
+}
+
+impl From<AuthenticatedError> for LoadError {
+    fn from(err: AuthenticatedError) -> Self {
+        match err {
+            AuthenticatedError::IOError(err) => LoadError::IOError(err),
+            err => LoadError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<AuthenticatedError> for ExecutionError {
+    fn from(err: AuthenticatedError) -> Self {
+        match err {
+            AuthenticatedError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<AuthenticatedError> for DecodeError {
+    fn from(err: AuthenticatedError) -> Self {
+        match err {
+            AuthenticatedError::IOError(err) => DecodeError::IOError(err),
+            err => DecodeError::ProtocolError(Box::new(err)),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Authenticated garbled circuits.
+//!
+//! Each wire `w` of the circuit is masked with a random bit `λ_w`, of which both parties hold
+//! an authenticated share. The evaluator learns the masked value `Λ_w = w ⊕ λ_w` and the label
+//! `L_w,0 ⊕ Λ_w·Δ_A` of every wire, where `Δ_A` is the delta of the generator.
+//!
+//! Given the shares of `σ = λ_α·λ_β` of an AND gate, row `(a, b)` of the garbled gate encrypts
+//! the share of the generator of `Λ_γ` for `Λ_α = a` and `Λ_β = b`, along with its MAC, under
+//! the labels of the input wires. As the evaluator checks the MAC, the generator can not change
+//! the output of a gate without being detected.
+
+use mpz_circuits::{Circuit, Gate};
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+use rand::thread_rng;
+
+use crate::config::Role;
+
+use super::{share::Share, AuthenticatedError};
+
+/// A row of a garbled AND gate, the encrypted share of the generator, its MAC and the
+/// encrypted label of the output wire.
+pub(crate) type Row = (bool, Block, Block);
+
+/// A garbled AND gate.
+pub(crate) type GarbledGate = [Row; 4];
+
+/// Returns the hashes which encrypt row `row` of gate `gid`.
+#[inline]
+fn row_hashes(gid: usize, row: usize, x: Block, y: Block) -> [Block; 3] {
+    let key = x ^ y.gfmul(Block::new(2u128.to_le_bytes()));
+    let tweaks = std::array::from_fn(|j| {
+        Block::new((((gid as u128) << 4) | (row << 2 | j) as u128).to_be_bytes())
+    });
+    let mut hashes = [key; 3];
+    FIXED_KEY_AES.tccr_many(&tweaks, &mut hashes);
+    hashes
+}
+
+/// Returns the share of `Λ_γ` for `Λ_α = a` and `Λ_β = b`.
+#[inline]
+fn row_share(role: Role, delta: Block, [x, y, sigma, z]: [Share; 4], a: bool, b: bool) -> Share {
+    // (a ⊕ λ_α)·(b ⊕ λ_β) ⊕ λ_γ
+    (sigma ^ z ^ y.and_const(a) ^ x.and_const(b)).add_const(a & b, role, delta)
+}
+
+/// Computes the shares of the wire masks of a circuit.
+///
+/// # Arguments
+///
+/// * `circ` - The circuit.
+/// * `role` - The role of this party.
+/// * `delta` - The delta of this party.
+/// * `masks` - The shares of the masks, set for the input wires.
+/// * `and_masks` - The shares of the masks of the output wires of the AND gates.
+pub(crate) fn propagate_masks(
+    circ: &Circuit,
+    role: Role,
+    delta: Block,
+    masks: &mut [Share],
+    and_masks: &[Share],
+) {
+    let mut and_masks = and_masks.iter();
+    for gate in circ.gates() {
+        match gate {
+            Gate::Xor { x, y, z } => masks[z.id()] = masks[x.id()] ^ masks[y.id()],
+            Gate::Inv { x, z } => masks[z.id()] = masks[x.id()].add_const(true, role, delta),
+            Gate::And { z, .. } => {
+                masks[z.id()] = *and_masks.next().expect("mask is provided for AND gate")
+            }
+            Gate::Lut(_) => unreachable!("lookup gates are rejected before garbling"),
+        }
+    }
+}
+
+/// Garbles a circuit.
+///
+/// # Arguments
+///
+/// * `circ` - The circuit.
+/// * `delta` - The delta of the generator.
+/// * `masks` - The shares of the wire masks.
+/// * `sigmas` - The shares of `λ_α·λ_β` of the AND gates.
+/// * `labels` - The 0-labels of the wires, set for the input wires.
+pub(crate) fn garble(
+    circ: &Circuit,
+    delta: Block,
+    masks: &[Share],
+    sigmas: &[Share],
+    labels: &mut [Block],
+) -> Vec<GarbledGate> {
+    let mut rng = thread_rng();
+    let mut gates = Vec::with_capacity(circ.and_count());
+    for gate in circ.gates() {
+        match gate {
+            Gate::Xor { x, y, z } => labels[z.id()] = labels[x.id()] ^ labels[y.id()],
+            Gate::Inv { x, z } => labels[z.id()] = labels[x.id()],
+            Gate::And { x, y, z } => {
+                let gid = gates.len();
+                let label = Block::random(&mut rng);
+                let shares = [masks[x.id()], masks[y.id()], sigmas[gid], masks[z.id()]];
+
+                let gate = std::array::from_fn(|row| {
+                    let (a, b) = (row >> 1 == 1, row & 1 == 1);
+                    let x_label = labels[x.id()] ^ (Block::SELECT_MASK[a as usize] & delta);
+                    let y_label = labels[y.id()] ^ (Block::SELECT_MASK[b as usize] & delta);
+                    let [h0, h1, h2] = row_hashes(gid, row, x_label, y_label);
+
+                    // The evaluator recovers the label of Λ_γ with the MAC of its share.
+                    let share = row_share(Role::Leader, delta, shares, a, b);
+                    let z_label =
+                        label ^ share.key ^ (Block::SELECT_MASK[share.bit as usize] & delta);
+
+                    (share.bit ^ (h0.lsb() == 1), share.mac ^ h1, z_label ^ h2)
+                });
+
+                labels[z.id()] = label;
+                gates.push(gate);
+            }
+            Gate::Lut(_) => unreachable!("lookup gates are rejected before garbling"),
+        }
+    }
+
+    gates
+}
+
+/// Evaluates an authenticated garbled circuit.
+///
+/// # Arguments
+///
+/// * `circ` - The circuit.
+/// * `delta` - The delta of the evaluator.
+/// * `masks` - The shares of the wire masks.
+/// * `sigmas` - The shares of `λ_α·λ_β` of the AND gates.
+/// * `wires` - The masked values and labels of the wires, set for the input wires.
+/// * `gates` - The garbled AND gates.
+pub(crate) fn evaluate(
+    circ: &Circuit,
+    delta: Block,
+    masks: &[Share],
+    sigmas: &[Share],
+    wires: &mut [(bool, Block)],
+    gates: &[GarbledGate],
+) -> Result<(), AuthenticatedError> {
+    let mut gates = gates.iter().enumerate();
+    for gate in circ.gates() {
+        match gate {
+            Gate::Xor { x, y, z } => {
+                let (a, x) = wires[x.id()];
+                let (b, y) = wires[y.id()];
+                wires[z.id()] = (a ^ b, x ^ y);
+            }
+            Gate::Inv { x, z } => wires[z.id()] = wires[x.id()],
+            Gate::And { x, y, z } => {
+                let (gid, gate) = gates.next().expect("gate count is checked");
+                let (a, x_label) = wires[x.id()];
+                let (b, y_label) = wires[y.id()];
+                let row = (a as usize) << 1 | b as usize;
+                let [h0, h1, h2] = row_hashes(gid, row, x_label, y_label);
+
+                let share = row_share(
+                    Role::Follower,
+                    delta,
+                    [masks[x.id()], masks[y.id()], sigmas[gid], masks[z.id()]],
+                    a,
+                    b,
+                );
+
+                let (bit, mac, label) = gate[row];
+                let bit = bit ^ (h0.lsb() == 1);
+                if mac ^ h1 != share.key ^ (Block::SELECT_MASK[bit as usize] & delta) {
+                    return Err(AuthenticatedError::InvalidGate(gid));
+                }
+
+                wires[z.id()] = (bit ^ share.bit, label ^ h2 ^ share.mac);
+            }
+            Gate::Lut(_) => unreachable!("lookup gates are rejected before garbling"),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Mocked authenticated garbling VMs for testing
+
+use mpz_common::executor::{test_st_executor, STExecutor};
+use mpz_core::Block;
+use mpz_ot::ideal::cot::{ideal_rcot_with_delta, IdealCOTReceiver, IdealCOTSender};
+use rand::thread_rng;
+use serio::channel::MemoryDuplex;
+
+use crate::{config::Role, protocol::authenticated::vm::AuthenticatedThread};
+
+type Ctx = STExecutor<MemoryDuplex>;
+
+/// Mock authenticated garbling leader.
+pub type MockLeader = AuthenticatedThread<Ctx, IdealCOTSender, IdealCOTReceiver>;
+/// Mock authenticated garbling follower.
+pub type MockFollower = AuthenticatedThread<Ctx, IdealCOTSender, IdealCOTReceiver>;
+
+/// Create a pair of mocked authenticated garbling VMs
+pub fn create_mock_authenticated_vm() -> (MockLeader, MockFollower) {
+    let mut rng = thread_rng();
+    let (leader_ctx, follower_ctx) = test_st_executor(128);
+    let leader_delta = Block::random(&mut rng);
+    let follower_delta = Block::random(&mut rng);
+    let (leader_cot_send, follower_cot_recv) = ideal_rcot_with_delta(leader_delta);
+    let (follower_cot_send, leader_cot_recv) = ideal_rcot_with_delta(follower_delta);
+
+    let leader = AuthenticatedThread::new(
+        Role::Leader,
+        leader_delta,
+        leader_ctx,
+        leader_cot_send,
+        leader_cot_recv,
+    );
+
+    let follower = AuthenticatedThread::new(
+        Role::Follower,
+        follower_delta,
+        follower_ctx,
+        follower_cot_send,
+        follower_cot_recv,
+    );
+
+    (leader, follower)
+}
@@ -0,0 +1,980 @@
+//! An implementation of authenticated garbling, secure against malicious adversaries.
+//!
+//! The protocol follows [WRK17](https://eprint.iacr.org/2017/030). Both parties hold
+//! authenticated shares of a random mask for every wire of a circuit. The leader garbles the
+//! circuit such that each AND gate also reveals the share of the leader of the masked output
+//! value to the follower, authenticated with a MAC under the delta of the follower. The
+//! follower evaluates the circuit, aborting if any MAC is invalid. The shares of the masks of the
+//! AND gates are computed from authenticated AND triples, which are generated from random
+//! correlated OTs in both directions.
+//!
+//! Unlike [DEAP](crate::protocol::deap), no information about the inputs is leaked to a
+//! malicious party and every circuit is garbled only once.
+//!
+//! Each party provides a [`RandomCOTSender`] whose correlation is its own delta, which must be
+//! kept secret, and a [`RandomCOTReceiver`] for the random COTs of the other party, eg KOS set up
+//! with [`Sender::setup_with_delta`](mpz_ot::kos::Sender::setup_with_delta) or Ferret
+//! bootstrapped from it. Lookup gates are not supported.
+
+mod error;
+mod garble;
+pub mod mock;
+mod share;
+mod triple;
+mod vm;
+
+use std::{
+    collections::HashMap,
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use mpz_circuits::{types::Value, Circuit, Gate};
+use mpz_common::{Context, ThreadId};
+use mpz_core::Block;
+use mpz_ot::{RandomCOTReceiver, RandomCOTSender};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::{
+    config::Role,
+    memory::ValueMemory,
+    value::{ValueId, ValueRef},
+    AssignedValues,
+};
+
+pub use error::AuthenticatedError;
+pub use vm::AuthenticatedThread;
+
+use self::{
+    garble::{evaluate, garble, propagate_masks, GarbledGate},
+    share::{open, random_shares, reveal, verify, Share},
+    triple::{generate_triples, Triple},
+};
+
+use super::common::{check_len, flatten, impl_value_memory, xor};
+
+/// The authenticated garbling protocol.
+#[derive(Debug)]
+pub struct Authenticated {
+    role: Role,
+    delta: Block,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    memory: ValueMemory,
+    /// The authenticated wires of each value.
+    wires: HashMap<ValueId, Vec<Wire>>,
+    /// Preprocessed AND triples of each thread.
+    triples: HashMap<ThreadId, Vec<Triple>>,
+}
+
+/// An authenticated wire.
+#[derive(Debug, Clone, Copy)]
+struct Wire {
+    /// The share of the wire mask.
+    mask: Share,
+    /// The masked value, which is only known to the follower.
+    masked: bool,
+    /// The 0-label for the leader, or the label of the masked value for the follower.
+    label: Block,
+}
+
+impl_value_memory!(Authenticated);
+
+impl Authenticated {
+    /// Creates a new authenticated garbling protocol instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    /// * `delta` - The delta of the COT sender of this party.
+    pub fn new(role: Role, delta: Block) -> Self {
+        Self {
+            role,
+            delta,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    /// Commits the provided input values.
+    ///
+    /// Values which are already committed are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to commit.
+    /// * `cot_send` - The COT sender.
+    /// * `cot_recv` - The COT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn commit<Ctx, COTS, COTR>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        cot_send: &mut COTS,
+        cot_recv: &mut COTR,
+    ) -> Result<(), AuthenticatedError>
+    where
+        Ctx: Context,
+        COTS: RandomCOTSender<Ctx, Block> + Send,
+        COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+    {
+        let AssignedValues {
+            public,
+            private,
+            blind,
+        } = self.state().memory.drain_assigned(values);
+
+        let (public_ids, public_bits) = flatten(public);
+        let (private_ids, private_bits) = flatten(private);
+        let blind_ids: Vec<(ValueId, usize)> =
+            blind.into_iter().map(|(id, typ)| (id, typ.len())).collect();
+
+        let (leader_ids, follower_ids) = match self.role {
+            Role::Leader => (&private_ids, &blind_ids),
+            Role::Follower => (&blind_ids, &private_ids),
+        };
+        let leader_len = leader_ids.iter().map(|(_, len)| len).sum::<usize>();
+        let follower_len = follower_ids.iter().map(|(_, len)| len).sum::<usize>();
+
+        let mut masks = random_shares(
+            ctx,
+            self.role,
+            cot_send,
+            cot_recv,
+            public_bits.len() + leader_len + follower_len,
+        )
+        .await?;
+        let follower_masks = masks.split_off(public_bits.len() + leader_len);
+        let leader_masks = masks.split_off(public_bits.len());
+        let public_masks = masks;
+
+        let delta = self.delta;
+        let (public_wires, leader_wires, follower_wires) = match self.role {
+            Role::Leader => {
+                // Public inputs, the masks are opened to both parties.
+                let lambda = open(ctx, &public_masks, delta).await?;
+                let public_wires = new_wires(&public_masks);
+                let masked = xor(&public_bits, &lambda);
+                ctx.io_mut()
+                    .send(active_labels(&public_wires, &masked, delta))
+                    .await?;
+
+                // Inputs of the leader, the share of the follower is revealed to the leader.
+                let s = verify(ctx, &leader_masks, delta).await?;
+                let leader_wires = new_wires(&leader_masks);
+                let masked: Vec<bool> = private_bits
+                    .iter()
+                    .zip(&leader_masks)
+                    .zip(&s)
+                    .map(|((x, r), s)| x ^ r.bit ^ s)
+                    .collect();
+                let labels = active_labels(&leader_wires, &masked, delta);
+                ctx.io_mut().send((masked, labels)).await?;
+
+                // Inputs of the follower, the share of the leader is revealed to the follower.
+                reveal(ctx, &follower_masks).await?;
+                let follower_wires = new_wires(&follower_masks);
+                let masked: Vec<bool> = ctx.io_mut().expect_next().await?;
+                check_len(follower_wires.len(), masked.len())?;
+                ctx.io_mut()
+                    .send(active_labels(&follower_wires, &masked, delta))
+                    .await?;
+
+                (public_wires, leader_wires, follower_wires)
+            }
+            Role::Follower => {
+                let lambda = open(ctx, &public_masks, delta).await?;
+                let masked = xor(&public_bits, &lambda);
+                let labels: Vec<Block> = ctx.io_mut().expect_next().await?;
+                check_len(public_masks.len(), labels.len())?;
+                let public_wires = active_wires(&public_masks, &masked, &labels);
+
+                reveal(ctx, &leader_masks).await?;
+                let (masked, labels): (Vec<bool>, Vec<Block>) = ctx.io_mut().expect_next().await?;
+                check_len(leader_masks.len(), masked.len())?;
+                check_len(leader_masks.len(), labels.len())?;
+                let leader_wires = active_wires(&leader_masks, &masked, &labels);
+
+                let r = verify(ctx, &follower_masks, delta).await?;
+                let masked: Vec<bool> = private_bits
+                    .iter()
+                    .zip(&follower_masks)
+                    .zip(&r)
+                    .map(|((y, s), r)| y ^ r ^ s.bit)
+                    .collect();
+                ctx.io_mut().send(masked.clone()).await?;
+                let labels: Vec<Block> = ctx.io_mut().expect_next().await?;
+                check_len(follower_masks.len(), labels.len())?;
+                let follower_wires = active_wires(&follower_masks, &masked, &labels);
+
+                (public_wires, leader_wires, follower_wires)
+            }
+        };
+
+        let mut state = self.state();
+        for (ids, wires) in [
+            (&public_ids, public_wires),
+            (leader_ids, leader_wires),
+            (follower_ids, follower_wires),
+        ] {
+            let mut wires = wires.into_iter();
+            for (id, len) in ids {
+                state
+                    .wires
+                    .insert(id.clone(), wires.by_ref().take(*len).collect());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Preprocesses the AND triples for executing the provided circuit.
+    ///
+    /// The triples are independent of the circuit and its inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit to load.
+    /// * `cot_send` - The COT sender.
+    /// * `cot_recv` - The COT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn load<Ctx, COTS, COTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        cot_send: &mut COTS,
+        cot_recv: &mut COTR,
+    ) -> Result<(), AuthenticatedError>
+    where
+        Ctx: Context,
+        COTS: RandomCOTSender<Ctx, Block> + Send,
+        COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+    {
+        let available = self.state().triples(ctx.id()).len();
+        let count = circ.and_count().saturating_sub(available);
+
+        let triples =
+            generate_triples(ctx, self.role, self.delta, cot_send, cot_recv, count).await?;
+
+        self.state().triples(ctx.id()).extend(triples);
+
+        Ok(())
+    }
+
+    /// Executes a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `cot_send` - The COT sender.
+    /// * `cot_recv` - The COT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn execute<Ctx, COTS, COTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        cot_send: &mut COTS,
+        cot_recv: &mut COTR,
+    ) -> Result<(), AuthenticatedError>
+    where
+        Ctx: Context,
+        COTS: RandomCOTSender<Ctx, Block> + Send,
+        COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+    {
+        if circ.lut_count() > 0 {
+            return Err(AuthenticatedError::LookupGate);
+        }
+
+        check_len(circ.inputs().len(), inputs.len())?;
+        check_len(circ.outputs().len(), outputs.len())?;
+        {
+            let state = self.state();
+            for (value, repr) in inputs
+                .iter()
+                .zip(circ.inputs())
+                .chain(outputs.iter().zip(circ.outputs()))
+            {
+                if state.memory.get_value_type(value) != repr.value_type() {
+                    return Err(AuthenticatedError::InvalidValue(value.clone()));
+                }
+            }
+        }
+
+        self.commit(ctx, inputs, cot_send, cot_recv).await?;
+
+        let input_wires = self.get_wires(inputs)?;
+
+        self.load(ctx, circ.clone(), cot_send, cot_recv).await?;
+        let triples: Vec<Triple> = self
+            .state()
+            .triples(ctx.id())
+            .drain(..circ.and_count())
+            .collect();
+
+        let delta = self.delta;
+        let and_masks = random_shares(ctx, self.role, cot_send, cot_recv, circ.and_count()).await?;
+
+        let mut masks = vec![Share::default(); circ.feed_count()];
+        for (wires, repr) in input_wires.iter().zip(circ.inputs()) {
+            for (wire, node) in wires.iter().zip(repr.iter()) {
+                masks[node.id()] = wire.mask;
+            }
+        }
+        propagate_masks(&circ, self.role, delta, &mut masks, &and_masks);
+
+        // Compute the shares of λ_α·λ_β of each AND gate from the triples, by opening
+        // d = λ_α ⊕ x and e = λ_β ⊕ y.
+        let de = open(
+            ctx,
+            &circ
+                .gates()
+                .iter()
+                .filter_map(|gate| match gate {
+                    Gate::And { x, y, .. } => Some((x.id(), y.id())),
+                    _ => None,
+                })
+                .zip(&triples)
+                .flat_map(|((x, y), triple)| [masks[x] ^ triple.x, masks[y] ^ triple.y])
+                .collect::<Vec<_>>(),
+            delta,
+        )
+        .await?;
+        let sigmas: Vec<Share> = triples
+            .iter()
+            .zip(de.chunks_exact(2))
+            .map(|(triple, de)| {
+                let (d, e) = (de[0], de[1]);
+                (triple.z ^ triple.y.and_const(d) ^ triple.x.and_const(e)).add_const(
+                    d & e,
+                    self.role,
+                    delta,
+                )
+            })
+            .collect();
+
+        let wires: Vec<Wire> = match self.role {
+            Role::Leader => {
+                let mut labels = vec![Block::ZERO; circ.feed_count()];
+                for (wires, repr) in input_wires.iter().zip(circ.inputs()) {
+                    for (wire, node) in wires.iter().zip(repr.iter()) {
+                        labels[node.id()] = wire.label;
+                    }
+                }
+
+                let gates = garble(&circ, delta, &masks, &sigmas, &mut labels);
+                ctx.io_mut().send(gates).await?;
+
+                masks
+                    .into_iter()
+                    .zip(labels)
+                    .map(|(mask, label)| Wire {
+                        mask,
+                        masked: false,
+                        label,
+                    })
+                    .collect()
+            }
+            Role::Follower => {
+                let mut active = vec![(false, Block::ZERO); circ.feed_count()];
+                for (wires, repr) in input_wires.iter().zip(circ.inputs()) {
+                    for (wire, node) in wires.iter().zip(repr.iter()) {
+                        active[node.id()] = (wire.masked, wire.label);
+                    }
+                }
+
+                let gates: Vec<GarbledGate> = ctx.io_mut().expect_next().await?;
+                check_len(circ.and_count(), gates.len())?;
+                evaluate(&circ, delta, &masks, &sigmas, &mut active, &gates)?;
+
+                masks
+                    .into_iter()
+                    .zip(active)
+                    .map(|(mask, (masked, label))| Wire {
+                        mask,
+                        masked,
+                        label,
+                    })
+                    .collect()
+            }
+        };
+
+        let mut state = self.state();
+        for (value, repr) in outputs.iter().zip(circ.outputs()) {
+            let nodes: Vec<_> = repr.iter().map(|node| wires[node.id()]).collect();
+            for (id, wires) in value.iter().zip(nodes.chunks(nodes.len() / value.len())) {
+                state.wires.insert(id.clone(), wires.to_vec());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, revealing them to both parties.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to decode.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, AuthenticatedError> {
+        let wires: Vec<Wire> = self.get_wires(values)?.concat();
+        let masks: Vec<Share> = wires.iter().map(|wire| wire.mask).collect();
+
+        let delta = self.delta;
+        let bits: Vec<bool> = match self.role {
+            Role::Leader => {
+                reveal(ctx, &masks).await?;
+                let s = verify(ctx, &masks, delta).await?;
+
+                // The follower proves the masked values with the corresponding labels.
+                let (masked, labels): (Vec<bool>, Vec<Block>) = ctx.io_mut().expect_next().await?;
+                check_len(wires.len(), masked.len())?;
+                check_len(wires.len(), labels.len())?;
+                if active_labels(&wires, &masked, delta) != labels {
+                    return Err(AuthenticatedError::InvalidLabel);
+                }
+
+                masked
+                    .iter()
+                    .zip(&wires)
+                    .zip(s)
+                    .map(|((masked, wire), s)| masked ^ wire.mask.bit ^ s)
+                    .collect()
+            }
+            Role::Follower => {
+                let r = verify(ctx, &masks, delta).await?;
+                reveal(ctx, &masks).await?;
+
+                let (masked, labels): (Vec<bool>, Vec<Block>) =
+                    wires.iter().map(|wire| (wire.masked, wire.label)).unzip();
+                ctx.io_mut().send((masked, labels)).await?;
+
+                wires
+                    .iter()
+                    .zip(r)
+                    .map(|(wire, r)| wire.masked ^ wire.mask.bit ^ r)
+                    .collect()
+            }
+        };
+
+        let state = self.state();
+        let mut bits = bits.into_iter();
+        Ok(values
+            .iter()
+            .map(|value| {
+                let typ = state.memory.get_value_type(value);
+                let bits: Vec<bool> = bits.by_ref().take(typ.len()).collect();
+                Value::from_bits(&typ, &bits).expect("bit count matches the type")
+            })
+            .collect())
+    }
+
+    /// Returns the wires of the provided values.
+    fn get_wires(&self, values: &[ValueRef]) -> Result<Vec<Vec<Wire>>, AuthenticatedError> {
+        let state = self.state();
+        values
+            .iter()
+            .map(|value| {
+                value
+                    .iter()
+                    .map(|id| {
+                        state
+                            .wires
+                            .get(id)
+                            .cloned()
+                            .ok_or_else(|| AuthenticatedError::UnassignedValue(id.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|wires| wires.concat())
+            })
+            .collect()
+    }
+}
+
+impl State {
+    fn triples(&mut self, id: &ThreadId) -> &mut Vec<Triple> {
+        self.triples.entry(id.clone()).or_default()
+    }
+}
+
+/// Returns new wires of the leader with random 0-labels.
+fn new_wires(masks: &[Share]) -> Vec<Wire> {
+    let mut rng = thread_rng();
+    masks
+        .iter()
+        .map(|&mask| Wire {
+            mask,
+            masked: false,
+            label: Block::random(&mut rng),
+        })
+        .collect()
+}
+
+/// Returns the labels of the masked values of the leader's wires.
+fn active_labels(wires: &[Wire], masked: &[bool], delta: Block) -> Vec<Block> {
+    wires
+        .iter()
+        .zip(masked)
+        .map(|(wire, &masked)| wire.label ^ (Block::SELECT_MASK[masked as usize] & delta))
+        .collect()
+}
+
+/// Returns the wires of the follower.
+fn active_wires(masks: &[Share], masked: &[bool], labels: &[Block]) -> Vec<Wire> {
+    masks
+        .iter()
+        .zip(masked)
+        .zip(labels)
+        .map(|((&mask, &masked), &label)| Wire {
+            mask,
+            masked,
+            label,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+    use mpz_common::executor::test_st_executor;
+    use mpz_ot::ideal::cot::ideal_rcot_with_delta;
+    use rand::Rng;
+
+    use crate::{
+        protocol::authenticated::mock::create_mock_authenticated_vm, Decode, Execute, Load, Memory,
+    };
+
+    use super::*;
+
+    fn adder_circ() -> Arc<Circuit> {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_triples() {
+        let mut rng = thread_rng();
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let delta_a = Block::random(&mut rng);
+        let delta_b = Block::random(&mut rng);
+        let (mut leader_cot_send, mut follower_cot_recv) = ideal_rcot_with_delta(delta_a);
+        let (mut follower_cot_send, mut leader_cot_recv) = ideal_rcot_with_delta(delta_b);
+
+        let (leader_triples, follower_triples) = tokio::try_join!(
+            generate_triples(
+                &mut ctx_a,
+                Role::Leader,
+                delta_a,
+                &mut leader_cot_send,
+                &mut leader_cot_recv,
+                10
+            ),
+            generate_triples(
+                &mut ctx_b,
+                Role::Follower,
+                delta_b,
+                &mut follower_cot_send,
+                &mut follower_cot_recv,
+                10
+            )
+        )
+        .unwrap();
+
+        assert!(leader_triples.len() >= 10);
+        assert_eq!(leader_triples.len(), follower_triples.len());
+
+        let check_mac = |share: &Share, key: Block, delta: Block| {
+            assert_eq!(
+                share.mac,
+                key ^ (Block::SELECT_MASK[share.bit as usize] & delta)
+            );
+        };
+
+        for (a, b) in leader_triples.iter().zip(&follower_triples) {
+            for (a, b) in [(a.x, b.x), (a.y, b.y), (a.z, b.z)] {
+                check_mac(&a, b.key, delta_b);
+                check_mac(&b, a.key, delta_a);
+            }
+
+            let x = a.x.bit ^ b.x.bit;
+            let y = a.y.bit ^ b.y.bit;
+            let z = a.z.bit ^ b.z.bit;
+            assert_eq!(z, x & y);
+        }
+    }
+
+    /// Returns authenticated shares of random bits for the leader and the follower.
+    fn deal_shares(count: usize, delta_a: Block, delta_b: Block) -> (Vec<Share>, Vec<Share>) {
+        let mut rng = thread_rng();
+        (0..count)
+            .map(|_| {
+                let (bit_a, bit_b): (bool, bool) = rng.gen();
+                let (key_a, key_b) = (Block::random(&mut rng), Block::random(&mut rng));
+                (
+                    Share {
+                        bit: bit_a,
+                        mac: key_b ^ (Block::SELECT_MASK[bit_a as usize] & delta_b),
+                        key: key_a,
+                    },
+                    Share {
+                        bit: bit_b,
+                        mac: key_a ^ (Block::SELECT_MASK[bit_b as usize] & delta_a),
+                        key: key_b,
+                    },
+                )
+            })
+            .unzip()
+    }
+
+    #[tokio::test]
+    async fn test_tampered_mac() {
+        let mut rng = thread_rng();
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let delta_a = Block::random(&mut rng);
+        let delta_b = Block::random(&mut rng);
+
+        let (leader_shares, mut follower_shares) = deal_shares(8, delta_a, delta_b);
+        follower_shares[0].mac ^= Block::ONE;
+
+        let (leader_bits, follower_bits) = tokio::join!(
+            open(&mut ctx_a, &leader_shares, delta_a),
+            open(&mut ctx_b, &follower_shares, delta_b)
+        );
+
+        assert!(matches!(
+            leader_bits.unwrap_err(),
+            AuthenticatedError::InvalidMac
+        ));
+        assert!(follower_bits.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tampered_triple() {
+        let mut rng = thread_rng();
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let delta_a = Block::random(&mut rng);
+        let delta_b = Block::random(&mut rng);
+        let (mut leader_cot_send, mut follower_cot_recv) = ideal_rcot_with_delta(delta_a);
+        let (mut follower_cot_send, mut leader_cot_recv) = ideal_rcot_with_delta(delta_b);
+
+        // The leader computes the half-authenticated ANDs with a delta which does not match its
+        // COTs, so the triples are incorrect. The context is dropped on abort, which unblocks
+        // the follower.
+        let leader_fut = async move {
+            generate_triples(
+                &mut ctx_a,
+                Role::Leader,
+                delta_a ^ Block::ONE,
+                &mut leader_cot_send,
+                &mut leader_cot_recv,
+                10,
+            )
+            .await
+        };
+        let follower_fut = async move {
+            generate_triples(
+                &mut ctx_b,
+                Role::Follower,
+                delta_b,
+                &mut follower_cot_send,
+                &mut follower_cot_recv,
+                10,
+            )
+            .await
+        };
+
+        let (leader_triples, follower_triples) = tokio::join!(leader_fut, follower_fut);
+
+        assert!(matches!(
+            leader_triples.unwrap_err(),
+            AuthenticatedError::InvalidTriple
+        ));
+        assert!(follower_triples.is_err());
+    }
+
+    #[test]
+    fn test_tampered_gate() {
+        let mut rng = thread_rng();
+        let circ = adder_circ();
+        let delta_a = Block::random(&mut rng);
+        let delta_b = Block::random(&mut rng);
+
+        let (input_a, input_b) = deal_shares(16, delta_a, delta_b);
+        let (and_a, and_b) = deal_shares(circ.and_count(), delta_a, delta_b);
+        let (sigma_a, sigma_b) = deal_shares(circ.and_count(), delta_a, delta_b);
+
+        let mut masks_a = vec![Share::default(); circ.feed_count()];
+        let mut masks_b = masks_a.clone();
+        let input_ids = circ
+            .inputs()
+            .iter()
+            .flat_map(|input| input.iter().map(|node| node.id()));
+        for (id, (a, b)) in input_ids.zip(input_a.into_iter().zip(input_b)) {
+            masks_a[id] = a;
+            masks_b[id] = b;
+        }
+        propagate_masks(&circ, Role::Leader, delta_a, &mut masks_a, &and_a);
+        propagate_masks(&circ, Role::Follower, delta_b, &mut masks_b, &and_b);
+
+        // The masked values of the inputs are zero, so the follower holds the 0-labels.
+        let mut labels: Vec<Block> = (0..circ.feed_count())
+            .map(|_| Block::random(&mut rng))
+            .collect();
+        let wires: Vec<(bool, Block)> = labels.iter().map(|&label| (false, label)).collect();
+        let mut gates = garble(&circ, delta_a, &masks_a, &sigma_a, &mut labels);
+
+        evaluate(
+            &circ,
+            delta_b,
+            &masks_b,
+            &sigma_b,
+            &mut wires.clone(),
+            &gates,
+        )
+        .unwrap();
+
+        for row in gates[0].iter_mut() {
+            row.0 = !row.0;
+        }
+
+        let err = evaluate(
+            &circ,
+            delta_b,
+            &masks_b,
+            &sigma_b,
+            &mut wires.clone(),
+            &gates,
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, AuthenticatedError::InvalidGate(0)));
+    }
+
+    #[tokio::test]
+    async fn test_tampered_label() {
+        let mut rng = thread_rng();
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let delta_a = Block::random(&mut rng);
+        let delta_b = Block::random(&mut rng);
+        let (mut leader_cot_send, mut follower_cot_recv) = ideal_rcot_with_delta(delta_a);
+        let (mut follower_cot_send, mut leader_cot_recv) = ideal_rcot_with_delta(delta_b);
+
+        let leader = Authenticated::new(Role::Leader, delta_a);
+        let follower = Authenticated::new(Role::Follower, delta_b);
+
+        let leader_value = leader.new_public_input::<u8>("a").unwrap();
+        let follower_value = follower.new_public_input::<u8>("a").unwrap();
+        leader.assign(&leader_value, 42u8).unwrap();
+        follower.assign(&follower_value, 42u8).unwrap();
+
+        let leader_values = [leader_value];
+        let follower_values = [follower_value];
+
+        tokio::try_join!(
+            leader.commit(
+                &mut ctx_a,
+                &leader_values,
+                &mut leader_cot_send,
+                &mut leader_cot_recv
+            ),
+            follower.commit(
+                &mut ctx_b,
+                &follower_values,
+                &mut follower_cot_send,
+                &mut follower_cot_recv
+            )
+        )
+        .unwrap();
+
+        // The follower claims the label of a different masked value.
+        for wires in follower.state().wires.values_mut() {
+            wires[0].label ^= delta_a;
+        }
+
+        let (leader_output, follower_output) = tokio::join!(
+            leader.decode(&mut ctx_a, &leader_values),
+            follower.decode(&mut ctx_b, &follower_values)
+        );
+
+        assert!(matches!(
+            leader_output.unwrap_err(),
+            AuthenticatedError::InvalidLabel
+        ));
+        assert!(follower_output.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_authenticated() {
+        let (mut leader, mut follower) = create_mock_authenticated_vm();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut msg = msg.into();
+
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            cipher.encrypt_block(&mut msg);
+
+            msg.into()
+        };
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::from(expected)]);
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_chained() {
+        let (mut leader, mut follower) = create_mock_authenticated_vm();
+        let circ = adder_circ();
+
+        let leader_fut = {
+            let a = leader.new_private_input::<u8>("a").unwrap();
+            let b = leader.new_blind_input::<u8>("b").unwrap();
+            let c = leader.new_public_input::<u8>("c").unwrap();
+            let sum = leader.new_output::<u8>("sum").unwrap();
+            let total = leader.new_output::<u8>("total").unwrap();
+
+            leader.assign(&a, 1u8).unwrap();
+            leader.assign(&c, 200u8).unwrap();
+
+            let circ = circ.clone();
+            async move {
+                leader.commit(&[a.clone(), b.clone()]).await.unwrap();
+                leader
+                    .execute(circ.clone(), &[a, b], &[sum.clone()])
+                    .await
+                    .unwrap();
+                leader
+                    .execute(circ, &[sum.clone(), c], &[total.clone()])
+                    .await
+                    .unwrap();
+
+                leader.decode(&[sum, total]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let a = follower.new_blind_input::<u8>("a").unwrap();
+            let b = follower.new_private_input::<u8>("b").unwrap();
+            let c = follower.new_public_input::<u8>("c").unwrap();
+            let sum = follower.new_output::<u8>("sum").unwrap();
+            let total = follower.new_output::<u8>("total").unwrap();
+
+            follower.assign(&b, 2u8).unwrap();
+            follower.assign(&c, 200u8).unwrap();
+
+            async move {
+                follower.commit(&[a.clone(), b.clone()]).await.unwrap();
+                follower
+                    .execute(circ.clone(), &[a, b], &[sum.clone()])
+                    .await
+                    .unwrap();
+                follower
+                    .execute(circ, &[sum.clone(), c], &[total.clone()])
+                    .await
+                    .unwrap();
+
+                follower.decode(&[sum, total]).await.unwrap()
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::U8(3), Value::U8(203)]);
+    }
+}
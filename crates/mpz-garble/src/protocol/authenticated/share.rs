@@ -0,0 +1,186 @@
+//! Authenticated shares of bits.
+
+use std::ops::BitXor;
+
+use mpz_common::Context;
+use mpz_core::{utils::blake3, Block};
+use mpz_ot::{RandomCOTReceiver, RandomCOTSender};
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::config::Role;
+
+use super::AuthenticatedError;
+
+/// An authenticated share of a bit.
+///
+/// A bit `x = x_A ⊕ x_B` is shared between the leader `A` and the follower `B`. Each party
+/// holds its share `x_i` along with a MAC `M[x_i] = K[x_i] ⊕ x_i·Δ_j` under the delta of the
+/// other party, and the key `K[x_j]` for the share of the other party under its own delta.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct Share {
+    /// The share of the bit.
+    pub(crate) bit: bool,
+    /// The MAC of the share.
+    pub(crate) mac: Block,
+    /// The key for the share of the other party.
+    pub(crate) key: Block,
+}
+
+impl Share {
+    /// Returns the share of `x ⊕ c` for a public constant `c`.
+    ///
+    /// The constant is added to the share of the leader, so the follower adjusts its key.
+    #[inline]
+    pub(crate) fn add_const(self, c: bool, role: Role, delta: Block) -> Self {
+        match role {
+            Role::Leader => Self {
+                bit: self.bit ^ c,
+                ..self
+            },
+            Role::Follower => Self {
+                key: self.key ^ (Block::SELECT_MASK[c as usize] & delta),
+                ..self
+            },
+        }
+    }
+
+    /// Returns the share of `c·x` for a public constant `c`.
+    #[inline]
+    pub(crate) fn and_const(self, c: bool) -> Self {
+        if c {
+            self
+        } else {
+            Self::default()
+        }
+    }
+}
+
+impl BitXor for Share {
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        Self {
+            bit: self.bit ^ rhs.bit,
+            mac: self.mac ^ rhs.mac,
+            key: self.key ^ rhs.key,
+        }
+    }
+}
+
+/// Returns the digest of the MACs of the provided bits.
+fn mac_digest(macs: impl Iterator<Item = Block>) -> [u8; 32] {
+    let bytes: Vec<u8> = macs.flat_map(|mac| mac.to_bytes()).collect();
+    blake3(&bytes)
+}
+
+/// Generates random authenticated shares.
+///
+/// The leader sends its correlated OTs first, so the COTs of both parties are used in the same
+/// order.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `role` - The role of this party.
+/// * `cot_send` - The COT sender, which must use the delta of this party.
+/// * `cot_recv` - The COT receiver.
+/// * `count` - The number of shares to generate.
+pub(crate) async fn random_shares<Ctx, COTS, COTR>(
+    ctx: &mut Ctx,
+    role: Role,
+    cot_send: &mut COTS,
+    cot_recv: &mut COTR,
+    count: usize,
+) -> Result<Vec<Share>, AuthenticatedError>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+{
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let (keys, received) = match role {
+        Role::Leader => {
+            let keys = cot_send.send_random_correlated(ctx, count).await?;
+            let received = cot_recv.receive_random_correlated(ctx, count).await?;
+            (keys, received)
+        }
+        Role::Follower => {
+            let received = cot_recv.receive_random_correlated(ctx, count).await?;
+            let keys = cot_send.send_random_correlated(ctx, count).await?;
+            (keys, received)
+        }
+    };
+
+    Ok(received
+        .choices
+        .into_iter()
+        .zip(received.msgs)
+        .zip(keys.msgs)
+        .map(|((bit, mac), key)| Share { bit, mac, key })
+        .collect())
+}
+
+/// Reveals the bits of the shares to the other party, see [`verify`].
+pub(crate) async fn reveal<Ctx: Context>(
+    ctx: &mut Ctx,
+    shares: &[Share],
+) -> Result<(), AuthenticatedError> {
+    let bits: Vec<bool> = shares.iter().map(|share| share.bit).collect();
+    let digest = mac_digest(shares.iter().map(|share| share.mac));
+
+    ctx.io_mut().send((bits, digest)).await?;
+
+    Ok(())
+}
+
+/// Receives the bits of the shares revealed by the other party, checking them against the keys
+/// of this party.
+///
+/// Returns the bits of the other party.
+pub(crate) async fn verify<Ctx: Context>(
+    ctx: &mut Ctx,
+    shares: &[Share],
+    delta: Block,
+) -> Result<Vec<bool>, AuthenticatedError> {
+    let (bits, digest): (Vec<bool>, [u8; 32]) = ctx.io_mut().expect_next().await?;
+
+    if bits.len() != shares.len() {
+        return Err(AuthenticatedError::InvalidLength {
+            expected: shares.len(),
+            actual: bits.len(),
+        });
+    }
+
+    let expected = mac_digest(
+        shares
+            .iter()
+            .zip(&bits)
+            .map(|(share, &bit)| share.key ^ (Block::SELECT_MASK[bit as usize] & delta)),
+    );
+
+    if digest != expected {
+        return Err(AuthenticatedError::InvalidMac);
+    }
+
+    Ok(bits)
+}
+
+/// Opens the shared bits to both parties.
+pub(crate) async fn open<Ctx: Context>(
+    ctx: &mut Ctx,
+    shares: &[Share],
+    delta: Block,
+) -> Result<Vec<bool>, AuthenticatedError> {
+    reveal(ctx, shares).await?;
+    let bits = verify(ctx, shares, delta).await?;
+
+    Ok(shares
+        .iter()
+        .zip(bits)
+        .map(|(share, bit)| share.bit ^ bit)
+        .collect())
+}
@@ -0,0 +1,284 @@
+//! Authenticated AND triples.
+//!
+//! Triples are generated in two steps, following WRK17. First, leaky triples are generated from
+//! random authenticated shares, using a half-authenticated AND for the cross terms and a
+//! consistency check which aborts on incorrect triples. A malicious party may guess bits of the
+//! first factor of a leaky triple, so the leaky triples are randomly assigned to buckets which
+//! are combined into one triple each.
+
+use mpz_common::Context;
+use mpz_core::{
+    aes::FIXED_KEY_AES,
+    commit::{Decommitment, HashCommit},
+    hash::Hash,
+    utils::blake3,
+    Block,
+};
+use mpz_ot::{RandomCOTReceiver, RandomCOTSender};
+use rand::{seq::SliceRandom, thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::config::Role;
+
+use super::{
+    share::{open, random_shares, Share},
+    AuthenticatedError,
+};
+
+/// The minimum number of triples which are generated at once, see [`bucket_size`].
+const MIN_TRIPLES: usize = 320;
+
+/// An authenticated AND triple, `z = x·y`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Triple {
+    pub(crate) x: Share,
+    pub(crate) y: Share,
+    pub(crate) z: Share,
+}
+
+/// Returns the bucket size for combining leaky triples into `count` triples, with 40 bits of
+/// statistical security.
+fn bucket_size(count: usize) -> usize {
+    match count {
+        count if count >= 280_000 => 3,
+        count if count >= 3_100 => 4,
+        _ => 5,
+    }
+}
+
+/// Returns the tweak for hashing the key of triple `i`, where `sender` is the role of the party
+/// which holds the key.
+#[inline]
+fn tweak(sender: Role, check: bool, i: usize) -> Block {
+    let tag = ((check as u128) << 1) | matches!(sender, Role::Follower) as u128;
+    Block::new((((i as u128) << 2) | tag).to_be_bytes())
+}
+
+/// Returns the role of the other party.
+#[inline]
+fn peer(role: Role) -> Role {
+    match role {
+        Role::Leader => Role::Follower,
+        Role::Follower => Role::Leader,
+    }
+}
+
+/// Generates at least `count` authenticated AND triples.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `role` - The role of this party.
+/// * `delta` - The delta of this party.
+/// * `cot_send` - The COT sender, which must use the delta of this party.
+/// * `cot_recv` - The COT receiver.
+/// * `count` - The number of triples to generate.
+pub(crate) async fn generate_triples<Ctx, COTS, COTR>(
+    ctx: &mut Ctx,
+    role: Role,
+    delta: Block,
+    cot_send: &mut COTS,
+    cot_recv: &mut COTR,
+    count: usize,
+) -> Result<Vec<Triple>, AuthenticatedError>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+{
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    let count = count.max(MIN_TRIPLES);
+    let bucket_size = bucket_size(count);
+
+    let mut leaky =
+        leaky_triples(ctx, role, delta, cot_send, cot_recv, count * bucket_size).await?;
+
+    // The buckets must be chosen after the leaky triples are fixed.
+    let seed = coin_toss(ctx, role).await?;
+    leaky.shuffle(&mut ChaCha12Rng::from_seed(seed));
+
+    let mut triples: Vec<Triple> = leaky.iter().step_by(bucket_size).copied().collect();
+    for j in 1..bucket_size {
+        let others: Vec<Triple> = leaky.iter().skip(j).step_by(bucket_size).copied().collect();
+
+        // (x, y, z) and (x', y', z') are combined into (x ⊕ x', y, z ⊕ z' ⊕ d·x') with
+        // d = y ⊕ y'.
+        let d = open(
+            ctx,
+            &triples
+                .iter()
+                .zip(&others)
+                .map(|(triple, other)| triple.y ^ other.y)
+                .collect::<Vec<_>>(),
+            delta,
+        )
+        .await?;
+
+        for ((triple, other), d) in triples.iter_mut().zip(others).zip(d) {
+            triple.x = triple.x ^ other.x;
+            triple.z = triple.z ^ other.z ^ other.x.and_const(d);
+        }
+    }
+
+    Ok(triples)
+}
+
+/// Generates leaky authenticated AND triples.
+async fn leaky_triples<Ctx, COTS, COTR>(
+    ctx: &mut Ctx,
+    role: Role,
+    delta: Block,
+    cot_send: &mut COTS,
+    cot_recv: &mut COTR,
+    count: usize,
+) -> Result<Vec<Triple>, AuthenticatedError>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send,
+{
+    let shares = random_shares(ctx, role, cot_send, cot_recv, 3 * count).await?;
+    let (x, rest) = shares.split_at(count);
+    let (y, r) = rest.split_at(count);
+
+    let hash = |sender: Role, check: bool, i: usize, block: Block| {
+        FIXED_KEY_AES.tccr(tweak(sender, check, i), block)
+    };
+
+    // Half-authenticated AND of the share of x of the other party and the share of y of this
+    // party: the other party learns t ⊕ x_j·y_i.
+    let t: Vec<bool> = {
+        let mut rng = thread_rng();
+        (0..count).map(|_| rng.gen()).collect()
+    };
+    let half: Vec<[bool; 2]> = (0..count)
+        .map(|i| {
+            let h0 = hash(role, false, i, x[i].key).lsb() == 1;
+            let h1 = hash(role, false, i, x[i].key ^ delta).lsb() == 1;
+            [h0 ^ t[i], h1 ^ t[i] ^ y[i].bit]
+        })
+        .collect();
+
+    // Φ = y_i·Δ_i ⊕ K[y_j] ⊕ M[y_i], where Φ_A ⊕ Φ_B = y·(Δ_A ⊕ Δ_B).
+    let phi: Vec<Block> = y
+        .iter()
+        .map(|y| (Block::SELECT_MASK[y.bit as usize] & delta) ^ y.key ^ y.mac)
+        .collect();
+    let u: Vec<Block> = (0..count)
+        .map(|i| hash(role, true, i, x[i].key) ^ hash(role, true, i, x[i].key ^ delta) ^ phi[i])
+        .collect();
+
+    ctx.io_mut().send((half, u)).await?;
+    let (peer_half, peer_u): (Vec<[bool; 2]>, Vec<Block>) = ctx.io_mut().expect_next().await?;
+
+    if peer_half.len() != count || peer_u.len() != count {
+        return Err(AuthenticatedError::InvalidLength {
+            expected: count,
+            actual: peer_half.len().min(peer_u.len()),
+        });
+    }
+
+    // The shares of z = x·y, where v_A ⊕ v_B = x_A·y_B ⊕ x_B·y_A.
+    let z: Vec<bool> = (0..count)
+        .map(|i| {
+            let h = hash(peer(role), false, i, x[i].mac).lsb() == 1;
+            let v = t[i] ^ peer_half[i][x[i].bit as usize] ^ h;
+            v ^ (x[i].bit & y[i].bit)
+        })
+        .collect();
+
+    // Authenticate z using the random shares r.
+    let d: Vec<bool> = z.iter().zip(r).map(|(z, r)| z ^ r.bit).collect();
+    ctx.io_mut().send(d).await?;
+    let peer_d: Vec<bool> = ctx.io_mut().expect_next().await?;
+
+    if peer_d.len() != count {
+        return Err(AuthenticatedError::InvalidLength {
+            expected: count,
+            actual: peer_d.len(),
+        });
+    }
+
+    let z: Vec<Share> = z
+        .into_iter()
+        .zip(r)
+        .zip(peer_d)
+        .map(|((z, r), d)| Share {
+            bit: z,
+            mac: r.mac,
+            key: r.key ^ (Block::SELECT_MASK[d as usize] & delta),
+        })
+        .collect();
+
+    // Check that z = x·y, in which case the check values of both parties are equal.
+    let mut check = Vec::with_capacity(count * 16);
+    for i in 0..count {
+        let s = hash(role, true, i, x[i].key);
+        let t = hash(peer(role), true, i, x[i].mac)
+            ^ (Block::SELECT_MASK[x[i].bit as usize] & peer_u[i]);
+        let w = (Block::SELECT_MASK[x[i].bit as usize] & phi[i]) ^ t ^ s;
+        let h = w ^ (Block::SELECT_MASK[z[i].bit as usize] & delta) ^ z[i].mac ^ z[i].key;
+        check.extend_from_slice(&h.to_bytes());
+    }
+    let check = blake3(&check);
+
+    match role {
+        Role::Leader => {
+            let (decommitment, commitment) = check.hash_commit();
+            ctx.io_mut().send(commitment).await?;
+            let peer_check: [u8; 32] = ctx.io_mut().expect_next().await?;
+            if peer_check != check {
+                return Err(AuthenticatedError::InvalidTriple);
+            }
+            ctx.io_mut().send(decommitment).await?;
+        }
+        Role::Follower => {
+            let commitment: Hash = ctx.io_mut().expect_next().await?;
+            ctx.io_mut().send(check).await?;
+            let decommitment: Decommitment<[u8; 32]> = ctx.io_mut().expect_next().await?;
+            decommitment.verify(&commitment)?;
+            if decommitment.data() != &check {
+                return Err(AuthenticatedError::InvalidTriple);
+            }
+        }
+    }
+
+    Ok((0..count)
+        .map(|i| Triple {
+            x: x[i],
+            y: y[i],
+            z: z[i],
+        })
+        .collect())
+}
+
+/// Returns a random seed which neither party can bias.
+async fn coin_toss<Ctx: Context>(
+    ctx: &mut Ctx,
+    role: Role,
+) -> Result<[u8; 32], AuthenticatedError> {
+    let seed: [u8; 32] = thread_rng().gen();
+
+    let peer_seed = match role {
+        Role::Leader => {
+            let (decommitment, commitment) = seed.hash_commit();
+            ctx.io_mut().send(commitment).await?;
+            let peer_seed: [u8; 32] = ctx.io_mut().expect_next().await?;
+            ctx.io_mut().send(decommitment).await?;
+            peer_seed
+        }
+        Role::Follower => {
+            let commitment: Hash = ctx.io_mut().expect_next().await?;
+            ctx.io_mut().send(seed).await?;
+            let decommitment: Decommitment<[u8; 32]> = ctx.io_mut().expect_next().await?;
+            decommitment.verify(&commitment)?;
+            decommitment.into_inner()
+        }
+    };
+
+    Ok(std::array::from_fn(|i| seed[i] ^ peer_seed[i]))
+}
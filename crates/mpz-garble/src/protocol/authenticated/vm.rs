@@ -0,0 +1,175 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::TryFutureExt;
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot::{RandomCOTReceiver, RandomCOTSender};
+
+use crate::{
+    config::{Role, Visibility},
+    value::ValueRef,
+    Decode, DecodeError, Execute, ExecutionError, Load, LoadError, Memory, MemoryError, Thread,
+};
+
+use super::Authenticated;
+
+/// An authenticated garbling thread.
+#[derive(Debug)]
+pub struct AuthenticatedThread<Ctx, COTS, COTR> {
+    /// The thread context.
+    ctx: Ctx,
+    /// COT sender.
+    cot_send: COTS,
+    /// COT receiver.
+    cot_recv: COTR,
+    protocol: Arc<Authenticated>,
+}
+
+impl<Ctx, COTS, COTR> AuthenticatedThread<Ctx, COTS, COTR> {
+    /// Creates a new authenticated garbling instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    /// * `delta` - The delta of the COT sender.
+    /// * `ctx` - The thread context.
+    /// * `cot_send` - The COT sender.
+    /// * `cot_recv` - The COT receiver.
+    pub fn new(role: Role, delta: Block, ctx: Ctx, cot_send: COTS, cot_recv: COTR) -> Self {
+        Self {
+            ctx,
+            cot_send,
+            cot_recv,
+            protocol: Arc::new(Authenticated::new(role, delta)),
+        }
+    }
+
+    /// Creates a new thread of the same instance.
+    ///
+    /// The COT sender of the thread must use the same delta.
+    pub fn new_thread(&self, ctx: Ctx, cot_send: COTS, cot_recv: COTR) -> Self {
+        Self {
+            ctx,
+            cot_send,
+            cot_recv,
+            protocol: self.protocol.clone(),
+        }
+    }
+}
+
+impl<Ctx, COTS, COTR> Thread for AuthenticatedThread<Ctx, COTS, COTR> {}
+
+impl<Ctx, COTS, COTR> Memory for AuthenticatedThread<Ctx, COTS, COTR> {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_input_with_type(id, typ, visibility)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_output_with_type(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign(value_ref, value)
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign_by_id(id, value)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.protocol.get_value(id)
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.protocol.get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        self.protocol.get_value_type_by_id(id)
+    }
+}
+
+#[async_trait]
+impl<Ctx, COTS, COTR> Load for AuthenticatedThread<Ctx, COTS, COTR>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send + Sync,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send + Sync,
+{
+    async fn load(
+        &mut self,
+        circ: Arc<Circuit>,
+        _inputs: &[ValueRef],
+        _outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        self.protocol
+            .load(&mut self.ctx, circ, &mut self.cot_send, &mut self.cot_recv)
+            .map_err(LoadError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, COTS, COTR> Execute for AuthenticatedThread<Ctx, COTS, COTR>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send + Sync,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send + Sync,
+{
+    async fn commit(&mut self, values: &[ValueRef]) -> Result<(), ExecutionError> {
+        self.protocol
+            .commit(
+                &mut self.ctx,
+                values,
+                &mut self.cot_send,
+                &mut self.cot_recv,
+            )
+            .map_err(ExecutionError::from)
+            .await
+    }
+
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol
+            .execute(
+                &mut self.ctx,
+                circ,
+                inputs,
+                outputs,
+                &mut self.cot_send,
+                &mut self.cot_recv,
+            )
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, COTS, COTR> Decode for AuthenticatedThread<Ctx, COTS, COTR>
+where
+    Ctx: Context,
+    COTS: RandomCOTSender<Ctx, Block> + Send + Sync,
+    COTR: RandomCOTReceiver<Ctx, bool, Block> + Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
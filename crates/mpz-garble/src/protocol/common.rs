@@ -0,0 +1,106 @@
+//! Helpers shared by the protocol implementations.
+
+use itybity::IntoBits;
+use mpz_circuits::types::Value;
+
+use crate::value::ValueId;
+
+/// Implements [`Memory`](crate::Memory) for a protocol which keeps its values in a
+/// [`ValueMemory`](crate::memory::ValueMemory), accessed via `self.state().memory`.
+macro_rules! impl_value_memory {
+    ($protocol:ty) => {
+        impl $crate::Memory for $protocol {
+            fn new_input_with_type(
+                &self,
+                id: &str,
+                typ: ::mpz_circuits::types::ValueType,
+                visibility: $crate::config::Visibility,
+            ) -> Result<$crate::value::ValueRef, $crate::MemoryError> {
+                self.state().memory.new_input(id, typ, visibility)
+            }
+
+            fn new_output_with_type(
+                &self,
+                id: &str,
+                typ: ::mpz_circuits::types::ValueType,
+            ) -> Result<$crate::value::ValueRef, $crate::MemoryError> {
+                self.state().memory.new_output(id, typ)
+            }
+
+            fn assign(
+                &self,
+                value_ref: &$crate::value::ValueRef,
+                value: impl Into<::mpz_circuits::types::Value>,
+            ) -> Result<(), $crate::MemoryError> {
+                self.state().memory.assign(value_ref, value.into())
+            }
+
+            fn assign_by_id(
+                &self,
+                id: &str,
+                value: impl Into<::mpz_circuits::types::Value>,
+            ) -> Result<(), $crate::MemoryError> {
+                let mut state = self.state();
+                let value_ref = state
+                    .memory
+                    .get_ref_by_id(id)
+                    .ok_or_else(|| $crate::MemoryError::Undefined(id.to_string()))?
+                    .clone();
+                state.memory.assign(&value_ref, value.into())
+            }
+
+            fn get_value(&self, id: &str) -> Option<$crate::value::ValueRef> {
+                self.state().memory.get_ref_by_id(id).cloned()
+            }
+
+            fn get_value_type(
+                &self,
+                value_ref: &$crate::value::ValueRef,
+            ) -> ::mpz_circuits::types::ValueType {
+                self.state().memory.get_value_type(value_ref)
+            }
+
+            fn get_value_type_by_id(&self, id: &str) -> Option<::mpz_circuits::types::ValueType> {
+                let state = self.state();
+                let value_ref = state.memory.get_ref_by_id(id)?;
+                Some(state.memory.get_value_type(value_ref))
+            }
+        }
+    };
+}
+
+pub(crate) use impl_value_memory;
+
+/// A message which does not have the expected length.
+#[derive(Debug)]
+pub(crate) struct InvalidLength {
+    pub(crate) expected: usize,
+    pub(crate) actual: usize,
+}
+
+/// Returns the IDs and lengths of the values, along with their bits.
+pub(crate) fn flatten(values: Vec<(ValueId, Value)>) -> (Vec<(ValueId, usize)>, Vec<bool>) {
+    let mut bits = Vec::new();
+    let ids = values
+        .into_iter()
+        .map(|(id, value)| {
+            let len = value.value_type().len();
+            bits.extend(value.into_iter_lsb0());
+            (id, len)
+        })
+        .collect();
+
+    (ids, bits)
+}
+
+pub(crate) fn xor(a: &[bool], b: &[bool]) -> Vec<bool> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+pub(crate) fn check_len(expected: usize, actual: usize) -> Result<(), InvalidLength> {
+    if expected != actual {
+        return Err(InvalidLength { expected, actual });
+    }
+
+    Ok(())
+}
@@ -378,7 +378,11 @@ impl<Ctx, OTS, OTR> PeerEncodings for DEAPThread<Ctx, OTS, OTR> {
 mod tests {
     use super::*;
 
-    use mpz_circuits::circuits::AES128;
+    use mpz_circuits::{
+        circuits::AES128,
+        ops::{Compare, WrappingSub},
+        CircuitBuilder,
+    };
 
     use crate::protocol::deap::mock::create_mock_deap_vm;
 
@@ -442,6 +446,76 @@ mod tests {
         follower_result.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_vm_signed() {
+        let (mut leader_vm, mut follower_vm) = create_mock_deap_vm();
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<i32>();
+        let b = builder.add_input::<i32>();
+        builder.add_output(a.wrapping_sub(b));
+        builder.add_output(a.lt(b));
+        let circ = Arc::new(builder.build().unwrap());
+
+        let a = -42i32;
+        let b = 69i32;
+
+        let leader_fut = {
+            let a_ref = leader_vm.new_private_input::<i32>("a").unwrap();
+            let b_ref = leader_vm.new_blind_input::<i32>("b").unwrap();
+            let diff_ref = leader_vm.new_output::<i32>("diff").unwrap();
+            let lt_ref = leader_vm.new_output::<bool>("lt").unwrap();
+
+            leader_vm.assign(&a_ref, a).unwrap();
+
+            async {
+                leader_vm
+                    .execute(
+                        circ.clone(),
+                        &[a_ref, b_ref],
+                        &[diff_ref.clone(), lt_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader_vm.decode(&[diff_ref, lt_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let a_ref = follower_vm.new_blind_input::<i32>("a").unwrap();
+            let b_ref = follower_vm.new_private_input::<i32>("b").unwrap();
+            let diff_ref = follower_vm.new_output::<i32>("diff").unwrap();
+            let lt_ref = follower_vm.new_output::<bool>("lt").unwrap();
+
+            follower_vm.assign(&b_ref, b).unwrap();
+
+            async {
+                follower_vm
+                    .execute(
+                        circ.clone(),
+                        &[a_ref, b_ref],
+                        &[diff_ref.clone(), lt_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower_vm.decode(&[diff_ref, lt_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_result, vec![Value::I32(a - b), Value::Bit(true)]);
+        assert_eq!(leader_result, follower_result);
+
+        let (leader_result, follower_result) =
+            futures::join!(leader_vm.finalize(), follower_vm.finalize());
+
+        leader_result.unwrap();
+        follower_result.unwrap();
+    }
+
     #[tokio::test]
     async fn test_peer_encodings() {
         let (mut leader_vm, mut follower_vm) = create_mock_deap_vm();
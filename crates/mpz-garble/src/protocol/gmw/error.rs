@@ -0,0 +1,58 @@
+use crate::{
+    protocol::common::InvalidLength,
+    value::{ValueId, ValueRef},
+    DecodeError, ExecutionError, LoadError,
+};
+
+/// Errors that can occur during the GMW protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum GmwError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    OTError(#[from] mpz_ot::OTError),
+    #[error(transparent)]
+    TypeError(#[from] mpz_circuits::types::TypeError),
+    #[error("value is not assigned: {0:?}")]
+    UnassignedValue(ValueId),
+    #[error("value does not match the circuit: {0:?}")]
+    InvalidValue(ValueRef),
+    #[error("lookup gates are not supported")]
+    LookupGate,
+    #[error("unexpected message length: expected {expected}, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl From<InvalidLength> for GmwError {
+    fn from(InvalidLength { expected, actual }: InvalidLength) -> Self {
+        GmwError::InvalidLength { expected, actual }
+    }
+}
+
+impl From<GmwError> for LoadError {
+    fn from(err: GmwError) -> Self {
+        match err {
+            GmwError::IOError(err) => LoadError::IOError(err),
+            err => LoadError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<GmwError> for ExecutionError {
+    fn from(err: GmwError) -> Self {
+        match err {
+            GmwError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<GmwError> for DecodeError {
+    fn from(err: GmwError) -> Self {
+        match err {
+            GmwError::IOError(err) => DecodeError::IOError(err),
+            err => DecodeError::ProtocolError(Box::new(err)),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Mocked GMW VMs for testing
+
+use mpz_common::executor::{test_st_executor, STExecutor};
+use mpz_ot::ideal::rot::{ideal_rot, IdealROTReceiver, IdealROTSender};
+use serio::channel::MemoryDuplex;
+
+use crate::{config::Role, protocol::gmw::vm::GmwThread};
+
+type Ctx = STExecutor<MemoryDuplex>;
+
+/// Mock GMW leader.
+pub type MockLeader = GmwThread<Ctx, IdealROTSender, IdealROTReceiver>;
+/// Mock GMW follower.
+pub type MockFollower = GmwThread<Ctx, IdealROTSender, IdealROTReceiver>;
+
+/// Create a pair of mocked GMW VMs
+pub fn create_mock_gmw_vm() -> (MockLeader, MockFollower) {
+    let (leader_ctx, follower_ctx) = test_st_executor(128);
+    let (leader_ot_send, follower_ot_recv) = ideal_rot();
+    let (follower_ot_send, leader_ot_recv) = ideal_rot();
+
+    let leader = GmwThread::new(Role::Leader, leader_ctx, leader_ot_send, leader_ot_recv);
+    let follower = GmwThread::new(
+        Role::Follower,
+        follower_ctx,
+        follower_ot_send,
+        follower_ot_recv,
+    );
+
+    (leader, follower)
+}
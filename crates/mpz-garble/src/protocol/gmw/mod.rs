@@ -0,0 +1,627 @@
+//! An implementation of the GMW protocol, secure against semi-honest adversaries.
+//!
+//! Both parties hold XOR shares of every value. XOR and INV gates are evaluated locally, while
+//! AND gates are evaluated using AND triples which are generated from random OTs in both
+//! directions. The AND gates of each layer of a circuit are evaluated in a single round, see
+//! [`Circuit::and_layers`].
+//!
+//! Unlike garbled circuits, the outputs of a circuit remain secret shared and can be used as
+//! inputs to further circuits without any additional communication. Lookup gates are not
+//! supported.
+
+mod error;
+pub mod mock;
+mod triple;
+mod vm;
+
+use std::{
+    collections::HashMap,
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use mpz_circuits::{types::Value, Circuit, Gate};
+use mpz_common::{Context, ThreadId};
+use mpz_ot::{RandomOTReceiver, RandomOTSender};
+use rand::{thread_rng, Rng};
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::{
+    config::Role,
+    memory::ValueMemory,
+    value::{ValueId, ValueRef},
+    AssignedValues,
+};
+
+pub use error::GmwError;
+pub use vm::GmwThread;
+
+use self::triple::{generate_triples, Triple};
+
+use super::common::{check_len, flatten, impl_value_memory, xor};
+
+/// The GMW protocol.
+#[derive(Debug)]
+pub struct Gmw {
+    role: Role,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    memory: ValueMemory,
+    /// The shares of each value.
+    shares: HashMap<ValueId, Vec<bool>>,
+    /// Preprocessed AND triples of each thread.
+    triples: HashMap<ThreadId, Vec<Triple>>,
+}
+
+impl_value_memory!(Gmw);
+
+impl Gmw {
+    /// Creates a new GMW protocol instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    /// Commits the provided input values.
+    ///
+    /// Values which are already committed are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to commit.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn commit<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GmwError> {
+        let AssignedValues {
+            public,
+            private,
+            blind,
+        } = self.state().memory.drain_assigned(values);
+
+        let (public_ids, public_bits) = flatten(public);
+        let (private_ids, private_bits) = flatten(private);
+        let blind_ids: Vec<(ValueId, usize)> =
+            blind.into_iter().map(|(id, typ)| (id, typ.len())).collect();
+
+        // The private values are shared by sending a random mask to the other party.
+        let masks: Vec<bool> = {
+            let mut rng = thread_rng();
+            (0..private_bits.len()).map(|_| rng.gen()).collect()
+        };
+
+        ctx.io_mut().send(masks.clone()).await?;
+        let peer_masks: Vec<bool> = ctx.io_mut().expect_next().await?;
+        check_len(blind_ids.iter().map(|(_, len)| len).sum(), peer_masks.len())?;
+
+        // Public values are shared by the leader.
+        let public_shares = match self.role {
+            Role::Leader => public_bits,
+            Role::Follower => vec![false; public_bits.len()],
+        };
+        let private_shares: Vec<bool> = private_bits
+            .iter()
+            .zip(&masks)
+            .map(|(x, mask)| x ^ mask)
+            .collect();
+
+        let mut state = self.state();
+        for (ids, shares) in [
+            (public_ids, public_shares),
+            (private_ids, private_shares),
+            (blind_ids, peer_masks),
+        ] {
+            let mut shares = shares.into_iter();
+            for (id, len) in ids {
+                state.shares.insert(id, shares.by_ref().take(len).collect());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Preprocesses the AND triples for executing the provided circuit.
+    ///
+    /// The triples are independent of the circuit and its inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit to load.
+    /// * `ot_send` - The random OT sender.
+    /// * `ot_recv` - The random OT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn load<Ctx, OTS, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        ot_send: &mut OTS,
+        ot_recv: &mut OTR,
+    ) -> Result<(), GmwError>
+    where
+        Ctx: Context,
+        OTS: RandomOTSender<Ctx, [bool; 2]> + Send,
+        OTR: RandomOTReceiver<Ctx, bool, bool> + Send,
+    {
+        let available = self.state().triples(ctx.id()).len();
+        let count = circ.and_count().saturating_sub(available);
+
+        let triples = generate_triples(ctx, self.role, ot_send, ot_recv, count).await?;
+
+        self.state().triples(ctx.id()).extend(triples);
+
+        Ok(())
+    }
+
+    /// Executes a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `ot_send` - The random OT sender.
+    /// * `ot_recv` - The random OT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn execute<Ctx, OTS, OTR>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot_send: &mut OTS,
+        ot_recv: &mut OTR,
+    ) -> Result<(), GmwError>
+    where
+        Ctx: Context,
+        OTS: RandomOTSender<Ctx, [bool; 2]> + Send,
+        OTR: RandomOTReceiver<Ctx, bool, bool> + Send,
+    {
+        if circ.lut_count() > 0 {
+            return Err(GmwError::LookupGate);
+        }
+
+        check_len(circ.inputs().len(), inputs.len())?;
+        check_len(circ.outputs().len(), outputs.len())?;
+        {
+            let state = self.state();
+            for (value, repr) in inputs
+                .iter()
+                .zip(circ.inputs())
+                .chain(outputs.iter().zip(circ.outputs()))
+            {
+                if state.memory.get_value_type(value) != repr.value_type() {
+                    return Err(GmwError::InvalidValue(value.clone()));
+                }
+            }
+        }
+
+        self.commit(ctx, inputs).await?;
+
+        let input_shares = self.get_shares(inputs)?;
+
+        self.load(ctx, circ.clone(), ot_send, ot_recv).await?;
+        let mut triples = self
+            .state()
+            .triples(ctx.id())
+            .drain(..circ.and_count())
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let mut shares = vec![false; circ.feed_count()];
+        for (bits, repr) in input_shares.iter().zip(circ.inputs()) {
+            for (bit, node) in bits.iter().zip(repr.iter()) {
+                shares[node.id()] = *bit;
+            }
+        }
+
+        let gates = circ.gates();
+        for layer in circ.and_layers() {
+            // The AND gates of a layer only depend on previous layers, so they are evaluated
+            // together by opening d = x ⊕ a and e = y ⊕ b.
+            let ands: Vec<((usize, usize, usize), Triple)> = layer
+                .gates()
+                .iter()
+                .filter_map(|&i| match &gates[i] {
+                    Gate::And { x, y, z } => Some((x.id(), y.id(), z.id())),
+                    _ => None,
+                })
+                .zip(triples.by_ref())
+                .collect();
+
+            if !ands.is_empty() {
+                let de = open(
+                    ctx,
+                    &ands
+                        .iter()
+                        .flat_map(|((x, y, _), triple)| {
+                            [shares[*x] ^ triple.a, shares[*y] ^ triple.b]
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .await?;
+
+                for (((_, _, z), triple), de) in ands.iter().zip(de.chunks_exact(2)) {
+                    let (d, e) = (de[0], de[1]);
+                    shares[*z] = triple.c
+                        ^ (d & triple.b)
+                        ^ (e & triple.a)
+                        ^ (matches!(self.role, Role::Leader) & d & e);
+                }
+            }
+
+            for &i in layer.gates() {
+                match &gates[i] {
+                    Gate::Xor { x, y, z } => shares[z.id()] = shares[x.id()] ^ shares[y.id()],
+                    Gate::Inv { x, z } => {
+                        shares[z.id()] = shares[x.id()] ^ matches!(self.role, Role::Leader)
+                    }
+                    Gate::And { .. } => {}
+                    Gate::Lut(_) => unreachable!("lookup gates are rejected"),
+                }
+            }
+        }
+
+        let mut state = self.state();
+        for (value, repr) in outputs.iter().zip(circ.outputs()) {
+            let bits: Vec<bool> = repr.iter().map(|node| shares[node.id()]).collect();
+            for (id, bits) in value.iter().zip(bits.chunks(bits.len() / value.len())) {
+                state.shares.insert(id.clone(), bits.to_vec());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, revealing them to both parties.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to decode.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, GmwError> {
+        let shares = self.get_shares(values)?.concat();
+        let bits = open(ctx, &shares).await?;
+
+        Ok(self.to_values(values, bits))
+    }
+
+    /// Decodes the provided values, revealing them only to this party.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to decode.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_private<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, GmwError> {
+        let shares = self.get_shares(values)?.concat();
+        let peer_shares: Vec<bool> = ctx.io_mut().expect_next().await?;
+        check_len(shares.len(), peer_shares.len())?;
+
+        Ok(self.to_values(values, xor(&shares, &peer_shares)))
+    }
+
+    /// Decodes the provided values, revealing them only to the other party.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `values` - The values to decode.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_blind<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), GmwError> {
+        let shares = self.get_shares(values)?.concat();
+        ctx.io_mut().send(shares).await?;
+
+        Ok(())
+    }
+
+    /// Returns the XOR shares of the provided values held by this party.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - The values to decode.
+    pub fn decode_shared(&self, values: &[ValueRef]) -> Result<Vec<Value>, GmwError> {
+        let shares = self.get_shares(values)?.concat();
+
+        Ok(self.to_values(values, shares))
+    }
+
+    /// Returns the shares of the provided values.
+    fn get_shares(&self, values: &[ValueRef]) -> Result<Vec<Vec<bool>>, GmwError> {
+        let state = self.state();
+        values
+            .iter()
+            .map(|value| {
+                value
+                    .iter()
+                    .map(|id| {
+                        state
+                            .shares
+                            .get(id)
+                            .cloned()
+                            .ok_or_else(|| GmwError::UnassignedValue(id.clone()))
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(|shares| shares.concat())
+            })
+            .collect()
+    }
+
+    /// Converts the concatenated bits of the provided values into values.
+    fn to_values(&self, values: &[ValueRef], bits: Vec<bool>) -> Vec<Value> {
+        let state = self.state();
+        let mut bits = bits.into_iter();
+        values
+            .iter()
+            .map(|value| {
+                let typ = state.memory.get_value_type(value);
+                let bits: Vec<bool> = bits.by_ref().take(typ.len()).collect();
+                Value::from_bits(&typ, &bits).expect("bit count matches the type")
+            })
+            .collect()
+    }
+}
+
+impl State {
+    fn triples(&mut self, id: &ThreadId) -> &mut Vec<Triple> {
+        self.triples.entry(id.clone()).or_default()
+    }
+}
+
+/// Opens the provided shares to both parties.
+async fn open<Ctx: Context>(ctx: &mut Ctx, shares: &[bool]) -> Result<Vec<bool>, GmwError> {
+    ctx.io_mut().send(shares.to_vec()).await?;
+    let peer_shares: Vec<bool> = ctx.io_mut().expect_next().await?;
+    check_len(shares.len(), peer_shares.len())?;
+
+    Ok(xor(shares, &peer_shares))
+}
+
+#[cfg(test)]
+mod tests {
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+    use mpz_common::executor::test_st_executor;
+    use mpz_ot::ideal::rot::ideal_rot;
+
+    use crate::{
+        protocol::gmw::mock::create_mock_gmw_vm, Decode, DecodePrivate, Execute, Load, Memory,
+    };
+
+    use super::*;
+
+    fn adder_circ() -> Arc<Circuit> {
+        let builder = CircuitBuilder::new();
+
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+
+        let c = a.wrapping_add(b);
+
+        builder.add_output(c);
+
+        Arc::new(builder.build().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_triples() {
+        let (mut ctx_a, mut ctx_b) = test_st_executor(8);
+        let (mut leader_ot_send, mut follower_ot_recv) = ideal_rot();
+        let (mut follower_ot_send, mut leader_ot_recv) = ideal_rot();
+
+        let (leader_triples, follower_triples) = tokio::try_join!(
+            generate_triples(
+                &mut ctx_a,
+                Role::Leader,
+                &mut leader_ot_send,
+                &mut leader_ot_recv,
+                100
+            ),
+            generate_triples(
+                &mut ctx_b,
+                Role::Follower,
+                &mut follower_ot_send,
+                &mut follower_ot_recv,
+                100
+            )
+        )
+        .unwrap();
+
+        assert_eq!(leader_triples.len(), 100);
+        assert_eq!(follower_triples.len(), 100);
+
+        for (a, b) in leader_triples.iter().zip(&follower_triples) {
+            assert_eq!(a.c ^ b.c, (a.a ^ b.a) & (a.b ^ b.b));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gmw() {
+        let (mut leader, mut follower) = create_mock_gmw_vm();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader.assign(&key_ref, key).unwrap();
+
+            async move {
+                leader
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower.assign(&msg_ref, msg).unwrap();
+
+            async move {
+                follower
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_output, follower_output) = tokio::join!(leader_fut, follower_fut);
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut msg = msg.into();
+
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            cipher.encrypt_block(&mut msg);
+
+            msg.into()
+        };
+
+        assert_eq!(leader_output, follower_output);
+        assert_eq!(leader_output, vec![Value::from(expected)]);
+    }
+
+    #[tokio::test]
+    async fn test_gmw_chained() {
+        let (mut leader, mut follower) = create_mock_gmw_vm();
+        let circ = adder_circ();
+
+        let leader_fut = {
+            let a = leader.new_private_input::<u8>("a").unwrap();
+            let b = leader.new_blind_input::<u8>("b").unwrap();
+            let c = leader.new_public_input::<u8>("c").unwrap();
+            let sum = leader.new_output::<u8>("sum").unwrap();
+            let total = leader.new_output::<u8>("total").unwrap();
+
+            leader.assign(&a, 1u8).unwrap();
+            leader.assign(&c, 200u8).unwrap();
+
+            let circ = circ.clone();
+            async move {
+                leader
+                    .execute(circ.clone(), &[a, b], &[sum.clone()])
+                    .await
+                    .unwrap();
+                leader
+                    .execute(circ, &[sum.clone(), c], &[total.clone()])
+                    .await
+                    .unwrap();
+
+                let shares = leader.decode_shared(&[total.clone()]).await.unwrap();
+                let sum = leader.decode_private(&[sum]).await.unwrap();
+                leader.decode_blind(&[total]).await.unwrap();
+
+                (sum, shares)
+            }
+        };
+
+        let follower_fut = {
+            let a = follower.new_blind_input::<u8>("a").unwrap();
+            let b = follower.new_private_input::<u8>("b").unwrap();
+            let c = follower.new_public_input::<u8>("c").unwrap();
+            let sum = follower.new_output::<u8>("sum").unwrap();
+            let total = follower.new_output::<u8>("total").unwrap();
+
+            follower.assign(&b, 2u8).unwrap();
+            follower.assign(&c, 200u8).unwrap();
+
+            async move {
+                follower
+                    .execute(circ.clone(), &[a, b], &[sum.clone()])
+                    .await
+                    .unwrap();
+                follower
+                    .execute(circ, &[sum.clone(), c], &[total.clone()])
+                    .await
+                    .unwrap();
+
+                let shares = follower.decode_shared(&[total.clone()]).await.unwrap();
+                follower.decode_blind(&[sum]).await.unwrap();
+                let total = follower.decode_private(&[total]).await.unwrap();
+
+                (total, shares)
+            }
+        };
+
+        let ((sum, leader_shares), (total, follower_shares)) =
+            tokio::join!(leader_fut, follower_fut);
+
+        assert_eq!(sum, vec![Value::U8(3)]);
+        assert_eq!(total, vec![Value::U8(203)]);
+
+        let (Value::U8(a), Value::U8(b)) = (&leader_shares[0], &follower_shares[0]) else {
+            panic!("unexpected share type");
+        };
+        assert_eq!(a ^ b, 203);
+    }
+}
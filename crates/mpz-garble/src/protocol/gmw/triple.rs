@@ -0,0 +1,84 @@
+//! Boolean AND triples.
+//!
+//! Each party runs one random OT as the sender and one as the receiver. For the messages
+//! `(m0, m1)` of the sender and the choice `c` of the receiver, `m0 ⊕ m_c = c·(m0 ⊕ m1)`, so the
+//! sender and receiver hold XOR shares of the product of `m0 ⊕ m1` and `c`. Combining both
+//! directions yields shares of `a·b` for `a = (m0 ⊕ m1)_A ⊕ (m0 ⊕ m1)_B` and `b = c_A ⊕ c_B`.
+
+use mpz_common::Context;
+use mpz_ot::{RandomOTReceiver, RandomOTSender};
+
+use crate::config::Role;
+
+use super::GmwError;
+
+/// A share of an AND triple, `c = a·b`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Triple {
+    pub(crate) a: bool,
+    pub(crate) b: bool,
+    pub(crate) c: bool,
+}
+
+/// Generates `count` AND triples.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `role` - The role of this party.
+/// * `ot_send` - The random OT sender.
+/// * `ot_recv` - The random OT receiver.
+/// * `count` - The number of triples to generate.
+pub(crate) async fn generate_triples<Ctx, OTS, OTR>(
+    ctx: &mut Ctx,
+    role: Role,
+    ot_send: &mut OTS,
+    ot_recv: &mut OTR,
+    count: usize,
+) -> Result<Vec<Triple>, GmwError>
+where
+    Ctx: Context,
+    OTS: RandomOTSender<Ctx, [bool; 2]> + Send,
+    OTR: RandomOTReceiver<Ctx, bool, bool> + Send,
+{
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+
+    // The parties must run the OTs in the same order.
+    let (sent, received) = match role {
+        Role::Leader => {
+            let sent = ot_send.send_random(ctx, count).await?;
+            let received = ot_recv.receive_random(ctx, count).await?;
+            (sent, received)
+        }
+        Role::Follower => {
+            let received = ot_recv.receive_random(ctx, count).await?;
+            let sent = ot_send.send_random(ctx, count).await?;
+            (sent, received)
+        }
+    };
+
+    if sent.msgs.len() != count || received.msgs.len() != count {
+        return Err(GmwError::InvalidLength {
+            expected: count,
+            actual: sent.msgs.len().min(received.msgs.len()),
+        });
+    }
+
+    Ok(sent
+        .msgs
+        .into_iter()
+        .zip(received.choices)
+        .zip(received.msgs)
+        .map(|(([m0, m1], choice), msg)| {
+            let a = m0 ^ m1;
+            let b = choice;
+            Triple {
+                a,
+                b,
+                c: (a & b) ^ m0 ^ msg,
+            }
+        })
+        .collect())
+}
@@ -0,0 +1,195 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::TryFutureExt;
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_common::Context;
+use mpz_ot::{RandomOTReceiver, RandomOTSender};
+
+use crate::{
+    config::{Role, Visibility},
+    value::ValueRef,
+    Decode, DecodeError, DecodePrivate, Execute, ExecutionError, Load, LoadError, Memory,
+    MemoryError, Thread,
+};
+
+use super::Gmw;
+
+/// A GMW thread.
+#[derive(Debug)]
+pub struct GmwThread<Ctx, OTS, OTR> {
+    /// The thread context.
+    ctx: Ctx,
+    /// Random OT sender.
+    ot_send: OTS,
+    /// Random OT receiver.
+    ot_recv: OTR,
+    protocol: Arc<Gmw>,
+}
+
+impl<Ctx, OTS, OTR> GmwThread<Ctx, OTS, OTR> {
+    /// Creates a new GMW instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role of this party.
+    /// * `ctx` - The thread context.
+    /// * `ot_send` - The random OT sender.
+    /// * `ot_recv` - The random OT receiver.
+    pub fn new(role: Role, ctx: Ctx, ot_send: OTS, ot_recv: OTR) -> Self {
+        Self {
+            ctx,
+            ot_send,
+            ot_recv,
+            protocol: Arc::new(Gmw::new(role)),
+        }
+    }
+
+    /// Creates a new thread of the same instance.
+    pub fn new_thread(&self, ctx: Ctx, ot_send: OTS, ot_recv: OTR) -> Self {
+        Self {
+            ctx,
+            ot_send,
+            ot_recv,
+            protocol: self.protocol.clone(),
+        }
+    }
+}
+
+impl<Ctx, OTS, OTR> Thread for GmwThread<Ctx, OTS, OTR> {}
+
+impl<Ctx, OTS, OTR> Memory for GmwThread<Ctx, OTS, OTR> {
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_input_with_type(id, typ, visibility)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_output_with_type(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign(value_ref, value)
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign_by_id(id, value)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.protocol.get_value(id)
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.protocol.get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        self.protocol.get_value_type_by_id(id)
+    }
+}
+
+#[async_trait]
+impl<Ctx, OTS, OTR> Load for GmwThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTS: RandomOTSender<Ctx, [bool; 2]> + Send + Sync,
+    OTR: RandomOTReceiver<Ctx, bool, bool> + Send + Sync,
+{
+    async fn load(
+        &mut self,
+        circ: Arc<Circuit>,
+        _inputs: &[ValueRef],
+        _outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        self.protocol
+            .load(&mut self.ctx, circ, &mut self.ot_send, &mut self.ot_recv)
+            .map_err(LoadError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OTS, OTR> Execute for GmwThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTS: RandomOTSender<Ctx, [bool; 2]> + Send + Sync,
+    OTR: RandomOTReceiver<Ctx, bool, bool> + Send + Sync,
+{
+    async fn commit(&mut self, values: &[ValueRef]) -> Result<(), ExecutionError> {
+        self.protocol
+            .commit(&mut self.ctx, values)
+            .map_err(ExecutionError::from)
+            .await
+    }
+
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol
+            .execute(
+                &mut self.ctx,
+                circ,
+                inputs,
+                outputs,
+                &mut self.ot_send,
+                &mut self.ot_recv,
+            )
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OTS, OTR> Decode for GmwThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTS: Send + Sync,
+    OTR: Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OTS, OTR> DecodePrivate for GmwThread<Ctx, OTS, OTR>
+where
+    Ctx: Context,
+    OTS: Send + Sync,
+    OTR: Send + Sync,
+{
+    async fn decode_private(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_private(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_blind(&mut self, values: &[ValueRef]) -> Result<(), DecodeError> {
+        self.protocol
+            .decode_blind(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_shared(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_shared(values)
+            .map_err(DecodeError::from)
+    }
+}
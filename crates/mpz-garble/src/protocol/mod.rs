@@ -1,3 +1,7 @@
 //! Implementations of garbled circuit protocols
 
+pub mod authenticated;
+mod common;
 pub mod deap;
+pub mod gmw;
+pub mod yao;
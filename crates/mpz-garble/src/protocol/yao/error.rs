@@ -0,0 +1,44 @@
+use crate::{DecodeError, ExecutionError, LoadError};
+
+/// Errors that can occur during the semi-honest Yao protocol.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum YaoError {
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    GeneratorError(#[from] crate::generator::GeneratorError),
+    #[error(transparent)]
+    EvaluatorError(#[from] crate::evaluator::EvaluatorError),
+    #[error(transparent)]
+    ValueError(#[from] mpz_garble_core::ValueError),
+    #[error("incorrect number of values: expected {expected}, got {actual}")]
+    IncorrectValueCount { expected: usize, actual: usize },
+}
+
+impl From<YaoError> for LoadError {
+    fn from(err: YaoError) -> Self {
+        match err {
+            YaoError::IOError(err) => LoadError::IOError(err),
+            err => LoadError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<YaoError> for ExecutionError {
+    fn from(err: YaoError) -> Self {
+        match err {
+            YaoError::IOError(err) => ExecutionError::IOError(err),
+            err => ExecutionError::ProtocolError(Box::new(err)),
+        }
+    }
+}
+
+impl From<YaoError> for DecodeError {
+    fn from(err: YaoError) -> Self {
+        match err {
+            YaoError::IOError(err) => DecodeError::IOError(err),
+            err => DecodeError::ProtocolError(Box::new(err)),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Mocked semi-honest Yao VMs for testing
+
+use mpz_common::executor::{test_st_executor, STExecutor};
+use mpz_core::Block;
+use mpz_ot::ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender};
+use serio::channel::MemoryDuplex;
+
+use crate::{
+    evaluator::EvaluatorConfig,
+    generator::GeneratorConfig,
+    protocol::yao::vm::{YaoEvaluator, YaoGenerator},
+};
+
+type Ctx = STExecutor<MemoryDuplex>;
+
+/// Mock semi-honest Yao leader.
+pub type MockLeader = YaoGenerator<Ctx, IdealOTSender<[Block; 2]>>;
+/// Mock semi-honest Yao follower.
+pub type MockFollower = YaoEvaluator<Ctx, IdealOTReceiver<Block>>;
+
+/// Create a pair of mocked semi-honest Yao VMs
+pub fn create_mock_yao_vm() -> (MockLeader, MockFollower) {
+    let (leader_ctx, follower_ctx) = test_st_executor(128);
+    let (leader_ot_send, follower_ot_recv) = ideal_ot();
+
+    let leader = MockLeader::new(
+        GeneratorConfig::default(),
+        [42u8; 32],
+        leader_ctx,
+        leader_ot_send,
+    );
+
+    let follower = MockFollower::new(EvaluatorConfig::default(), follower_ctx, follower_ot_recv);
+
+    (leader, follower)
+}
@@ -0,0 +1,457 @@
+//! An implementation of Yao's garbled circuit protocol, secure against semi-honest adversaries.
+//!
+//! The leader garbles every circuit and the follower evaluates it, receiving the active
+//! encodings of its private inputs via oblivious transfer. Unlike
+//! [DEAP](crate::protocol::deap), every circuit is garbled and evaluated only once, which halves
+//! the cost of execution, but the protocol provides no security against a malicious party.
+//!
+//! The leader is a [`YaoGenerator`] and the follower is a [`YaoEvaluator`], which both implement
+//! the same VM traits as [`DEAPThread`](crate::protocol::deap::DEAPThread).
+
+mod error;
+mod memory;
+pub mod mock;
+mod vm;
+
+use std::{
+    collections::HashMap,
+    ops::DerefMut,
+    sync::{Arc, Mutex},
+};
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_common::{Context, Counter, ThreadId};
+use mpz_garble_core::{encoding_state, EncodedValue};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt, SinkExt};
+
+use crate::{
+    config::{Role, Visibility},
+    evaluator::{Evaluator, EvaluatorConfig},
+    generator::{Generator, GeneratorConfig},
+    internal_circuits::build_otp_circuit,
+    memory::ValueMemory,
+    ot::{OTReceiveEncoding, OTSendEncoding},
+    value::ValueRef,
+};
+
+pub use error::YaoError;
+pub use vm::{YaoEvaluator, YaoGenerator, YaoThread};
+
+/// The semi-honest Yao protocol.
+///
+/// The leader is a `Yao<Generator>` and the follower is a `Yao<Evaluator>`.
+#[derive(Debug)]
+pub struct Yao<T> {
+    role: Role,
+    core: T,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    memory: ValueMemory,
+    /// A counter for the number of operations performed by each thread.
+    operation_counters: HashMap<ThreadId, Counter>,
+}
+
+impl<T> Yao<T> {
+    fn state(&self) -> impl DerefMut<Target = State> + '_ {
+        self.state.lock().unwrap()
+    }
+
+    /// Returns the one-time pads and the masked outputs for decoding the provided values as
+    /// shares, where the leader provides the one-time pads.
+    fn new_shared_otps(
+        &self,
+        ctx_id: &ThreadId,
+        values: &[ValueRef],
+    ) -> (Vec<ValueRef>, Vec<ValueType>, Vec<Value>, Vec<ValueRef>) {
+        let mut state = self.state();
+        let id = state
+            .operation_counters
+            .entry(ctx_id.clone())
+            .or_default()
+            .next();
+
+        let mut otp_refs = Vec::with_capacity(values.len());
+        let mut otp_typs = Vec::with_capacity(values.len());
+        let mut otp_values = Vec::with_capacity(values.len());
+        let mut mask_refs = Vec::with_capacity(values.len());
+        for (idx, value) in values.iter().enumerate() {
+            let typ = state.memory.get_value_type(value);
+
+            let otp_id = format!("{ctx_id}/{id}/{idx}/otp");
+            let otp_ref = match self.role {
+                Role::Leader => {
+                    let otp_ref = state
+                        .memory
+                        .new_input(&otp_id, typ.clone(), Visibility::Private)
+                        .expect("otp id is unique");
+                    let otp_value = Value::random(&mut thread_rng(), &typ);
+                    state
+                        .memory
+                        .assign(&otp_ref, otp_value.clone())
+                        .expect("value should assign");
+                    otp_values.push(otp_value);
+                    otp_ref
+                }
+                Role::Follower => state
+                    .memory
+                    .new_input(&otp_id, typ.clone(), Visibility::Blind)
+                    .expect("otp id is unique"),
+            };
+
+            let mask_ref = state
+                .memory
+                .new_output(&format!("{ctx_id}/{id}/{idx}/mask"), typ.clone())
+                .expect("mask id is unique");
+
+            otp_refs.push(otp_ref);
+            otp_typs.push(typ);
+            mask_refs.push(mask_ref);
+        }
+
+        (otp_refs, otp_typs, otp_values, mask_refs)
+    }
+}
+
+impl Yao<Generator> {
+    /// Creates a new leader, which garbles the circuits.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The generator configuration.
+    /// * `encoder_seed` - The seed used to generate the encodings of the inputs.
+    pub fn new(config: GeneratorConfig, encoder_seed: [u8; 32]) -> Self {
+        Self {
+            role: Role::Leader,
+            core: Generator::new(config, encoder_seed),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn encode_input(&self, value: &ValueRef, typ: &ValueType) {
+        self.core.generate_input_encoding(value, typ);
+    }
+
+    /// Commits the provided input values.
+    ///
+    /// Values which are already committed are ignored.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn commit<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<(), YaoError>
+    where
+        Ctx: Context,
+        OT: OTSendEncoding<Ctx> + Send,
+    {
+        let assigned = self.state().memory.drain_assigned(values);
+        self.core.setup_assigned_values(ctx, &assigned, ot).await?;
+
+        Ok(())
+    }
+
+    /// Garbles the provided circuit ahead of execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to load.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn load<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), YaoError> {
+        self.core
+            .generate(ctx, circ, inputs, outputs, false)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Executes a circuit.
+    ///
+    /// If the circuit is already loaded it is not garbled again.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `ot` - The OT sender.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn execute<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<(), YaoError>
+    where
+        Ctx: Context,
+        OT: OTSendEncoding<Ctx> + Send,
+    {
+        self.commit(ctx, inputs, ot).await?;
+        self.core
+            .generate(ctx, circ, inputs, outputs, false)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, returning the plaintext values to both parties.
+    ///
+    /// The values are authenticated with the active encodings returned by the follower.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, YaoError> {
+        self.core.decode(ctx, values).await?;
+        self.decode_private(ctx, values).await
+    }
+
+    /// Decodes the provided values, returning the plaintext values to only the leader.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_private<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, YaoError> {
+        let full = self.core.get_encodings(values)?;
+
+        let active: Vec<EncodedValue<encoding_state::Active>> = ctx.io_mut().expect_next().await?;
+
+        if active.len() != values.len() {
+            return Err(YaoError::IncorrectValueCount {
+                expected: values.len(),
+                actual: active.len(),
+            });
+        }
+
+        Ok(full
+            .iter()
+            .zip(&active)
+            .map(|(full, active)| full.decode(active))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Decodes the provided values, returning the plaintext values to only the follower.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_blind<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), YaoError> {
+        self.core.decode(ctx, values).await?;
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, returning XOR shares of the plaintext values to both
+    /// parties.
+    ///
+    /// The share of the leader is a random one-time pad, and the follower decodes the values
+    /// masked with it.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_shared<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<Vec<Value>, YaoError>
+    where
+        Ctx: Context,
+        OT: OTSendEncoding<Ctx> + Send,
+    {
+        let (otp_refs, otp_typs, otp_values, mask_refs) = self.new_shared_otps(ctx.id(), values);
+        for (otp_ref, otp_typ) in otp_refs.iter().zip(&otp_typs) {
+            self.encode_input(otp_ref, otp_typ);
+        }
+
+        let inputs = values
+            .iter()
+            .zip(&otp_refs)
+            .flat_map(|(value, otp)| [value, otp])
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.execute(ctx, build_otp_circuit(&otp_typs), &inputs, &mask_refs, ot)
+            .await?;
+        self.decode_blind(ctx, &mask_refs).await?;
+
+        Ok(otp_values)
+    }
+}
+
+impl Yao<Evaluator> {
+    /// Creates a new follower, which evaluates the circuits.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The evaluator configuration, which must use the same hash and encoder as
+    ///   the leader.
+    pub fn new(config: EvaluatorConfig) -> Self {
+        Self {
+            role: Role::Follower,
+            core: Evaluator::new(config),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn encode_input(&self, _value: &ValueRef, _typ: &ValueType) {
+        // The encodings are generated by the leader.
+    }
+
+    /// Commits the provided input values.
+    ///
+    /// Values which are already committed are ignored.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn commit<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<(), YaoError>
+    where
+        Ctx: Context,
+        OT: OTReceiveEncoding<Ctx> + Send,
+    {
+        let assigned = self.state().memory.drain_assigned(values);
+        self.core.setup_assigned_values(ctx, &assigned, ot).await?;
+
+        Ok(())
+    }
+
+    /// Receives the garbled circuit ahead of execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to load.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn load<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), YaoError> {
+        self.core
+            .receive_garbled_circuit(ctx, circ, inputs, outputs)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Executes a circuit.
+    ///
+    /// If the circuit is already loaded the received garbled circuit is evaluated.
+    ///
+    /// # Arguments
+    ///
+    /// * `circ` - The circuit to execute.
+    /// * `inputs` - The inputs to the circuit.
+    /// * `outputs` - The outputs of the circuit.
+    /// * `ot` - The OT receiver.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn execute<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<(), YaoError>
+    where
+        Ctx: Context,
+        OT: OTReceiveEncoding<Ctx> + Send,
+    {
+        self.commit(ctx, inputs, ot).await?;
+        self.core.evaluate(ctx, circ, inputs, outputs).await?;
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, returning the plaintext values to both parties.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, YaoError> {
+        let decoded = self.decode_private(ctx, values).await?;
+        self.decode_blind(ctx, values).await?;
+
+        Ok(decoded)
+    }
+
+    /// Decodes the provided values, returning the plaintext values to only the follower.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_private<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<Vec<Value>, YaoError> {
+        Ok(self.core.decode(ctx, values).await?)
+    }
+
+    /// Decodes the provided values, returning the plaintext values to only the leader.
+    ///
+    /// The active encodings of the values are sent to the leader.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_blind<Ctx: Context>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+    ) -> Result<(), YaoError> {
+        let active = self.core.get_encodings(values)?;
+        ctx.io_mut().send(active).await?;
+
+        Ok(())
+    }
+
+    /// Decodes the provided values, returning XOR shares of the plaintext values to both
+    /// parties.
+    ///
+    /// The share of the follower is the values masked with a one-time pad of the leader.
+    #[tracing::instrument(fields(role = %self.role, thread = %ctx.id()), skip_all)]
+    pub async fn decode_shared<Ctx, OT>(
+        &self,
+        ctx: &mut Ctx,
+        values: &[ValueRef],
+        ot: &mut OT,
+    ) -> Result<Vec<Value>, YaoError>
+    where
+        Ctx: Context,
+        OT: OTReceiveEncoding<Ctx> + Send,
+    {
+        let (otp_refs, otp_typs, _, mask_refs) = self.new_shared_otps(ctx.id(), values);
+
+        let inputs = values
+            .iter()
+            .zip(&otp_refs)
+            .flat_map(|(value, otp)| [value, otp])
+            .cloned()
+            .collect::<Vec<_>>();
+
+        self.execute(ctx, build_otp_circuit(&otp_typs), &inputs, &mask_refs, ot)
+            .await?;
+
+        self.decode_private(ctx, &mask_refs).await
+    }
+}
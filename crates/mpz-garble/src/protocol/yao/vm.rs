@@ -0,0 +1,518 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::TryFutureExt;
+
+use mpz_circuits::{
+    types::{Value, ValueType},
+    Circuit,
+};
+use mpz_common::Context;
+
+use crate::{
+    config::Visibility,
+    evaluator::{Evaluator, EvaluatorConfig},
+    generator::{Generator, GeneratorConfig},
+    ot::{OTReceiveEncoding, OTSendEncoding},
+    value::ValueRef,
+    Decode, DecodeError, DecodePrivate, Execute, ExecutionError, Load, LoadError, Memory,
+    MemoryError, Thread,
+};
+
+use super::Yao;
+
+/// A semi-honest Yao thread of the leader, which garbles the circuits.
+pub type YaoGenerator<Ctx, OT> = YaoThread<Ctx, Generator, OT>;
+/// A semi-honest Yao thread of the follower, which evaluates the circuits.
+pub type YaoEvaluator<Ctx, OT> = YaoThread<Ctx, Evaluator, OT>;
+
+/// A semi-honest Yao thread.
+#[derive(Debug)]
+pub struct YaoThread<Ctx, T, OT> {
+    /// The thread context.
+    ctx: Ctx,
+    /// OT sender of the leader, or OT receiver of the follower.
+    ot: OT,
+    protocol: Arc<Yao<T>>,
+}
+
+impl<Ctx, OT> YaoThread<Ctx, Generator, OT> {
+    /// Creates a new leader instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The generator configuration.
+    /// * `encoder_seed` - The seed used to generate the encodings of the inputs.
+    /// * `ctx` - The thread context.
+    /// * `ot` - The OT sender.
+    pub fn new(config: GeneratorConfig, encoder_seed: [u8; 32], ctx: Ctx, ot: OT) -> Self {
+        Self {
+            ctx,
+            ot,
+            protocol: Arc::new(Yao::<Generator>::new(config, encoder_seed)),
+        }
+    }
+}
+
+impl<Ctx, OT> YaoThread<Ctx, Evaluator, OT> {
+    /// Creates a new follower instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The evaluator configuration.
+    /// * `ctx` - The thread context.
+    /// * `ot` - The OT receiver.
+    pub fn new(config: EvaluatorConfig, ctx: Ctx, ot: OT) -> Self {
+        Self {
+            ctx,
+            ot,
+            protocol: Arc::new(Yao::<Evaluator>::new(config)),
+        }
+    }
+}
+
+impl<Ctx, T, OT> YaoThread<Ctx, T, OT> {
+    /// Creates a new thread of the same instance.
+    pub fn new_thread(&self, ctx: Ctx, ot: OT) -> Self {
+        Self {
+            ctx,
+            ot,
+            protocol: self.protocol.clone(),
+        }
+    }
+}
+
+impl<Ctx, T, OT> Thread for YaoThread<Ctx, T, OT> where Yao<T>: Memory {}
+
+impl<Ctx, T, OT> Memory for YaoThread<Ctx, T, OT>
+where
+    Yao<T>: Memory,
+{
+    fn new_input_with_type(
+        &self,
+        id: &str,
+        typ: ValueType,
+        visibility: Visibility,
+    ) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_input_with_type(id, typ, visibility)
+    }
+
+    fn new_output_with_type(&self, id: &str, typ: ValueType) -> Result<ValueRef, MemoryError> {
+        self.protocol.new_output_with_type(id, typ)
+    }
+
+    fn assign(&self, value_ref: &ValueRef, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign(value_ref, value)
+    }
+
+    fn assign_by_id(&self, id: &str, value: impl Into<Value>) -> Result<(), MemoryError> {
+        self.protocol.assign_by_id(id, value)
+    }
+
+    fn get_value(&self, id: &str) -> Option<ValueRef> {
+        self.protocol.get_value(id)
+    }
+
+    fn get_value_type(&self, value_ref: &ValueRef) -> ValueType {
+        self.protocol.get_value_type(value_ref)
+    }
+
+    fn get_value_type_by_id(&self, id: &str) -> Option<ValueType> {
+        self.protocol.get_value_type_by_id(id)
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Load for YaoThread<Ctx, Generator, OT>
+where
+    Ctx: Context,
+    OT: OTSendEncoding<Ctx> + Send + Sync,
+{
+    async fn load(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        self.protocol
+            .load(&mut self.ctx, circ, inputs, outputs)
+            .map_err(LoadError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Load for YaoThread<Ctx, Evaluator, OT>
+where
+    Ctx: Context,
+    OT: OTReceiveEncoding<Ctx> + Send + Sync,
+{
+    async fn load(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), LoadError> {
+        self.protocol
+            .load(&mut self.ctx, circ, inputs, outputs)
+            .map_err(LoadError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Execute for YaoThread<Ctx, Generator, OT>
+where
+    Ctx: Context,
+    OT: OTSendEncoding<Ctx> + Send + Sync,
+{
+    async fn commit(&mut self, values: &[ValueRef]) -> Result<(), ExecutionError> {
+        self.protocol
+            .commit(&mut self.ctx, values, &mut self.ot)
+            .map_err(ExecutionError::from)
+            .await
+    }
+
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol
+            .execute(&mut self.ctx, circ, inputs, outputs, &mut self.ot)
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Execute for YaoThread<Ctx, Evaluator, OT>
+where
+    Ctx: Context,
+    OT: OTReceiveEncoding<Ctx> + Send + Sync,
+{
+    async fn commit(&mut self, values: &[ValueRef]) -> Result<(), ExecutionError> {
+        self.protocol
+            .commit(&mut self.ctx, values, &mut self.ot)
+            .map_err(ExecutionError::from)
+            .await
+    }
+
+    async fn execute(
+        &mut self,
+        circ: Arc<Circuit>,
+        inputs: &[ValueRef],
+        outputs: &[ValueRef],
+    ) -> Result<(), ExecutionError> {
+        self.protocol
+            .execute(&mut self.ctx, circ, inputs, outputs, &mut self.ot)
+            .map_err(ExecutionError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Decode for YaoThread<Ctx, Generator, OT>
+where
+    Ctx: Context,
+    OT: OTSendEncoding<Ctx> + Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> Decode for YaoThread<Ctx, Evaluator, OT>
+where
+    Ctx: Context,
+    OT: OTReceiveEncoding<Ctx> + Send + Sync,
+{
+    async fn decode(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> DecodePrivate for YaoThread<Ctx, Generator, OT>
+where
+    Ctx: Context,
+    OT: OTSendEncoding<Ctx> + Send + Sync,
+{
+    async fn decode_private(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_private(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_blind(&mut self, values: &[ValueRef]) -> Result<(), DecodeError> {
+        self.protocol
+            .decode_blind(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_shared(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_shared(&mut self.ctx, values, &mut self.ot)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT> DecodePrivate for YaoThread<Ctx, Evaluator, OT>
+where
+    Ctx: Context,
+    OT: OTReceiveEncoding<Ctx> + Send + Sync,
+{
+    async fn decode_private(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_private(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_blind(&mut self, values: &[ValueRef]) -> Result<(), DecodeError> {
+        self.protocol
+            .decode_blind(&mut self.ctx, values)
+            .map_err(DecodeError::from)
+            .await
+    }
+
+    async fn decode_shared(&mut self, values: &[ValueRef]) -> Result<Vec<Value>, DecodeError> {
+        self.protocol
+            .decode_shared(&mut self.ctx, values, &mut self.ot)
+            .map_err(DecodeError::from)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use mpz_circuits::{circuits::AES128, ops::WrappingAdd, CircuitBuilder};
+
+    use crate::protocol::yao::mock::create_mock_yao_vm;
+
+    #[tokio::test]
+    async fn test_vm() {
+        let (mut leader_vm, mut follower_vm) = create_mock_yao_vm();
+
+        let key = [42u8; 16];
+        let msg = [69u8; 16];
+
+        let leader_fut = {
+            let key_ref = leader_vm.new_private_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = leader_vm.new_blind_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = leader_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            leader_vm.assign(&key_ref, key).unwrap();
+
+            async {
+                leader_vm
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader_vm
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                leader_vm.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let key_ref = follower_vm.new_blind_input::<[u8; 16]>("key").unwrap();
+            let msg_ref = follower_vm.new_private_input::<[u8; 16]>("msg").unwrap();
+            let ciphertext_ref = follower_vm.new_output::<[u8; 16]>("ciphertext").unwrap();
+
+            follower_vm.assign(&msg_ref, msg).unwrap();
+
+            async {
+                follower_vm
+                    .load(
+                        AES128.clone(),
+                        &[key_ref.clone(), msg_ref.clone()],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower_vm
+                    .execute(
+                        AES128.clone(),
+                        &[key_ref, msg_ref],
+                        &[ciphertext_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+
+                follower_vm.decode(&[ciphertext_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        let expected: [u8; 16] = {
+            use aes::{
+                cipher::{BlockEncrypt, KeyInit},
+                Aes128,
+            };
+
+            let mut msg = msg.into();
+
+            let cipher = Aes128::new_from_slice(&key).unwrap();
+            cipher.encrypt_block(&mut msg);
+
+            msg.into()
+        };
+
+        assert_eq!(leader_result, follower_result);
+        assert_eq!(leader_result, vec![Value::from(expected)]);
+    }
+
+    #[tokio::test]
+    async fn test_vm_decode_private() {
+        let (mut leader_vm, mut follower_vm) = create_mock_yao_vm();
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_add(b));
+        let circ = Arc::new(builder.build().unwrap());
+
+        let leader_fut = {
+            let a_ref = leader_vm.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader_vm.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader_vm.new_output::<u8>("c").unwrap();
+            let d_ref = leader_vm.new_output::<u8>("d").unwrap();
+
+            leader_vm.assign(&a_ref, 1u8).unwrap();
+
+            let circ = circ.clone();
+            async move {
+                leader_vm
+                    .execute(
+                        circ.clone(),
+                        &[a_ref.clone(), b_ref.clone()],
+                        &[c_ref.clone()],
+                    )
+                    .await
+                    .unwrap();
+                leader_vm
+                    .execute(circ, &[c_ref.clone(), b_ref], &[d_ref.clone()])
+                    .await
+                    .unwrap();
+
+                let c = leader_vm.decode_private(&[c_ref]).await.unwrap();
+                leader_vm.decode_blind(&[d_ref]).await.unwrap();
+
+                c
+            }
+        };
+
+        let follower_fut = {
+            let a_ref = follower_vm.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower_vm.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower_vm.new_output::<u8>("c").unwrap();
+            let d_ref = follower_vm.new_output::<u8>("d").unwrap();
+
+            follower_vm.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower_vm
+                    .execute(circ.clone(), &[a_ref, b_ref.clone()], &[c_ref.clone()])
+                    .await
+                    .unwrap();
+                follower_vm
+                    .execute(circ, &[c_ref.clone(), b_ref], &[d_ref.clone()])
+                    .await
+                    .unwrap();
+
+                follower_vm.decode_blind(&[c_ref]).await.unwrap();
+                follower_vm.decode_private(&[d_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        assert_eq!(leader_result, vec![Value::U8(3)]);
+        assert_eq!(follower_result, vec![Value::U8(5)]);
+    }
+
+    #[tokio::test]
+    async fn test_vm_decode_shared() {
+        let (mut leader_vm, mut follower_vm) = create_mock_yao_vm();
+
+        let builder = CircuitBuilder::new();
+        let a = builder.add_input::<u8>();
+        let b = builder.add_input::<u8>();
+        builder.add_output(a.wrapping_add(b));
+        let circ = Arc::new(builder.build().unwrap());
+
+        let leader_fut = {
+            let a_ref = leader_vm.new_private_input::<u8>("a").unwrap();
+            let b_ref = leader_vm.new_blind_input::<u8>("b").unwrap();
+            let c_ref = leader_vm.new_output::<u8>("c").unwrap();
+
+            leader_vm.assign(&a_ref, 1u8).unwrap();
+
+            let circ = circ.clone();
+            async move {
+                leader_vm
+                    .execute(circ, &[a_ref, b_ref], &[c_ref.clone()])
+                    .await
+                    .unwrap();
+
+                leader_vm.decode_shared(&[c_ref]).await.unwrap()
+            }
+        };
+
+        let follower_fut = {
+            let a_ref = follower_vm.new_blind_input::<u8>("a").unwrap();
+            let b_ref = follower_vm.new_private_input::<u8>("b").unwrap();
+            let c_ref = follower_vm.new_output::<u8>("c").unwrap();
+
+            follower_vm.assign(&b_ref, 2u8).unwrap();
+
+            async move {
+                follower_vm
+                    .execute(circ, &[a_ref, b_ref], &[c_ref.clone()])
+                    .await
+                    .unwrap();
+
+                follower_vm.decode_shared(&[c_ref]).await.unwrap()
+            }
+        };
+
+        let (leader_result, follower_result) = futures::join!(leader_fut, follower_fut);
+
+        let (Value::U8(leader_share), Value::U8(follower_share)) =
+            (&leader_result[0], &follower_result[0])
+        else {
+            panic!("shares should be u8");
+        };
+
+        assert_eq!(leader_share ^ follower_share, 3);
+    }
+}
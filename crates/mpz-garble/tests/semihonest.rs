@@ -2,18 +2,37 @@ use mpz_circuits::{circuits::AES128, types::StaticValueType};
 use mpz_common::executor::test_st_executor;
 use mpz_ot::ideal::ot::ideal_ot;
 
-use mpz_garble::{config::Visibility, Evaluator, Generator, GeneratorConfigBuilder, ValueMemory};
-
+use mpz_garble::{
+    config::Visibility, Evaluator, EvaluatorConfigBuilder, Generator, GeneratorConfigBuilder,
+    ValueMemory,
+};
+use mpz_garble_core::{EncoderKind, GarbleHash};
+use rstest::rstest;
+
+#[rstest]
+#[case::default(GarbleHash::FixedKeyAes, EncoderKind::ChaCha)]
+#[case::re_keyed_aes(GarbleHash::ReKeyedAes([7u8; 16]), EncoderKind::ChaCha)]
+#[case::blake3_aes_ctr(GarbleHash::Blake3, EncoderKind::AesCtr)]
 #[tokio::test]
-async fn test_semi_honest() {
+async fn test_semi_honest(#[case] hash: GarbleHash, #[case] encoder: EncoderKind) {
     let (mut ctx_a, mut ctx_b) = test_st_executor(8);
     let (mut ot_send, mut ot_recv) = ideal_ot();
 
     let gen = Generator::new(
-        GeneratorConfigBuilder::default().build().unwrap(),
+        GeneratorConfigBuilder::default()
+            .hash(hash)
+            .encoder(encoder)
+            .build()
+            .unwrap(),
         [0u8; 32],
     );
-    let ev = Evaluator::default();
+    let ev = Evaluator::new(
+        EvaluatorConfigBuilder::default()
+            .hash(hash)
+            .encoder(encoder)
+            .build()
+            .unwrap(),
+    );
 
     let key = [69u8; 16];
     let msg = [42u8; 16];
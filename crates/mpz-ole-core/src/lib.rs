@@ -12,6 +12,7 @@ pub mod core;
 pub mod msg;
 mod receiver;
 mod sender;
+pub mod vole;
 
 pub use receiver::{BatchReceiverAdjust, OLEReceiver};
 pub use sender::{BatchSenderAdjust, OLESender};
@@ -54,6 +55,10 @@ pub enum OLEError {
     MultipleOf(usize, usize),
     #[error("Wrong transfer id. Got {0}, expected {1}")]
     WrongId(TransferId, TransferId),
+    #[error("Invalid parameters: {0}")]
+    InvalidParameters(String),
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
 }
 
 #[cfg(test)]
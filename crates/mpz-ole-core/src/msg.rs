@@ -1,6 +1,7 @@
 //! Message types for OLE.
 
 use crate::{core::MaskedCorrelation, OLEError, TransferId};
+use mpz_core::Block;
 use mpz_fields::Field;
 use serde::{Deserialize, Serialize};
 
@@ -43,3 +44,24 @@ pub struct BatchAdjust<F> {
     pub id: TransferId,
     pub adjustments: Vec<F>,
 }
+
+/// Message type for sending the seed of the LPN matrix of VOLE to the sender.
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VOLESeed {
+    pub seed: Block,
+}
+
+/// Message type for sending the corrections of the single-point VOLEs to the sender.
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VOLECorrections<F> {
+    pub corrections: Vec<F>,
+}
+
+/// Message type for sending the derandomization of random VOLEs to the receiver.
+#[allow(missing_docs)]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VOLEDerandomize<F> {
+    pub adjustments: Vec<F>,
+}
@@ -0,0 +1,186 @@
+//! Vector OLE (VOLE) from a Ferret-style pseudorandom correlation generator.
+//!
+//! This is the generalization of [Ferret](https://eprint.iacr.org/2020/924.pdf) to arbitrary
+//! fields, see Wolverine (<https://eprint.iacr.org/2020/925>, Figures 4 and 5). The sender gets
+//! `u_k` and `x_k` and the receiver gets `y_k`, such that `y_k = u_k * delta + x_k` holds, where
+//! `delta` is the receiver's global secret.
+//!
+//! Each extension computes `u = A * u' + e` and `x = A * x' + s`, where
+//!
+//! - `u'` and `x'` are the sender's shares of `k` VOLEs from the previous extension and `A` is the
+//!   random local linear code of [`LpnEncoder`](mpz_core::lpn::LpnEncoder).
+//! - `e` is a regular error vector with `t` non-zero field elements, and `s` is the sender's share
+//!   of single-point VOLEs, which are computed from punctured GGM trees.
+//!
+//! The first `k + t` VOLEs of an extension are reserved for the next one, so only the setup is
+//! bootstrapped from another OLE.
+//!
+//! Note that this implementation is only secure against semi-honest adversaries.
+
+mod receiver;
+mod sender;
+
+pub use receiver::VOLEReceiver;
+pub use sender::VOLESender;
+
+use mpz_core::{lpn::LpnParameters, prg::Prg, Block};
+use mpz_fields::Field;
+use rand::SeedableRng;
+
+use crate::OLEError;
+
+/// The number of non-zero values in each row of the LPN matrix.
+pub(crate) const LPN_WEIGHT: usize = 10;
+
+/// LPN parameters with regular noise for VOLE.
+///
+/// Derived from the parameters of Wolverine for large fields, with `n / t` rounded to a power of
+/// two.
+pub const LPN_PARAMETERS_VOLE: LpnParameters = LpnParameters {
+    n: 10485760,
+    k: 589760,
+    t: 1280,
+};
+
+/// Checks the LPN parameters and returns the depth of the GGM trees.
+pub(crate) fn ggm_depth(lpn_parameters: &LpnParameters) -> Result<usize, OLEError> {
+    let LpnParameters { n, k, t } = *lpn_parameters;
+
+    if t == 0 || n % t != 0 || !(n / t).is_power_of_two() || n / t < 4 {
+        return Err(OLEError::InvalidParameters(format!(
+            "n / t must be a power of two of at least 4, got n = {n}, t = {t}"
+        )));
+    }
+
+    if k < LPN_WEIGHT {
+        return Err(OLEError::InvalidParameters(format!(
+            "k must be at least {LPN_WEIGHT}, got {k}"
+        )));
+    }
+
+    if n <= k + t {
+        return Err(OLEError::InvalidParameters(format!(
+            "an extension must produce more than the {} reserved VOLEs, got n = {n}",
+            k + t
+        )));
+    }
+
+    Ok((n / t).trailing_zeros() as usize)
+}
+
+/// Maps a leaf of a GGM tree to a field element.
+#[inline]
+pub(crate) fn leaf_to_field<F: Field>(leaf: Block) -> F {
+    F::rand(&mut Prg::from_seed(leaf))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg::VOLECorrections;
+    use mpz_fields::{gf2_128::Gf2_128, p256::P256, UniformRand};
+
+    const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
+        n: 4096,
+        k: 400,
+        t: 16,
+    };
+
+    fn setup<F: Field>(lpn_parameters: LpnParameters) -> (VOLESender<F>, VOLEReceiver<F>, F) {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let count = lpn_parameters.k + lpn_parameters.t;
+
+        let delta = F::rand(&mut rng);
+        let u: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
+        let x: Vec<F> = (0..count).map(|_| F::rand(&mut rng)).collect();
+        let y = u.iter().zip(&x).map(|(&u, &x)| u * delta + x).collect();
+
+        let seed = rng.random_block();
+        let sender = VOLESender::new(lpn_parameters, seed, u, x).unwrap();
+        let receiver = VOLEReceiver::new(lpn_parameters, seed, delta, y).unwrap();
+
+        (sender, receiver, delta)
+    }
+
+    fn extend<F: Field>(sender: &mut VOLESender<F>, receiver: &mut VOLEReceiver<F>) {
+        let choices = sender.start_extend().unwrap();
+        let (ot_msgs, corrections) = receiver.extend();
+
+        let keys = ot_msgs
+            .iter()
+            .zip(choices)
+            .map(|(msgs, choice)| msgs[choice as usize])
+            .collect::<Vec<_>>();
+
+        sender.finish_extend(&keys, corrections).unwrap();
+    }
+
+    fn test_vole<F: Field>() {
+        let (mut sender, mut receiver, delta) = setup::<F>(LPN_PARAMETERS_TEST);
+
+        for _ in 0..2 {
+            extend(&mut sender, &mut receiver);
+        }
+
+        let produced = LPN_PARAMETERS_TEST.n - LPN_PARAMETERS_TEST.k - LPN_PARAMETERS_TEST.t;
+        assert_eq!(sender.cache_size(), 2 * produced);
+        assert_eq!(receiver.cache_size(), 2 * produced);
+        assert_eq!(receiver.delta(), delta);
+
+        let (u, x) = sender.consume(2 * produced).unwrap();
+        let y = receiver.consume(2 * produced).unwrap();
+
+        u.iter()
+            .zip(x)
+            .zip(y)
+            .for_each(|((&u, x), y)| assert_eq!(y, u * delta + x));
+    }
+
+    #[test]
+    fn test_vole_p256() {
+        test_vole::<P256>();
+    }
+
+    #[test]
+    fn test_vole_gf2_128() {
+        test_vole::<Gf2_128>();
+    }
+
+    #[test]
+    fn test_vole_invalid_parameters() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+
+        // n / t is not a power of two.
+        let lpn_parameters = LpnParameters {
+            n: 4000,
+            k: 400,
+            t: 16,
+        };
+        let count = lpn_parameters.k + lpn_parameters.t;
+        let u: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        assert!(matches!(
+            VOLESender::new(lpn_parameters, Block::ZERO, u.clone(), u),
+            Err(OLEError::InvalidParameters(_))
+        ));
+    }
+
+    #[test]
+    fn test_vole_unexpected_extension() {
+        let (mut sender, mut receiver, _) = setup::<P256>(LPN_PARAMETERS_TEST);
+
+        let (_, corrections) = receiver.extend();
+
+        // The sender must start the extension first.
+        assert!(sender.finish_extend(&[], corrections).is_err());
+
+        let choices = sender.start_extend().unwrap();
+        assert!(sender.start_extend().is_err());
+
+        let corrections = VOLECorrections {
+            corrections: Vec::new(),
+        };
+        let keys = vec![Block::ZERO; choices.len()];
+        assert!(sender.finish_extend(&keys, corrections).is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! VOLE receiver.
+
+use crate::{
+    msg::VOLECorrections,
+    vole::{ggm_depth, leaf_to_field, LPN_WEIGHT},
+    OLEError,
+};
+use mpz_core::{
+    ggm_tree::GgmTree,
+    lpn::{LpnEncoder, LpnParameters},
+    Block,
+};
+use mpz_fields::Field;
+use rand::thread_rng;
+use std::collections::VecDeque;
+
+/// A VOLE receiver.
+///
+/// The receiver gets random `y_k`, such that `y_k = u_k * delta + x_k`, where `delta` is the
+/// receiver's global secret and `u_k` and `x_k` are the [`VOLESender`](super::VOLESender)'s inputs
+/// and outputs respectively.
+pub struct VOLEReceiver<F> {
+    lpn_parameters: LpnParameters,
+    lpn_encoder: LpnEncoder<LPN_WEIGHT>,
+    /// The depth of the GGM trees.
+    h: usize,
+    /// The receiver's global secret.
+    delta: F,
+    /// The VOLEs reserved for the next extension.
+    y: Vec<F>,
+    cache: VecDeque<F>,
+}
+
+impl<F> std::fmt::Debug for VOLEReceiver<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VOLEReceiver").finish_non_exhaustive()
+    }
+}
+
+impl<F: Field> VOLEReceiver<F> {
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `lpn_parameters` - The LPN parameters, where `n / t` must be a power of two.
+    /// * `seed` - The seed to generate the LPN matrix, which is to be sent to the sender.
+    /// * `delta` - The receiver's global secret.
+    /// * `y` - The receiver's outputs of `k + t` bootstrap VOLEs with `delta`.
+    pub fn new(
+        lpn_parameters: LpnParameters,
+        seed: Block,
+        delta: F,
+        y: Vec<F>,
+    ) -> Result<Self, OLEError> {
+        let h = ggm_depth(&lpn_parameters)?;
+
+        let count = lpn_parameters.k + lpn_parameters.t;
+        if y.len() != count {
+            return Err(OLEError::ExpectedMultipleOf(y.len(), count));
+        }
+
+        Ok(Self {
+            lpn_parameters,
+            lpn_encoder: LpnEncoder::new(seed, lpn_parameters.k as u32),
+            h,
+            delta,
+            y,
+            cache: VecDeque::default(),
+        })
+    }
+
+    /// Returns the receiver's global secret.
+    pub fn delta(&self) -> F {
+        self.delta
+    }
+
+    /// Performs an extension, storing the new VOLEs internally.
+    ///
+    /// # Returns
+    ///
+    /// * The GGM tree keys, which are to be sent via OT with the sender's choices.
+    /// * [`VOLECorrections`], which are to be sent to the sender.
+    pub fn extend(&mut self) -> (Vec<[Block; 2]>, VOLECorrections<F>) {
+        let LpnParameters { n, k, t } = self.lpn_parameters;
+        let h = self.h;
+
+        let ggm = GgmTree::new(h);
+        let mut tree = vec![Block::ZERO; 1 << h];
+        let mut k0 = vec![Block::ZERO; h];
+        let mut k1 = vec![Block::ZERO; h];
+
+        let mut rng = thread_rng();
+        let mut ot_msgs = Vec::with_capacity(t * h);
+        let mut corrections = Vec::with_capacity(t);
+
+        // Compute the single-point VOLEs, where the bootstrap VOLEs after the first `k` provide
+        // the noise values.
+        let mut y = vec![F::zero(); n];
+        for (j, block) in y.chunks_exact_mut(1 << h).enumerate() {
+            ggm.gen(Block::random(&mut rng), &mut tree, &mut k0, &mut k1);
+            ot_msgs.extend(k0.iter().zip(&k1).map(|(k0, k1)| [*k0, *k1]));
+
+            let mut sum = F::zero();
+            for (y, leaf) in block.iter_mut().zip(&tree) {
+                *y = leaf_to_field(*leaf);
+                sum = sum + *y;
+            }
+            corrections.push(sum + -self.y[k + j]);
+        }
+
+        // Compute y = A * y' + s.
+        self.lpn_encoder.compute_add(&mut y, &self.y[..k]);
+
+        let cache = y.split_off(k + t);
+
+        self.y = y;
+        self.cache.extend(cache);
+
+        (ot_msgs, VOLECorrections { corrections })
+    }
+
+    /// Returns VOLEs from internal cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of VOLEs to return.
+    ///
+    /// # Returns
+    ///
+    /// * The receiver's outputs `y_k`.
+    pub fn consume(&mut self, count: usize) -> Option<Vec<F>> {
+        if count > self.cache.len() {
+            return None;
+        }
+
+        Some(self.cache.drain(..count).collect())
+    }
+
+    /// Returns the number of VOLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.cache.len()
+    }
+}
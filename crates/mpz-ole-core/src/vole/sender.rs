@@ -0,0 +1,191 @@
+//! VOLE sender.
+
+use crate::{
+    msg::VOLECorrections,
+    vole::{ggm_depth, leaf_to_field, LPN_WEIGHT},
+    OLEError,
+};
+use mpz_core::{
+    ggm_tree::GgmTree,
+    lpn::{LpnEncoder, LpnParameters},
+    Block,
+};
+use mpz_fields::Field;
+use rand::{thread_rng, Rng};
+use std::collections::VecDeque;
+
+/// A VOLE sender.
+///
+/// The sender gets random `u_k` and `x_k`, such that `y_k = u_k * delta + x_k`, where `delta` and
+/// `y_k` are the [`VOLEReceiver`](super::VOLEReceiver)'s global secret and outputs respectively.
+pub struct VOLESender<F> {
+    lpn_parameters: LpnParameters,
+    lpn_encoder: LpnEncoder<LPN_WEIGHT>,
+    /// The depth of the GGM trees.
+    h: usize,
+    /// The VOLEs reserved for the next extension.
+    u: Vec<F>,
+    x: Vec<F>,
+    /// The noise positions of the pending extension.
+    alphas: Option<Vec<usize>>,
+    cache: VecDeque<(F, F)>,
+}
+
+impl<F> std::fmt::Debug for VOLESender<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VOLESender").finish_non_exhaustive()
+    }
+}
+
+impl<F: Field> VOLESender<F> {
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `lpn_parameters` - The LPN parameters, where `n / t` must be a power of two.
+    /// * `seed` - The seed received from the receiver to generate the LPN matrix.
+    /// * `u` - The sender's inputs of `k + t` bootstrap VOLEs.
+    /// * `x` - The sender's outputs of `k + t` bootstrap VOLEs.
+    pub fn new(
+        lpn_parameters: LpnParameters,
+        seed: Block,
+        u: Vec<F>,
+        x: Vec<F>,
+    ) -> Result<Self, OLEError> {
+        let h = ggm_depth(&lpn_parameters)?;
+
+        let count = lpn_parameters.k + lpn_parameters.t;
+        if u.len() != count || x.len() != count {
+            return Err(OLEError::ExpectedMultipleOf(u.len().max(x.len()), count));
+        }
+
+        Ok(Self {
+            lpn_parameters,
+            lpn_encoder: LpnEncoder::new(seed, lpn_parameters.k as u32),
+            h,
+            u,
+            x,
+            alphas: None,
+            cache: VecDeque::default(),
+        })
+    }
+
+    /// Starts an extension by sampling the noise positions.
+    ///
+    /// # Returns
+    ///
+    /// * The choice bits of the `t * h` OTs of the GGM tree keys.
+    pub fn start_extend(&mut self) -> Result<Vec<bool>, OLEError> {
+        if self.alphas.is_some() {
+            return Err(OLEError::InvalidState(
+                "an extension is already pending".to_string(),
+            ));
+        }
+
+        let mut rng = thread_rng();
+        let alphas: Vec<usize> = (0..self.lpn_parameters.t)
+            .map(|_| rng.gen_range(0..1 << self.h))
+            .collect();
+
+        let choices = alphas
+            .iter()
+            .flat_map(|alpha| choices(*alpha, self.h))
+            .collect();
+
+        self.alphas = Some(alphas);
+
+        Ok(choices)
+    }
+
+    /// Completes an extension, storing the new VOLEs internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The GGM tree keys received via OT.
+    /// * `corrections` - The corrections of the single-point VOLEs sent by the receiver.
+    pub fn finish_extend(
+        &mut self,
+        keys: &[Block],
+        corrections: VOLECorrections<F>,
+    ) -> Result<(), OLEError> {
+        let alphas = self
+            .alphas
+            .take()
+            .ok_or_else(|| OLEError::InvalidState("no extension is pending".to_string()))?;
+
+        let LpnParameters { n, k, t } = self.lpn_parameters;
+        let h = self.h;
+        let VOLECorrections { corrections } = corrections;
+
+        if keys.len() != t * h {
+            return Err(OLEError::ExpectedMultipleOf(keys.len(), t * h));
+        }
+
+        if corrections.len() != t {
+            return Err(OLEError::ExpectedMultipleOf(corrections.len(), t));
+        }
+
+        let ggm = GgmTree::new(h);
+        let mut tree = vec![Block::ZERO; 1 << h];
+
+        // Compute the single-point VOLEs, where the bootstrap VOLEs after the first `k` provide
+        // the noise values.
+        let mut e = vec![F::zero(); n];
+        let mut s = vec![F::zero(); n];
+        for (j, (alpha, correction)) in alphas.into_iter().zip(corrections).enumerate() {
+            ggm.reconstruct(&mut tree, &keys[j * h..(j + 1) * h], &choices(alpha, h));
+
+            let block = &mut s[j << h..(j + 1) << h];
+            let mut sum = F::zero();
+            for (i, (s, leaf)) in block.iter_mut().zip(&tree).enumerate() {
+                if i != alpha {
+                    *s = leaf_to_field(*leaf);
+                    sum = sum + *s;
+                }
+            }
+            block[alpha] = correction + self.x[k + j] + -sum;
+
+            e[(j << h) + alpha] = self.u[k + j];
+        }
+
+        // Compute u = A * u' + e and x = A * x' + s.
+        self.lpn_encoder.compute_add(&mut e, &self.u[..k]);
+        self.lpn_encoder.compute_add(&mut s, &self.x[..k]);
+
+        let u = e.split_off(k + t);
+        let x = s.split_off(k + t);
+
+        self.u = e;
+        self.x = s;
+        self.cache.extend(u.into_iter().zip(x));
+
+        Ok(())
+    }
+
+    /// Returns VOLEs from internal cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of VOLEs to return.
+    ///
+    /// # Returns
+    ///
+    /// * The sender's inputs `u_k` and outputs `x_k`.
+    pub fn consume(&mut self, count: usize) -> Option<(Vec<F>, Vec<F>)> {
+        if count > self.cache.len() {
+            return None;
+        }
+
+        Some(self.cache.drain(..count).unzip())
+    }
+
+    /// Returns the number of VOLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+/// Returns the choice bits which puncture a GGM tree of depth `h` at `alpha`.
+fn choices(alpha: usize, h: usize) -> Vec<bool> {
+    (0..h).map(|i| (alpha >> (h - 1 - i)) & 1 == 0).collect()
+}
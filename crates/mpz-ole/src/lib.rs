@@ -18,6 +18,7 @@ use std::{
 #[cfg(feature = "ideal")]
 pub mod ideal;
 pub mod rot;
+pub mod vole;
 
 /// Batch OLE Sender.
 ///
@@ -59,6 +60,50 @@ pub trait OLEReceiver<Ctx: Context, F: Field> {
     async fn receive(&mut self, ctx: &mut Ctx, inputs: Vec<F>) -> Result<Vec<F>, OLEError>;
 }
 
+/// Random VOLE Sender.
+///
+/// The sender gets random field elements `u_k` and `x_k`, such that `y_k = u_k * delta + x_k`
+/// holds, where `delta` and `y_k` are the [`RandomVOLEReceiver`]'s global secret and outputs
+/// respectively.
+#[async_trait]
+pub trait RandomVOLESender<Ctx: Context, F: Field> {
+    /// Outputs random VOLEs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of VOLEs.
+    ///
+    /// # Returns
+    ///
+    /// * The sender's random inputs `u_k` and outputs `x_k`.
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(Vec<F>, Vec<F>), OLEError>;
+}
+
+/// Random VOLE Receiver.
+///
+/// The receiver gets random field elements `y_k`, such that `y_k = u_k * delta + x_k` holds,
+/// where `delta` is the receiver's global secret and `u_k` and `x_k` are the
+/// [`RandomVOLESender`]'s inputs and outputs respectively.
+#[async_trait]
+pub trait RandomVOLEReceiver<Ctx: Context, F: Field> {
+    /// Outputs random VOLEs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    /// * `count` - The number of VOLEs.
+    ///
+    /// # Returns
+    ///
+    /// * The receiver's outputs `y_k`.
+    async fn receive_random(&mut self, ctx: &mut Ctx, count: usize) -> Result<Vec<F>, OLEError>;
+}
+
 /// An OLE error.
 #[derive(Debug, thiserror::Error)]
 pub struct OLEError {
@@ -88,6 +133,7 @@ impl Display for OLEError {
             OLEErrorKind::Core => write!(f, "OLE Core Error"),
             OLEErrorKind::Field => write!(f, "FieldError"),
             OLEErrorKind::InsufficientOLEs => write!(f, "Insufficient OLEs"),
+            OLEErrorKind::InvalidInput => write!(f, "Invalid input"),
         }?;
 
         if let Some(source) = self.source.as_ref() {
@@ -106,6 +152,7 @@ pub(crate) enum OLEErrorKind {
     Core,
     Field,
     InsufficientOLEs,
+    InvalidInput,
 }
 
 impl From<mpz_common::ContextError> for OLEError {
@@ -0,0 +1,123 @@
+//! Implementation of VOLE from a Ferret-style pseudorandom correlation generator, see
+//! [`mpz_ole_core::vole`].
+//!
+//! The setup is bootstrapped from any OLE and each extension uses OTs for the GGM tree keys, so the
+//! OT must support being preprocessed repeatedly, e.g. SoftSpoken.
+//!
+//! Note that this implementation is only secure against semi-honest adversaries.
+
+mod receiver;
+mod sender;
+
+pub use mpz_core::lpn::LpnParameters;
+pub use mpz_ole_core::vole::LPN_PARAMETERS_VOLE;
+pub use receiver::VOLEReceiver;
+pub use sender::VOLESender;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        rot::{OLEReceiver, OLESender},
+        OLEReceiver as _, OLESender as _, RandomVOLEReceiver as _, RandomVOLESender as _,
+    };
+    use mpz_common::executor::test_st_executor;
+    use mpz_core::{prg::Prg, Block};
+    use mpz_fields::{p256::P256, UniformRand};
+    use mpz_ot::ideal::{
+        ot::{ideal_ot, IdealOTReceiver, IdealOTSender},
+        rot::{ideal_rot, IdealROTReceiver, IdealROTSender},
+    };
+    use rand::SeedableRng;
+
+    const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
+        n: 4096,
+        k: 400,
+        t: 16,
+    };
+
+    #[allow(clippy::type_complexity)]
+    fn create_vole_pair(
+        delta: P256,
+    ) -> (
+        VOLESender<IdealOTReceiver<Block>, OLESender<IdealROTSender, P256>, P256>,
+        VOLEReceiver<IdealOTSender<[Block; 2]>, OLEReceiver<IdealROTReceiver, P256>, P256>,
+    ) {
+        let (ot_sender, ot_receiver) = ideal_ot();
+        let (rot_sender, rot_receiver) = ideal_rot();
+
+        let vole_sender =
+            VOLESender::new(LPN_PARAMETERS_TEST, ot_receiver, OLESender::new(rot_sender));
+        let vole_receiver = VOLEReceiver::new(
+            LPN_PARAMETERS_TEST,
+            delta,
+            ot_sender,
+            OLEReceiver::new(rot_receiver),
+        );
+
+        (vole_sender, vole_receiver)
+    }
+
+    #[tokio::test]
+    async fn test_random_vole() {
+        // Requires more than a single extension.
+        let count = 4000;
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let delta = P256::rand(&mut rng);
+
+        let (mut vole_sender, mut vole_receiver) = create_vole_pair(delta);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let ((u_k, x_k), y_k) = tokio::try_join!(
+            vole_sender.send_random(&mut ctx_sender, count),
+            vole_receiver.receive_random(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_eq!(vole_receiver.delta(), delta);
+        assert_eq!(u_k.len(), count);
+        assert_eq!(y_k.len(), count);
+        u_k.iter()
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|((&u, x), y)| assert_eq!(y, u * delta + x));
+    }
+
+    #[tokio::test]
+    async fn test_vole_ole() {
+        let count = 12;
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let delta = P256::rand(&mut rng);
+
+        let (mut vole_sender, mut vole_receiver) = create_vole_pair(delta);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(10);
+
+        let a_k: Vec<P256> = (0..count).map(|_| P256::rand(&mut rng)).collect();
+
+        let (x_k, y_k) = tokio::try_join!(
+            vole_sender.send(&mut ctx_sender, a_k.clone()),
+            vole_receiver.receive(&mut ctx_receiver, vec![delta; count])
+        )
+        .unwrap();
+
+        assert_eq!(x_k.len(), count);
+        assert_eq!(y_k.len(), count);
+        a_k.iter()
+            .zip(x_k)
+            .zip(y_k)
+            .for_each(|((&a, x), y)| assert_eq!(y, a * delta + x));
+    }
+
+    #[tokio::test]
+    async fn test_vole_ole_invalid_input() {
+        let mut rng = Prg::from_seed(Block::ZERO);
+        let delta = P256::rand(&mut rng);
+
+        let (_, mut vole_receiver) = create_vole_pair(delta);
+        let (_, mut ctx_receiver) = test_st_executor(10);
+
+        let b_k = vec![delta, P256::rand(&mut rng)];
+
+        assert!(vole_receiver.receive(&mut ctx_receiver, b_k).await.is_err());
+    }
+}
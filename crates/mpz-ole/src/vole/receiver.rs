@@ -0,0 +1,240 @@
+use std::mem;
+
+use crate::{
+    OLEError, OLEErrorKind, OLEReceiver as OLEReceive, RandomVOLEReceiver as RandomVOLEReceive,
+};
+use async_trait::async_trait;
+use mpz_common::{Allocate, Context, Preprocess};
+use mpz_core::{lpn::LpnParameters, Block};
+use mpz_fields::Field;
+use mpz_ole_core::{
+    msg::{VOLEDerandomize, VOLESeed},
+    vole::VOLEReceiver as VOLECoreReceiver,
+};
+use mpz_ot::{OTError, OTSender, OTSetup};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
+
+/// VOLE receiver.
+#[derive(Debug)]
+pub struct VOLEReceiver<OT, BaseOLE, F> {
+    lpn_parameters: LpnParameters,
+    delta: F,
+    ot_sender: OT,
+    ole_receiver: BaseOLE,
+    core: Option<VOLECoreReceiver<F>>,
+    alloc: usize,
+}
+
+impl<OT, BaseOLE, F> VOLEReceiver<OT, BaseOLE, F>
+where
+    OT: Send,
+    BaseOLE: Send,
+    F: Field + Serialize + Deserialize,
+{
+    /// Creates a new receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `lpn_parameters` - The LPN parameters, e.g.
+    ///                      [`LPN_PARAMETERS_VOLE`](crate::vole::LPN_PARAMETERS_VOLE).
+    /// * `delta` - The receiver's global secret, which is its input to every OLE.
+    /// * `ot_sender` - The OT sender for the GGM tree keys, which is preprocessed once per
+    ///                 extension.
+    /// * `ole_receiver` - The OLE receiver used for bootstrapping.
+    pub fn new(
+        lpn_parameters: LpnParameters,
+        delta: F,
+        ot_sender: OT,
+        ole_receiver: BaseOLE,
+    ) -> Self {
+        Self {
+            lpn_parameters,
+            delta,
+            ot_sender,
+            ole_receiver,
+            core: None,
+            alloc: 0,
+        }
+    }
+
+    /// Performs the setup, bootstrapping `k + t` VOLEs from the OLE receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OLEError>
+    where
+        OT: OTSetup<Ctx>,
+        BaseOLE: Preprocess<Ctx, Error = OLEError> + OLEReceive<Ctx, F>,
+    {
+        if self.core.is_some() {
+            return Ok(());
+        }
+
+        self.ot_sender.setup(ctx).await?;
+
+        let count = self.lpn_parameters.k + self.lpn_parameters.t;
+
+        self.ole_receiver.alloc(count);
+        self.ole_receiver.preprocess(ctx).await?;
+        let y = self
+            .ole_receiver
+            .receive(ctx, vec![self.delta; count])
+            .await?;
+
+        let seed = Block::random(&mut thread_rng());
+        ctx.io_mut().send(VOLESeed { seed }).await?;
+
+        self.core = Some(VOLECoreReceiver::new(
+            self.lpn_parameters,
+            seed,
+            self.delta,
+            y,
+        )?);
+
+        Ok(())
+    }
+
+    /// Returns the receiver's global secret.
+    pub fn delta(&self) -> F {
+        self.delta
+    }
+
+    /// Returns the number of VOLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.core
+            .as_ref()
+            .map(|core| core.cache_size())
+            .unwrap_or_default()
+    }
+
+    async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OLEError>
+    where
+        OT: Preprocess<Ctx, Error = OTError> + OTSender<Ctx, [Block; 2]>,
+    {
+        let core = self.core.as_mut().ok_or_else(|| {
+            OLEError::new(
+                OLEErrorKind::InsufficientOLEs,
+                "setup has not been performed",
+            )
+        })?;
+
+        let (ot_msgs, corrections) = core.extend();
+
+        self.ot_sender.alloc(ot_msgs.len());
+        self.ot_sender.preprocess(ctx).await?;
+        self.ot_sender.send(ctx, &ot_msgs).await?;
+
+        ctx.io_mut().send(corrections).await?;
+
+        Ok(())
+    }
+
+    async fn random<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<Vec<F>, OLEError>
+    where
+        OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTSender<Ctx, [Block; 2]>,
+        BaseOLE: Preprocess<Ctx, Error = OLEError> + OLEReceive<Ctx, F>,
+    {
+        self.setup(ctx).await?;
+
+        while self.cache_size() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(self
+            .core
+            .as_mut()
+            .and_then(|core| core.consume(count))
+            .expect("enough VOLEs should be available"))
+    }
+}
+
+impl<OT, BaseOLE, F> Allocate for VOLEReceiver<OT, BaseOLE, F> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> Preprocess<Ctx> for VOLEReceiver<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTSender<Ctx, [Block; 2]> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLEReceive<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    type Error = OLEError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
+        self.setup(ctx).await?;
+
+        let count = mem::take(&mut self.alloc);
+        while self.cache_size() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> RandomVOLEReceive<Ctx, F> for VOLEReceiver<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTSender<Ctx, [Block; 2]> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLEReceive<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive_random(&mut self, ctx: &mut Ctx, count: usize) -> Result<Vec<F>, OLEError> {
+        self.random(ctx, count).await
+    }
+}
+
+/// The receiver's inputs must all be equal to its global secret `delta`, as VOLE is an OLE with
+/// a fixed receiver input.
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> OLEReceive<Ctx, F> for VOLEReceiver<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTSender<Ctx, [Block; 2]> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLEReceive<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn receive(&mut self, ctx: &mut Ctx, b_k: Vec<F>) -> Result<Vec<F>, OLEError> {
+        if b_k.iter().any(|b| *b != self.delta) {
+            return Err(OLEError::new(
+                OLEErrorKind::InvalidInput,
+                "the inputs of a VOLE receiver must be equal to its delta",
+            ));
+        }
+
+        let y_k = self.random(ctx, b_k.len()).await?;
+
+        let VOLEDerandomize { adjustments } =
+            ctx.io_mut().expect_next::<VOLEDerandomize<F>>().await?;
+
+        if adjustments.len() != y_k.len() {
+            return Err(OLEError::new(
+                OLEErrorKind::InvalidInput,
+                format!(
+                    "expected {} adjustments, got {}",
+                    y_k.len(),
+                    adjustments.len()
+                ),
+            ));
+        }
+
+        let y_k = y_k
+            .into_iter()
+            .zip(adjustments)
+            .map(|(y, d)| y + d * self.delta)
+            .collect();
+
+        Ok(y_k)
+    }
+}
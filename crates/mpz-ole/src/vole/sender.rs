@@ -0,0 +1,199 @@
+use std::mem;
+
+use crate::{OLEError, OLEErrorKind, OLESender as OLESend, RandomVOLESender as RandomVOLESend};
+use async_trait::async_trait;
+use mpz_common::{Allocate, Context, Preprocess};
+use mpz_core::{lpn::LpnParameters, Block};
+use mpz_fields::Field;
+use mpz_ole_core::{
+    msg::{VOLEDerandomize, VOLESeed},
+    vole::VOLESender as VOLECoreSender,
+};
+use mpz_ot::{OTError, OTReceiver, OTSetup};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt, Deserialize, Serialize, SinkExt};
+
+/// VOLE sender.
+#[derive(Debug)]
+pub struct VOLESender<OT, BaseOLE, F> {
+    lpn_parameters: LpnParameters,
+    ot_receiver: OT,
+    ole_sender: BaseOLE,
+    core: Option<VOLECoreSender<F>>,
+    alloc: usize,
+}
+
+impl<OT, BaseOLE, F> VOLESender<OT, BaseOLE, F>
+where
+    OT: Send,
+    BaseOLE: Send,
+    F: Field + Serialize + Deserialize,
+{
+    /// Creates a new sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `lpn_parameters` - The LPN parameters, e.g.
+    ///                      [`LPN_PARAMETERS_VOLE`](crate::vole::LPN_PARAMETERS_VOLE).
+    /// * `ot_receiver` - The OT receiver for the GGM tree keys, which is preprocessed once per
+    ///                   extension.
+    /// * `ole_sender` - The OLE sender used for bootstrapping.
+    pub fn new(lpn_parameters: LpnParameters, ot_receiver: OT, ole_sender: BaseOLE) -> Self {
+        Self {
+            lpn_parameters,
+            ot_receiver,
+            ole_sender,
+            core: None,
+            alloc: 0,
+        }
+    }
+
+    /// Performs the setup, bootstrapping `k + t` VOLEs from the OLE sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context.
+    pub async fn setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OLEError>
+    where
+        OT: OTSetup<Ctx>,
+        BaseOLE: Preprocess<Ctx, Error = OLEError> + OLESend<Ctx, F>,
+    {
+        if self.core.is_some() {
+            return Ok(());
+        }
+
+        self.ot_receiver.setup(ctx).await?;
+
+        let count = self.lpn_parameters.k + self.lpn_parameters.t;
+        let u: Vec<F> = {
+            let mut rng = thread_rng();
+            (0..count).map(|_| F::rand(&mut rng)).collect()
+        };
+
+        self.ole_sender.alloc(count);
+        self.ole_sender.preprocess(ctx).await?;
+        let x = self.ole_sender.send(ctx, u.clone()).await?;
+
+        let VOLESeed { seed } = ctx.io_mut().expect_next().await?;
+
+        self.core = Some(VOLECoreSender::new(self.lpn_parameters, seed, u, x)?);
+
+        Ok(())
+    }
+
+    /// Returns the number of VOLEs that are available.
+    pub fn cache_size(&self) -> usize {
+        self.core
+            .as_ref()
+            .map(|core| core.cache_size())
+            .unwrap_or_default()
+    }
+
+    async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), OLEError>
+    where
+        OT: Preprocess<Ctx, Error = OTError> + OTReceiver<Ctx, bool, Block>,
+    {
+        let core = self.core.as_mut().ok_or_else(|| {
+            OLEError::new(
+                OLEErrorKind::InsufficientOLEs,
+                "setup has not been performed",
+            )
+        })?;
+
+        let choices = core.start_extend()?;
+
+        self.ot_receiver.alloc(choices.len());
+        self.ot_receiver.preprocess(ctx).await?;
+        let keys = self.ot_receiver.receive(ctx, &choices).await?.msgs;
+
+        let corrections = ctx.io_mut().expect_next().await?;
+        core.finish_extend(&keys, corrections)?;
+
+        Ok(())
+    }
+
+    async fn random<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(Vec<F>, Vec<F>), OLEError>
+    where
+        OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTReceiver<Ctx, bool, Block>,
+        BaseOLE: Preprocess<Ctx, Error = OLEError> + OLESend<Ctx, F>,
+    {
+        self.setup(ctx).await?;
+
+        while self.cache_size() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(self
+            .core
+            .as_mut()
+            .and_then(|core| core.consume(count))
+            .expect("enough VOLEs should be available"))
+    }
+}
+
+impl<OT, BaseOLE, F> Allocate for VOLESender<OT, BaseOLE, F> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> Preprocess<Ctx> for VOLESender<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTReceiver<Ctx, bool, Block> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLESend<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    type Error = OLEError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OLEError> {
+        self.setup(ctx).await?;
+
+        let count = mem::take(&mut self.alloc);
+        while self.cache_size() < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> RandomVOLESend<Ctx, F> for VOLESender<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTReceiver<Ctx, bool, Block> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLESend<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn send_random(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(Vec<F>, Vec<F>), OLEError> {
+        self.random(ctx, count).await
+    }
+}
+
+#[async_trait]
+impl<Ctx, OT, BaseOLE, F> OLESend<Ctx, F> for VOLESender<OT, BaseOLE, F>
+where
+    Ctx: Context,
+    OT: OTSetup<Ctx> + Preprocess<Ctx, Error = OTError> + OTReceiver<Ctx, bool, Block> + Send,
+    BaseOLE: Preprocess<Ctx, Error = OLEError> + OLESend<Ctx, F> + Send,
+    F: Field + Serialize + Deserialize,
+{
+    async fn send(&mut self, ctx: &mut Ctx, a_k: Vec<F>) -> Result<Vec<F>, OLEError> {
+        let (u_k, x_k) = self.random(ctx, a_k.len()).await?;
+
+        let adjustments = a_k.into_iter().zip(u_k).map(|(a, u)| a + -u).collect();
+        ctx.io_mut().send(VOLEDerandomize { adjustments }).await?;
+
+        Ok(x_k)
+    }
+}
@@ -0,0 +1,254 @@
+//! Components shared by the IKNP-style OT extensions, [`KOS15`](crate::kos) and
+//! [`SoftSpoken`](crate::softspoken).
+//!
+//! Both extensions produce Δ-correlated rows `q = t ⊕ x · Δ`, which are checked with the
+//! consistency check of [`KOS15`](https://eprint.iacr.org/2015/546.pdf) and then randomized into
+//! OT keys with a tweakable correlation robust hash.
+
+use itybity::{FromBitIterator, ToBits};
+use mpz_core::{aes::FIXED_KEY_AES, Block};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{kos::msgs::Check, msgs::Derandomize, TransferId};
+
+/// Returns the weights for the consistency check of `count` rows.
+fn chis(chi_seed: Block, count: usize) -> Vec<Block> {
+    let mut seed = <ChaCha20Rng as SeedableRng>::Seed::default();
+    seed.iter_mut()
+        .zip(chi_seed.to_bytes().into_iter().cycle())
+        .for_each(|(s, c)| *s = c);
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    // Figure 7, "Check correlation", point 1.
+    // Sample random weights for the consistency check.
+    (0..count).map(|_| Block::random(&mut rng)).collect()
+}
+
+/// Computes the receiver's message for the consistency check of the rows `ts` with the choices
+/// `choices`.
+///
+/// See section 3.1 of the KOS15 paper for more details.
+///
+/// # Arguments
+///
+/// * `chi_seed` - The seed used to generate the consistency check weights.
+/// * `ts` - The receiver's unchecked rows.
+/// * `choices` - The receiver's unchecked choices.
+pub(crate) fn receiver_check(chi_seed: Block, ts: &[Block], choices: &[bool]) -> Check {
+    let chis = chis(chi_seed, ts.len());
+
+    // Figure 7, "Check correlation", point 2.
+    // Compute the random linear combinations.
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "rayon")] {
+            let (x, t0, t1) = choices.par_iter()
+                .zip(ts)
+                .zip(chis)
+                .map(|((c, t), chi)| {
+                    let x = if *c { chi } else { Block::ZERO };
+                    let (t0, t1) = t.clmul(chi);
+                    (x, t0, t1)
+                })
+                .reduce(
+                    || (Block::ZERO, Block::ZERO, Block::ZERO),
+                    |(_x, _t0, _t1), (x, t0, t1)| {
+                        (_x ^ x, _t0 ^ t0, _t1 ^ t1)
+                    },
+                );
+        } else {
+            let (x, t0, t1) = choices.iter()
+                .zip(ts)
+                .zip(chis)
+                .map(|((c, t), chi)| {
+                    let x = if *c { chi } else { Block::ZERO };
+                    let (t0, t1) = t.clmul(chi);
+                    (x, t0, t1)
+                })
+                .reduce(|(_x, _t0, _t1), (x, t0, t1)| {
+                    (_x ^ x, _t0 ^ t0, _t1 ^ t1)
+                }).unwrap();
+        }
+    }
+
+    Check { x, t0, t1 }
+}
+
+/// Returns `true` if the receiver's consistency check message is valid for the sender's rows
+/// `qs`.
+///
+/// See section 3.1 of the KOS15 paper for more details.
+///
+/// # Arguments
+///
+/// * `delta` - The sender's global secret.
+/// * `chi_seed` - The seed used to generate the consistency check weights.
+/// * `qs` - The sender's unchecked rows.
+/// * `receiver_check` - The receiver's consistency check message.
+pub(crate) fn sender_check(
+    delta: Block,
+    chi_seed: Block,
+    qs: &[Block],
+    receiver_check: Check,
+) -> bool {
+    let chis = chis(chi_seed, qs.len());
+
+    // Figure 7, "Check correlation", point 3.
+    // Compute the random linear combinations.
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "rayon")] {
+            let check = qs.par_iter()
+                .zip(chis)
+                .map(|(q, chi)| q.clmul(chi))
+                .reduce(
+                    || (Block::ZERO, Block::ZERO),
+                    |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
+                );
+        } else {
+            let check = qs.iter()
+                .zip(chis)
+                .map(|(q, chi)| q.clmul(chi))
+                .reduce(
+                    |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
+                ).unwrap();
+        }
+    }
+
+    let Check { x, t0, t1 } = receiver_check;
+    let tmp = x.clmul(delta);
+    let check = (check.0 ^ tmp.0, check.1 ^ tmp.1);
+
+    check == (t0, t1)
+}
+
+/// Randomizes the sender's checked rows into OT keys.
+///
+/// # Arguments
+///
+/// * `delta` - The sender's global secret.
+/// * `index` - The index of the first row, used as the tweak of the hash.
+/// * `qs` - The sender's checked rows.
+pub(crate) fn sender_keys(delta: Block, index: usize, qs: &[Block]) -> Vec<[Block; 2]> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "rayon")] {
+            let iter = qs.par_iter().enumerate();
+        } else {
+            let iter = qs.iter().enumerate();
+        }
+    }
+
+    // Figure 7, "Randomization"
+    let cipher = &(*FIXED_KEY_AES);
+    iter.map(|(j, &q)| {
+        let j = Block::new(((index + j) as u128).to_be_bytes());
+
+        let k0 = cipher.tccr(j, q);
+        let k1 = cipher.tccr(j, q ^ delta);
+
+        [k0, k1]
+    })
+    .collect()
+}
+
+/// Randomizes the receiver's checked rows into OT keys.
+///
+/// # Arguments
+///
+/// * `index` - The index of the first row, used as the tweak of the hash.
+/// * `ts` - The receiver's checked rows.
+pub(crate) fn receiver_keys(index: usize, ts: &[Block]) -> Vec<Block> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "rayon")] {
+            let iter = ts.par_iter().enumerate();
+        } else {
+            let iter = ts.iter().enumerate();
+        }
+    }
+
+    let cipher = &(*FIXED_KEY_AES);
+    iter.map(|(j, &t)| {
+        let j = Block::new(((index + j) as u128).to_be_bytes());
+        cipher.tccr(j, t)
+    })
+    .collect()
+}
+
+/// Returns the Beaver derandomization of the receiver's random choices to `new_choices`, and
+/// overwrites the random choices.
+///
+/// The caller must check that `choices` and `new_choices` have the same length.
+pub(crate) fn derandomize(
+    id: TransferId,
+    choices: &mut [bool],
+    new_choices: &[bool],
+) -> Derandomize {
+    let derandomize = Derandomize {
+        id,
+        count: choices.len() as u32,
+        flip: Vec::<u8>::from_lsb0_iter(
+            choices
+                .iter()
+                .zip(new_choices)
+                .map(|(setup_choice, new_choice)| setup_choice ^ new_choice),
+        ),
+    };
+
+    choices.copy_from_slice(new_choices);
+
+    derandomize
+}
+
+/// Returns the bits flipping the receiver's random choices of `count` OTs.
+///
+/// If there is no derandomization the receiver's choices are kept.
+pub(crate) fn flip_bits(derandomize: Option<Derandomize>, count: usize) -> Vec<u8> {
+    derandomize
+        .map(|x| x.flip)
+        .unwrap_or_else(|| vec![0; count / 8 + 1])
+}
+
+/// Encrypts the sender's messages with the keys, swapping the keys where the receiver's choice is
+/// flipped.
+///
+/// The caller must check that `keys` and `msgs` have the same length.
+pub(crate) fn encrypt_blocks(
+    keys: Vec<[Block; 2]>,
+    msgs: &[[Block; 2]],
+    derandomize: Option<Derandomize>,
+) -> Vec<Block> {
+    let flip = flip_bits(derandomize, keys.len());
+
+    // Encrypt the chosen messages using the generated keys from ROT.
+    keys.into_iter()
+        .zip(msgs)
+        .zip(flip.iter_lsb0())
+        .flat_map(|(([k0, k1], [m0, m1]), flip)| {
+            // Use Beaver derandomization to correct the receiver's choices
+            // from the extension phase.
+            if flip {
+                [k1 ^ *m0, k0 ^ *m1]
+            } else {
+                [k0 ^ *m0, k1 ^ *m1]
+            }
+        })
+        .collect()
+}
+
+/// Decrypts the chosen messages from the sender's ciphertexts.
+///
+/// The caller must check that there are two ciphertexts for each key.
+pub(crate) fn decrypt_blocks(
+    keys: Vec<Block>,
+    choices: Vec<bool>,
+    ciphertexts: &[Block],
+) -> Vec<Block> {
+    keys.into_iter()
+        .zip(choices)
+        .zip(ciphertexts.chunks(2))
+        .map(|((key, c), ct)| if c { key ^ ct[1] } else { key ^ ct[0] })
+        .collect()
+}
@@ -1,6 +1,9 @@
 //! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) protocol.
 
-use mpz_core::lpn::LpnParameters;
+use mpz_core::{
+    lpn::{ExpandAccumulateEncoder, ExpandConvoluteEncoder, LpnCode, LpnEncoder, LpnParameters},
+    Block,
+};
 
 pub mod cuckoo;
 pub mod error;
@@ -36,7 +39,7 @@ pub const LPN_PARAMETERS_UNIFORM: LpnParameters = LpnParameters {
 };
 
 /// The type of Lpn parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LpnType {
     /// Uniform error distribution.
     Uniform,
@@ -44,9 +47,83 @@ pub enum LpnType {
     Regular,
 }
 
+/// LPN parameters with regular noise for the dual LPN codes, see [`LpnCodeType`].
+///
+/// The noisy vector has length `n` and is compressed into `n - k` COTs.
+pub const LPN_PARAMETERS_DUAL: LpnParameters = LpnParameters {
+    n: 2097152,
+    k: 1048576,
+    t: 1024,
+};
+
+/// The number of non-zero values in each row of the local linear code.
+pub const LOCAL_CODE_WEIGHT: usize = 10;
+
+/// The expander weight of the expand-accumulate code.
+pub const EXPAND_ACCUMULATE_WEIGHT: usize = 21;
+
+/// The expander weight of the expand-convolute code.
+pub const EXPAND_CONVOLUTE_WEIGHT: usize = 7;
+
+/// The type of the LPN code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LpnCodeType {
+    /// Primal LPN with a random local linear code, as in the Ferret paper.
+    ///
+    /// The `k` COTs of the LPN secret are provided during setup, and afterwards reserved from the
+    /// output of each extension.
+    Local,
+    /// Dual LPN with an expand-accumulate code.
+    ///
+    /// The MPCOT output of length `n` is compressed into `n - k` COTs, so no LPN secret is
+    /// needed. Use with parameters such as [`LPN_PARAMETERS_DUAL`].
+    ExpandAccumulate,
+    /// Dual LPN with an expand-convolute code.
+    ///
+    /// The MPCOT output of length `n` is compressed into `n - k` COTs, so no LPN secret is
+    /// needed. Use with parameters such as [`LPN_PARAMETERS_DUAL`].
+    ExpandConvolute,
+}
+
+impl LpnCodeType {
+    /// Returns the length of the LPN secret, i.e., the number of COTs which must be provided
+    /// during setup.
+    pub fn secret_len(&self, lpn_parameters: &LpnParameters) -> usize {
+        match self {
+            LpnCodeType::Local => lpn_parameters.k,
+            LpnCodeType::ExpandAccumulate | LpnCodeType::ExpandConvolute => 0,
+        }
+    }
+
+    /// Returns the encoder of the code.
+    pub(crate) fn encoder(
+        &self,
+        seed: Block,
+        lpn_parameters: &LpnParameters,
+    ) -> Box<dyn LpnCode + Send + Sync> {
+        match self {
+            LpnCodeType::Local => Box::new(LpnEncoder::<LOCAL_CODE_WEIGHT>::new(
+                seed,
+                lpn_parameters.k as u32,
+            )),
+            LpnCodeType::ExpandAccumulate => Box::new(ExpandAccumulateEncoder::<
+                EXPAND_ACCUMULATE_WEIGHT,
+            >::new(
+                seed, lpn_parameters.n as u32
+            )),
+            LpnCodeType::ExpandConvolute => Box::new(ExpandConvoluteEncoder::<
+                EXPAND_CONVOLUTE_WEIGHT,
+            >::new(
+                seed, lpn_parameters.n as u32
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::*;
 
     use msgs::LpnMatrixSeed;
     use receiver::Receiver;
@@ -95,6 +172,7 @@ mod tests {
             .setup(
                 LPN_PARAMETERS_TEST,
                 LpnType::Regular,
+                LpnCodeType::Local,
                 lpn_matrix_seed,
                 &u,
                 &w,
@@ -110,6 +188,7 @@ mod tests {
                 delta,
                 LPN_PARAMETERS_TEST,
                 LpnType::Regular,
+                LpnCodeType::Local,
                 lpn_matrix_seed,
                 &v,
             )
@@ -139,4 +218,63 @@ mod tests {
 
         assert_cot(delta, &choices, &msgs, &received);
     }
+
+    #[rstest]
+    #[case::expand_accumulate(LpnCodeType::ExpandAccumulate)]
+    #[case::expand_convolute(LpnCodeType::ExpandConvolute)]
+    fn ferret_dual_test(#[case] lpn_code: LpnCodeType) {
+        const LPN_PARAMETERS_DUAL_TEST: LpnParameters = LpnParameters {
+            n: 8192,
+            k: 4096,
+            t: 64,
+        };
+
+        let mut prg = Prg::from_seed([2u8; 16].into());
+        let delta = prg.random_block();
+        let mut ideal_mpcot = IdealMpcot::default();
+
+        ideal_mpcot.set_delta(delta);
+
+        // The dual codes do not need COTs in the setup phase.
+        let lpn_matrix_seed = prg.random_block();
+
+        let (mut receiver, LpnMatrixSeed { seed }) = Receiver::new()
+            .setup(
+                LPN_PARAMETERS_DUAL_TEST,
+                LpnType::Regular,
+                lpn_code,
+                lpn_matrix_seed,
+                &[],
+                &[],
+            )
+            .unwrap();
+
+        let mut sender = Sender::new()
+            .setup(
+                delta,
+                LPN_PARAMETERS_DUAL_TEST,
+                LpnType::Regular,
+                lpn_code,
+                seed,
+                &[],
+            )
+            .unwrap();
+
+        for _ in 0..2 {
+            let _ = sender.get_mpcot_query();
+            let query = receiver.get_mpcot_query();
+
+            let (MPCOTSenderOutput { s, .. }, MPCOTReceiverOutput { r, .. }) =
+                ideal_mpcot.extend(&query.0, query.1);
+
+            let msgs = sender.extend(&s).unwrap();
+            let (choices, received) = receiver.extend(&r).unwrap();
+
+            assert_eq!(
+                msgs.len(),
+                LPN_PARAMETERS_DUAL_TEST.n - LPN_PARAMETERS_DUAL_TEST.k
+            );
+            assert_cot(delta, &choices, &msgs, &received);
+        }
+    }
 }
@@ -7,11 +7,114 @@ pub mod receiver_regular;
 pub mod sender;
 pub mod sender_regular;
 
+use std::sync::Arc;
+
+use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
+use rand_core::SeedableRng;
+
+use crate::ferret::{
+    cuckoo::{compute_table_length, Bucket, Item},
+    CUCKOO_HASH_NUM,
+};
+use error::SenderError;
+use msgs::HashSeed;
+
+/// Returns the depths of the GGM trees used by the MPCOT for general indices to extend `t`-point
+/// COTs of length `n`.
+///
+/// The depths only depend on public values, so the receiver can compute them before sampling the
+/// points.
+///
+/// # Arguments
+///
+/// * `hash_seed` - The seed for Cuckoo hash chosen by the receiver.
+/// * `t` - The number of queried indices.
+/// * `n` - The total number of indices.
+pub fn uniform_depths(hash_seed: &HashSeed, t: u32, n: u32) -> Result<Vec<usize>, SenderError> {
+    if t > n {
+        return Err(SenderError::InvalidInput(
+            "t should not exceed n".to_string(),
+        ));
+    }
+
+    Ok(buckets(hashes(hash_seed.seed), t, n)
+        .iter()
+        .map(|bin| depth(bin.len() + 1))
+        .collect())
+}
+
+/// Returns the depths of the GGM trees used by the MPCOT for regular indices to extend `t`-point
+/// COTs of length `n`.
+///
+/// # Arguments
+///
+/// * `t` - The number of queried indices.
+/// * `n` - The total number of indices.
+pub fn regular_depths(t: u32, n: u32) -> Result<Vec<usize>, SenderError> {
+    Ok(regular_lengths(t, n)?.into_iter().map(depth).collect())
+}
+
+/// Returns the Cuckoo hash functions derived from `seed`.
+pub(crate) fn hashes(seed: Block) -> Arc<[AesEncryptor; CUCKOO_HASH_NUM]> {
+    let mut prg = Prg::from_seed(seed);
+    Arc::new(std::array::from_fn(|_| {
+        AesEncryptor::new(prg.random_block())
+    }))
+}
+
+/// Returns the buckets of the indices `[0..n-1]` for `t` queried indices.
+///
+/// See Step 1 to Step 3 in Figure 7.
+pub(crate) fn buckets(
+    hashes: Arc<[AesEncryptor; CUCKOO_HASH_NUM]>,
+    t: u32,
+    n: u32,
+) -> Vec<Vec<Item>> {
+    // Compute m = 1.5 * t.
+    let m = compute_table_length(t);
+
+    Bucket::new(hashes, m).insert(n)
+}
+
+/// Returns the lengths of the intervals of `t` regular indices in `[0..n-1]`.
+pub(crate) fn regular_lengths(t: u32, n: u32) -> Result<Vec<usize>, SenderError> {
+    if t > n {
+        return Err(SenderError::InvalidInput(
+            "t should not exceed n".to_string(),
+        ));
+    }
+
+    // The range of each interval.
+    let k = (n + t - 1) / t;
+
+    if n % t == 0 {
+        return Ok(vec![k as usize; t as usize]);
+    }
+
+    let mut lengths = vec![k as usize; (t - 1) as usize];
+    lengths.push((n % k) as usize);
+    if lengths.iter().sum::<usize>() != n as usize {
+        return Err(SenderError::InvalidInput(
+            "the input parameters (t,n) are not regular".to_string(),
+        ));
+    }
+
+    Ok(lengths)
+}
+
+/// Returns the depth of a GGM tree with at least `len` leaves.
+pub(crate) fn depth(len: usize) -> usize {
+    len.checked_next_power_of_two()
+        .expect("length should be less than usize::MAX / 2 - 1")
+        .ilog2() as usize
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        receiver::Receiver as MpcotReceiver, receiver_regular::Receiver as RegularReceiver,
-        sender::Sender as MpcotSender, sender_regular::Sender as RegularSender,
+        msgs::HashSeed, receiver::Receiver as MpcotReceiver,
+        receiver_regular::Receiver as RegularReceiver, regular_depths,
+        sender::Sender as MpcotSender, sender_regular::Sender as RegularSender, uniform_depths,
     };
     use crate::ideal::spcot::IdealSpcot;
     use crate::{SPCOTReceiverOutput, SPCOTSenderOutput};
@@ -166,4 +269,28 @@ mod tests {
 
         assert_eq!(output_sender, output_receiver);
     }
+
+    #[test]
+    fn mpcot_depths_test() {
+        let mut prg = Prg::from_seed([2u8; 16].into());
+        let delta = prg.random_block();
+        let hash_seed = HashSeed {
+            seed: prg.random_block(),
+        };
+
+        let (_, depths) = MpcotSender::new()
+            .setup(delta, hash_seed.clone())
+            .pre_extend(5, 100)
+            .unwrap();
+        assert_eq!(uniform_depths(&hash_seed, 5, 100).unwrap(), depths);
+
+        let (_, depths) = RegularSender::new()
+            .setup(delta)
+            .pre_extend(5, 102)
+            .unwrap();
+        assert_eq!(regular_depths(5, 102).unwrap(), depths);
+
+        assert!(uniform_depths(&hash_seed, 101, 100).is_err());
+        assert!(regular_depths(101, 100).is_err());
+    }
 }
@@ -2,12 +2,11 @@
 use std::sync::Arc;
 
 use crate::ferret::{
-    cuckoo::{find_pos, hash_to_index, Bucket, CuckooHash, Item},
-    mpcot::error::ReceiverError,
+    cuckoo::{find_pos, hash_to_index, CuckooHash, Item},
+    mpcot::{buckets, depth, error::ReceiverError, hashes},
     CUCKOO_HASH_NUM,
 };
-use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
-use rand_core::SeedableRng;
+use mpz_core::{aes::AesEncryptor, Block};
 
 use super::msgs::HashSeed;
 
@@ -33,12 +32,10 @@ impl Receiver {
     ///
     /// * `hash_seed` - Random seed to generate hashes, will be sent to the sender.
     pub fn setup(self, hash_seed: Block) -> (Receiver<state::PreExtension>, HashSeed) {
-        let mut prg = Prg::from_seed(hash_seed);
-        let hashes = std::array::from_fn(|_| AesEncryptor::new(prg.random_block()));
         let recv = Receiver {
             state: state::PreExtension {
                 counter: 0,
-                hashes: Arc::new(hashes),
+                hashes: hashes(hash_seed),
             },
         };
 
@@ -76,10 +73,8 @@ impl Receiver<state::PreExtension> {
 
         let m = table.len();
 
-        let bucket = Bucket::new(self.state.hashes.clone(), m);
-
         // Generates the buckets.
-        let buckets = bucket.insert(n);
+        let buckets = buckets(self.state.hashes.clone(), alphas.len() as u32, n);
 
         // Generates queries for SPCOT.
         // See Step 4 in Figure 7.
@@ -87,11 +82,8 @@ impl Receiver<state::PreExtension> {
         let mut buckets_length = vec![];
         for (alpha, bin) in table.iter().zip(buckets.iter()) {
             // pad to power of 2.
-            let power_of_two = (bin.len() + 1)
-                .checked_next_power_of_two()
-                .expect("bucket length should be less than usize::MAX / 2 - 1");
-
-            let power = power_of_two.ilog2() as usize;
+            let power = depth(bin.len() + 1);
+            let power_of_two = 1 << power;
 
             if let Some(x) = alpha {
                 let pos = find_pos(bin, x)?;
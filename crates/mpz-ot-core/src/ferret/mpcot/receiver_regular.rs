@@ -2,7 +2,7 @@
 
 use mpz_core::Block;
 
-use crate::ferret::mpcot::error::ReceiverError;
+use crate::ferret::mpcot::{depth, error::ReceiverError, regular_lengths};
 
 /// MPCOT receiver.
 #[derive(Debug, Default)]
@@ -49,30 +49,12 @@ impl Receiver<state::PreExtension> {
         // The range of each interval.
         let k = (n + t - 1) / t;
 
-        let queries_length = if n % t == 0 {
-            vec![k as usize; t as usize]
-        } else {
-            let mut tmp = vec![k as usize; (t - 1) as usize];
-            tmp.push((n % k) as usize);
-            if tmp.iter().sum::<usize>() != n as usize {
-                return Err(ReceiverError::InvalidInput(
-                    "the input parameters (t,n) are not regular".to_string(),
-                ));
-            } else {
-                tmp
-            }
-        };
-
-        let mut queries_depth = Vec::with_capacity(queries_length.len());
-        for len in queries_length.iter() {
-            // pad `len` to power of 2.
-            let power = len
-                .checked_next_power_of_two()
-                .expect("len should be less than usize::MAX / 2 - 1")
-                .ilog2() as usize;
+        let queries_length = regular_lengths(t, n).map_err(|_| {
+            ReceiverError::InvalidInput("the input parameters (t,n) are not regular".to_string())
+        })?;
 
-            queries_depth.push(power);
-        }
+        // pad `len` to power of 2.
+        let queries_depth: Vec<usize> = queries_length.iter().map(|&len| depth(len)).collect();
 
         if !alphas
             .iter()
@@ -2,12 +2,11 @@
 use std::sync::Arc;
 
 use crate::ferret::{
-    cuckoo::{compute_table_length, find_pos, hash_to_index, Bucket, Item},
-    mpcot::error::SenderError,
+    cuckoo::{find_pos, hash_to_index, Item},
+    mpcot::{buckets, depth, error::SenderError, hashes},
     CUCKOO_HASH_NUM,
 };
-use mpz_core::{aes::AesEncryptor, prg::Prg, Block};
-use rand_core::SeedableRng;
+use mpz_core::{aes::AesEncryptor, Block};
 
 use super::msgs::HashSeed;
 
@@ -33,13 +32,11 @@ impl Sender {
     /// * `hash_seed` - The seed for Cuckoo hash sent by the receiver.
     pub fn setup(self, delta: Block, hash_seed: HashSeed) -> Sender<state::PreExtension> {
         let HashSeed { seed: hash_seed } = hash_seed;
-        let mut prg = Prg::from_seed(hash_seed);
-        let hashes = std::array::from_fn(|_| AesEncryptor::new(prg.random_block()));
         Sender {
             state: state::PreExtension {
                 delta,
                 counter: 0,
-                hashes: Arc::new(hashes),
+                hashes: hashes(hash_seed),
             },
         }
     }
@@ -66,24 +63,13 @@ impl Sender<state::PreExtension> {
             ));
         }
 
-        // Compute m = 1.5 * t.
-        let m = compute_table_length(t);
-
-        let bucket = Bucket::new(self.state.hashes.clone(), m);
-
         // Generates the buckets.
-        let buckets = bucket.insert(n);
+        let buckets = buckets(self.state.hashes.clone(), t, n);
+        let m = buckets.len();
 
         // First pad (length + 1) to a pow of 2, then computes `log(length + 1)` of each bucket.
-        let mut bs = vec![];
-        let mut buckets_length = vec![];
-        for bin in buckets.iter() {
-            let power_of_two = (bin.len() + 1)
-                .checked_next_power_of_two()
-                .expect("bucket length should be less than usize::MAX / 2 - 1");
-            bs.push(power_of_two.ilog2() as usize);
-            buckets_length.push(power_of_two);
-        }
+        let bs: Vec<usize> = buckets.iter().map(|bin| depth(bin.len() + 1)).collect();
+        let buckets_length = bs.iter().map(|&b| 1 << b).collect();
 
         let sender = Sender {
             state: state::Extension {
@@ -1,10 +1,10 @@
 //! Ferret receiver
 use mpz_core::{
-    lpn::{LpnEncoder, LpnParameters},
+    lpn::{LpnCode, LpnParameters},
     Block,
 };
 
-use crate::ferret::{error::ReceiverError, LpnType};
+use crate::ferret::{error::ReceiverError, LpnCodeType, LpnType};
 
 use super::msgs::LpnMatrixSeed;
 
@@ -31,23 +31,28 @@ impl Receiver {
     /// * `lpn_parameters` - The lpn parameters.
     /// * `seed` - The seed to generate lpn matrix.
     /// * `lpn_type` - The lpn type.
+    /// * `lpn_code` - The lpn code.
     /// * `u` - The bits received from the COT ideal functionality.
     /// * `w` - The vector received from the COT ideal functionality.
+    ///
+    /// `u` and `w` have the length of the lpn secret, see [`LpnCodeType::secret_len`].
     pub fn setup(
         self,
         lpn_parameters: LpnParameters,
         lpn_type: LpnType,
+        lpn_code: LpnCodeType,
         seed: Block,
         u: &[bool],
         w: &[Block],
     ) -> Result<(Receiver<state::Extension>, LpnMatrixSeed), ReceiverError> {
-        if u.len() != lpn_parameters.k || w.len() != lpn_parameters.k {
+        let secret_len = lpn_code.secret_len(&lpn_parameters);
+        if u.len() != secret_len || w.len() != secret_len {
             return Err(ReceiverError(
-                "the length of u and w should be k".to_string(),
+                "the length of u and w should be equal to the length of the lpn secret".to_string(),
             ));
         }
 
-        let lpn_encoder = LpnEncoder::<10>::new(seed, lpn_parameters.k as u32);
+        let lpn_encoder = lpn_code.encoder(seed, &lpn_parameters);
 
         Ok((
             Receiver {
@@ -56,6 +61,7 @@ impl Receiver {
                     lpn_parameters,
                     lpn_encoder,
                     lpn_type,
+                    lpn_code,
                     u: u.to_vec(),
                     w: w.to_vec(),
                     e: Vec::default(),
@@ -105,6 +111,25 @@ impl Receiver<state::Extension> {
             return Err(ReceiverError("the length of r should be n".to_string()));
         }
 
+        if let LpnCodeType::ExpandAccumulate | LpnCodeType::ExpandConvolute = self.state.lpn_code {
+            let len = self.state.lpn_parameters.n - self.state.lpn_parameters.k;
+
+            // Compute z = H * r.
+            let mut z = vec![Block::ZERO; len];
+            self.state.lpn_encoder.compute(&mut z, r);
+
+            // Compute x = H * e.
+            let mut x = vec![Block::ZERO; len];
+            self.state.lpn_encoder.compute(&mut x, &self.state.e);
+
+            let x = x.iter().map(|a| a.lsb() == 1).collect::<Vec<bool>>();
+
+            // Update counter
+            self.state.counter += 1;
+
+            return Ok((x, z));
+        }
+
         // Compute z = A * w + r.
         let mut z = r.to_vec();
         self.state.lpn_encoder.compute(&mut z, &self.state.w);
@@ -166,11 +191,13 @@ pub mod state {
         /// Lpn parameters.
         pub(super) lpn_parameters: LpnParameters,
         /// Lpn encoder.
-        pub(super) lpn_encoder: LpnEncoder<10>,
+        pub(super) lpn_encoder: Box<dyn LpnCode + Send + Sync>,
         /// Lpn type.
         pub(super) lpn_type: LpnType,
+        /// Lpn code.
+        pub(super) lpn_code: LpnCodeType,
 
-        /// Receiver's COT messages in the setup phase.
+        /// Receiver's COT messages in the setup phase, empty with a dual lpn code.
         pub(super) u: Vec<bool>,
         pub(super) w: Vec<Block>,
 
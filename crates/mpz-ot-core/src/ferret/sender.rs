@@ -1,10 +1,10 @@
 //! Ferret sender.
 use mpz_core::{
-    lpn::{LpnEncoder, LpnParameters},
+    lpn::{LpnCode, LpnParameters},
     Block,
 };
 
-use crate::ferret::{error::SenderError, LpnType};
+use crate::ferret::{error::SenderError, LpnCodeType, LpnType};
 
 /// Ferret sender.
 #[derive(Debug, Default)]
@@ -29,22 +29,25 @@ impl Sender {
     /// * `delta` - The sender's global secret.
     /// * `lpn_parameters` - The lpn parameters.
     /// * `lpn_type` - The lpn type.
+    /// * `lpn_code` - The lpn code.
     /// * `seed` - The seed received from receiver to generate lpn matrix.
-    /// * `v` - The vector received from the COT ideal functionality.
+    /// * `v` - The vector received from the COT ideal functionality, with the length of the lpn
+    ///         secret, see [`LpnCodeType::secret_len`].
     pub fn setup(
         self,
         delta: Block,
         lpn_parameters: LpnParameters,
         lpn_type: LpnType,
+        lpn_code: LpnCodeType,
         seed: Block,
         v: &[Block],
     ) -> Result<Sender<state::Extension>, SenderError> {
-        if v.len() != lpn_parameters.k {
+        if v.len() != lpn_code.secret_len(&lpn_parameters) {
             return Err(SenderError(
-                "the length of v should be equal to k".to_string(),
+                "the length of v should be equal to the length of the lpn secret".to_string(),
             ));
         }
-        let lpn_encoder = LpnEncoder::<10>::new(seed, lpn_parameters.k as u32);
+        let lpn_encoder = lpn_code.encoder(seed, &lpn_parameters);
 
         Ok(Sender {
             state: state::Extension {
@@ -52,6 +55,7 @@ impl Sender {
                 counter: 0,
                 lpn_parameters,
                 lpn_type,
+                lpn_code,
                 lpn_encoder,
                 v: v.to_vec(),
             },
@@ -83,14 +87,28 @@ impl Sender<state::Extension> {
             return Err(SenderError("the length of s should be n".to_string()));
         }
 
-        // Compute y = A * v + s
-        let mut y = s.to_vec();
-        self.state.lpn_encoder.compute(&mut y, &self.state.v);
+        let y_ = match self.state.lpn_code {
+            LpnCodeType::Local => {
+                // Compute y = A * v + s
+                let mut y = s.to_vec();
+                self.state.lpn_encoder.compute(&mut y, &self.state.v);
 
-        let y_ = y.split_off(self.state.lpn_parameters.k);
+                let y_ = y.split_off(self.state.lpn_parameters.k);
 
-        // Update v to y[0..k]
-        self.state.v = y;
+                // Update v to y[0..k]
+                self.state.v = y;
+
+                y_
+            }
+            LpnCodeType::ExpandAccumulate | LpnCodeType::ExpandConvolute => {
+                // Compute y = H * s
+                let mut y =
+                    vec![Block::ZERO; self.state.lpn_parameters.n - self.state.lpn_parameters.k];
+                self.state.lpn_encoder.compute(&mut y, s);
+
+                y
+            }
+        };
 
         // Update counter
         self.state.counter += 1;
@@ -136,10 +154,12 @@ pub mod state {
         pub(super) lpn_type: LpnType,
         /// Lpn parameters.
         pub(super) lpn_parameters: LpnParameters,
+        /// Lpn code.
+        pub(super) lpn_code: LpnCodeType,
         /// Lpn encoder.
-        pub(super) lpn_encoder: LpnEncoder<10>,
+        pub(super) lpn_encoder: Box<dyn LpnCode + Send + Sync>,
 
-        /// Sender's COT message in the setup phase.
+        /// Sender's COT message in the setup phase, empty with a dual lpn code.
         pub(super) v: Vec<Block>,
     }
 
@@ -1,4 +1,6 @@
 //! SPCOT receiver
+use std::collections::VecDeque;
+
 use crate::ferret::{spcot::error::ReceiverError, CSP};
 use itybity::ToBits;
 use mpz_core::{
@@ -33,6 +35,7 @@ impl Receiver {
                 unchecked_ws: Vec::default(),
                 chis: Vec::default(),
                 alphas_and_length: Vec::default(),
+                mask_bits: VecDeque::default(),
                 cot_counter: 0,
                 exec_counter: 0,
                 extended: false,
@@ -86,8 +89,9 @@ impl Receiver<state::Extension> {
             .map(|(alpha, &r)| alpha == r)
             .collect();
 
-        // Updates hasher.
-        self.state.hasher.update(&bs.to_bytes());
+        // The mask bits are hashed in `extend`, so that the transcript is hashed in the same order
+        // as the sender even if the mask bits of multiple extensions are sent at once.
+        self.state.mask_bits.push_back(bs.clone());
 
         Ok(MaskBits { bs })
     }
@@ -134,7 +138,14 @@ impl Receiver<state::Extension> {
             ));
         }
 
+        let Some(bs) = self.state.mask_bits.pop_front() else {
+            return Err(ReceiverError::InvalidState(
+                "mask bits should be computed before extension".to_string(),
+            ));
+        };
+
         // Updates hasher
+        self.state.hasher.update(&bs.to_bytes());
         self.state.hasher.update(&ms.to_bytes());
         self.state.hasher.update(&sum.to_bytes());
 
@@ -292,6 +303,8 @@ pub mod state {
         pub(super) chis: Vec<Block>,
         /// Stores the alpha and the length in each extend phase.
         pub(super) alphas_and_length: Vec<(u32, u32)>,
+        /// The mask bits which have not been hashed yet.
+        pub(super) mask_bits: VecDeque<Vec<bool>>,
 
         /// Current COT counter
         pub(super) cot_counter: usize,
@@ -54,6 +54,8 @@ mod tests {
     use rand_chacha::ChaCha12Rng;
     use rand_core::SeedableRng;
 
+    use crate::test::assert_cot;
+
     #[fixture]
     fn choices() -> Vec<bool> {
         let mut rng = ChaCha12Rng::seed_from_u64(0);
@@ -180,6 +182,47 @@ mod tests {
         assert_eq!(received, expected);
     }
 
+    #[rstest]
+    fn test_kos_extension_correlated(
+        delta: Block,
+        sender_seeds: [Block; CSP],
+        receiver_seeds: [[Block; 2]; CSP],
+        chi_seed: Block,
+    ) {
+        let sender = Sender::new(SenderConfig::default());
+        let receiver = Receiver::new(ReceiverConfig::default());
+
+        let mut sender = sender.setup(delta, sender_seeds);
+        let mut receiver = receiver.setup(receiver_seeds);
+
+        let receiver_setup = receiver.extend(512).unwrap();
+        sender.extend(512, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let sender_output = sender.correlated(128).unwrap();
+        let receiver_output = receiver.correlated(128).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_cot(
+            delta,
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+
+        // The remaining OTs are still usable as random OTs.
+        let sender_keys = sender.keys(128).unwrap().take_keys();
+        let (choices, receiver_keys) = receiver.keys(128).unwrap().take_choices_and_keys();
+
+        sender_keys
+            .iter()
+            .zip(choices)
+            .zip(receiver_keys)
+            .for_each(|((keys, c), key)| assert_eq!(keys[c as usize], key));
+    }
+
     #[rstest]
     fn test_kos_extension_stream_extends(
         delta: Block,
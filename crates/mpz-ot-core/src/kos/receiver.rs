@@ -4,13 +4,14 @@ use std::{
 };
 
 use crate::{
+    extension::{decrypt_blocks, derandomize, receiver_check, receiver_keys},
     kos::{
         error::ReceiverVerifyError,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
         Aes128Ctr, ReceiverConfig, ReceiverError, Rng, RngSeed, CSP, SSP,
     },
     msgs::Derandomize,
-    TransferId,
+    RCOTReceiverOutput, TransferId,
 };
 
 use itybity::{FromBitIterator, IntoBits, ToBits};
@@ -92,7 +93,6 @@ impl Receiver {
             state: state::Extension {
                 rngs,
                 ts: Vec::default(),
-                keys: Vec::default(),
                 choices: Vec::default(),
                 index: 0,
                 transfer_id: TransferId::default(),
@@ -113,7 +113,7 @@ impl Receiver<state::Extension> {
 
     /// The number of remaining OTs which can be consumed.
     pub fn remaining(&self) -> usize {
-        self.state.keys.len()
+        self.state.ts.len()
     }
 
     /// Perform the IKNP OT extension.
@@ -230,91 +230,53 @@ impl Receiver<state::Extension> {
             ));
         }
 
-        let mut seed = RngSeed::default();
-        seed.iter_mut()
-            .zip(chi_seed.to_bytes().into_iter().cycle())
-            .for_each(|(s, c)| *s = c);
-
-        let mut rng = Rng::from_seed(seed);
-
         let mut unchecked_ts = std::mem::take(&mut self.state.unchecked_ts);
         let mut unchecked_choices = std::mem::take(&mut self.state.unchecked_choices);
 
-        // Figure 7, "Check correlation", point 1.
-        // Sample random weights for the consistency check.
-        let chis = (0..unchecked_ts.len())
-            .map(|_| Block::random(&mut rng))
-            .collect::<Vec<_>>();
-
-        // Figure 7, "Check correlation", point 2.
-        // Compute the random linear combinations.
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "rayon")] {
-                let (x, t0, t1) = unchecked_choices.par_iter()
-                    .zip(&unchecked_ts)
-                    .zip(chis)
-                    .map(|((c, t), chi)| {
-                        let x = if *c { chi } else { Block::ZERO };
-                        let (t0, t1) = t.clmul(chi);
-                        (x, t0, t1)
-                    })
-                    .reduce(
-                        || (Block::ZERO, Block::ZERO, Block::ZERO),
-                        |(_x, _t0, _t1), (x, t0, t1)| {
-                            (_x ^ x, _t0 ^ t0, _t1 ^ t1)
-                        },
-                    );
-            } else {
-                let (x, t0, t1) = unchecked_choices.iter()
-                    .zip(&unchecked_ts)
-                    .zip(chis)
-                    .map(|((c, t), chi)| {
-                        let x = if *c { chi } else { Block::ZERO };
-                        let (t0, t1) = t.clmul(chi);
-                        (x, t0, t1)
-                    })
-                    .reduce(|(_x, _t0, _t1), (x, t0, t1)| {
-                        (_x ^ x, _t0 ^ t0, _t1 ^ t1)
-                    }).unwrap();
-            }
-        }
+        let check = receiver_check(chi_seed, &unchecked_ts, &unchecked_choices);
 
         // Strip off the rows sacrificed for the consistency check.
         let nrows = unchecked_ts.len() - (CSP + SSP);
         unchecked_ts.truncate(nrows);
         unchecked_choices.truncate(nrows);
 
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "rayon")] {
-                let iter = unchecked_ts.par_iter().enumerate();
-            } else {
-                let iter = unchecked_ts.iter().enumerate();
-            }
-        }
-
-        let cipher = &(*FIXED_KEY_AES);
-        let keys = iter
-            .map(|(j, t)| {
-                let j = Block::from(((self.state.index + j) as u128).to_be_bytes());
-                cipher.tccr(j, *t)
-            })
-            .collect::<Vec<_>>();
+        self.state.ts.extend(unchecked_ts);
+        self.state.choices.extend(unchecked_choices);
 
-        self.state.index += keys.len();
+        // Disable any further extensions.
+        self.state.extended = true;
 
-        // Add to existing keys.
-        self.state.keys.extend(keys);
-        self.state.choices.extend(unchecked_choices);
+        Ok(check)
+    }
 
-        // If we're recording, we track `ts` too
-        if self.state.tape.is_some() {
-            self.state.ts.extend(unchecked_ts);
+    /// Returns random COTs, with the blocks being `q ⊕ x · Δ` for the sender's `q`.
+    ///
+    /// These are the checked rows of the extension before randomization, so no further
+    /// communication is required.
+    ///
+    /// # Verifiable OT
+    ///
+    /// No record of these OTs is kept on the tape.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of COTs.
+    pub fn correlated(
+        &mut self,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, ReceiverError> {
+        if count > self.state.ts.len() {
+            return Err(ReceiverError::InsufficientSetup(count, self.state.ts.len()));
         }
 
-        // Disable any further extensions.
-        self.state.extended = true;
+        // Keep the tweaks of later keys unique.
+        self.state.index += count;
 
-        Ok(Check { x, t0, t1 })
+        Ok(RCOTReceiverOutput {
+            id: self.state.transfer_id.next(),
+            choices: self.state.choices.drain(..count).collect(),
+            msgs: self.state.ts.drain(..count).collect(),
+        })
     }
 
     /// Returns receiver's keys for the given number of OTs.
@@ -323,26 +285,24 @@ impl Receiver<state::Extension> {
     ///
     /// * `count` - The number of keys to take.
     pub fn keys(&mut self, count: usize) -> Result<ReceiverKeys, ReceiverError> {
-        if count > self.state.keys.len() {
-            return Err(ReceiverError::InsufficientSetup(
-                count,
-                self.state.keys.len(),
-            ));
+        if count > self.state.ts.len() {
+            return Err(ReceiverError::InsufficientSetup(count, self.state.ts.len()));
         }
 
         let id = self.state.transfer_id.next();
-        let index = self.state.index - self.state.keys.len();
+        let index = self.state.index;
+        let ts = self.state.ts.drain(..count).collect::<Vec<_>>();
+        let keys = receiver_keys(index, &ts);
+
+        self.state.index += count;
 
         Ok(ReceiverKeys {
             id,
             index,
-            keys: self.state.keys.drain(..count).collect(),
+            keys,
             choices: self.state.choices.drain(..count).collect(),
-            ts: if self.state.tape.is_some() {
-                Some(self.state.ts.drain(..count).collect())
-            } else {
-                None
-            },
+            // If we're recording, we track `ts` too
+            ts: self.state.tape.is_some().then_some(ts),
             tape: self.state.tape.clone(),
         })
     }
@@ -449,20 +409,7 @@ impl ReceiverKeys {
             ));
         }
 
-        let derandomize = Derandomize {
-            id: self.id,
-            count: self.choices.len() as u32,
-            flip: Vec::<u8>::from_lsb0_iter(
-                self.choices
-                    .iter()
-                    .zip(choices)
-                    .map(|(setup_choice, new_choice)| setup_choice ^ new_choice),
-            ),
-        };
-
-        self.choices.copy_from_slice(choices);
-
-        Ok(derandomize)
+        Ok(derandomize(self.id, &mut self.choices, choices))
     }
 
     /// Decrypts the sender's payload.
@@ -506,13 +453,7 @@ impl ReceiverKeys {
             );
         }
 
-        Ok(self
-            .keys
-            .into_iter()
-            .zip(self.choices)
-            .zip(ciphertexts.chunks(2))
-            .map(|((key, c), ct)| if c { key ^ ct[1] } else { key ^ ct[0] })
-            .collect())
+        Ok(decrypt_blocks(self.keys, self.choices, &ciphertexts))
     }
 
     /// Decrypts the sender's payload.
@@ -703,10 +644,8 @@ pub mod state {
     pub struct Extension {
         /// Receiver's rngs
         pub(super) rngs: Vec<[ChaCha20Rng; 2]>,
-        /// Receiver's ts
+        /// Receiver's checked ts
         pub(super) ts: Vec<Block>,
-        /// Receiver's keys
-        pub(super) keys: Vec<Block>,
         /// Receiver's random choices
         pub(super) choices: Vec<bool>,
         /// Current OT index
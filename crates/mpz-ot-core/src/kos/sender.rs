@@ -1,16 +1,17 @@
 use crate::{
+    extension::{encrypt_blocks, flip_bits, sender_check, sender_keys},
     kos::{
         extension_matrix_size,
         msgs::{Check, Ciphertexts, Extend, SenderPayload},
         Aes128Ctr, Rng, RngSeed, SenderConfig, SenderError, CSP, SSP,
     },
     msgs::Derandomize,
-    TransferId,
+    RCOTSenderOutput, TransferId,
 };
 
 use cipher::{KeyIvInit, StreamCipher};
 use itybity::ToBits;
-use mpz_core::{aes::FIXED_KEY_AES, Block};
+use mpz_core::Block;
 
 use rand::{Rng as _, SeedableRng};
 use rand_chacha::ChaCha20Rng;
@@ -20,8 +21,6 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "rayon")] {
         use itybity::ToParallelBits;
         use rayon::prelude::*;
-    } else {
-        use itybity::ToBits;
     }
 }
 
@@ -80,7 +79,7 @@ impl Sender {
             state: state::Extension {
                 delta,
                 rngs,
-                keys: Vec::default(),
+                qs: Vec::default(),
                 transfer_id: TransferId::default(),
                 counter: 0,
                 extended: false,
@@ -91,9 +90,14 @@ impl Sender {
 }
 
 impl Sender<state::Extension> {
+    /// Returns the sender's delta.
+    pub fn delta(&self) -> Block {
+        self.state.delta
+    }
+
     /// The number of remaining OTs which can be consumed.
     pub fn remaining(&self) -> usize {
-        self.state.keys.len()
+        self.state.qs.len()
     }
 
     /// Perform the IKNP OT extension.
@@ -205,50 +209,12 @@ impl Sender<state::Extension> {
             ));
         }
 
-        let mut seed = RngSeed::default();
-        seed.iter_mut()
-            .zip(chi_seed.to_bytes().into_iter().cycle())
-            .for_each(|(s, c)| *s = c);
-
-        let mut rng = Rng::from_seed(seed);
-
         let mut unchecked_qs = std::mem::take(&mut self.state.unchecked_qs);
 
-        // Figure 7, "Check correlation", point 1.
-        // Sample random weights for the consistency check.
-        let chis = (0..unchecked_qs.len())
-            .map(|_| rng.gen())
-            .collect::<Vec<_>>();
-
-        // Figure 7, "Check correlation", point 3.
-        // Compute the random linear combinations.
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "rayon")] {
-                let check = unchecked_qs.par_iter()
-                    .zip(chis)
-                    .map(|(q, chi)| q.clmul(chi))
-                    .reduce(
-                        || (Block::ZERO, Block::ZERO),
-                        |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
-                    );
-            } else {
-                let check = unchecked_qs.iter()
-                    .zip(chis)
-                    .map(|(q, chi)| q.clmul(chi))
-                    .reduce(
-                        |(_a, _b), (a, b)| (a ^ _a, b ^ _b),
-                    ).unwrap();
-            }
-        }
-
-        let Check { x, t0, t1 } = receiver_check;
-        let tmp = x.clmul(self.state.delta);
-        let check = (check.0 ^ tmp.0, check.1 ^ tmp.1);
-
         // The Receiver is malicious.
         //
         // Call the police!
-        if check != (t0, t1) {
+        if !sender_check(self.state.delta, chi_seed, &unchecked_qs, receiver_check) {
             return Err(SenderError::ConsistencyCheckFailed);
         }
 
@@ -256,32 +222,32 @@ impl Sender<state::Extension> {
         let nrows = unchecked_qs.len() - (CSP + SSP);
         unchecked_qs.truncate(nrows);
 
-        // Figure 7, "Randomization"
-        cfg_if::cfg_if! {
-            if #[cfg(feature = "rayon")] {
-                let iter = unchecked_qs.into_par_iter().enumerate();
-            } else {
-                let iter = unchecked_qs.into_iter().enumerate();
-            }
-        }
-
-        let cipher = &(*FIXED_KEY_AES);
-        let keys = iter
-            .map(|(j, q)| {
-                let j = Block::new(((self.state.counter + j) as u128).to_be_bytes());
+        self.state.qs.extend(unchecked_qs);
+        self.state.extended = true;
 
-                let k0 = cipher.tccr(j, q);
-                let k1 = cipher.tccr(j, q ^ self.state.delta);
+        Ok(())
+    }
 
-                [k0, k1]
-            })
-            .collect::<Vec<_>>();
+    /// Returns random COTs, with the receiver's blocks being `q ⊕ x · Δ`.
+    ///
+    /// These are the checked rows of the extension before randomization, so no further
+    /// communication is required.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of COTs.
+    pub fn correlated(&mut self, count: usize) -> Result<RCOTSenderOutput<Block>, SenderError> {
+        if count > self.state.qs.len() {
+            return Err(SenderError::InsufficientSetup(count, self.state.qs.len()));
+        }
 
-        self.state.counter += keys.len();
-        self.state.keys.extend(keys);
-        self.state.extended = true;
+        // Keep the tweaks of later keys unique.
+        self.state.counter += count;
 
-        Ok(())
+        Ok(RCOTSenderOutput {
+            id: self.state.transfer_id.next(),
+            msgs: self.state.qs.drain(..count).collect(),
+        })
     }
 
     /// Reserves a set of keys which can be used to encrypt a payload later.
@@ -290,15 +256,23 @@ impl Sender<state::Extension> {
     ///
     /// * `count` - The number of keys to reserve.
     pub fn keys(&mut self, count: usize) -> Result<SenderKeys, SenderError> {
-        if count > self.state.keys.len() {
-            return Err(SenderError::InsufficientSetup(count, self.state.keys.len()));
+        if count > self.state.qs.len() {
+            return Err(SenderError::InsufficientSetup(count, self.state.qs.len()));
         }
 
         let id = self.state.transfer_id.next();
+        let keys = sender_keys(
+            self.state.delta,
+            self.state.counter,
+            &self.state.qs[..count],
+        );
+
+        self.state.qs.drain(..count);
+        self.state.counter += count;
 
         Ok(SenderKeys {
             id,
-            keys: self.state.keys.drain(..count).collect(),
+            keys,
             derandomize: None,
         })
     }
@@ -352,29 +326,7 @@ impl SenderKeys {
             return Err(SenderError::InsufficientSetup(msgs.len(), self.keys.len()));
         }
 
-        // If we have derandomization, use it to correct the receiver's choices, else we use
-        // default
-        let flip = self
-            .derandomize
-            .map(|x| x.flip)
-            .unwrap_or_else(|| vec![0; self.keys.len() / 8 + 1]);
-
-        // Encrypt the chosen messages using the generated keys from ROT.
-        let ciphertexts = self
-            .keys
-            .into_iter()
-            .zip(msgs)
-            .zip(flip.iter_lsb0())
-            .flat_map(|(([k0, k1], [m0, m1]), flip)| {
-                // Use Beaver derandomization to correct the receiver's choices
-                // from the extension phase.
-                if flip {
-                    [k1 ^ *m0, k0 ^ *m1]
-                } else {
-                    [k0 ^ *m0, k1 ^ *m1]
-                }
-            })
-            .collect();
+        let ciphertexts = encrypt_blocks(self.keys, msgs, self.derandomize);
 
         Ok(SenderPayload {
             id: self.id,
@@ -399,12 +351,8 @@ impl SenderKeys {
         // This is safe because every message is encrypted with a different key.
         let iv: [u8; 16] = rand::thread_rng().gen();
 
-        // If we have derandomization, use it to correct the receiver's choices, else we use
-        // default
-        let flip = self
-            .derandomize
-            .map(|x| x.flip)
-            .unwrap_or_else(|| vec![0; self.keys.len() / 8 + 1]);
+        // If we have derandomization, use it to correct the receiver's choices.
+        let flip = flip_bits(self.derandomize, self.keys.len());
 
         // Encrypt the chosen messages using the generated keys from ROT.
         let ciphertexts = self
@@ -482,8 +430,8 @@ pub mod state {
         pub(super) delta: Block,
         /// Receiver's rngs seeded from seeds obliviously received from base OT
         pub(super) rngs: Vec<ChaCha20Rng>,
-        /// Sender's keys
-        pub(super) keys: Vec<[Block; 2]>,
+        /// Sender's checked qs
+        pub(super) qs: Vec<Block>,
 
         /// Current transfer id
         pub(super) transfer_id: TransferId,
@@ -0,0 +1,87 @@
+use derive_builder::Builder;
+
+use crate::softspoken::CSP;
+
+/// The default number of bits of Δ covered by each small-field VOLE.
+const DEFAULT_K: usize = 4;
+
+/// Checks that `k` is supported, i.e. `2 <= k <= 8` and `k` divides [`CSP`].
+fn validate_k(k: Option<usize>) -> Result<(), String> {
+    match k {
+        Some(k) if !(2..=8).contains(&k) || CSP % k != 0 => Err(format!(
+            "k must be at least 2, at most 8 and divide {CSP}, got {k}"
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// SoftSpoken sender configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct SenderConfig {
+    /// The number of bits of Δ covered by each small-field VOLE.
+    #[builder(default = "DEFAULT_K")]
+    k: usize,
+}
+
+impl SenderConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_k(self.k)
+    }
+}
+
+impl Default for SenderConfig {
+    fn default() -> Self {
+        Self { k: DEFAULT_K }
+    }
+}
+
+impl SenderConfig {
+    /// Creates a new builder for SenderConfig.
+    pub fn builder() -> SenderConfigBuilder {
+        SenderConfigBuilder::default()
+    }
+
+    /// The number of bits of Δ covered by each small-field VOLE.
+    ///
+    /// Increasing `k` divides the communication by `k`, at the cost of `2^k` PRG evaluations per
+    /// `k` bits of Δ.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
+
+/// SoftSpoken receiver configuration.
+#[derive(Debug, Clone, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct ReceiverConfig {
+    /// The number of bits of Δ covered by each small-field VOLE.
+    #[builder(default = "DEFAULT_K")]
+    k: usize,
+}
+
+impl ReceiverConfigBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_k(self.k)
+    }
+}
+
+impl Default for ReceiverConfig {
+    fn default() -> Self {
+        Self { k: DEFAULT_K }
+    }
+}
+
+impl ReceiverConfig {
+    /// Creates a new builder for ReceiverConfig.
+    pub fn builder() -> ReceiverConfigBuilder {
+        ReceiverConfigBuilder::default()
+    }
+
+    /// The number of bits of Δ covered by each small-field VOLE.
+    ///
+    /// Must be the same as the sender's.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
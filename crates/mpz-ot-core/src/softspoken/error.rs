@@ -0,0 +1,35 @@
+use crate::TransferId;
+
+/// Errors that can occur when using the SoftSpoken sender.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum SenderError {
+    #[error("invalid count, must be a multiple of 64: {0}")]
+    InvalidCount(usize),
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(TransferId, TransferId),
+    #[error("invalid extend")]
+    InvalidExtend,
+    #[error("consistency check failed")]
+    ConsistencyCheckFailed,
+    #[error("not enough OTs are setup: expected {0}, actual {1}")]
+    InsufficientSetup(usize, usize),
+}
+
+/// Errors that can occur when using the SoftSpoken receiver.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum ReceiverError {
+    #[error("invalid count, must be a multiple of 64: {0}")]
+    InvalidCount(usize),
+    #[error("count mismatch: expected {0}, got {1}")]
+    CountMismatch(usize, usize),
+    #[error("id mismatch: expected {0}, got {1}")]
+    IdMismatch(TransferId, TransferId),
+    #[error("not enough OTs are setup: expected {0}, actual {1}")]
+    InsufficientSetup(usize, usize),
+    #[error("invalid payload")]
+    InvalidPayload(String),
+}
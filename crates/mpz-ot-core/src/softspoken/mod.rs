@@ -0,0 +1,233 @@
+//! An implementation of the [`SoftSpoken`](https://eprint.iacr.org/2022/192.pdf) oblivious transfer extension protocol.
+//!
+//! SoftSpoken generalizes IKNP by splitting Δ into chunks of `k` bits, each of which is used as
+//! the correlation of a VOLE over `GF(2^k)` built from a punctured GGM tree. This reduces the
+//! communication of the extension to `CSP / k - 1` bits per OT, at the cost of `2^k` PRG
+//! evaluations per chunk. The extension is checked with the consistency check of
+//! [`KOS15`](crate::kos).
+
+mod config;
+mod error;
+pub mod msgs;
+mod receiver;
+mod sender;
+
+pub use config::{
+    ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, SenderConfig,
+    SenderConfigBuilder, SenderConfigBuilderError,
+};
+pub use error::{ReceiverError, SenderError};
+pub use receiver::{state as receiver_state, Receiver, ReceiverKeys};
+pub use sender::{state as sender_state, Sender, SenderKeys};
+
+pub use crate::kos::{pad_ot_count, CSP, SSP};
+
+use itybity::ToBits;
+use mpz_core::Block;
+
+/// Returns the size in bytes of the extension message for a given number of OTs.
+pub fn extension_size(count: usize, k: usize) -> usize {
+    (CSP / k - 1) * count / 8
+}
+
+/// Splits `delta` into chunks of `k` bits, each being the punctured leaf of a GGM tree.
+pub(crate) fn delta_chunks(delta: Block, k: usize) -> Vec<usize> {
+    delta
+        .iter_lsb0()
+        .collect::<Vec<_>>()
+        .chunks_exact(k)
+        .map(|bits| {
+            bits.iter()
+                .rev()
+                .fold(0, |chunk, &bit| (chunk << 1) | bit as usize)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use mpz_core::Block;
+
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::test::assert_cot;
+
+    #[fixture]
+    fn choices() -> Vec<bool> {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        (0..128).map(|_| rng.gen()).collect()
+    }
+
+    #[fixture]
+    fn data() -> Vec<[Block; 2]> {
+        let mut rng = ChaCha12Rng::seed_from_u64(1);
+        (0..128)
+            .map(|_| [rng.gen::<[u8; 16]>().into(), rng.gen::<[u8; 16]>().into()])
+            .collect()
+    }
+
+    #[fixture]
+    fn delta() -> Block {
+        let mut rng = ChaCha12Rng::seed_from_u64(2);
+        rng.gen::<[u8; 16]>().into()
+    }
+
+    #[fixture]
+    fn seed() -> Block {
+        let mut rng = ChaCha12Rng::seed_from_u64(3);
+        rng.gen::<[u8; 16]>().into()
+    }
+
+    #[fixture]
+    fn chi_seed() -> Block {
+        let mut rng = ChaCha12Rng::seed_from_u64(4);
+        rng.gen::<[u8; 16]>().into()
+    }
+
+    #[fixture]
+    fn expected(data: Vec<[Block; 2]>, choices: Vec<bool>) -> Vec<Block> {
+        data.iter()
+            .zip(choices.iter())
+            .map(|([a, b], choice)| if *choice { *b } else { *a })
+            .collect()
+    }
+
+    /// Sets up a sender and receiver, performing the base OTs in the clear.
+    fn setup(
+        k: usize,
+        delta: Block,
+        seed: Block,
+    ) -> (
+        Sender<sender_state::Extension>,
+        Receiver<receiver_state::Extension>,
+    ) {
+        let sender = Sender::new(SenderConfig::builder().k(k).build().unwrap());
+        let receiver = Receiver::new(ReceiverConfig::builder().k(k).build().unwrap());
+
+        let (receiver, base_msgs) = receiver.setup(seed);
+        let base_choices = sender.base_choices(delta);
+
+        let keys: [Block; CSP] = base_msgs
+            .iter()
+            .zip(base_choices)
+            .map(|([k0, k1], choice)| if choice { *k1 } else { *k0 })
+            .collect::<Vec<_>>()
+            .try_into()
+            .unwrap();
+
+        (sender.setup(delta, keys), receiver)
+    }
+
+    #[rstest]
+    #[case::k2(2)]
+    #[case::k4(4)]
+    #[case::k8(8)]
+    fn test_softspoken_extension(
+        #[case] k: usize,
+        delta: Block,
+        seed: Block,
+        chi_seed: Block,
+        choices: Vec<bool>,
+        data: Vec<[Block; 2]>,
+        expected: Vec<Block>,
+    ) {
+        let (mut sender, mut receiver) = setup(k, delta, seed);
+
+        let receiver_setup = receiver.extend(choices.len() + 256).unwrap();
+        assert_eq!(
+            receiver_setup.us.len(),
+            extension_size(choices.len() + 256, k)
+        );
+        sender.extend(data.len() + 256, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        sender.check(chi_seed, receiver_check).unwrap();
+
+        let mut receiver_keys = receiver.keys(choices.len()).unwrap();
+        let derandomize = receiver_keys.derandomize(&choices).unwrap();
+
+        let mut sender_keys = sender.keys(data.len()).unwrap();
+        sender_keys.derandomize(derandomize).unwrap();
+        let payload = sender_keys.encrypt_blocks(&data).unwrap();
+
+        let received = receiver_keys.decrypt_blocks(payload).unwrap();
+
+        assert_eq!(received, expected);
+    }
+
+    #[rstest]
+    fn test_softspoken_extension_correlated(delta: Block, seed: Block, chi_seed: Block) {
+        let (mut sender, mut receiver) = setup(4, delta, seed);
+
+        // Extending multiple times is supported, as long as each extension is checked.
+        for _ in 0..2 {
+            let receiver_setup = receiver.extend(512).unwrap();
+            sender.extend(512, receiver_setup).unwrap();
+
+            let receiver_check = receiver.check(chi_seed).unwrap();
+            sender.check(chi_seed, receiver_check).unwrap();
+        }
+
+        assert_eq!(sender.remaining(), 2 * (512 - 256));
+        assert_eq!(receiver.remaining(), sender.remaining());
+
+        let sender_output = sender.correlated(sender.remaining()).unwrap();
+        let receiver_output = receiver.correlated(receiver.remaining()).unwrap();
+
+        assert_eq!(sender_output.id, receiver_output.id);
+        assert_cot(
+            delta,
+            &receiver_output.choices,
+            &sender_output.msgs,
+            &receiver_output.msgs,
+        );
+    }
+
+    #[rstest]
+    fn test_softspoken_extension_insufficient_setup(delta: Block, seed: Block, chi_seed: Block) {
+        let (mut sender, mut receiver) = setup(4, delta, seed);
+
+        let receiver_setup = receiver.extend(64).unwrap();
+        sender.extend(64, receiver_setup).unwrap();
+
+        let err = receiver.check(chi_seed).unwrap_err();
+
+        assert!(matches!(err, ReceiverError::InsufficientSetup(_, _)));
+    }
+
+    #[rstest]
+    fn test_softspoken_extension_bad_consistency_check(delta: Block, seed: Block, chi_seed: Block) {
+        let (mut sender, mut receiver) = setup(4, delta, seed);
+
+        let mut receiver_setup = receiver.extend(512).unwrap();
+
+        // Flip a bit in each of the receiver's corrections (breaking the mono-chrome choice
+        // vector)
+        receiver_setup
+            .us
+            .chunks_exact_mut(512 / 8)
+            .for_each(|correction| correction[0] ^= 1);
+
+        sender.extend(512, receiver_setup).unwrap();
+
+        let receiver_check = receiver.check(chi_seed).unwrap();
+        let err = sender.check(chi_seed, receiver_check).unwrap_err();
+
+        assert!(matches!(err, SenderError::ConsistencyCheckFailed));
+    }
+
+    #[test]
+    fn test_softspoken_config() {
+        assert_eq!(SenderConfig::default().k(), 4);
+        assert_eq!(ReceiverConfig::builder().k(8).build().unwrap().k(), 8);
+
+        assert!(SenderConfig::builder().k(1).build().is_err());
+        assert!(SenderConfig::builder().k(3).build().is_err());
+        assert!(ReceiverConfig::builder().k(16).build().is_err());
+    }
+}
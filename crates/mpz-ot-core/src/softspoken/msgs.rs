@@ -0,0 +1,7 @@
+//! Messages for the SoftSpoken protocol.
+//!
+//! SoftSpoken has the same message flow as KOS15, so its messages are re-exported from
+//! [`kos::msgs`](crate::kos::msgs). The extension message holds the receiver's corrections of
+//! all but the first small-field VOLE.
+
+pub use crate::kos::msgs::{Check, Ciphertexts, Extend, ExtendChunks, SenderPayload, StartExtend};
@@ -0,0 +1,399 @@
+use crate::{
+    extension::{decrypt_blocks, derandomize, receiver_check, receiver_keys},
+    msgs::Derandomize,
+    softspoken::{
+        msgs::{Check, Ciphertexts, Extend, SenderPayload},
+        ReceiverConfig, ReceiverError, CSP, SSP,
+    },
+    RCOTReceiverOutput, TransferId,
+};
+
+use itybity::IntoBits;
+use mpz_core::{ggm_tree::GgmTree, prg::Prg, Block};
+
+use rand::SeedableRng;
+use rand_core::RngCore;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// SoftSpoken receiver.
+#[derive(Debug, Default)]
+pub struct Receiver<T: state::State = state::Initialized> {
+    config: ReceiverConfig,
+    state: T,
+}
+
+impl<T> Receiver<T>
+where
+    T: state::State,
+{
+    /// Returns the Receiver's configuration
+    pub fn config(&self) -> &ReceiverConfig {
+        &self.config
+    }
+}
+
+impl Receiver {
+    /// Creates a new Receiver
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Receiver's configuration
+    pub fn new(config: ReceiverConfig) -> Self {
+        Receiver {
+            config,
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Complete the setup phase of the protocol, returning the messages of the base OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed used to generate the GGM trees.
+    pub fn setup(self, seed: Block) -> (Receiver<state::Extension>, [[Block; 2]; CSP]) {
+        let k = self.config.k();
+        let ggm = GgmTree::new(k);
+        let mut prg = Prg::from_seed(seed);
+
+        // The base OTs transfer the XORs of the left and right nodes of each level, from which the
+        // sender reconstructs every leaf except the one at its chunk of delta.
+        let mut base_msgs = Vec::with_capacity(CSP);
+        let prgs = (0..CSP / k)
+            .map(|_| {
+                let mut leaves = vec![Block::ZERO; 1 << k];
+                let mut k0 = vec![Block::ZERO; k];
+                let mut k1 = vec![Block::ZERO; k];
+                ggm.gen(prg.random_block(), &mut leaves, &mut k0, &mut k1);
+
+                base_msgs.extend(k0.into_iter().zip(k1).map(|(k0, k1)| [k0, k1]));
+
+                leaves.into_iter().map(Prg::from_seed).collect::<Vec<_>>()
+            })
+            .collect();
+
+        let base_msgs: [[Block; 2]; CSP] = base_msgs
+            .try_into()
+            .expect("k should divide the number of base OTs");
+
+        (
+            Receiver {
+                config: self.config,
+                state: state::Extension {
+                    prgs,
+                    ts: Vec::default(),
+                    choices: Vec::default(),
+                    index: 0,
+                    transfer_id: TransferId::default(),
+                    unchecked_ts: Vec::default(),
+                    unchecked_choices: Vec::default(),
+                },
+            },
+            base_msgs,
+        )
+    }
+}
+
+impl Receiver<state::Extension> {
+    /// Returns the current transfer id.
+    pub fn current_transfer_id(&self) -> TransferId {
+        self.state.transfer_id
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> usize {
+        self.state.ts.len()
+    }
+
+    /// Performs the SoftSpoken OT extension.
+    ///
+    /// The provided count _must_ be a multiple of 64, otherwise an error will be returned.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing the consistency check sacrifices 256 OTs, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # Streaming
+    ///
+    /// Extension can be performed in a streaming fashion by calling this method multiple times, sending
+    /// the `Extend` messages to the sender in-between calls.
+    ///
+    /// The freshly extended OTs are not available until after the consistency check has been
+    /// performed. See [`Receiver::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of OTs to extend (must be a multiple of 64).
+    pub fn extend(&mut self, count: usize) -> Result<Extend, ReceiverError> {
+        if count % 64 != 0 {
+            return Err(ReceiverError::InvalidCount(count));
+        }
+
+        const NROWS: usize = CSP;
+        let k = self.config.k();
+        let row_width = count / 8;
+
+        let mut ts = vec![0u8; NROWS * row_width];
+        let mut us = vec![0u8; NROWS / k * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.prgs
+                    .par_iter_mut()
+                    .zip(ts.par_chunks_exact_mut(k * row_width))
+                    .zip(us.par_chunks_exact_mut(row_width));
+            } else {
+                let iter = self.state.prgs
+                    .iter_mut()
+                    .zip(ts.chunks_exact_mut(k * row_width))
+                    .zip(us.chunks_exact_mut(row_width));
+            }
+        }
+
+        iter.for_each(|((prgs, t), u)| {
+            // Evaluate the small-field VOLE `u = Σ_x PRG(s_x)` and `v_j = Σ_x x_j · PRG(s_x)`.
+            let mut buf = vec![0u8; row_width];
+            for (x, prg) in prgs.iter_mut().enumerate() {
+                prg.fill_bytes(&mut buf);
+                u.iter_mut().zip(&buf).for_each(|(u, b)| *u ^= b);
+                for (j, t) in t.chunks_exact_mut(row_width).enumerate() {
+                    if (x >> j) & 1 == 1 {
+                        t.iter_mut().zip(&buf).for_each(|(t, b)| *t ^= b);
+                    }
+                }
+            }
+        });
+
+        // The `u` of the first VOLE is the choice vector, and the others are corrected to it.
+        let mut corrections = us.split_off(row_width);
+        let choice_vector = us;
+        corrections
+            .chunks_exact_mut(row_width)
+            .for_each(|correction| {
+                correction
+                    .iter_mut()
+                    .zip(&choice_vector)
+                    .for_each(|(c, x)| *c ^= x)
+            });
+
+        let choices = choice_vector
+            .into_iter()
+            .flat_map(|x| x.into_iter_lsb0())
+            .collect::<Vec<_>>();
+
+        matrix_transpose::transpose_bits(&mut ts, NROWS).expect("matrix is rectangular");
+
+        self.state.unchecked_ts.extend(
+            ts.chunks_exact(NROWS / 8)
+                .map(|t| Block::try_from(t).unwrap()),
+        );
+        self.state.unchecked_choices.extend(choices);
+
+        Ok(Extend { us: corrections })
+    }
+
+    /// Performs the correlation check for all outstanding OTS.
+    ///
+    /// This is the same check as [`kos::Receiver::check`](crate::kos::Receiver::check).
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing this check sacrifices 256 OTs for the consistency check, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure
+    /// coin-toss protocol **after** the receiver has sent their setup message, ie
+    /// after they have already committed to their choice vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    pub fn check(&mut self, chi_seed: Block) -> Result<Check, ReceiverError> {
+        // Make sure we have enough sacrificial OTs to perform the consistency check.
+        if self.state.unchecked_ts.len() < CSP + SSP {
+            return Err(ReceiverError::InsufficientSetup(
+                CSP + SSP,
+                self.state.unchecked_ts.len(),
+            ));
+        }
+
+        let mut unchecked_ts = std::mem::take(&mut self.state.unchecked_ts);
+        let mut unchecked_choices = std::mem::take(&mut self.state.unchecked_choices);
+
+        let check = receiver_check(chi_seed, &unchecked_ts, &unchecked_choices);
+
+        // Strip off the rows sacrificed for the consistency check.
+        let nrows = unchecked_ts.len() - (CSP + SSP);
+        unchecked_ts.truncate(nrows);
+        unchecked_choices.truncate(nrows);
+
+        self.state.ts.extend(unchecked_ts);
+        self.state.choices.extend(unchecked_choices);
+
+        Ok(check)
+    }
+
+    /// Returns random COTs, with the blocks being `q ⊕ x · Δ` for the sender's `q`.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of COTs.
+    pub fn correlated(
+        &mut self,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, ReceiverError> {
+        if count > self.state.ts.len() {
+            return Err(ReceiverError::InsufficientSetup(count, self.state.ts.len()));
+        }
+
+        // Keep the tweaks of later keys unique.
+        self.state.index += count;
+
+        Ok(RCOTReceiverOutput {
+            id: self.state.transfer_id.next(),
+            choices: self.state.choices.drain(..count).collect(),
+            msgs: self.state.ts.drain(..count).collect(),
+        })
+    }
+
+    /// Returns receiver's keys for the given number of OTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of keys to take.
+    pub fn keys(&mut self, count: usize) -> Result<ReceiverKeys, ReceiverError> {
+        if count > self.state.ts.len() {
+            return Err(ReceiverError::InsufficientSetup(count, self.state.ts.len()));
+        }
+
+        let id = self.state.transfer_id.next();
+        let keys = receiver_keys(self.state.index, &self.state.ts[..count]);
+
+        self.state.ts.drain(..count);
+
+        self.state.index += count;
+
+        Ok(ReceiverKeys {
+            id,
+            keys,
+            choices: self.state.choices.drain(..count).collect(),
+        })
+    }
+}
+
+/// SoftSpoken receiver's keys for a single transfer.
+///
+/// Returned by the [`Receiver::keys`] method, used in cases where the receiver
+/// wishes to reserve a set of keys for a transfer, but hasn't yet received the
+/// payload.
+pub struct ReceiverKeys {
+    /// Transfer ID
+    id: TransferId,
+    /// Decryption keys
+    keys: Vec<Block>,
+    /// The Receiver's choices. If derandomization is performed, these are the overwritten
+    /// with the derandomized choices.
+    choices: Vec<bool>,
+}
+
+opaque_debug::implement!(ReceiverKeys);
+
+impl ReceiverKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> TransferId {
+        self.id
+    }
+
+    /// Derandomizes the receiver's choices.
+    pub fn derandomize(&mut self, choices: &[bool]) -> Result<Derandomize, ReceiverError> {
+        if choices.len() != self.choices.len() {
+            return Err(ReceiverError::CountMismatch(
+                self.choices.len(),
+                choices.len(),
+            ));
+        }
+
+        Ok(derandomize(self.id, &mut self.choices, choices))
+    }
+
+    /// Decrypts the sender's payload.
+    pub fn decrypt_blocks(self, payload: SenderPayload) -> Result<Vec<Block>, ReceiverError> {
+        let SenderPayload { id, ciphertexts } = payload;
+
+        let Ciphertexts::Blocks { ciphertexts } = ciphertexts else {
+            return Err(ReceiverError::InvalidPayload(
+                "expected block ciphertexts".to_string(),
+            ));
+        };
+
+        if id != self.id {
+            return Err(ReceiverError::IdMismatch(self.id, id));
+        }
+
+        if ciphertexts.len() / 2 != self.keys.len() {
+            return Err(ReceiverError::CountMismatch(
+                self.keys.len(),
+                ciphertexts.len() / 2,
+            ));
+        }
+
+        Ok(decrypt_blocks(self.keys, self.choices, &ciphertexts))
+    }
+
+    /// Returns the choices and the keys
+    pub fn take_choices_and_keys(self) -> (Vec<bool>, Vec<Block>) {
+        (self.choices, self.keys)
+    }
+}
+
+/// The receiver's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The receiver's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The receiver's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The receiver's state after the setup phase.
+    ///
+    /// In this state the receiver performs OT extension (potentially multiple times). Also in this
+    /// state the receiver sends OT requests.
+    pub struct Extension {
+        /// PRGs seeded with the leaves of the GGM tree of each chunk of delta
+        pub(super) prgs: Vec<Vec<Prg>>,
+        /// Receiver's checked ts
+        pub(super) ts: Vec<Block>,
+        /// Receiver's random choices
+        pub(super) choices: Vec<bool>,
+        /// Current OT index
+        pub(super) index: usize,
+        /// Current transfer id
+        pub(super) transfer_id: TransferId,
+
+        /// Receiver's unchecked ts
+        pub(super) unchecked_ts: Vec<Block>,
+        /// Receiver's unchecked choices
+        pub(super) unchecked_choices: Vec<bool>,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
@@ -0,0 +1,416 @@
+use crate::{
+    extension::{encrypt_blocks, sender_check, sender_keys},
+    msgs::Derandomize,
+    softspoken::{
+        delta_chunks, extension_size,
+        msgs::{Check, Ciphertexts, Extend, SenderPayload},
+        SenderConfig, SenderError, CSP, SSP,
+    },
+    RCOTSenderOutput, TransferId,
+};
+
+use mpz_core::{ggm_tree::GgmTree, prg::Prg, Block};
+
+use rand::SeedableRng;
+use rand_core::RngCore;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Returns the base OT choices which puncture a GGM tree of depth `k` at the leaf `chunk`.
+fn puncture_choices(chunk: usize, k: usize) -> impl Iterator<Item = bool> {
+    // The bits of the punctured leaf are the complement of the choices, from the root down.
+    (0..k).rev().map(move |i| (chunk >> i) & 1 == 0)
+}
+
+/// SoftSpoken sender.
+#[derive(Debug, Default)]
+pub struct Sender<T: state::State = state::Initialized> {
+    config: SenderConfig,
+    state: T,
+}
+
+impl<T> Sender<T>
+where
+    T: state::State,
+{
+    /// Returns the Sender's configuration
+    pub fn config(&self) -> &SenderConfig {
+        &self.config
+    }
+}
+
+impl Sender {
+    /// Creates a new Sender
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration
+    pub fn new(config: SenderConfig) -> Self {
+        Sender {
+            config,
+            state: state::Initialized::default(),
+        }
+    }
+
+    /// Returns the sender's base OT choice bits for the given delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's global secret.
+    pub fn base_choices(&self, delta: Block) -> Vec<bool> {
+        let k = self.config.k();
+        delta_chunks(delta, k)
+            .into_iter()
+            .flat_map(|chunk| puncture_choices(chunk, k))
+            .collect()
+    }
+
+    /// Complete the setup phase of the protocol.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The sender's global secret.
+    /// * `keys` - The keys received during base OT, using the choices returned by
+    ///            [`Sender::base_choices`].
+    pub fn setup(self, delta: Block, keys: [Block; CSP]) -> Sender<state::Extension> {
+        let k = self.config.k();
+        let ggm = GgmTree::new(k);
+        let chunks = delta_chunks(delta, k);
+
+        let prgs = chunks
+            .iter()
+            .zip(keys.chunks_exact(k))
+            .map(|(&chunk, keys)| {
+                let choices = puncture_choices(chunk, k).collect::<Vec<_>>();
+
+                // Reconstruct every leaf except the one at `chunk`, which is set to zero. Its
+                // PRG is never used, as it is weighted by `chunk ⊕ chunk = 0`.
+                let mut leaves = vec![Block::ZERO; 1 << k];
+                ggm.reconstruct(&mut leaves, keys, &choices);
+
+                leaves.into_iter().map(Prg::from_seed).collect::<Vec<_>>()
+            })
+            .collect();
+
+        Sender {
+            config: self.config,
+            state: state::Extension {
+                delta,
+                chunks,
+                prgs,
+                qs: Vec::default(),
+                transfer_id: TransferId::default(),
+                counter: 0,
+                unchecked_qs: Vec::default(),
+            },
+        }
+    }
+}
+
+impl Sender<state::Extension> {
+    /// Returns the sender's delta.
+    pub fn delta(&self) -> Block {
+        self.state.delta
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> usize {
+        self.state.qs.len()
+    }
+
+    /// Performs the SoftSpoken OT extension.
+    ///
+    /// The provided count _must_ be a multiple of 64, otherwise an error will be returned.
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing the consistency check sacrifices 256 OTs, so be sure to extend enough to
+    /// compensate for this.
+    ///
+    /// # Streaming
+    ///
+    /// Extension can be performed in a streaming fashion by processing an extension in batches via
+    /// multiple calls to this method.
+    ///
+    /// The freshly extended OTs are not available until after the consistency check has been
+    /// performed. See [`Sender::check`].
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of additional OTs to extend (must be a multiple of 64).
+    /// * `extend` - The receiver's setup message.
+    pub fn extend(&mut self, count: usize, extend: Extend) -> Result<(), SenderError> {
+        if count % 64 != 0 {
+            return Err(SenderError::InvalidCount(count));
+        }
+
+        const NROWS: usize = CSP;
+        let k = self.config.k();
+        let row_width = count / 8;
+
+        let Extend { us } = extend;
+
+        if us.len() != extension_size(count, k) {
+            return Err(SenderError::InvalidExtend);
+        }
+
+        // The first VOLE is not corrected, as its `u` is the receiver's choice vector.
+        let zero = vec![0u8; row_width];
+        let corrections = std::iter::once(zero.as_slice())
+            .chain(us.chunks_exact(row_width))
+            .collect::<Vec<_>>();
+
+        let mut qs = vec![0u8; NROWS * row_width];
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "rayon")] {
+                let iter = self.state.chunks
+                    .par_iter()
+                    .zip(self.state.prgs.par_iter_mut())
+                    .zip(qs.par_chunks_exact_mut(k * row_width))
+                    .zip(corrections.par_iter());
+            } else {
+                let iter = self.state.chunks
+                    .iter()
+                    .zip(self.state.prgs.iter_mut())
+                    .zip(qs.chunks_exact_mut(k * row_width))
+                    .zip(corrections.iter());
+            }
+        }
+
+        iter.for_each(|(((&chunk, prgs), q), correction)| {
+            // Evaluate the small-field VOLE `w_j = Σ_x (x ⊕ Δ)_j · PRG(s_x) = v_j ⊕ Δ_j · u`.
+            let mut buf = vec![0u8; row_width];
+            for (x, prg) in prgs.iter_mut().enumerate() {
+                prg.fill_bytes(&mut buf);
+                for (j, q) in q.chunks_exact_mut(row_width).enumerate() {
+                    if ((x ^ chunk) >> j) & 1 == 1 {
+                        q.iter_mut().zip(&buf).for_each(|(q, b)| *q ^= b);
+                    }
+                }
+            }
+
+            // Correct `u` to the choice vector, so that `q_j = t_j ⊕ Δ_j · x`.
+            for (j, q) in q.chunks_exact_mut(row_width).enumerate() {
+                if (chunk >> j) & 1 == 1 {
+                    q.iter_mut().zip(*correction).for_each(|(q, c)| *q ^= c);
+                }
+            }
+        });
+
+        matrix_transpose::transpose_bits(&mut qs, NROWS).expect("matrix is rectangular");
+
+        self.state
+            .unchecked_qs
+            .extend(qs.chunks_exact(NROWS / 8).map(|q| {
+                let q: Block = q.try_into().unwrap();
+                q
+            }));
+
+        Ok(())
+    }
+
+    /// Performs the correlation check for all outstanding OTS.
+    ///
+    /// This is the same check as [`kos::Sender::check`](crate::kos::Sender::check).
+    ///
+    /// # Sacrificial OTs
+    ///
+    /// Performing this check sacrifices 256 OTs for the consistency check, so be sure to
+    /// extend enough OTs to compensate for this.
+    ///
+    /// # ⚠️ Warning ⚠️
+    ///
+    /// The provided seed must be unbiased! It should be generated using a secure
+    /// coin-toss protocol **after** the receiver has sent their extension message, ie
+    /// after they have already committed to their choice vectors.
+    ///
+    /// # Arguments
+    ///
+    /// * `chi_seed` - The seed used to generate the consistency check weights.
+    /// * `receiver_check` - The receiver's consistency check message.
+    pub fn check(&mut self, chi_seed: Block, receiver_check: Check) -> Result<(), SenderError> {
+        // Make sure we have enough sacrificial OTs to perform the consistency check.
+        if self.state.unchecked_qs.len() < CSP + SSP {
+            return Err(SenderError::InsufficientSetup(
+                CSP + SSP,
+                self.state.unchecked_qs.len(),
+            ));
+        }
+
+        let mut unchecked_qs = std::mem::take(&mut self.state.unchecked_qs);
+
+        // The Receiver is malicious.
+        if !sender_check(self.state.delta, chi_seed, &unchecked_qs, receiver_check) {
+            return Err(SenderError::ConsistencyCheckFailed);
+        }
+
+        // Strip off the rows sacrificed for the consistency check.
+        let nrows = unchecked_qs.len() - (CSP + SSP);
+        unchecked_qs.truncate(nrows);
+
+        self.state.qs.extend(unchecked_qs);
+
+        Ok(())
+    }
+
+    /// Returns random COTs, with the receiver's blocks being `q ⊕ x · Δ`.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of COTs.
+    pub fn correlated(&mut self, count: usize) -> Result<RCOTSenderOutput<Block>, SenderError> {
+        if count > self.state.qs.len() {
+            return Err(SenderError::InsufficientSetup(count, self.state.qs.len()));
+        }
+
+        // Keep the tweaks of later keys unique.
+        self.state.counter += count;
+
+        Ok(RCOTSenderOutput {
+            id: self.state.transfer_id.next(),
+            msgs: self.state.qs.drain(..count).collect(),
+        })
+    }
+
+    /// Reserves a set of keys which can be used to encrypt a payload later.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of keys to reserve.
+    pub fn keys(&mut self, count: usize) -> Result<SenderKeys, SenderError> {
+        if count > self.state.qs.len() {
+            return Err(SenderError::InsufficientSetup(count, self.state.qs.len()));
+        }
+
+        let id = self.state.transfer_id.next();
+        let keys = sender_keys(
+            self.state.delta,
+            self.state.counter,
+            &self.state.qs[..count],
+        );
+
+        self.state.qs.drain(..count);
+        self.state.counter += count;
+
+        Ok(SenderKeys {
+            id,
+            keys,
+            derandomize: None,
+        })
+    }
+}
+
+/// SoftSpoken sender's keys for a single transfer.
+///
+/// Returned by the [`Sender::keys`] method, used in cases where the sender
+/// wishes to reserve a set of keys for use later, while still being able to process
+/// other payloads.
+pub struct SenderKeys {
+    /// Transfer ID
+    id: TransferId,
+    /// Encryption keys
+    keys: Vec<[Block; 2]>,
+    /// Derandomization
+    derandomize: Option<Derandomize>,
+}
+
+opaque_debug::implement!(SenderKeys);
+
+impl SenderKeys {
+    /// Returns the transfer ID.
+    pub fn id(&self) -> TransferId {
+        self.id
+    }
+
+    /// Applies Beaver derandomization to correct the receiver's choices made during extension.
+    pub fn derandomize(&mut self, derandomize: Derandomize) -> Result<(), SenderError> {
+        if derandomize.id != self.id {
+            return Err(SenderError::IdMismatch(self.id, derandomize.id));
+        }
+
+        if derandomize.count as usize != self.keys.len() {
+            return Err(SenderError::CountMismatch(
+                self.keys.len(),
+                derandomize.count as usize,
+            ));
+        }
+
+        self.derandomize = Some(derandomize);
+
+        Ok(())
+    }
+
+    /// Encrypts the provided messages using the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `msgs` - The messages to encrypt
+    pub fn encrypt_blocks(self, msgs: &[[Block; 2]]) -> Result<SenderPayload, SenderError> {
+        if msgs.len() != self.keys.len() {
+            return Err(SenderError::InsufficientSetup(msgs.len(), self.keys.len()));
+        }
+
+        let ciphertexts = encrypt_blocks(self.keys, msgs, self.derandomize);
+
+        Ok(SenderPayload {
+            id: self.id,
+            ciphertexts: Ciphertexts::Blocks { ciphertexts },
+        })
+    }
+
+    /// Returns the keys
+    pub fn take_keys(self) -> Vec<[Block; 2]> {
+        self.keys
+    }
+}
+
+/// The sender's state.
+pub mod state {
+    use super::*;
+
+    mod sealed {
+        pub trait Sealed {}
+
+        impl Sealed for super::Initialized {}
+        impl Sealed for super::Extension {}
+    }
+
+    /// The sender's state.
+    pub trait State: sealed::Sealed {}
+
+    /// The sender's initial state.
+    #[derive(Default)]
+    pub struct Initialized {}
+
+    impl State for Initialized {}
+
+    opaque_debug::implement!(Initialized);
+
+    /// The sender's state after the setup phase.
+    ///
+    /// In this state the sender performs OT extension (potentially multiple times). Also in this
+    /// state the sender responds to OT requests.
+    pub struct Extension {
+        /// Sender's global secret
+        pub(super) delta: Block,
+        /// The chunks of delta, each being the punctured leaf of a GGM tree
+        pub(super) chunks: Vec<usize>,
+        /// PRGs seeded with the leaves of the punctured GGM tree of each chunk
+        pub(super) prgs: Vec<Vec<Prg>>,
+        /// Sender's checked qs
+        pub(super) qs: Vec<Block>,
+
+        /// Current transfer id
+        pub(super) transfer_id: TransferId,
+        /// Current OT counter
+        pub(super) counter: usize,
+
+        /// Sender's unchecked qs
+        pub(super) unchecked_qs: Vec<Block>,
+    }
+
+    impl State for Extension {}
+
+    opaque_debug::implement!(Extension);
+}
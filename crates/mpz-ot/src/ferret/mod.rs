@@ -0,0 +1,329 @@
+//! An implementation of the [`Ferret`](https://eprint.iacr.org/2020/924.pdf) correlated oblivious
+//! transfer extension protocol.
+//!
+//! Ferret is bootstrapped during setup from random COTs of another protocol, e.g.
+//! [`kos`](crate::kos), after which each extension produces `n - k` random COTs. The random COTs
+//! consumed by the single-point COTs of an extension are reserved from the output of the previous
+//! extension, so the bootstrap protocol is not used after setup.
+
+mod error;
+mod mpcot;
+mod pool;
+mod receiver;
+mod sender;
+mod spcot;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_core::lpn::LpnParameters;
+pub use mpz_ot_core::ferret::{
+    LpnCodeType, LpnType, LPN_PARAMETERS_DUAL, LPN_PARAMETERS_REGULAR, LPN_PARAMETERS_UNIFORM,
+};
+
+/// Ferret configuration.
+#[derive(Debug, Clone, Copy)]
+pub struct FerretConfig {
+    lpn_parameters: LpnParameters,
+    lpn_type: LpnType,
+    lpn_code: LpnCodeType,
+}
+
+impl FerretConfig {
+    /// Creates a new configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `lpn_parameters` - The LPN parameters. With [`LpnType::Regular`], `n` must be a multiple
+    ///                      of `t`.
+    /// * `lpn_type` - The type of the LPN error vectors.
+    ///
+    /// The configuration uses the local linear code of the Ferret paper, see
+    /// [`FerretConfig::with_lpn_code`] to select another one.
+    pub fn new(lpn_parameters: LpnParameters, lpn_type: LpnType) -> Self {
+        Self {
+            lpn_parameters,
+            lpn_type,
+            lpn_code: LpnCodeType::Local,
+        }
+    }
+
+    /// Sets the LPN code.
+    ///
+    /// The dual codes, e.g. [`LpnCodeType::ExpandAccumulate`], compress the MPCOT output instead
+    /// of adding it to an LPN secret, so the setup only bootstraps the random COTs consumed by
+    /// the first extension. They are used with parameters such as [`LPN_PARAMETERS_DUAL`].
+    pub fn with_lpn_code(mut self, lpn_code: LpnCodeType) -> Self {
+        self.lpn_code = lpn_code;
+        self
+    }
+
+    /// Returns the LPN parameters.
+    pub fn lpn_parameters(&self) -> LpnParameters {
+        self.lpn_parameters
+    }
+
+    /// Returns the type of the LPN error vectors.
+    pub fn lpn_type(&self) -> LpnType {
+        self.lpn_type
+    }
+
+    /// Returns the LPN code.
+    pub fn lpn_code(&self) -> LpnCodeType {
+        self.lpn_code
+    }
+}
+
+impl Default for FerretConfig {
+    fn default() -> Self {
+        Self::new(LPN_PARAMETERS_REGULAR, LpnType::Regular)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use futures::TryFutureExt;
+    use mpz_common::{executor::test_st_executor, Allocate, Preprocess};
+    use mpz_core::Block;
+    use mpz_ot_core::test::assert_cot;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    use crate::{
+        ideal::{cot::ideal_rcot_with_delta, ot::ideal_ot},
+        kos, OTError, OTSetup, RandomCOTReceiver, RandomCOTSender,
+    };
+
+    const LPN_PARAMETERS_TEST: LpnParameters = LpnParameters {
+        n: 9600,
+        k: 1220,
+        t: 600,
+    };
+
+    #[rstest]
+    #[case::regular(LpnType::Regular)]
+    #[case::uniform(LpnType::Uniform)]
+    #[tokio::test]
+    async fn test_ferret(#[case] lpn_type: LpnType) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let delta = Block::random(&mut rng);
+
+        let (base_sender, base_receiver) = ideal_ot();
+        let mut kos_sender = kos::Sender::new(kos::SenderConfig::default(), base_receiver);
+        let mut kos_receiver = kos::Receiver::new(kos::ReceiverConfig::default(), base_sender);
+
+        tokio::try_join!(
+            kos_sender
+                .setup_with_delta(&mut ctx_sender, delta)
+                .map_err(OTError::from),
+            kos_receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let config = FerretConfig::new(LPN_PARAMETERS_TEST, lpn_type);
+        let mut sender = Sender::new(config, delta, kos_sender);
+        let mut receiver = Receiver::new(config, kos_receiver);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // Preprocess more than a single extension produces.
+        let count = 2 * (LPN_PARAMETERS_TEST.n - LPN_PARAMETERS_TEST.k);
+        sender.alloc(count);
+        receiver.alloc(count);
+
+        tokio::try_join!(
+            sender.preprocess(&mut ctx_sender),
+            receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        assert!(sender.remaining().unwrap() >= count);
+        assert_eq!(sender.remaining().unwrap(), receiver.remaining().unwrap());
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send_random_correlated(&mut ctx_sender, count),
+            receiver.receive_random_correlated(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_sender.msgs.len(), count);
+        assert_cot(
+            delta,
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+
+        // Extends on demand if there are not enough COTs left.
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send_random_correlated(&mut ctx_sender, count),
+            receiver.receive_random_correlated(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_cot(
+            delta,
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ferret_ideal_bootstrap() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let delta = Block::random(&mut rng);
+
+        let (rcot_sender, rcot_receiver) = ideal_rcot_with_delta(delta);
+
+        let config = FerretConfig::new(LPN_PARAMETERS_TEST, LpnType::Regular);
+        let mut sender = Sender::new(config, delta, rcot_sender);
+        let mut receiver = Receiver::new(config, rcot_receiver);
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            async {
+                sender.setup(&mut ctx_sender).await?;
+                sender.send_random_correlated(&mut ctx_sender, 10).await
+            },
+            async {
+                receiver.setup(&mut ctx_receiver).await?;
+                receiver
+                    .receive_random_correlated(&mut ctx_receiver, 10)
+                    .await
+            }
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_cot(
+            delta,
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ferret_self_bootstrap() {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let delta = Block::random(&mut rng);
+
+        let (rcot_sender, rcot_receiver) = ideal_rcot_with_delta(delta);
+
+        let config = FerretConfig::new(LPN_PARAMETERS_TEST, LpnType::Regular);
+        let mut sender = Sender::new(config, delta, rcot_sender);
+        let mut receiver = Receiver::new(config, rcot_receiver);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        // After setup, every extension is fed by the output of the previous one.
+        let count = 1 << 20;
+        for _ in 0..2 {
+            let (output_sender, output_receiver) = tokio::try_join!(
+                sender.send_random_correlated(&mut ctx_sender, count),
+                receiver.receive_random_correlated(&mut ctx_receiver, count)
+            )
+            .unwrap();
+
+            assert_eq!(output_sender.id, output_receiver.id);
+            assert_eq!(output_receiver.msgs.len(), count);
+            assert_cot(
+                delta,
+                &output_receiver.choices,
+                &output_sender.msgs,
+                &output_receiver.msgs,
+            );
+        }
+    }
+
+    #[rstest]
+    #[case::expand_accumulate(LpnCodeType::ExpandAccumulate)]
+    #[case::expand_convolute(LpnCodeType::ExpandConvolute)]
+    #[tokio::test]
+    async fn test_ferret_dual(#[case] lpn_code: LpnCodeType) {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let delta = Block::random(&mut rng);
+
+        let (rcot_sender, rcot_receiver) = ideal_rcot_with_delta(delta);
+
+        let lpn_parameters = LpnParameters {
+            n: 8192,
+            k: 4096,
+            t: 64,
+        };
+        let config = FerretConfig::new(lpn_parameters, LpnType::Regular).with_lpn_code(lpn_code);
+        let mut sender = Sender::new(config, delta, rcot_sender);
+        let mut receiver = Receiver::new(config, rcot_receiver);
+
+        tokio::try_join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let count = 2 * (lpn_parameters.n - lpn_parameters.k);
+        let (output_sender, output_receiver) = tokio::try_join!(
+            sender.send_random_correlated(&mut ctx_sender, count),
+            receiver.receive_random_correlated(&mut ctx_receiver, count)
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_receiver.msgs.len(), count);
+        assert_cot(
+            delta,
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ferret_insufficient_output() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let delta = Block::random(&mut ChaCha12Rng::seed_from_u64(0));
+
+        let (rcot_sender, rcot_receiver) = ideal_rcot_with_delta(delta);
+
+        // An extension produces 1024 - 768 = 256 random COTs but consumes 32 · 5 + 128 = 288.
+        let config = FerretConfig::new(
+            LpnParameters {
+                n: 1024,
+                k: 768,
+                t: 32,
+            },
+            LpnType::Regular,
+        );
+        let mut sender = Sender::new(config, delta, rcot_sender);
+        let mut receiver = Receiver::new(config, rcot_receiver);
+
+        let (sender_result, receiver_result) = tokio::join!(
+            sender.setup(&mut ctx_sender),
+            receiver.setup(&mut ctx_receiver)
+        );
+
+        assert!(sender_result.is_err());
+        assert!(receiver_result.is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Multi-point COT, see Figure 7 of the Ferret paper.
+//!
+//! Regular error vectors are handled by splitting the output into `t` intervals of equal length,
+//! while uniform error vectors are assigned to buckets using Cuckoo hashing.
+
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot_core::ferret::{
+    mpcot::{
+        msgs::HashSeed,
+        receiver::{state as uniform_receiver_state, Receiver as UniformReceiver},
+        receiver_regular::{state as regular_receiver_state, Receiver as RegularReceiver},
+        regular_depths,
+        sender::{state as uniform_sender_state, Sender as UniformSender},
+        sender_regular::{state as regular_sender_state, Sender as RegularSender},
+        uniform_depths,
+    },
+    LpnType,
+};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::ferret::{spcot, ReceiverError, SenderError};
+
+/// MPCOT sender.
+#[derive(Debug)]
+pub(crate) enum Sender {
+    Uniform(UniformSender<uniform_sender_state::PreExtension>),
+    Regular(RegularSender<regular_sender_state::PreExtension>),
+}
+
+impl Sender {
+    /// Sets up the MPCOT sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `delta` - The sender's global secret.
+    /// * `lpn_type` - The type of the error vectors.
+    pub(crate) async fn setup<Ctx: Context>(
+        ctx: &mut Ctx,
+        delta: Block,
+        lpn_type: LpnType,
+    ) -> Result<Self, SenderError> {
+        Ok(match lpn_type {
+            LpnType::Uniform => {
+                let hash_seed: HashSeed = ctx.io_mut().expect_next().await?;
+                Sender::Uniform(UniformSender::new().setup(delta, hash_seed))
+            }
+            LpnType::Regular => Sender::Regular(RegularSender::new().setup(delta)),
+        })
+    }
+
+    /// Prepares the extension of `t`-point COTs of length `n`, returning the depths of the GGM
+    /// trees.
+    pub(crate) fn pre_extend(
+        self,
+        t: u32,
+        n: u32,
+    ) -> Result<(SenderExtension, Vec<usize>), SenderError> {
+        Ok(match self {
+            Sender::Uniform(sender) => {
+                let (sender, hs) = sender.pre_extend(t, n)?;
+                (SenderExtension::Uniform(sender), hs)
+            }
+            Sender::Regular(sender) => {
+                let (sender, hs) = sender.pre_extend(t, n)?;
+                (SenderExtension::Regular(sender), hs)
+            }
+        })
+    }
+}
+
+/// MPCOT sender which is ready to extend.
+#[derive(Debug)]
+pub(crate) enum SenderExtension {
+    Uniform(UniformSender<uniform_sender_state::Extension>),
+    Regular(RegularSender<regular_sender_state::Extension>),
+}
+
+impl SenderExtension {
+    /// Extends the multi-point COTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `delta` - The sender's global secret.
+    /// * `hs` - The depths of the GGM trees.
+    /// * `qs` - The random COTs consumed by SPCOT, see [`spcot::cot_count`].
+    pub(crate) async fn extend<Ctx: Context>(
+        self,
+        ctx: &mut Ctx,
+        delta: Block,
+        hs: &[usize],
+        qs: &[Block],
+    ) -> Result<(Sender, Vec<Block>), SenderError> {
+        let st = spcot::send(ctx, delta, hs, qs).await?;
+
+        Ok(match self {
+            SenderExtension::Uniform(sender) => {
+                let (sender, s) = sender.extend(&st)?;
+                (Sender::Uniform(sender), s)
+            }
+            SenderExtension::Regular(sender) => {
+                let (sender, s) = sender.extend(&st)?;
+                (Sender::Regular(sender), s)
+            }
+        })
+    }
+}
+
+/// MPCOT receiver.
+#[derive(Debug)]
+pub(crate) enum Receiver {
+    Uniform(UniformReceiver<uniform_receiver_state::PreExtension>),
+    Regular(RegularReceiver<regular_receiver_state::PreExtension>),
+}
+
+impl Receiver {
+    /// Sets up the MPCOT receiver, returning the depths of the GGM trees used to extend `t`-point
+    /// COTs of length `n`.
+    ///
+    /// The depths only depend on public values, so they are computed before the points are
+    /// sampled.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `lpn_type` - The type of the error vectors.
+    /// * `t` - The number of points.
+    /// * `n` - The length of the COTs.
+    pub(crate) async fn setup<Ctx: Context>(
+        ctx: &mut Ctx,
+        lpn_type: LpnType,
+        t: u32,
+        n: u32,
+    ) -> Result<(Self, Vec<usize>), ReceiverError> {
+        Ok(match lpn_type {
+            LpnType::Uniform => {
+                let seed = Block::random(&mut thread_rng());
+                let (receiver, hash_seed) = UniformReceiver::new().setup(seed);
+                let hs = uniform_depths(&hash_seed, t, n)
+                    .map_err(|e| ReceiverError::ConfigError(e.to_string()))?;
+                ctx.io_mut().send(hash_seed).await?;
+                (Receiver::Uniform(receiver), hs)
+            }
+            LpnType::Regular => {
+                let hs =
+                    regular_depths(t, n).map_err(|e| ReceiverError::ConfigError(e.to_string()))?;
+                (Receiver::Regular(RegularReceiver::new().setup()), hs)
+            }
+        })
+    }
+
+    /// Prepares the extension of COTs of length `n` with the points `alphas`, returning the
+    /// queries for SPCOT.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn pre_extend(
+        self,
+        alphas: &[u32],
+        n: u32,
+    ) -> Result<(ReceiverExtension, Vec<(usize, u32)>), ReceiverError> {
+        Ok(match self {
+            Receiver::Uniform(receiver) => {
+                let (receiver, queries) = receiver.pre_extend(alphas, n)?;
+                (ReceiverExtension::Uniform(receiver), queries)
+            }
+            Receiver::Regular(receiver) => {
+                let (receiver, queries) = receiver.pre_extend(alphas, n)?;
+                (ReceiverExtension::Regular(receiver), queries)
+            }
+        })
+    }
+}
+
+/// MPCOT receiver which is ready to extend.
+#[derive(Debug)]
+pub(crate) enum ReceiverExtension {
+    Uniform(UniformReceiver<uniform_receiver_state::Extension>),
+    Regular(RegularReceiver<regular_receiver_state::Extension>),
+}
+
+impl ReceiverExtension {
+    /// Extends the multi-point COTs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `queries` - The SPCOT queries.
+    /// * `rs` - The choice bits of the random COTs consumed by SPCOT, see [`spcot::cot_count`].
+    /// * `ts` - The received blocks of the random COTs consumed by SPCOT.
+    pub(crate) async fn extend<Ctx: Context>(
+        self,
+        ctx: &mut Ctx,
+        queries: &[(usize, u32)],
+        rs: &[bool],
+        ts: &[Block],
+    ) -> Result<(Receiver, Vec<Block>), ReceiverError> {
+        let rt = spcot::receive(ctx, queries, rs, ts).await?;
+
+        Ok(match self {
+            ReceiverExtension::Uniform(receiver) => {
+                let (receiver, r) = receiver.extend(&rt)?;
+                (Receiver::Uniform(receiver), r)
+            }
+            ReceiverExtension::Regular(receiver) => {
+                let (receiver, r) = receiver.extend(&rt)?;
+                (Receiver::Regular(receiver), r)
+            }
+        })
+    }
+}
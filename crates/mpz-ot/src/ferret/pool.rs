@@ -0,0 +1,86 @@
+//! A buffered pool of random COTs.
+
+/// A pool of the random COTs produced by Ferret extensions.
+///
+/// Every extension consumes random COTs for its single-point COTs, which are reserved from the
+/// output of the previous extension. The rest of the output can be consumed by the caller.
+#[derive(Debug)]
+pub(crate) struct Pool<T> {
+    /// Random COTs reserved for the next extension.
+    reserve: Vec<T>,
+    /// Random COTs which can be consumed.
+    available: Vec<T>,
+}
+
+impl<T> Pool<T> {
+    /// Creates a new pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `reserve` - The random COTs reserved for the first extension.
+    pub(crate) fn new(reserve: Vec<T>) -> Self {
+        Self {
+            reserve,
+            available: Vec::new(),
+        }
+    }
+
+    /// Returns the number of random COTs which can be consumed.
+    pub(crate) fn available(&self) -> usize {
+        self.available.len()
+    }
+
+    /// Takes `count` reserved random COTs for an extension, returning `None` if the reserve is
+    /// not sufficient.
+    pub(crate) fn take_reserve(&mut self, count: usize) -> Option<Vec<T>> {
+        if self.reserve.len() < count {
+            return None;
+        }
+
+        Some(self.reserve.drain(..count).collect())
+    }
+
+    /// Refills the pool with the output of an extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output of the extension.
+    /// * `count` - The number of random COTs consumed by the next extension.
+    pub(crate) fn refill(&mut self, mut output: Vec<T>, count: usize) {
+        let reserved = count.saturating_sub(self.reserve.len()).min(output.len());
+        self.reserve.extend(output.drain(..reserved));
+        self.available.extend(output);
+    }
+
+    /// Takes `count` random COTs, returning `None` if not enough are available.
+    pub(crate) fn take(&mut self, count: usize) -> Option<Vec<T>> {
+        if self.available.len() < count {
+            return None;
+        }
+
+        Some(self.available.drain(..count).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool() {
+        let mut pool = Pool::new(vec![0, 1, 2]);
+
+        assert_eq!(pool.available(), 0);
+        assert_eq!(pool.take_reserve(4), None);
+        assert_eq!(pool.take_reserve(2), Some(vec![0, 1]));
+
+        // The reserve is topped up before any COTs become available.
+        pool.refill((3..10).collect(), 3);
+
+        assert_eq!(pool.available(), 5);
+        assert_eq!(pool.take_reserve(3), Some(vec![2, 3, 4]));
+        assert_eq!(pool.take(6), None);
+        assert_eq!(pool.take(2), Some(vec![5, 6]));
+        assert_eq!(pool.available(), 3);
+    }
+}
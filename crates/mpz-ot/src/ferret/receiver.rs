@@ -0,0 +1,275 @@
+use std::mem;
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::{Allocate, Context, Preprocess};
+use mpz_core::Block;
+use mpz_ot_core::{
+    ferret::receiver::{state, Receiver as ReceiverCore},
+    RCOTReceiverOutput, TransferId,
+};
+use rand::thread_rng;
+use serio::SinkExt as _;
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    ferret::{mpcot, pool::Pool, spcot, FerretConfig, ReceiverError},
+    OTError, OTSetup, RandomCOTReceiver,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(ReceiverCore<state::Initialized>),
+    Extension(Box<Extension>),
+    Error,
+}
+
+#[derive(Debug)]
+pub(crate) struct Extension {
+    core: ReceiverCore<state::Extension>,
+    /// MPCOT receiver, prepared for the next extension.
+    mpcot: mpcot::ReceiverExtension,
+    /// The SPCOT queries of the next extension.
+    queries: Vec<(usize, u32)>,
+    pool: Pool<(bool, Block)>,
+}
+
+/// Ferret receiver.
+#[derive(Debug)]
+pub struct Receiver<RandomCOT> {
+    state: State,
+    config: FerretConfig,
+    rcot: RandomCOT,
+    alloc: usize,
+    transfer_id: TransferId,
+}
+
+impl<RandomCOT: Send> Receiver<RandomCOT> {
+    /// Creates a new Receiver.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Receiver's configuration.
+    /// * `rcot` - The random COT used for bootstrapping.
+    pub fn new(config: FerretConfig, rcot: RandomCOT) -> Self {
+        Self {
+            state: State::Initialized(ReceiverCore::new()),
+            config,
+            rcot,
+            alloc: 0,
+            transfer_id: TransferId::default(),
+        }
+    }
+
+    /// The number of remaining COTs which can be consumed.
+    pub fn remaining(&self) -> Result<usize, ReceiverError> {
+        Ok(self.state.try_as_extension()?.pool.available())
+    }
+
+    /// Returns random COTs from the bootstrap protocol.
+    async fn bootstrap<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(Vec<bool>, Vec<Block>), ReceiverError>
+    where
+        RandomCOT: RandomCOTReceiver<Ctx, bool, Block> + Preprocess<Ctx, Error = OTError>,
+    {
+        self.rcot.alloc(count);
+        self.rcot.preprocess(ctx).await?;
+
+        let RCOTReceiverOutput { choices, msgs, .. } =
+            self.rcot.receive_random_correlated(ctx, count).await?;
+
+        if choices.len() != count || msgs.len() != count {
+            return Err(ReceiverError::InvalidLength {
+                expected: count,
+                actual: choices.len().min(msgs.len()),
+            });
+        }
+
+        Ok((choices, msgs))
+    }
+
+    async fn _setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError>
+    where
+        RandomCOT:
+            OTSetup<Ctx> + RandomCOTReceiver<Ctx, bool, Block> + Preprocess<Ctx, Error = OTError>,
+    {
+        let core = mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        self.rcot.setup(ctx).await?;
+
+        let lpn_parameters = self.config.lpn_parameters();
+        let lpn_type = self.config.lpn_type();
+        let lpn_code = self.config.lpn_code();
+
+        let (mpcot, hs) = mpcot::Receiver::setup(
+            ctx,
+            lpn_type,
+            lpn_parameters.t as u32,
+            lpn_parameters.n as u32,
+        )
+        .await?;
+
+        // Every extension must produce the random COTs consumed by the next one, so that only
+        // the setup is bootstrapped.
+        let count = spcot::cot_count(hs.iter().copied());
+        let produced = lpn_parameters.n.saturating_sub(lpn_parameters.k);
+        if produced <= count {
+            return Err(ReceiverError::ConfigError(format!(
+                "an extension produces {produced} random COTs but consumes {count}"
+            )));
+        }
+
+        // The bootstrap protocol is used once, as some protocols, e.g. KOS, can only be extended
+        // once.
+        let secret_len = lpn_code.secret_len(&lpn_parameters);
+        let (mut u, mut w) = self.bootstrap(ctx, secret_len + count).await?;
+        let choices = u.split_off(secret_len);
+        let msgs = w.split_off(secret_len);
+
+        let seed = Block::random(&mut thread_rng());
+        let (mut core, seed) = core.setup(lpn_parameters, lpn_type, lpn_code, seed, &u, &w)?;
+        ctx.io_mut().send(seed).await?;
+
+        let (alphas, n) = core.get_mpcot_query();
+        let (mpcot, queries) = mpcot.pre_extend(&alphas, n as u32)?;
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            mpcot,
+            queries,
+            pool: Pool::new(choices.into_iter().zip(msgs).collect()),
+        }));
+
+        Ok(())
+    }
+
+    /// Performs Ferret extension, producing `n - k` random COTs of which those consumed by the
+    /// next extension are reserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    pub async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), ReceiverError> {
+        let Extension {
+            mut core,
+            mpcot,
+            queries,
+            mut pool,
+        } = *mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let (rs, ts): (Vec<bool>, Vec<Block>) = pool
+            .take_reserve(spcot::cot_count(queries.iter().map(|(h, _)| *h)))
+            .ok_or(ReceiverError::InsufficientReserve)?
+            .into_iter()
+            .unzip();
+
+        let (mpcot, r) = mpcot.extend(ctx, &queries, &rs, &ts).await?;
+
+        let (mut core, (choices, msgs)) =
+            Backend::spawn(move || core.extend(&r).map(|output| (core, output))).await?;
+
+        // Prepare the next extension, so that its random COTs can be reserved.
+        let (alphas, n) = core.get_mpcot_query();
+        let (mpcot, queries) = mpcot.pre_extend(&alphas, n as u32)?;
+
+        pool.refill(
+            choices.into_iter().zip(msgs).collect(),
+            spcot::cot_count(queries.iter().map(|(h, _)| *h)),
+        );
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            mpcot,
+            queries,
+            pool,
+        }));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> OTSetup<Ctx> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx>
+        + RandomCOTReceiver<Ctx, bool, Block>
+        + Preprocess<Ctx, Error = OTError>
+        + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        self._setup(ctx).await.map_err(OTError::from)
+    }
+}
+
+impl<RandomCOT> Allocate for Receiver<RandomCOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> Preprocess<Ctx> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx>
+        + RandomCOTReceiver<Ctx, bool, Block>
+        + Preprocess<Ctx, Error = OTError>
+        + Send,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        while self.remaining()? < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> RandomCOTReceiver<Ctx, bool, Block> for Receiver<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: Send,
+{
+    async fn receive_random_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        while self.remaining()? < count {
+            self.extend(ctx).await?;
+        }
+
+        let (choices, msgs) = self
+            .state
+            .try_as_extension_mut()
+            .map_err(ReceiverError::from)?
+            .pool
+            .take(count)
+            .expect("pool should have enough random COTs after extension")
+            .into_iter()
+            .unzip();
+
+        Ok(RCOTReceiverOutput {
+            id: self.transfer_id.next(),
+            choices,
+            msgs,
+        })
+    }
+}
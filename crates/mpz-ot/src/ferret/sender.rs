@@ -0,0 +1,259 @@
+use std::mem;
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_common::{Allocate, Context, Preprocess};
+use mpz_core::Block;
+use mpz_ot_core::{
+    ferret::{
+        msgs::LpnMatrixSeed,
+        sender::{state, Sender as SenderCore},
+    },
+    RCOTSenderOutput, TransferId,
+};
+use serio::stream::IoStreamExt as _;
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    ferret::{mpcot, pool::Pool, spcot, FerretConfig, SenderError},
+    OTError, OTSetup, RandomCOTSender,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(Box<Extension>),
+    Error,
+}
+
+#[derive(Debug)]
+pub(crate) struct Extension {
+    core: SenderCore<state::Extension>,
+    /// MPCOT sender, prepared for the next extension.
+    mpcot: mpcot::SenderExtension,
+    /// The depths of the GGM trees of the next extension.
+    hs: Vec<usize>,
+    pool: Pool<Block>,
+}
+
+/// Ferret sender.
+#[derive(Debug)]
+pub struct Sender<RandomCOT> {
+    state: State,
+    config: FerretConfig,
+    delta: Block,
+    rcot: RandomCOT,
+    alloc: usize,
+    transfer_id: TransferId,
+}
+
+impl<RandomCOT: Send> Sender<RandomCOT> {
+    /// Creates a new Sender.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration.
+    /// * `delta` - The Sender's global secret, which must be the correlation of `rcot`.
+    /// * `rcot` - The random COT used for bootstrapping.
+    pub fn new(config: FerretConfig, delta: Block, rcot: RandomCOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new()),
+            config,
+            delta,
+            rcot,
+            alloc: 0,
+            transfer_id: TransferId::default(),
+        }
+    }
+
+    /// Returns the Sender's global secret.
+    pub fn delta(&self) -> Block {
+        self.delta
+    }
+
+    /// The number of remaining COTs which can be consumed.
+    pub fn remaining(&self) -> Result<usize, SenderError> {
+        Ok(self.state.try_as_extension()?.pool.available())
+    }
+
+    /// Returns random COTs from the bootstrap protocol.
+    async fn bootstrap<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<Vec<Block>, SenderError>
+    where
+        RandomCOT: RandomCOTSender<Ctx, Block> + Preprocess<Ctx, Error = OTError>,
+    {
+        self.rcot.alloc(count);
+        self.rcot.preprocess(ctx).await?;
+
+        let RCOTSenderOutput { msgs, .. } = self.rcot.send_random_correlated(ctx, count).await?;
+
+        if msgs.len() != count {
+            return Err(SenderError::InvalidLength {
+                expected: count,
+                actual: msgs.len(),
+            });
+        }
+
+        Ok(msgs)
+    }
+
+    async fn _setup<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError>
+    where
+        RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block> + Preprocess<Ctx, Error = OTError>,
+    {
+        let core = mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        self.rcot.setup(ctx).await?;
+
+        let lpn_parameters = self.config.lpn_parameters();
+        let lpn_type = self.config.lpn_type();
+        let lpn_code = self.config.lpn_code();
+
+        let mpcot = mpcot::Sender::setup(ctx, self.delta, lpn_type).await?;
+        let (mpcot, hs) = mpcot.pre_extend(lpn_parameters.t as u32, lpn_parameters.n as u32)?;
+
+        // Every extension must produce the random COTs consumed by the next one, so that only
+        // the setup is bootstrapped.
+        let count = spcot::cot_count(hs.iter().copied());
+        let produced = lpn_parameters.n.saturating_sub(lpn_parameters.k);
+        if produced <= count {
+            return Err(SenderError::ConfigError(format!(
+                "an extension produces {produced} random COTs but consumes {count}"
+            )));
+        }
+
+        // The bootstrap protocol is used once, as some protocols, e.g. KOS, can only be extended
+        // once.
+        let secret_len = lpn_code.secret_len(&lpn_parameters);
+        let mut v = self.bootstrap(ctx, secret_len + count).await?;
+        let reserve = v.split_off(secret_len);
+
+        let LpnMatrixSeed { seed } = ctx.io_mut().expect_next().await?;
+        let core = core.setup(self.delta, lpn_parameters, lpn_type, lpn_code, seed, &v)?;
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            mpcot,
+            hs,
+            pool: Pool::new(reserve),
+        }));
+
+        Ok(())
+    }
+
+    /// Performs Ferret extension, producing `n - k` random COTs of which those consumed by the
+    /// next extension are reserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    pub async fn extend<Ctx: Context>(&mut self, ctx: &mut Ctx) -> Result<(), SenderError> {
+        let Extension {
+            mut core,
+            mpcot,
+            hs,
+            mut pool,
+        } = *mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let qs = pool
+            .take_reserve(spcot::cot_count(hs.iter().copied()))
+            .ok_or(SenderError::InsufficientReserve)?;
+
+        let (mpcot, s) = mpcot.extend(ctx, self.delta, &hs, &qs).await?;
+
+        let (core, output) =
+            Backend::spawn(move || core.extend(&s).map(|output| (core, output))).await?;
+
+        // Prepare the next extension, so that its random COTs can be reserved.
+        let (t, n) = core.get_mpcot_query();
+        let (mpcot, hs) = mpcot.pre_extend(t, n)?;
+
+        pool.refill(output, spcot::cot_count(hs.iter().copied()));
+
+        self.state = State::Extension(Box::new(Extension {
+            core,
+            mpcot,
+            hs,
+            pool,
+        }));
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> OTSetup<Ctx> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block> + Preprocess<Ctx, Error = OTError> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        self._setup(ctx).await.map_err(OTError::from)
+    }
+}
+
+impl<RandomCOT> Allocate for Sender<RandomCOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> Preprocess<Ctx> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: OTSetup<Ctx> + RandomCOTSender<Ctx, Block> + Preprocess<Ctx, Error = OTError> + Send,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        while self.remaining()? < count {
+            self.extend(ctx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, RandomCOT> RandomCOTSender<Ctx, Block> for Sender<RandomCOT>
+where
+    Ctx: Context,
+    RandomCOT: Send,
+{
+    async fn send_random_correlated(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        while self.remaining()? < count {
+            self.extend(ctx).await?;
+        }
+
+        let msgs = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?
+            .pool
+            .take(count)
+            .expect("pool should have enough random COTs after extension");
+
+        Ok(RCOTSenderOutput {
+            id: self.transfer_id.next(),
+            msgs,
+        })
+    }
+}
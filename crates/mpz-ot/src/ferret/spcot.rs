@@ -0,0 +1,135 @@
+//! Single-point COT, see Figure 6 of the Ferret paper.
+//!
+//! All the single-point COTs of one MPCOT execution are performed at once, using a single
+//! consistency check.
+
+use mpz_common::Context;
+use mpz_core::Block;
+use mpz_ot_core::ferret::{
+    spcot::{
+        msgs::{CheckFromReceiver, CheckFromSender, ExtendFromSender, MaskBits},
+        receiver::Receiver as ReceiverCore,
+        sender::Sender as SenderCore,
+    },
+    CSP,
+};
+use rand::thread_rng;
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+
+use crate::ferret::{ReceiverError, SenderError};
+
+/// Returns the number of random COTs which are consumed by SPCOT with trees of the provided
+/// depths.
+pub(crate) fn cot_count(depths: impl IntoIterator<Item = usize>) -> usize {
+    depths.into_iter().sum::<usize>() + CSP
+}
+
+/// Executes SPCOT as the sender.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `delta` - The sender's global secret.
+/// * `hs` - The depths of the GGM trees.
+/// * `qs` - The random COTs of the sender, see [`cot_count`].
+pub(crate) async fn send<Ctx: Context>(
+    ctx: &mut Ctx,
+    delta: Block,
+    hs: &[usize],
+    qs: &[Block],
+) -> Result<Vec<Vec<Block>>, SenderError> {
+    let expected = cot_count(hs.iter().copied());
+    if qs.len() != expected {
+        return Err(SenderError::InvalidLength {
+            expected,
+            actual: qs.len(),
+        });
+    }
+
+    let mut sender = SenderCore::new().setup(delta, Block::random(&mut thread_rng()));
+
+    let masks: Vec<MaskBits> = ctx.io_mut().expect_next().await?;
+    if masks.len() != hs.len() {
+        return Err(SenderError::InvalidLength {
+            expected: hs.len(),
+            actual: masks.len(),
+        });
+    }
+
+    let (mut qs, y_star) = qs.split_at(qs.len() - CSP);
+    let mut extends = Vec::with_capacity(hs.len());
+    for (&h, mask) in hs.iter().zip(masks) {
+        let (q, rest) = qs.split_at(h);
+        qs = rest;
+
+        extends.push(sender.extend(h, q, mask)?);
+    }
+
+    ctx.io_mut().send(extends).await?;
+
+    let check: CheckFromReceiver = ctx.io_mut().expect_next().await?;
+    let (vs, check) = sender.check(y_star, check)?;
+
+    ctx.io_mut().send(check).await?;
+
+    Ok(vs)
+}
+
+/// Executes SPCOT as the receiver.
+///
+/// # Arguments
+///
+/// * `ctx` - The thread context.
+/// * `queries` - The depth of the GGM tree and the chosen position of each query.
+/// * `rs` - The choice bits of the receiver's random COTs, see [`cot_count`].
+/// * `ts` - The received blocks of the receiver's random COTs.
+pub(crate) async fn receive<Ctx: Context>(
+    ctx: &mut Ctx,
+    queries: &[(usize, u32)],
+    rs: &[bool],
+    ts: &[Block],
+) -> Result<Vec<Vec<Block>>, ReceiverError> {
+    let expected = cot_count(queries.iter().map(|(h, _)| *h));
+    if rs.len() != expected || ts.len() != expected {
+        return Err(ReceiverError::InvalidLength {
+            expected,
+            actual: rs.len().min(ts.len()),
+        });
+    }
+
+    let mut receiver = ReceiverCore::new().setup();
+
+    let (rs, x_star) = rs.split_at(rs.len() - CSP);
+    let (ts, z_star) = ts.split_at(ts.len() - CSP);
+
+    let mut masks = Vec::with_capacity(queries.len());
+    let mut offset = 0;
+    for &(h, alpha) in queries {
+        masks.push(receiver.extend_mask_bits(h, alpha, &rs[offset..offset + h])?);
+        offset += h;
+    }
+
+    ctx.io_mut().send(masks).await?;
+
+    let extends: Vec<ExtendFromSender> = ctx.io_mut().expect_next().await?;
+    if extends.len() != queries.len() {
+        return Err(ReceiverError::InvalidLength {
+            expected: queries.len(),
+            actual: extends.len(),
+        });
+    }
+
+    let mut offset = 0;
+    for (&(h, alpha), extend) in queries.iter().zip(extends) {
+        receiver.extend(h, alpha, &ts[offset..offset + h], extend)?;
+        offset += h;
+    }
+
+    let check = receiver.check_pre(x_star)?;
+    ctx.io_mut().send(check).await?;
+
+    let check: CheckFromSender = ctx.io_mut().expect_next().await?;
+    let ws = receiver.check(z_star, check)?;
+
+    Ok(ws.into_iter().map(|(ws, _)| ws).collect())
+}
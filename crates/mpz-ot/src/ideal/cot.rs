@@ -11,6 +11,8 @@ use mpz_ot_core::{
     ideal::cot::IdealCOT, COTReceiverOutput, COTSenderOutput, RCOTReceiverOutput, RCOTSenderOutput,
 };
 
+use rand::thread_rng;
+
 use crate::{COTReceiver, COTSender, OTError, OTSetup, RandomCOTReceiver, RandomCOTSender};
 
 fn cot(
@@ -45,6 +47,16 @@ pub fn ideal_rcot() -> (IdealCOTSender, IdealCOTReceiver) {
     (IdealCOTSender(alice), IdealCOTReceiver(bob))
 }
 
+/// Returns an ideal random COT sender and receiver with the provided correlation.
+///
+/// # Arguments
+///
+/// * `delta` - The correlation of the sender.
+pub fn ideal_rcot_with_delta(delta: Block) -> (IdealCOTSender, IdealCOTReceiver) {
+    let (alice, bob) = ideal_f2p(IdealCOT::new(Block::random(&mut thread_rng()), delta));
+    (IdealCOTSender(alice), IdealCOTReceiver(bob))
+}
+
 /// Ideal COT sender.
 #[derive(Debug, Clone)]
 pub struct IdealCOTSender(Alice<IdealCOT>);
@@ -39,14 +39,15 @@ mod tests {
     use itybity::ToBits;
     use mpz_common::{executor::test_st_executor, Context};
     use mpz_core::Block;
+    use mpz_ot_core::test::assert_cot;
     use rand::Rng;
     use rand_chacha::ChaCha12Rng;
     use rand_core::SeedableRng;
 
     use crate::{
         ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender},
-        CommittedOTSender, OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver,
-        RandomOTSender, VerifiableOTReceiver,
+        CommittedOTSender, OTError, OTReceiver, OTSender, OTSetup, RandomCOTReceiver,
+        RandomCOTSender, RandomOTReceiver, RandomOTSender, VerifiableOTReceiver,
     };
 
     #[fixture]
@@ -156,6 +157,37 @@ mod tests {
         assert_eq!(output_receiver.msgs, expected);
     }
 
+    #[tokio::test]
+    async fn test_kos_random_correlated() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(
+            SenderConfig::default(),
+            ReceiverConfig::default(),
+            &mut ctx_sender,
+            &mut ctx_receiver,
+            10,
+        )
+        .await;
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            RandomCOTSender::<_, Block>::send_random_correlated(&mut sender, &mut ctx_sender, 10),
+            RandomCOTReceiver::<_, bool, Block>::receive_random_correlated(
+                &mut receiver,
+                &mut ctx_receiver,
+                10
+            )
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_cot(
+            sender.delta().unwrap(),
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_kos_bytes(data: Vec<[Block; 2]>, choices: Vec<bool>) {
@@ -12,7 +12,7 @@ use mpz_ot_core::{
         pad_ot_count, receiver_state as state, Receiver as ReceiverCore, ReceiverConfig,
         ReceiverKeys, CSP,
     },
-    OTReceiverOutput, ROTReceiverOutput, TransferId,
+    OTReceiverOutput, RCOTReceiverOutput, ROTReceiverOutput, TransferId,
 };
 
 use enum_try_as_inner::EnumTryAsInner;
@@ -26,8 +26,8 @@ use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
 
 use super::{ReceiverError, ReceiverVerifyError, EXTEND_CHUNK_SIZE};
 use crate::{
-    OTError, OTReceiver, OTSender, OTSetup, RandomOTReceiver, VerifiableOTReceiver,
-    VerifiableOTSender,
+    OTError, OTReceiver, OTSender, OTSetup, RandomCOTReceiver, RandomOTReceiver,
+    VerifiableOTReceiver, VerifiableOTSender,
 };
 
 #[derive(Debug, EnumTryAsInner)]
@@ -316,6 +316,26 @@ where
     }
 }
 
+#[async_trait]
+impl<Ctx, BaseOT> RandomCOTReceiver<Ctx, bool, Block> for Receiver<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn receive_random_correlated(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTReceiverOutput<bool, Block>, OTError> {
+        let receiver = self
+            .state
+            .try_as_extension_mut()
+            .map_err(ReceiverError::from)?;
+
+        Ok(receiver.correlated(count).map_err(ReceiverError::from)?)
+    }
+}
+
 #[async_trait]
 impl<Ctx, const N: usize, BaseOT> OTReceiver<Ctx, bool, [u8; N]> for Receiver<BaseOT>
 where
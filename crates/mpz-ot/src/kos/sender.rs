@@ -13,7 +13,7 @@ use mpz_ot_core::{
         msgs::{Extend, StartExtend},
         pad_ot_count, sender_state as state, Sender as SenderCore, SenderConfig, SenderKeys, CSP,
     },
-    OTSenderOutput, ROTSenderOutput,
+    OTSenderOutput, RCOTSenderOutput, ROTSenderOutput,
 };
 use rand::{
     distributions::{Distribution, Standard},
@@ -25,7 +25,7 @@ use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
 
 use crate::{
     kos::SenderError, CommittedOTReceiver, CommittedOTSender, OTError, OTReceiver, OTSender,
-    OTSetup, RandomOTSender,
+    OTSetup, RandomCOTSender, RandomOTSender,
 };
 
 #[derive(Debug, EnumTryAsInner)]
@@ -66,6 +66,11 @@ impl<BaseOT: Send> Sender<BaseOT> {
         Ok(self.state.try_as_extension()?.remaining())
     }
 
+    /// Returns the sender's delta, which is the correlation of the random COTs.
+    pub fn delta(&self) -> Result<Block, SenderError> {
+        Ok(self.state.try_as_extension()?.delta())
+    }
+
     /// Returns the provided number of keys.
     pub(crate) fn take_keys(&mut self, count: usize) -> Result<SenderKeys, SenderError> {
         self.state
@@ -395,6 +400,26 @@ where
     }
 }
 
+#[async_trait]
+impl<Ctx, BaseOT> RandomCOTSender<Ctx, Block> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn send_random_correlated(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        let sender = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        Ok(sender.correlated(count).map_err(SenderError::from)?)
+    }
+}
+
 #[async_trait]
 impl<Ctx, BaseOT> CommittedOTSender<Ctx, [Block; 2]> for Sender<BaseOT>
 where
@@ -10,9 +10,11 @@
 )]
 
 pub mod chou_orlandi;
+pub mod ferret;
 #[cfg(any(test, feature = "ideal"))]
 pub mod ideal;
 pub mod kos;
+pub mod softspoken;
 
 use async_trait::async_trait;
 
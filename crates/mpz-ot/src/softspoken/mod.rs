@@ -0,0 +1,208 @@
+//! An implementation of the [`SoftSpoken`](https://eprint.iacr.org/2022/192.pdf) oblivious transfer extension protocol.
+//!
+//! The base OTs are performed with a [`chou_orlandi`](crate::chou_orlandi) (or any other) OT, with
+//! the roles reversed: the SoftSpoken receiver acts as the base OT sender.
+
+mod error;
+mod receiver;
+mod sender;
+
+pub use error::{ReceiverError, SenderError};
+pub use receiver::Receiver;
+pub use sender::Sender;
+
+pub(crate) use receiver::StateError as ReceiverStateError;
+pub(crate) use sender::StateError as SenderStateError;
+
+pub use mpz_ot_core::softspoken::{
+    msgs, ReceiverConfig, ReceiverConfigBuilder, ReceiverConfigBuilderError, ReceiverKeys,
+    SenderConfig, SenderConfigBuilder, SenderConfigBuilderError, SenderKeys,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    use futures::TryFutureExt;
+    use itybity::ToBits;
+    use mpz_common::{executor::test_st_executor, Allocate, Context, Preprocess};
+    use mpz_core::Block;
+    use mpz_ot_core::test::assert_cot;
+    use rand::Rng;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        chou_orlandi,
+        ideal::ot::{ideal_ot, IdealOTReceiver, IdealOTSender},
+        OTError, OTReceiver, OTSender, OTSetup, RandomCOTReceiver, RandomCOTSender,
+        RandomOTReceiver, RandomOTSender,
+    };
+
+    #[fixture]
+    fn choices() -> Vec<bool> {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        (0..128).map(|_| rng.gen()).collect()
+    }
+
+    #[fixture]
+    fn data() -> Vec<[Block; 2]> {
+        let mut rng = ChaCha12Rng::seed_from_u64(0);
+        (0..128)
+            .map(|_| [rng.gen::<[u8; 16]>().into(), rng.gen::<[u8; 16]>().into()])
+            .collect()
+    }
+
+    fn choose<T>(
+        data: impl Iterator<Item = [T; 2]>,
+        choices: impl Iterator<Item = bool>,
+    ) -> impl Iterator<Item = T> {
+        data.zip(choices)
+            .map(|([zero, one], choice)| if choice { one } else { zero })
+    }
+
+    async fn setup<Ctx: Context>(
+        k: usize,
+        ctx_sender: &mut Ctx,
+        ctx_receiver: &mut Ctx,
+        count: usize,
+    ) -> (
+        Sender<IdealOTReceiver<Block>>,
+        Receiver<IdealOTSender<[Block; 2]>>,
+    ) {
+        let (base_sender, base_receiver) = ideal_ot();
+
+        let mut sender = Sender::new(SenderConfig::builder().k(k).build().unwrap(), base_receiver);
+        let mut receiver =
+            Receiver::new(ReceiverConfig::builder().k(k).build().unwrap(), base_sender);
+
+        tokio::try_join!(sender.setup(ctx_sender), receiver.setup(ctx_receiver)).unwrap();
+        tokio::try_join!(
+            sender.extend(ctx_sender, count).map_err(OTError::from),
+            receiver.extend(ctx_receiver, count).map_err(OTError::from)
+        )
+        .unwrap();
+
+        (sender, receiver)
+    }
+
+    #[rstest]
+    #[case::k2(2)]
+    #[case::k4(4)]
+    #[case::k8(8)]
+    #[tokio::test]
+    async fn test_softspoken(#[case] k: usize, data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) =
+            setup(k, &mut ctx_sender, &mut ctx_receiver, data.len()).await;
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data),
+            OTReceiver::<_, bool, Block>::receive(&mut receiver, &mut ctx_receiver, &choices)
+        )
+        .unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_receiver.msgs, expected);
+    }
+
+    #[tokio::test]
+    async fn test_softspoken_random() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(4, &mut ctx_sender, &mut ctx_receiver, 10).await;
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            RandomOTSender::<_, [Block; 2]>::send_random(&mut sender, &mut ctx_sender, 10),
+            RandomOTReceiver::<_, bool, Block>::receive_random(
+                &mut receiver,
+                &mut ctx_receiver,
+                10
+            )
+        )
+        .unwrap();
+
+        let expected = output_sender
+            .msgs
+            .into_iter()
+            .zip(output_receiver.choices)
+            .map(|(output, choice)| output[choice as usize])
+            .collect::<Vec<_>>();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_receiver.msgs, expected);
+    }
+
+    #[tokio::test]
+    async fn test_softspoken_random_correlated() {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+        let (mut sender, mut receiver) = setup(4, &mut ctx_sender, &mut ctx_receiver, 10).await;
+
+        // Unlike KOS, SoftSpoken can be extended again.
+        sender.alloc(1000);
+        receiver.alloc(1000);
+
+        tokio::try_join!(
+            sender.preprocess(&mut ctx_sender),
+            receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let count = sender.remaining().unwrap();
+        assert!(count >= 1010);
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            RandomCOTSender::<_, Block>::send_random_correlated(
+                &mut sender,
+                &mut ctx_sender,
+                count
+            ),
+            RandomCOTReceiver::<_, bool, Block>::receive_random_correlated(
+                &mut receiver,
+                &mut ctx_receiver,
+                count
+            )
+        )
+        .unwrap();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_cot(
+            sender.delta().unwrap(),
+            &output_receiver.choices,
+            &output_sender.msgs,
+            &output_receiver.msgs,
+        );
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_softspoken_chou_orlandi(data: Vec<[Block; 2]>, choices: Vec<bool>) {
+        let (mut ctx_sender, mut ctx_receiver) = test_st_executor(8);
+
+        let mut sender = Sender::new(SenderConfig::default(), chou_orlandi::Receiver::default());
+        let mut receiver =
+            Receiver::new(ReceiverConfig::default(), chou_orlandi::Sender::default());
+
+        sender.alloc(data.len());
+        receiver.alloc(data.len());
+
+        tokio::try_join!(
+            sender.preprocess(&mut ctx_sender),
+            receiver.preprocess(&mut ctx_receiver)
+        )
+        .unwrap();
+
+        let (output_sender, output_receiver) = tokio::try_join!(
+            OTSender::<_, [Block; 2]>::send(&mut sender, &mut ctx_sender, &data),
+            OTReceiver::<_, bool, Block>::receive(&mut receiver, &mut ctx_receiver, &choices)
+        )
+        .unwrap();
+
+        let expected = choose(data.iter().copied(), choices.iter_lsb0()).collect::<Vec<_>>();
+
+        assert_eq!(output_sender.id, output_receiver.id);
+        assert_eq!(output_receiver.msgs, expected);
+    }
+}
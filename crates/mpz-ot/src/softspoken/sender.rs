@@ -0,0 +1,298 @@
+use std::mem;
+
+use async_trait::async_trait;
+use enum_try_as_inner::EnumTryAsInner;
+use mpz_cointoss as cointoss;
+use mpz_common::{Allocate, Context, Preprocess};
+use mpz_core::{prg::Prg, Block};
+use mpz_ot_core::{
+    softspoken::{
+        extension_size,
+        msgs::{Extend, StartExtend},
+        pad_ot_count, sender_state as state, Sender as SenderCore, SenderConfig, CSP,
+    },
+    OTSenderOutput, RCOTSenderOutput, ROTSenderOutput,
+};
+use rand::{
+    distributions::{Distribution, Standard},
+    thread_rng, Rng,
+};
+use rand_core::SeedableRng;
+use serio::{stream::IoStreamExt as _, SinkExt as _};
+use utils_aio::non_blocking_backend::{Backend, NonBlockingBackend};
+
+use crate::{
+    softspoken::SenderError, OTError, OTReceiver, OTSender, OTSetup, RandomCOTSender,
+    RandomOTSender,
+};
+
+#[derive(Debug, EnumTryAsInner)]
+#[derive_err(Debug)]
+pub(crate) enum State {
+    Initialized(SenderCore<state::Initialized>),
+    Extension(SenderCore<state::Extension>),
+    Error,
+}
+
+/// SoftSpoken sender.
+#[derive(Debug)]
+pub struct Sender<BaseOT> {
+    state: State,
+    base: BaseOT,
+    alloc: usize,
+}
+
+impl<BaseOT: Send> Sender<BaseOT> {
+    /// Creates a new Sender
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The Sender's configuration
+    /// * `base` - The base OT receiver
+    pub fn new(config: SenderConfig, base: BaseOT) -> Self {
+        Self {
+            state: State::Initialized(SenderCore::new(config)),
+            base,
+            alloc: 0,
+        }
+    }
+
+    /// The number of remaining OTs which can be consumed.
+    pub fn remaining(&self) -> Result<usize, SenderError> {
+        Ok(self.state.try_as_extension()?.remaining())
+    }
+
+    /// Returns the sender's delta, which is the correlation of the random COTs.
+    pub fn delta(&self) -> Result<Block, SenderError> {
+        Ok(self.state.try_as_extension()?.delta())
+    }
+
+    /// Performs the base OT setup with the provided delta.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `delta` - The delta value to use for the base OT setup.
+    pub async fn setup_with_delta<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        delta: Block,
+    ) -> Result<(), SenderError>
+    where
+        BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block>,
+    {
+        let ext_sender = mem::replace(&mut self.state, State::Error).try_into_initialized()?;
+
+        self.base.setup(ctx).await?;
+
+        let choices = ext_sender.base_choices(delta);
+        let base_output = self.base.receive(ctx, &choices).await?;
+
+        let keys: [Block; CSP] = base_output
+            .msgs
+            .try_into()
+            .expect("keys should be CSP length");
+
+        self.state = State::Extension(ext_sender.setup(delta, keys));
+
+        Ok(())
+    }
+
+    /// Performs OT extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The thread context.
+    /// * `count` - The number of OTs to extend.
+    pub async fn extend<Ctx: Context>(
+        &mut self,
+        ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<(), SenderError> {
+        let mut ext_sender = mem::replace(&mut self.state, State::Error).try_into_extension()?;
+
+        let count = pad_ot_count(count);
+
+        let StartExtend {
+            count: receiver_count,
+        } = ctx.io_mut().expect_next().await?;
+
+        if count != receiver_count {
+            return Err(SenderError::ConfigError(
+                "sender and receiver count mismatch".to_string(),
+            ));
+        }
+
+        let expected_us = extension_size(count, ext_sender.config().k());
+        let mut extend = Extend {
+            us: Vec::with_capacity(expected_us),
+        };
+
+        // Receive the corrections from the receiver.
+        while extend.us.len() < expected_us {
+            let Extend { us: chunk } = ctx.io_mut().expect_next().await?;
+
+            extend.us.extend(chunk);
+        }
+
+        // Extend the OTs.
+        let mut ext_sender =
+            Backend::spawn(move || ext_sender.extend(count, extend).map(|_| ext_sender)).await?;
+
+        // Sample chi_seed with coin-toss.
+        let seed: Block = thread_rng().gen();
+        let chi_seed = cointoss::cointoss_receiver(ctx, vec![seed]).await?[0];
+
+        // Receive the receiver's check.
+        let receiver_check = ctx.io_mut().expect_next().await?;
+
+        // Check consistency of extension.
+        let ext_sender = Backend::spawn(move || {
+            ext_sender
+                .check(chi_seed, receiver_check)
+                .map(|_| ext_sender)
+        })
+        .await?;
+
+        self.state = State::Extension(ext_sender);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OTSetup<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block> + Send,
+{
+    async fn setup(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_extension() {
+            return Ok(());
+        }
+
+        let delta = Block::random(&mut thread_rng());
+
+        self.setup_with_delta(ctx, delta)
+            .await
+            .map_err(OTError::from)
+    }
+}
+
+impl<BaseOT> Allocate for Sender<BaseOT> {
+    fn alloc(&mut self, count: usize) {
+        self.alloc += count;
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> Preprocess<Ctx> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: OTSetup<Ctx> + OTReceiver<Ctx, bool, Block> + Send,
+{
+    type Error = OTError;
+
+    async fn preprocess(&mut self, ctx: &mut Ctx) -> Result<(), OTError> {
+        if self.state.is_initialized() {
+            self.setup(ctx).await?;
+        }
+
+        let count = mem::take(&mut self.alloc);
+        if count == 0 {
+            return Ok(());
+        }
+
+        self.extend(ctx, count).await.map_err(OTError::from)
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> OTSender<Ctx, [Block; 2]> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn send(
+        &mut self,
+        ctx: &mut Ctx,
+        msgs: &[[Block; 2]],
+    ) -> Result<OTSenderOutput, OTError> {
+        let sender = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        let derandomize = ctx.io_mut().expect_next().await?;
+
+        let mut sender_keys = sender.keys(msgs.len()).map_err(SenderError::from)?;
+        sender_keys
+            .derandomize(derandomize)
+            .map_err(SenderError::from)?;
+        let payload = sender_keys
+            .encrypt_blocks(msgs)
+            .map_err(SenderError::from)?;
+        let id = payload.id;
+
+        ctx.io_mut()
+            .send(payload)
+            .await
+            .map_err(SenderError::from)?;
+
+        Ok(OTSenderOutput { id })
+    }
+}
+
+#[async_trait]
+impl<Ctx, T, BaseOT> RandomOTSender<Ctx, [T; 2]> for Sender<BaseOT>
+where
+    Ctx: Context,
+    Standard: Distribution<T>,
+    BaseOT: Send,
+{
+    async fn send_random(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<ROTSenderOutput<[T; 2]>, OTError> {
+        let sender = self
+            .state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?;
+
+        let keys = sender.keys(count).map_err(SenderError::from)?;
+        let id = keys.id();
+
+        let msgs = keys
+            .take_keys()
+            .into_iter()
+            .map(|[k0, k1]| {
+                let mut prg_0 = Prg::from_seed(k0);
+                let mut prg_1 = Prg::from_seed(k1);
+
+                [prg_0.gen::<T>(), prg_1.gen::<T>()]
+            })
+            .collect();
+
+        Ok(ROTSenderOutput { id, msgs })
+    }
+}
+
+#[async_trait]
+impl<Ctx, BaseOT> RandomCOTSender<Ctx, Block> for Sender<BaseOT>
+where
+    Ctx: Context,
+    BaseOT: Send,
+{
+    async fn send_random_correlated(
+        &mut self,
+        _ctx: &mut Ctx,
+        count: usize,
+    ) -> Result<RCOTSenderOutput<Block>, OTError> {
+        self.state
+            .try_as_extension_mut()
+            .map_err(SenderError::from)?
+            .correlated(count)
+            .map_err(OTError::from)
+    }
+}